@@ -0,0 +1,94 @@
+//! Instructions per second on the host, for loops in SRAM run from the decode cache and decoded every time.
+//! Run with `cargo bench`.
+
+use std::time::Instant;
+
+use rp2350_sim::cortex_m33::opcodes::*;
+use rp2350_sim::cortex_m33::registers::Register;
+use rp2350_sim::runner::{Runner, Stop};
+use rp2350_sim::{registers, RAM_START_ADDRESS, RP2350};
+
+const INSTRUCTIONS: u64 = 20_000_000;
+
+/// A counter loop mixing loads, stores, 16 and 32-bit data processing and a conditional branch
+fn sram_loop() -> RP2350 {
+    let rp2350 = RP2350::new();
+    let registers = rp2350.cortex_m33.registers;
+    let (r0, r1, r2, r3, r4) = (&registers.r0, &registers.r1, &registers.r2, &registers.r3, &registers.r4);
+
+    let mut code = Vec::new();
+    for opcode in [
+        LdrImmediateT1::opcode(r2, r1, 0),
+        AddsT2::opcode(r2, 1),
+        StrImmediateT1::opcode(r2, r1, 0),
+    ] {
+        code.extend(opcode.to_le_bytes());
+    }
+    code.extend(AddImmediateT3::opcode(false, r3, r3, 0x400).to_le_bytes());
+    for opcode in [
+        EorRegisterT1::opcode(r3, r2),
+        LslImmediateT1::opcode(r4, r3, 3),
+        SubT2::opcode(r0, 1),
+        // bne back to the ldr, 16 bytes before the PC
+        BT1::opcode(0b0001, -20i16 as u16 & 0x1fe),
+    ] {
+        code.extend(opcode.to_le_bytes());
+    }
+
+    load(rp2350, &code)
+}
+
+/// A loop calling a function that saves and restores registers on the stack
+fn call_loop() -> RP2350 {
+    let rp2350 = RP2350::new();
+    let registers = rp2350.cortex_m33.registers;
+    let (r0, r4) = (&registers.r0, &registers.r4);
+
+    let mut code = Vec::new();
+    // bl to the function after the loop, 4 bytes past the PC
+    code.extend(BlT1::opcode(4).to_le_bytes());
+    for opcode in [
+        SubT2::opcode(r0, 1),
+        // bne back to the bl, 10 bytes before the PC
+        BT1::opcode(0b0001, -10i16 as u16 & 0x1fe),
+        PushT1::opcode(true, registers![r4]),
+        AddsT2::opcode(r4, 1),
+        PopT1::opcode(true, registers![r4]),
+    ] {
+        code.extend(opcode.to_le_bytes());
+    }
+
+    load(rp2350, &code)
+}
+
+fn load(mut rp2350: RP2350, code: &[u8]) -> RP2350 {
+    rp2350.memory_mut().program(RAM_START_ADDRESS, code).unwrap();
+    rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+    rp2350.cortex_m33.registers.sp.set(RAM_START_ADDRESS + 0x2000);
+    rp2350.cortex_m33.registers.r0.set(u32::MAX);
+    rp2350.cortex_m33.registers.r1.set(RAM_START_ADDRESS + 0x1000);
+    rp2350
+}
+
+fn run(name: &str, mut rp2350: RP2350) {
+    let mut runner = Runner::new();
+    runner.max_instructions = Some(INSTRUCTIONS);
+
+    let start = Instant::now();
+    let summary = runner.run(&mut rp2350).unwrap();
+    let elapsed = start.elapsed();
+    assert_eq!(summary.stop, Stop::InstructionLimit, "{name} stopped early");
+
+    let mips = summary.instructions as f64 / elapsed.as_secs_f64() / 1e6;
+    println!("{name:<28} {:>8.1} MIPS", mips);
+}
+
+fn main() {
+    for (name, workload) in [("sram loop", sram_loop as fn() -> RP2350), ("call loop", call_loop)] {
+        run(name, workload());
+
+        let mut uncached = workload();
+        uncached.cortex_m33.decode_cache.enabled = false;
+        run(&format!("{name}, no decode cache"), uncached);
+    }
+}
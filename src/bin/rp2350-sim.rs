@@ -0,0 +1,263 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use rp2350_sim::gdbstub::{GdbStub, DEFAULT_PORT};
+use rp2350_sim::loader::uf2::ARM_SECURE_FAMILY_IDS;
+use rp2350_sim::runner::{register_dump, Runner, Stop};
+use rp2350_sim::trace::binary::BinarySink;
+use rp2350_sim::trace::TextSink;
+use rp2350_sim::{FLASH_START_ADDRESS, RP2350};
+
+const USAGE: &str = "usage: rp2350-sim [options] <image.hex|image.elf|image.uf2|image.bin>
+
+Boots the image from flash and runs it until a stop condition, then prints the registers. The semihosting
+console goes to stdout.
+
+options:
+  --cycles <n>          stop after n core cycles
+  --instructions <n>    stop after n instructions
+  --break <symbol>      stop when the PC reaches an ELF symbol or a hex address
+  --timeout <seconds>   stop after this much wall clock time
+  --uart0               print what UART0 sends to stdout
+  --semihosting-root <dir>
+                        directory the firmware can open files in through semihosting
+  --semihosting-input <path>
+                        file the firmware reads as semihosting console input
+  --rom <path>          boot by running a bootrom image instead of the built in one
+  --trace <path>        write a trace of every instruction to the file, - for stderr
+  --trace-binary        write the trace in the compact binary format instead of text
+  --quiet               don't print the registers at the end
+  --gdb [port]          serve GDB on the local port, 3333 by default, instead of running to a stop condition
+
+exit status: the low byte of the firmware's own on a semihosting exit, or 1 where that would make a nonzero
+status 0. 0 when a limit or the breakpoint is reached, 1 when the core locks up or halts on a BKPT, 2 on a bad
+command line or image, 124 on a timeout. Under --gdb, 0 once GDB detaches and 1 if the connection fails";
+
+struct Options {
+    image: String,
+    rom: Option<String>,
+    semihosting_root: Option<String>,
+    semihosting_input: Option<String>,
+    trace: Option<String>,
+    trace_binary: bool,
+    breakpoint: Option<String>,
+    quiet: bool,
+    gdb: Option<u16>,
+    runner: Runner,
+}
+
+fn parse_args(args: impl Iterator<Item = String>) -> Result<Options> {
+    let mut args = args.peekable();
+    let mut image = None;
+    let mut options = Options {
+        image: String::new(),
+        rom: None,
+        semihosting_root: None,
+        semihosting_input: None,
+        trace: None,
+        trace_binary: false,
+        breakpoint: None,
+        quiet: false,
+        gdb: None,
+        runner: Runner::new(),
+    };
+    options.runner.console = Some(Box::new(std::io::stdout()));
+
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().with_context(|| format!("{} needs a value", arg));
+
+        match arg.as_str() {
+            "--cycles" => options.runner.max_cycles = Some(value()?.parse().context("invalid --cycles")?),
+            "--instructions" => {
+                options.runner.max_instructions = Some(value()?.parse().context("invalid --instructions")?)
+            }
+            "--break" => options.breakpoint = Some(value()?),
+            "--timeout" => {
+                let seconds: f64 = value()?.parse().context("invalid --timeout")?;
+                options.runner.timeout = Some(Duration::try_from_secs_f64(seconds).context("invalid --timeout")?);
+            }
+            "--rom" => options.rom = Some(value()?),
+            "--semihosting-root" => options.semihosting_root = Some(value()?),
+            "--semihosting-input" => options.semihosting_input = Some(value()?),
+            "--trace" => options.trace = Some(value()?),
+            "--trace-binary" => options.trace_binary = true,
+            "--uart0" => options.runner.uart0 = Some(Box::new(std::io::stdout())),
+            "--quiet" => options.quiet = true,
+            "--gdb" => {
+                // The port is optional, so only a number after the flag is taken as one
+                let port = args.peek().and_then(|port| port.parse().ok());
+                if port.is_some() {
+                    args.next();
+                }
+                options.gdb = Some(port.unwrap_or(DEFAULT_PORT));
+            }
+            "-h" | "--help" => bail!("{}", USAGE),
+            flag if flag.starts_with("--") => bail!("unknown option {}\n\n{}", flag, USAGE),
+            _ if image.is_some() => bail!("more than one image given\n\n{}", USAGE),
+            _ => image = Some(arg),
+        }
+    }
+
+    options.image = image.with_context(|| USAGE.to_string())?;
+    Ok(options)
+}
+
+/// Loads the image by its extension. Raw binaries go to the start of flash.
+fn load_image(mcu: &mut RP2350, path: &str) -> Result<()> {
+    let bytes = std::fs::read(path).with_context(|| format!("cannot read {}", path))?;
+    let extension = Path::new(path).extension().and_then(|extension| extension.to_str());
+
+    match extension.map(str::to_ascii_lowercase).as_deref() {
+        Some("hex") => {
+            mcu.load_hex(&String::from_utf8(bytes).context("HEX file is not text")?)?;
+        }
+        Some("elf") | Some("axf") => {
+            mcu.load_elf(&bytes)?;
+        }
+        Some("uf2") => {
+            let report = mcu.load_uf2(&bytes, &ARM_SECURE_FAMILY_IDS)?;
+            if !report.is_complete() {
+                eprintln!("warning: UF2 file is incomplete, {:?}", report);
+            }
+        }
+        Some("bin") => mcu
+            .memory_mut()
+            .program(FLASH_START_ADDRESS, &bytes)
+            .context("image does not fit in flash")?,
+        _ => bail!("can't tell the format of {}, expected .hex, .elf, .uf2 or .bin", path),
+    }
+
+    Ok(())
+}
+
+/// A breakpoint is a symbol from the ELF image, or failing that a hex address
+fn resolve_breakpoint(mcu: &RP2350, breakpoint: &str) -> Result<u32> {
+    if let Some(symbol) = mcu.symbols.get(breakpoint) {
+        return Ok(symbol.address);
+    }
+
+    let digits = breakpoint.trim_start_matches("0x");
+    match u32::from_str_radix(digits, 16) {
+        Ok(address) => Ok(address & !1),
+        Err(_) => bail!("no symbol named {}", breakpoint),
+    }
+}
+
+fn setup(options: &mut Options) -> Result<RP2350> {
+    let mut mcu = RP2350::new();
+
+    if let Some(rom) = &options.rom {
+        let image = std::fs::read(rom).with_context(|| format!("cannot read {}", rom))?;
+        mcu.load_rom(&image)?;
+    }
+    load_image(&mut mcu, &options.image)?;
+
+    mcu.semihosting.root = options.semihosting_root.as_ref().map(PathBuf::from);
+    if let Some(input) = &options.semihosting_input {
+        let input = std::fs::read(input).with_context(|| format!("cannot read {}", input))?;
+        mcu.semihosting.input = input.into();
+    }
+
+    if let Some(trace) = &options.trace {
+        let writer: Box<dyn Write> = match trace.as_str() {
+            "-" => Box::new(std::io::stderr()),
+            path => Box::new(BufWriter::new(File::create(path).with_context(|| format!("cannot create {}", path))?)),
+        };
+        mcu.tracer = Some(match options.trace_binary {
+            true => Box::new(BinarySink::new(writer)),
+            false => Box::new(TextSink::new(writer)),
+        });
+    }
+
+    if let Some(breakpoint) = &options.breakpoint {
+        options.runner.breakpoint = Some(resolve_breakpoint(&mcu, breakpoint)?);
+    }
+
+    mcu.reset();
+    if let Some(Err(error)) = &mcu.bootrom.boot {
+        eprintln!("boot failed: {}", error);
+    }
+
+    Ok(mcu)
+}
+
+/// A process exit status only keeps the low byte, so a failing status like 256 becomes 1 rather than passing
+fn exit_status(status: u32) -> u8 {
+    match status as u8 {
+        0 if status != 0 => 1,
+        low => low,
+    }
+}
+
+/// Hands the reset chip to GDB until it detaches or disconnects
+fn debug(mcu: &mut RP2350, port: u16) -> ExitCode {
+    eprintln!("waiting for gdb on 127.0.0.1:{}", port);
+    let result = GdbStub::new().listen(mcu, port);
+
+    if let Some(Err(error)) = mcu.tracer.as_mut().map(|tracer| tracer.flush()) {
+        eprintln!("error: writing the trace failed, {}", error);
+    }
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: gdb connection failed, {}", error);
+            ExitCode::from(1)
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let mut options = match parse_args(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(error) => {
+            eprintln!("{}", error);
+            return ExitCode::from(2);
+        }
+    };
+
+    let mut mcu = match setup(&mut options) {
+        Ok(mcu) => mcu,
+        Err(error) => {
+            eprintln!("error: {:#}", error);
+            return ExitCode::from(2);
+        }
+    };
+
+    if let Some(port) = options.gdb {
+        return debug(&mut mcu, port);
+    }
+
+    let summary = match options.runner.run(&mut mcu) {
+        Ok(summary) => summary,
+        Err(error) => {
+            eprintln!("error: writing output failed, {}", error);
+            return ExitCode::from(2);
+        }
+    };
+
+    if let Some(Err(error)) = mcu.tracer.as_mut().map(|tracer| tracer.flush()) {
+        eprintln!("error: writing the trace failed, {}", error);
+    }
+
+    if !options.quiet {
+        eprintln!(
+            "stopped: {} after {} instructions in {:.3}s",
+            summary.stop,
+            summary.instructions,
+            summary.elapsed.as_secs_f64()
+        );
+        eprint!("{}", register_dump(&mut mcu));
+    }
+
+    match summary.stop {
+        Stop::CycleLimit | Stop::InstructionLimit | Stop::Breakpoint(_) => ExitCode::SUCCESS,
+        Stop::Exit(status) => ExitCode::from(exit_status(status)),
+        Stop::LockedUp | Stop::Bkpt(_) => ExitCode::from(1),
+        Stop::Timeout => ExitCode::from(124),
+    }
+}
@@ -0,0 +1,386 @@
+pub mod picobin;
+
+use byteorder::{ByteOrder, LittleEndian};
+
+use crate::bootrom::picobin::{Cpu, EntryPoint, ImageType, Security};
+use crate::cortex_m33::fault::Fault;
+use crate::cortex_m33::registers::Register;
+use crate::{BusError, BOOT_VECTOR_TABLE, FLASH_START_ADDRESS, RP2350};
+
+pub const ROM_START_ADDRESS: u32 = 0x00000000;
+pub const ROM_SIZE: usize = 32 * 1024;
+
+/// 'M', 'u', the chip and the bootrom version, where software checks it is running on a Raspberry Pi ROM
+pub const ROM_MAGIC_ADDRESS: u32 = 0x10;
+const ROM_MAGIC: [u8; 4] = [b'M', b'u', 0x02, 0x02];
+/// Holds the halfword address of rom_table_lookup for Arm code
+pub const ROM_TABLE_LOOKUP_ADDRESS: u32 = 0x16;
+/// Native functions each get a `bx lr` here, which is never executed since calls to them are run natively
+pub const NATIVE_FUNCTIONS_ADDRESS: u32 = 0x7f00;
+
+// Flags for which kind of entry rom_table_lookup is after
+pub const RT_FLAG_FUNC_RISCV: u32 = 0x0001;
+pub const RT_FLAG_FUNC_ARM_SEC: u32 = 0x0004;
+pub const RT_FLAG_FUNC_ARM_NONSEC: u32 = 0x0010;
+pub const RT_FLAG_DATA: u32 = 0x0040;
+
+pub const BOOTROM_OK: i32 = 0;
+pub const BOOTROM_ERROR_BUFFER_TOO_SMALL: i32 = -13;
+
+// get_sys_info flags
+pub const SYS_INFO_CHIP_INFO: u32 = 0x0001;
+pub const SYS_INFO_CRITICAL: u32 = 0x0002;
+pub const SYS_INFO_CPU_INFO: u32 = 0x0004;
+pub const SYS_INFO_FLASH_DEV_INFO: u32 = 0x0008;
+pub const SYS_INFO_BOOT_RANDOM: u32 = 0x0010;
+pub const SYS_INFO_BOOT_INFO: u32 = 0x0040;
+
+pub const FLASH_SECTOR_SIZE: u32 = 4096;
+
+/// Flash size as FLASH_DEVINFO has it, CS0 in 4 kB << n with no CS1
+const FLASH_DEVINFO: u32 = 10 << 8;
+
+/// The two character code a ROM function is looked up by
+pub const fn rom_table_code(c1: u8, c2: u8) -> u32 {
+    c1 as u32 | (c2 as u32) << 8
+}
+
+/// ROM functions the simulator runs natively instead of executing ROM code
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RomFunction {
+    TableLookup,
+    ConnectInternalFlash,
+    FlashExitXip,
+    FlashRangeErase,
+    FlashRangeProgram,
+    FlashFlushCache,
+    Reboot,
+    GetSysInfo,
+    BootromStateReset,
+}
+
+const ROM_FUNCTIONS: [RomFunction; 9] = [
+    RomFunction::TableLookup,
+    RomFunction::ConnectInternalFlash,
+    RomFunction::FlashExitXip,
+    RomFunction::FlashRangeErase,
+    RomFunction::FlashRangeProgram,
+    RomFunction::FlashFlushCache,
+    RomFunction::Reboot,
+    RomFunction::GetSysInfo,
+    RomFunction::BootromStateReset,
+];
+
+impl RomFunction {
+    /// The table code, rom_table_lookup itself is found through ROM_TABLE_LOOKUP_ADDRESS instead
+    pub fn code(self) -> Option<u32> {
+        match self {
+            RomFunction::TableLookup => None,
+            RomFunction::ConnectInternalFlash => Some(rom_table_code(b'I', b'F')),
+            RomFunction::FlashExitXip => Some(rom_table_code(b'E', b'X')),
+            RomFunction::FlashRangeErase => Some(rom_table_code(b'R', b'E')),
+            RomFunction::FlashRangeProgram => Some(rom_table_code(b'R', b'P')),
+            RomFunction::FlashFlushCache => Some(rom_table_code(b'F', b'C')),
+            RomFunction::Reboot => Some(rom_table_code(b'R', b'B')),
+            RomFunction::GetSysInfo => Some(rom_table_code(b'G', b'S')),
+            RomFunction::BootromStateReset => Some(rom_table_code(b'S', b'R')),
+        }
+    }
+
+    pub fn address(self) -> u32 {
+        let index = ROM_FUNCTIONS.iter().position(|&function| function == self).unwrap();
+        NATIVE_FUNCTIONS_ADDRESS + 4 * index as u32
+    }
+
+    /// The function whose entry is at the address, with the Thumb bit cleared
+    pub fn at(address: u32) -> Option<Self> {
+        // Checked on every instruction, so the entry is worked out from the address rather than searched for
+        let offset = address.checked_sub(NATIVE_FUNCTIONS_ADDRESS)?;
+        if offset % 4 != 0 {
+            return None;
+        }
+        ROM_FUNCTIONS.get((offset / 4) as usize).copied()
+    }
+
+    pub fn lookup(code: u32) -> Option<Self> {
+        ROM_FUNCTIONS.into_iter().find(|function| function.code() == Some(code))
+    }
+}
+
+/// Builds the ROM contents behind the native functions: the magic, the pointer to rom_table_lookup and an entry
+/// for each function.
+pub fn native_rom() -> Box<[u8; ROM_SIZE]> {
+    let mut rom: Box<[u8; ROM_SIZE]> = vec![0; ROM_SIZE].into_boxed_slice().try_into().unwrap();
+
+    let magic = ROM_MAGIC_ADDRESS as usize;
+    rom[magic..magic + 4].copy_from_slice(&ROM_MAGIC);
+
+    let lookup = ROM_TABLE_LOOKUP_ADDRESS as usize;
+    LittleEndian::write_u16(&mut rom[lookup..], (RomFunction::TableLookup.address() | 1) as u16);
+
+    for function in ROM_FUNCTIONS {
+        // bx lr
+        LittleEndian::write_u16(&mut rom[function.address() as usize..], 0x4770);
+    }
+
+    rom
+}
+
+/// How the last reset booted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Boot {
+    /// None when booting from a bare vector table, either a flash image without a block or a loaded ROM
+    pub image_type: Option<ImageType>,
+    pub vector_table: u32,
+    pub pc: u32,
+    pub sp: u32,
+}
+
+/// Why the last reset didn't boot, the core is left locked up
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BootError {
+    /// There are IMAGE_DEFs, but none is an Arm secure executable for the RP2350
+    NoBootableImage(ImageType),
+    /// Reading the initial SP and PC from the vector table failed
+    VectorTable { address: u32, error: BusError },
+}
+
+impl std::fmt::Display for BootError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BootError::NoBootableImage(image_type) => {
+                write!(f, "no bootable image, the last IMAGE_DEF has image type {:#06x}", image_type.0)
+            }
+            BootError::VectorTable { address, error } => {
+                write!(f, "cannot read the vector table at {:#010x}, {}", address, error)
+            }
+        }
+    }
+}
+
+impl std::error::Error for BootError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bootrom {
+    /// Whether boot and the ROM functions are run natively. Loading a ROM image clears it, after which reset and
+    /// calls into the ROM run its code.
+    pub native: bool,
+    /// None until the first reset
+    pub boot: Option<Result<Boot, BootError>>,
+}
+
+impl Default for Bootrom {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Bootrom {
+    pub fn new() -> Self {
+        Self {
+            native: true,
+            boot: None,
+        }
+    }
+}
+
+/// Whether the bootrom running on the Arm cores in secure mode, as the simulator does, would launch the image
+fn is_bootable(image_type: ImageType) -> bool {
+    image_type.is_exe()
+        && image_type.is_rp2350()
+        && image_type.cpu() == Cpu::Arm
+        && image_type.security() != Security::NonSecure
+}
+
+impl RP2350 {
+    /// Runs the boot path for flash. It picks the last bootable IMAGE_DEF in the block loop at the start of
+    /// flash, then enters it with the stack pointer and PC from its entry point item, or else from its vector
+    /// table. XIP needs no setting up, flash is always mapped. Images without any block boot from the vector
+    /// table at the start of flash, as they did before there was a bootrom.
+    pub(crate) fn boot(&mut self) -> Result<Boot, BootError> {
+        let blocks = picobin::find_block_loop(&self.memory().flash[..], FLASH_START_ADDRESS);
+        let image_defs: Vec<_> = blocks.iter().filter(|block| block.image_type.is_some()).collect();
+
+        let (image_type, vector_table, entry_point) = if image_defs.is_empty() {
+            (None, BOOT_VECTOR_TABLE, None)
+        } else {
+            let block = image_defs
+                .iter()
+                .rev()
+                .find(|block| block.image_type.is_some_and(is_bootable))
+                .ok_or(BootError::NoBootableImage(image_defs[image_defs.len() - 1].image_type.unwrap()))?;
+
+            (
+                block.image_type,
+                block.vector_table.unwrap_or(BOOT_VECTOR_TABLE),
+                block.entry_point,
+            )
+        };
+
+        self.enter(image_type, vector_table, entry_point)
+    }
+
+    /// Takes reset the way the core does in hardware, from the vector table at the start of a loaded ROM, which
+    /// then runs its own boot path.
+    pub(crate) fn boot_loaded_rom(&mut self) -> Result<Boot, BootError> {
+        self.enter(None, ROM_START_ADDRESS, None)
+    }
+
+    /// Sets VTOR and enters the image with the entry point, or the initial SP and reset handler from its vector
+    /// table.
+    fn enter(
+        &mut self,
+        image_type: Option<ImageType>,
+        vector_table: u32,
+        entry_point: Option<EntryPoint>,
+    ) -> Result<Boot, BootError> {
+        let EntryPoint { pc, sp, sp_limit } = match entry_point {
+            Some(entry_point) => entry_point,
+            None => {
+                let memory = &self.cortex_m33.memory;
+                let read = |address: u32| {
+                    memory
+                        .read_u32(address)
+                        .map_err(|error| BootError::VectorTable { address, error })
+                };
+
+                EntryPoint {
+                    sp: read(vector_table)?,
+                    pc: read(vector_table.wrapping_add(4))?,
+                    sp_limit: None,
+                }
+            }
+        };
+
+        let cortex = &mut self.cortex_m33;
+        cortex.vtor = vector_table;
+        cortex.msplim = sp_limit.unwrap_or(0);
+        cortex.registers.sp.set_msp(sp & !0b11);
+        cortex.registers.lr.set(0xFFFFFFFF);
+        cortex.registers.pc.set(pc & !1);
+        cortex.xpsr.epsr.set_t(pc & 1 != 0);
+
+        Ok(Boot {
+            image_type,
+            vector_table,
+            pc,
+            sp,
+        })
+    }
+
+    /// Runs a ROM function natively with its arguments from r0 to r3, puts the result in r0 and returns to LR.
+    pub(crate) fn call_rom_function(&mut self, function: RomFunction) -> Result<(), Fault> {
+        let registers = &self.cortex_m33.registers;
+        let args = [registers.r0.get(), registers.r1.get(), registers.r2.get(), registers.r3.get()];
+
+        let result = match function {
+            RomFunction::TableLookup => Self::rom_table_lookup(args[0], args[1]),
+            RomFunction::FlashRangeErase => {
+                self.flash_range_erase(args[0], args[1]);
+                BOOTROM_OK as u32
+            }
+            RomFunction::FlashRangeProgram => {
+                self.flash_range_program(args[0], args[1], args[2])?;
+                BOOTROM_OK as u32
+            }
+            RomFunction::Reboot => {
+                // The reset happens before the next instruction, so whatever the flags the call doesn't return
+                self.cortex_m33.reset_requested = true;
+                BOOTROM_OK as u32
+            }
+            RomFunction::GetSysInfo => self.get_sys_info(args[0], args[1], args[2])? as u32,
+            // There is no QSPI interface to set up, flash is always mapped
+            RomFunction::ConnectInternalFlash
+            | RomFunction::FlashExitXip
+            | RomFunction::BootromStateReset => BOOTROM_OK as u32,
+            RomFunction::FlashFlushCache => {
+                self.memory_mut().flush_xip();
+                BOOTROM_OK as u32
+            }
+        };
+
+        self.cortex_m33.registers.r0.set(result);
+        self.cortex_m33.return_from_call()
+    }
+
+    fn rom_table_lookup(code: u32, mask: u32) -> u32 {
+        if mask & (RT_FLAG_FUNC_ARM_SEC | RT_FLAG_FUNC_ARM_NONSEC) == 0 {
+            return 0;
+        }
+
+        RomFunction::lookup(code).map_or(0, |function| function.address() | 1)
+    }
+
+    /// Erases the sectors covering count bytes from the offset into flash, leaving them all ones.
+    fn flash_range_erase(&mut self, offset: u32, count: u32) {
+        let flash = &mut self.memory_mut().flash;
+        let start = (offset - offset % FLASH_SECTOR_SIZE) as usize;
+        let end = offset.saturating_add(count).div_ceil(FLASH_SECTOR_SIZE).saturating_mul(FLASH_SECTOR_SIZE) as usize;
+        let end = end.min(flash.len());
+        // Nothing past the end of flash is erased
+        if start >= end {
+            return;
+        }
+
+        flash[start..end].fill(0xff);
+        self.memory_mut().code_written(FLASH_START_ADDRESS + start as u32, (end - start) as u32);
+    }
+
+    /// Programs count bytes from data to the offset into flash. Like NOR flash, programming can only clear bits.
+    fn flash_range_program(&mut self, offset: u32, data: u32, count: u32) -> Result<(), Fault> {
+        for i in 0..count {
+            let address = data.wrapping_add(i);
+            let value = self
+                .cortex_m33
+                .memory
+                .read(address)
+                .map_err(|error| Fault::PreciseBus { address, error })?;
+
+            if let Some(byte) = self.memory_mut().flash.get_mut(offset.wrapping_add(i) as usize) {
+                *byte &= value;
+            }
+        }
+
+        self.memory_mut().code_written(FLASH_START_ADDRESS.wrapping_add(offset), count);
+        Ok(())
+    }
+
+    /// Fills the buffer with the requested system information, led by a word of the flags that were filled in.
+    /// Returns the number of words written.
+    fn get_sys_info(&mut self, buffer: u32, buffer_words: u32, flags: u32) -> Result<i32, Fault> {
+        let mut words = vec![0];
+        let mut supported = 0;
+
+        let info: [(u32, &[u32]); 6] = [
+            // Package, device ID and wafer ID, all unset in OTP
+            (SYS_INFO_CHIP_INFO, &[0, 0, 0]),
+            (SYS_INFO_CRITICAL, &[0]),
+            // The core is Arm
+            (SYS_INFO_CPU_INFO, &[0]),
+            (SYS_INFO_FLASH_DEV_INFO, &[FLASH_DEVINFO]),
+            (SYS_INFO_BOOT_RANDOM, &[0; 4]),
+            (SYS_INFO_BOOT_INFO, &[0; 4]),
+        ];
+        for (flag, values) in info {
+            if flags & flag != 0 {
+                supported |= flag;
+                words.extend_from_slice(values);
+            }
+        }
+        words[0] = supported;
+
+        if words.len() > buffer_words as usize {
+            return Ok(BOOTROM_ERROR_BUFFER_TOO_SMALL);
+        }
+
+        for (i, word) in words.iter().enumerate() {
+            let address = buffer.wrapping_add(4 * i as u32);
+            self.cortex_m33
+                .memory
+                .write_u32(address, *word)
+                .map_err(|error| Fault::PreciseBus { address, error })?;
+        }
+
+        Ok(words.len() as i32)
+    }
+}
@@ -0,0 +1,178 @@
+use byteorder::{ByteOrder, LittleEndian};
+
+pub const BLOCK_MARKER_START: u32 = 0xffffded3;
+pub const BLOCK_MARKER_END: u32 = 0xab123579;
+
+/// The bootrom looks for the first block in this much of the start of an image
+pub const BLOCK_SEARCH_WINDOW: usize = 4 * 1024;
+/// Blocks in a loop after this many are ignored, in case the links never lead back to the first block
+const MAX_BLOCK_LOOP: usize = 16;
+
+const ITEM_1BS_VECTOR_TABLE: u8 = 0x03;
+const ITEM_1BS_IMAGE_TYPE: u8 = 0x42;
+const ITEM_1BS_ENTRY_POINT: u8 = 0x44;
+const ITEM_2BS_LAST: u8 = 0xff;
+/// Items with this bit set in their type have a two byte size
+const ITEM_SIZE_2_BYTES: u8 = 0x80;
+
+const IMAGE_TYPE_IMAGE_TYPE_BITS: u16 = 0x0007;
+pub const IMAGE_TYPE_EXE: u16 = 0x0001;
+pub const IMAGE_TYPE_DATA: u16 = 0x0002;
+const IMAGE_TYPE_EXE_SECURITY_LSB: u16 = 4;
+pub const IMAGE_TYPE_EXE_SECURITY_NS: u16 = 1 << IMAGE_TYPE_EXE_SECURITY_LSB;
+pub const IMAGE_TYPE_EXE_SECURITY_S: u16 = 2 << IMAGE_TYPE_EXE_SECURITY_LSB;
+const IMAGE_TYPE_EXE_CPU_LSB: u16 = 8;
+pub const IMAGE_TYPE_EXE_CPU_ARM: u16 = 0;
+pub const IMAGE_TYPE_EXE_CPU_RISCV: u16 = 1 << IMAGE_TYPE_EXE_CPU_LSB;
+const IMAGE_TYPE_EXE_CHIP_LSB: u16 = 12;
+pub const IMAGE_TYPE_EXE_CHIP_RP2040: u16 = 0;
+pub const IMAGE_TYPE_EXE_CHIP_RP2350: u16 = 1 << IMAGE_TYPE_EXE_CHIP_LSB;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cpu {
+    Arm,
+    RiscV,
+    Unknown(u8),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Security {
+    Unspecified,
+    NonSecure,
+    Secure,
+}
+
+/// The flags from an IMAGE_DEF's image type item
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImageType(pub u16);
+
+impl ImageType {
+    pub fn is_exe(&self) -> bool {
+        self.0 & IMAGE_TYPE_IMAGE_TYPE_BITS == IMAGE_TYPE_EXE
+    }
+
+    pub fn security(&self) -> Security {
+        match (self.0 >> IMAGE_TYPE_EXE_SECURITY_LSB) & 0b11 {
+            1 => Security::NonSecure,
+            2 => Security::Secure,
+            _ => Security::Unspecified,
+        }
+    }
+
+    pub fn cpu(&self) -> Cpu {
+        match (self.0 >> IMAGE_TYPE_EXE_CPU_LSB) & 0b111 {
+            0 => Cpu::Arm,
+            1 => Cpu::RiscV,
+            cpu => Cpu::Unknown(cpu as u8),
+        }
+    }
+
+    pub fn is_rp2350(&self) -> bool {
+        (self.0 >> IMAGE_TYPE_EXE_CHIP_LSB) & 0b111 == 1
+    }
+}
+
+/// Initial PC and stack pointer that take the place of the vector table's
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EntryPoint {
+    pub pc: u32,
+    pub sp: u32,
+    pub sp_limit: Option<u32>,
+}
+
+/// A block of picobin items, between the start and end markers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Block {
+    pub address: u32,
+    /// Only set for blocks that are an IMAGE_DEF
+    pub image_type: Option<ImageType>,
+    pub vector_table: Option<u32>,
+    pub entry_point: Option<EntryPoint>,
+    /// The address of the next block in the loop, the block's own address if it is the only one
+    pub next: u32,
+}
+
+/// Parses the block at the offset into the image, which starts at base. None unless the markers and item
+/// sizes are all consistent.
+pub fn parse_block(image: &[u8], base: u32, offset: usize) -> Option<Block> {
+    let word = |offset: usize| image.get(offset..offset.checked_add(4)?).map(LittleEndian::read_u32);
+
+    if word(offset)? != BLOCK_MARKER_START {
+        return None;
+    }
+
+    let address = base.wrapping_add(offset as u32);
+    let mut block = Block {
+        address,
+        image_type: None,
+        vector_table: None,
+        entry_point: None,
+        next: address,
+    };
+
+    let items = offset + 4;
+    let mut position = items;
+    loop {
+        let header = word(position)?;
+        let item_type = header as u8;
+
+        if item_type == ITEM_2BS_LAST {
+            // The last item holds the size of the ones before it, then come the link and the end marker
+            let size = (header >> 8) as u16 as usize;
+            if size * 4 != position - items || word(position + 8)? != BLOCK_MARKER_END {
+                return None;
+            }
+
+            block.next = address.wrapping_add(word(position + 4)?);
+            return Some(block);
+        }
+
+        let size = if item_type & ITEM_SIZE_2_BYTES != 0 {
+            (header >> 8) as u16 as usize
+        } else {
+            (header >> 8) as u8 as usize
+        };
+        if size == 0 {
+            return None;
+        }
+
+        match (item_type, size) {
+            (ITEM_1BS_IMAGE_TYPE, 1) => block.image_type = Some(ImageType((header >> 16) as u16)),
+            (ITEM_1BS_VECTOR_TABLE, 2) => block.vector_table = Some(word(position + 4)?),
+            (ITEM_1BS_ENTRY_POINT, 3 | 4) => {
+                block.entry_point = Some(EntryPoint {
+                    pc: word(position + 4)?,
+                    sp: word(position + 8)?,
+                    sp_limit: if size == 4 { Some(word(position + 12)?) } else { None },
+                })
+            }
+            _ => {}
+        }
+
+        position += 4 * size;
+    }
+}
+
+/// Finds the first block in the search window at the start of the image and follows its links, returning the
+/// blocks of the loop in order. A link that doesn't lead to a valid block ends the loop early.
+pub fn find_block_loop(image: &[u8], base: u32) -> Vec<Block> {
+    let window = image.len().min(BLOCK_SEARCH_WINDOW);
+    let Some(first) = (0..window).step_by(4).find_map(|offset| parse_block(image, base, offset)) else {
+        return Vec::new();
+    };
+
+    let mut blocks = vec![first];
+    while blocks.len() < MAX_BLOCK_LOOP {
+        let next = blocks[blocks.len() - 1].next;
+        if next == blocks[0].address {
+            break;
+        }
+
+        match parse_block(image, base, next.wrapping_sub(base) as usize) {
+            Some(block) => blocks.push(block),
+            None => break,
+        }
+    }
+
+    blocks
+}
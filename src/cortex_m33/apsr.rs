@@ -1,6 +1,6 @@
 use bitmatch::*;
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone, Copy)]
 pub struct Xpsr {
     pub apsr: Apsr,
     pub ipsr: Ipsr,
@@ -18,12 +18,12 @@ impl Xpsr {
         self.epsr.set_from_u32(value);
     }
 
-    pub fn into_u32(&self) -> u32 {
+    pub fn into_u32(self) -> u32 {
         self.apsr.into_u32() | self.ipsr.into_u32() | self.epsr.into_u32()
     }
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone, Copy)]
 pub struct Epsr {
     ici_0: u8,
     t: bool,
@@ -36,8 +36,14 @@ impl Epsr {
         Epsr::default()
     }
 
-    pub fn ici(&self) -> u8 {
-        todo!();
+    /// The ITSTATE bits. IT[7:5] hold the base condition, IT[4:0] the size of the block and the condition of each instruction in it.
+    pub fn it(&self) -> u8 {
+        (self.ici_1 << 2) | self.ici_0
+    }
+
+    pub fn set_it(&mut self, value: u8) {
+        self.ici_0 = value & 0b11;
+        self.ici_1 = value >> 2;
     }
 
     pub fn set_t(&mut self, value: bool) {
@@ -59,7 +65,7 @@ impl Epsr {
     #[bitmatch]
     pub fn set_from_u32(&mut self, value: u32) {
         #[bitmatch]
-        let "????_?uut_??b?_????_??ll_lll?_????_????" = value;
+        let "????_?uut_??b?_????_llll_ll??_????_????" = value;
         self.ici_0 = u as u8;
         self.ici_1 = l as u8;
         self.set_b(b != 0);
@@ -67,16 +73,16 @@ impl Epsr {
     }
 
     #[bitmatch]
-    pub fn into_u32(&self) -> u32 {
-        let l: u32 = self.ici_0.into();
-        let u: u32 = self.ici_1.into();
+    pub fn into_u32(self) -> u32 {
+        let u: u32 = self.ici_0.into();
+        let l: u32 = self.ici_1.into();
         let b = self.b as u32;
         let t = self.t as u32;
-        bitpack!("0000_0uut_00b0_0000_00ll_lll0_0000_0000")
+        bitpack!("0000_0uut_00b0_0000_llll_ll00_0000_0000")
     }
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone, Copy)]
 pub struct Ipsr(u8);
 
 impl Ipsr {
@@ -89,12 +95,12 @@ impl Ipsr {
     }
 
     #[bitmatch]
-    pub fn into_u32(&self) -> u32 {
+    pub fn into_u32(self) -> u32 {
         self.0 as u32
     }
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone, Copy)]
 pub struct Apsr {
     n: bool,
     z: bool,
@@ -216,7 +222,7 @@ impl Apsr {
     }
 
     #[bitmatch]
-    pub fn into_u32(&self) -> u32 {
+    pub fn into_u32(self) -> u32 {
         let n = self.n() as u32;
         let z = self.z() as u32;
         let c = self.c() as u32;
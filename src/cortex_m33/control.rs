@@ -16,6 +16,12 @@ pub struct Control {
     pub spsel: SpSel,
 }
 
+impl Default for Control {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Control {
     pub fn new() -> Self {
         Self {
@@ -0,0 +1,135 @@
+use std::collections::HashMap;
+use std::hash::{BuildHasherDefault, Hasher};
+
+use crate::cortex_m33::fault::Fault;
+use crate::cortex_m33::instructions::Instruction;
+use crate::cortex_m33::registers::Register;
+use crate::cortex_m33::{CortexM33, OpCode};
+
+/// The size of the blocks the cache is dropped in when the memory under it is written
+pub const CODE_PAGE_SIZE: u32 = 1024;
+
+type Page = Box<[Option<Instruction>]>;
+
+/// Page addresses are already well spread, so they are used as their own hash rather than paying for SipHash on
+/// every instruction
+#[derive(Default)]
+struct PageHasher(u64);
+
+impl Hasher for PageHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = (self.0 << 8) | *byte as u64;
+        }
+    }
+
+    fn write_u32(&mut self, value: u32) {
+        self.0 = (value / CODE_PAGE_SIZE) as u64;
+    }
+}
+
+/// Instructions already decoded, by address. Only memory that reports writes to it is cached, and a page of
+/// entries is dropped as soon as the memory says it was written.
+pub struct DecodeCache {
+    /// Decoding goes straight to memory when off
+    pub enabled: bool,
+    pages: HashMap<u32, Page, BuildHasherDefault<PageHasher>>,
+}
+
+impl Default for DecodeCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DecodeCache {
+    pub fn new() -> Self {
+        Self {
+            enabled: true,
+            pages: HashMap::default(),
+        }
+    }
+
+    /// Drops every decoded instruction
+    pub fn clear(&mut self) {
+        self.pages.clear();
+    }
+
+    /// The number of decoded instructions held
+    pub fn len(&self) -> usize {
+        self.pages.values().map(|page| page.iter().flatten().count()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn get(&self, address: u32) -> Option<Instruction> {
+        let page = self.pages.get(&(address & !(CODE_PAGE_SIZE - 1)))?;
+        page[(address % CODE_PAGE_SIZE / 2) as usize]
+    }
+
+    fn insert(&mut self, address: u32, instruction: Instruction) {
+        let page = self
+            .pages
+            .entry(address & !(CODE_PAGE_SIZE - 1))
+            .or_insert_with(|| vec![None; (CODE_PAGE_SIZE / 2) as usize].into_boxed_slice());
+        page[(address % CODE_PAGE_SIZE / 2) as usize] = Some(instruction);
+    }
+}
+
+impl CortexM33 {
+    /// Executes the instruction at the PC like [`OpCode::execute`], taking it from the decode cache when it has
+    /// been decoded before
+    pub fn execute_cached(&mut self) -> Result<(), Fault> {
+        let address = self.registers.pc.get();
+        if !self.xpsr.epsr.t() {
+            return Err(Fault::InvalidState);
+        }
+
+        let result = self.decode(address).and_then(|instruction| instruction.execute(self));
+        if result.is_err() {
+            self.registers.pc.set(address);
+        }
+
+        result
+    }
+
+    fn decode(&mut self, address: u32) -> Result<Instruction, Fault> {
+        if !self.decode_cache.enabled {
+            return self.fetch(address);
+        }
+
+        for page in self.memory.take_code_writes() {
+            self.decode_cache.pages.remove(&page);
+        }
+        if let Some(instruction) = self.decode_cache.get(address) {
+            return Ok(instruction);
+        }
+
+        let instruction = self.fetch(address)?;
+        // An instruction across two pages would outlive a write to the second
+        let end = address.wrapping_add(if instruction.opcode.is_32bit() { 2 } else { 0 });
+        let same_page = address / CODE_PAGE_SIZE == end / CODE_PAGE_SIZE;
+        if same_page && self.memory.watch_code(address) {
+            self.decode_cache.insert(address, instruction);
+        }
+
+        Ok(instruction)
+    }
+
+    fn fetch(&self, address: u32) -> Result<Instruction, Fault> {
+        let opcode = OpCode::from_address(self, address)?;
+        let opcode_2 = if opcode.is_32bit() {
+            OpCode::from_address(self, address.wrapping_add(2))?
+        } else {
+            OpCode { code: 0, address: address.wrapping_add(2) }
+        };
+
+        Instruction::new(&opcode, &opcode_2)
+    }
+}
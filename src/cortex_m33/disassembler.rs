@@ -0,0 +1,719 @@
+use std::fmt;
+
+use crate::cortex_m33::instructions::{Instruction, InstructionType, InstructionType::*};
+use crate::cortex_m33::operation::{get_bit, get_bits, thumb_expand_imm_c};
+use crate::cortex_m33::{CortexM33, OpCode};
+use crate::loader::elf::SymbolTable;
+
+/// Register names as GNU objdump prints them
+const REGISTERS: [&str; 16] = [
+    "r0", "r1", "r2", "r3", "r4", "r5", "r6", "r7", "r8", "r9", "sl", "fp", "ip", "sp", "lr", "pc",
+];
+
+const CONDITIONS: [&str; 16] = [
+    "eq", "ne", "cs", "cc", "mi", "pl", "vs", "vc", "hi", "ls", "ge", "lt", "gt", "le", "", "",
+];
+
+impl OpCode {
+    /// The instruction in GNU assembler syntax, given the halfword after it for 32 bit encodings. Assumes the
+    /// instruction is outside an IT block.
+    pub fn disassemble(&self, next: OpCode) -> String {
+        self.disassemble_in_it_block(next, 0)
+    }
+
+    /// Like [`OpCode::disassemble`], with the ITSTATE the instruction runs under so instructions inside an IT
+    /// block get their condition
+    pub fn disassemble_in_it_block(&self, next: OpCode, itstate: u8) -> String {
+        format_instruction(self, &next, itstate, None)
+    }
+}
+
+/// One line of a listing
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Disassembly {
+    pub address: u32,
+    /// The first halfword in the low half, and for a 32 bit instruction the second in the high half
+    pub opcode: u32,
+    pub is_32bit: bool,
+    pub text: String,
+}
+
+impl fmt::Display for Disassembly {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let opcode = if self.is_32bit {
+            format!("{:04x} {:04x}", self.opcode & 0xffff, self.opcode >> 16)
+        } else {
+            format!("{:04x}", self.opcode & 0xffff)
+        };
+        write!(f, "{:08x}:  {:<9}  {}", self.address, opcode, self.text)
+    }
+}
+
+/// Disassembles count instructions from the address, following IT blocks from the start of the range and naming
+/// branch and literal targets after the symbols. Stops early at memory that can't be read.
+pub fn disassemble(cortex: &CortexM33, symbols: &SymbolTable, address: u32, count: usize) -> Vec<Disassembly> {
+    let mut lines = Vec::new();
+    let mut address = address & !1;
+    let mut itstate = 0;
+
+    while lines.len() < count {
+        let Ok(first) = OpCode::from_address(cortex, address) else {
+            break;
+        };
+        let second = OpCode::from_address(cortex, address.wrapping_add(2))
+            .unwrap_or(OpCode { code: 0, address: address.wrapping_add(2) });
+        let is_32bit = first.is_32bit();
+
+        lines.push(Disassembly {
+            address,
+            opcode: if is_32bit { first.code as u32 | (second.code as u32) << 16 } else { first.code as u32 },
+            is_32bit,
+            text: format_instruction(&first, &second, itstate, Some(symbols)),
+        });
+
+        itstate = next_itstate(first.code, itstate);
+        address = address.wrapping_add(if is_32bit { 4 } else { 2 });
+    }
+
+    lines
+}
+
+/// ITSTATE for the instruction after this one, an IT instruction starting a new block
+fn next_itstate(code: u16, itstate: u8) -> u8 {
+    if code >> 8 == 0b10111111 && code & 0xf != 0 {
+        code as u8
+    } else if get_bits(itstate, 0..=2) == 0 {
+        0
+    } else {
+        (itstate & 0b11100000) | ((itstate << 1) & 0b00011111)
+    }
+}
+
+fn sign_extend(value: u32, bits: u32) -> u32 {
+    (((value << (32 - bits)) as i32) >> (32 - bits)) as u32
+}
+
+fn register(n: u16) -> &'static str {
+    REGISTERS[n as usize & 0xf]
+}
+
+fn register_list(list: u16) -> String {
+    let names: Vec<_> = (0..16).filter(|&n| get_bit(list, n)).map(|n| register(n as u16)).collect();
+    format!("{{{}}}", names.join(", "))
+}
+
+/// The shift applied to a register operand, empty for none
+fn shift(shift_type: u16, imm5: u16) -> String {
+    match (shift_type, imm5) {
+        (0b00, 0) => String::new(),
+        (0b00, amount) => format!(", lsl #{}", amount),
+        (0b01, amount) => format!(", lsr #{}", if amount == 0 { 32 } else { amount }),
+        (0b10, amount) => format!(", asr #{}", if amount == 0 { 32 } else { amount }),
+        (_, 0) => ", rrx".to_string(),
+        (_, amount) => format!(", ror #{}", amount),
+    }
+}
+
+/// An address operand with an immediate offset, pre or post indexed as the P and W bits say
+fn indexed(rn: u16, offset: i64, index: bool, writeback: bool) -> String {
+    match (index, writeback) {
+        (true, false) if offset == 0 => format!("[{}]", register(rn)),
+        (true, false) => format!("[{}, #{}]", register(rn), offset),
+        (true, true) => format!("[{}, #{}]!", register(rn), offset),
+        (false, _) => format!("[{}], #{}", register(rn), offset),
+    }
+}
+
+fn format_instruction(opcode: &OpCode, next: &OpCode, itstate: u8, symbols: Option<&SymbolTable>) -> String {
+    let Ok(instruction) = Instruction::new(opcode, next) else {
+        return if opcode.is_32bit() {
+            format!(".inst.w 0x{:04x}{:04x}", opcode.code, next.code)
+        } else {
+            format!(".inst.n 0x{:04x}", opcode.code)
+        };
+    };
+
+    let (mnemonic, operands) = operands(&instruction, itstate, symbols);
+    if operands.is_empty() {
+        mnemonic
+    } else {
+        format!("{} {}", mnemonic, operands)
+    }
+}
+
+/// Splits an instruction into its mnemonic, with any flag setting, condition and width suffixes, and its operands
+fn operands(instruction: &Instruction, itstate: u8, symbols: Option<&SymbolTable>) -> (String, String) {
+    let hw1 = instruction.opcode.code;
+    let hw2 = instruction.opcode_2.code;
+    let pc = instruction.opcode.address.wrapping_add(4);
+    let aligned_pc = pc & !3;
+
+    let in_it_block = get_bits(itstate, 0..=3) != 0;
+    let cond = if in_it_block { CONDITIONS[(itstate >> 4) as usize] } else { "" };
+    // 16 bit data processing sets the flags only outside an IT block
+    let narrow_s = if in_it_block { "" } else { "s" };
+    let wide_s = if get_bit(hw1, 4) { "s" } else { "" };
+
+    let name = |base: &str, s: &str, width: &str| format!("{}{}{}{}", base, s, cond, width);
+    let target = |address: u32| match symbols.and_then(|symbols| symbols.resolve(address)) {
+        Some(symbol) if symbol.address == address => format!("{:x} <{}>", address, symbol.name),
+        Some(symbol) => format!("{:x} <{}+0x{:x}>", address, symbol.name, address - symbol.address),
+        None => format!("{:x}", address),
+    };
+
+    // Fields shared by the 16 bit encodings
+    let r0 = get_bits(hw1, 0..=2);
+    let r3 = get_bits(hw1, 3..=5);
+    let r6 = get_bits(hw1, 6..=8);
+    let r8 = get_bits(hw1, 8..=10);
+    let imm5 = get_bits(hw1, 6..=10);
+    let imm8 = get_bits(hw1, 0..=7);
+
+    // Fields shared by the 32 bit encodings
+    let rn = get_bits(hw1, 0..=3);
+    let rd = get_bits(hw2, 8..=11);
+    let rt = get_bits(hw2, 12..=15);
+    let rm = get_bits(hw2, 0..=3);
+    let shift_imm5 = (get_bits(hw2, 12..=14) << 2) | get_bits(hw2, 6..=7);
+    let shift_type = get_bits(hw2, 4..=5);
+    let imm12 = (get_bits(hw1, 10..=10) << 11) | (get_bits(hw2, 12..=14) << 8) | get_bits(hw2, 0..=7);
+    let modified = thumb_expand_imm_c(imm12, false).0;
+    let imm16 = (get_bits(hw1, 0..=3) << 12) | imm12;
+
+    let r = register;
+    let plain = |base: &str, width: &str| (name(base, "", width), String::new());
+
+    match instruction.instruction {
+        // 16 bit data processing
+        LslImmediateT1 if imm5 == 0 => (name("mov", narrow_s, ""), format!("{}, {}", r(r0), r(r3))),
+        LslImmediateT1 => (name("lsl", narrow_s, ""), format!("{}, {}, #{}", r(r0), r(r3), imm5)),
+        LsrImmediateT1 | AsrImmediateT1 => {
+            let base = if matches!(instruction.instruction, LsrImmediateT1) { "lsr" } else { "asr" };
+            let amount = if imm5 == 0 { 32 } else { imm5 };
+            (name(base, narrow_s, ""), format!("{}, {}, #{}", r(r0), r(r3), amount))
+        }
+        AddRegisterT1 => (name("add", narrow_s, ""), format!("{}, {}, {}", r(r0), r(r3), r(r6))),
+        SubRegisterT1 => (name("sub", narrow_s, ""), format!("{}, {}, {}", r(r0), r(r3), r(r6))),
+        AddsT1 => (name("add", narrow_s, ""), format!("{}, {}, #{}", r(r0), r(r3), r6)),
+        SubT1 => (name("sub", narrow_s, ""), format!("{}, {}, #{}", r(r0), r(r3), r6)),
+        MovImmediateT1 => (name("mov", narrow_s, ""), format!("{}, #{}", r(r8), imm8)),
+        CmpImmediateT1 => (name("cmp", "", ""), format!("{}, #{}", r(r8), imm8)),
+        AddsT2 => (name("add", narrow_s, ""), format!("{}, #{}", r(r8), imm8)),
+        SubT2 => (name("sub", narrow_s, ""), format!("{}, #{}", r(r8), imm8)),
+        AndRegisterT1 | EorRegisterT1 | LslRegisterT1 | LsrRegisterT1 | AsrRegisterT1 | AdcT1 | SbcRegisterT1
+        | RorRegisterT1 | OrrRegisterT1 | MulT1 | BicRegisterT1 | MvnT1 => {
+            let base = match instruction.instruction {
+                AndRegisterT1 => "and",
+                EorRegisterT1 => "eor",
+                LslRegisterT1 => "lsl",
+                LsrRegisterT1 => "lsr",
+                AsrRegisterT1 => "asr",
+                AdcT1 => "adc",
+                SbcRegisterT1 => "sbc",
+                RorRegisterT1 => "ror",
+                OrrRegisterT1 => "orr",
+                MulT1 => "mul",
+                BicRegisterT1 => "bic",
+                _ => "mvn",
+            };
+            (name(base, narrow_s, ""), format!("{}, {}", r(r0), r(r3)))
+        }
+        RsbImmediateT1 => (name("neg", narrow_s, ""), format!("{}, {}", r(r0), r(r3))),
+        TstRegisterT1 => (name("tst", "", ""), format!("{}, {}", r(r0), r(r3))),
+        CmpRegisterT1 => (name("cmp", "", ""), format!("{}, {}", r(r0), r(r3))),
+        CmnRegisterT1 => (name("cmn", "", ""), format!("{}, {}", r(r0), r(r3))),
+        // The Thumb 1 NOP, which objdump names for what it is
+        MovRegisterT1 if hw1 == 0x46c0 => (name("nop", "", ""), "@ (mov r8, r8)".to_string()),
+        AddRegisterT2 | CmpRegisterT2 | MovRegisterT1 => {
+            let rdn = (get_bits(hw1, 7..=7) << 3) | r0;
+            let base = match instruction.instruction {
+                AddRegisterT2 => "add",
+                CmpRegisterT2 => "cmp",
+                _ => "mov",
+            };
+            (name(base, "", ""), format!("{}, {}", r(rdn), r(get_bits(hw1, 3..=6))))
+        }
+        AdrT1 => {
+            let offset = imm8 << 2;
+            let address = aligned_pc.wrapping_add(offset as u32);
+            (name("add", "", ""), format!("{}, pc, #{} @ (adr {}, {})", r(r8), offset, r(r8), target(address)))
+        }
+        AddSpPlusImmediateT1 => (name("add", "", ""), format!("{}, sp, #{}", r(r8), imm8 << 2)),
+        AddSpPlusImmediateT2 => (name("add", "", ""), format!("sp, #{}", get_bits(hw1, 0..=6) << 2)),
+        SubSpMinusImmediateT1 => (name("sub", "", ""), format!("sp, #{}", get_bits(hw1, 0..=6) << 2)),
+        SxthT1 | SxtbT1 | UxthT1 | UxtbT1 | RevT1 | Rev16T1 | RevshT1 => {
+            let base = match instruction.instruction {
+                SxthT1 => "sxth",
+                SxtbT1 => "sxtb",
+                UxthT1 => "uxth",
+                UxtbT1 => "uxtb",
+                RevT1 => "rev",
+                Rev16T1 => "rev16",
+                _ => "revsh",
+            };
+            (name(base, "", ""), format!("{}, {}", r(r0), r(r3)))
+        }
+
+        // 16 bit loads and stores
+        LdrImmediateT1 | StrImmediateT1 | LdrbImmediateT1 | StrbImmediateT1 | LdrhImmediateT1
+        | StrhImmediateT1 => {
+            let (base, scale) = match instruction.instruction {
+                LdrImmediateT1 => ("ldr", 2),
+                StrImmediateT1 => ("str", 2),
+                LdrbImmediateT1 => ("ldrb", 0),
+                StrbImmediateT1 => ("strb", 0),
+                LdrhImmediateT1 => ("ldrh", 1),
+                _ => ("strh", 1),
+            };
+            (name(base, "", ""), format!("{}, [{}, #{}]", r(r0), r(r3), imm5 << scale))
+        }
+        LdrImmediateT2 => (name("ldr", "", ""), format!("{}, [sp, #{}]", r(r8), imm8 << 2)),
+        StrImmediateT2 => (name("str", "", ""), format!("{}, [sp, #{}]", r(r8), imm8 << 2)),
+        LdrLiteralT1 => {
+            let offset = imm8 << 2;
+            let address = aligned_pc.wrapping_add(offset as u32);
+            (name("ldr", "", ""), format!("{}, [pc, #{}] @ ({})", r(r8), offset, target(address)))
+        }
+        LdrRegisterT1 | StrRegisterT1 | LdrbRegisterT1 | StrbRegisterT1 | LdrhRegisterT1 | StrhRegisterT1
+        | LdrsbRegisterT1 | LdrshRegisterT1 => {
+            let base = match instruction.instruction {
+                LdrRegisterT1 => "ldr",
+                StrRegisterT1 => "str",
+                LdrbRegisterT1 => "ldrb",
+                StrbRegisterT1 => "strb",
+                LdrhRegisterT1 => "ldrh",
+                StrhRegisterT1 => "strh",
+                LdrsbRegisterT1 => "ldrsb",
+                _ => "ldrsh",
+            };
+            (name(base, "", ""), format!("{}, [{}, {}]", r(r0), r(r3), r(r6)))
+        }
+        LdmiaT1 => {
+            let writeback = if get_bit(imm8, r8 as usize) { "" } else { "!" };
+            (name("ldmia", "", ""), format!("{}{}, {}", r(r8), writeback, register_list(imm8)))
+        }
+        StmiaT1 => (name("stmia", "", ""), format!("{}!, {}", r(r8), register_list(imm8))),
+        PushT1 => (name("push", "", ""), register_list(imm8 | (get_bits(hw1, 8..=8) << 14))),
+        PopT1 => (name("pop", "", ""), register_list(imm8 | (get_bits(hw1, 8..=8) << 15))),
+
+        // 16 bit branches and miscellaneous
+        BT1 => {
+            let address = pc.wrapping_add(sign_extend((imm8 as u32) << 1, 9));
+            (format!("b{}.n", CONDITIONS[get_bits(hw1, 8..=11) as usize]), target(address))
+        }
+        BT2 => {
+            let address = pc.wrapping_add(sign_extend((get_bits(hw1, 0..=10) as u32) << 1, 12));
+            (name("b", "", ".n"), target(address))
+        }
+        BxT1 => (name("bx", "", ""), r(get_bits(hw1, 3..=6)).to_string()),
+        BlxT1 => (name("blx", "", ""), r(get_bits(hw1, 3..=6)).to_string()),
+        CbzT1 | CbnzT1 => {
+            let offset = (get_bits(hw1, 9..=9) << 6) | (get_bits(hw1, 3..=7) << 1);
+            let base = if matches!(instruction.instruction, CbzT1) { "cbz" } else { "cbnz" };
+            (base.to_string(), format!("{}, {}", r(r0), target(pc.wrapping_add(offset as u32))))
+        }
+        ItT1 => {
+            let firstcond = get_bits(hw1, 4..=7);
+            let mask = get_bits(hw1, 0..=3);
+            let suffix: String = (1..4 - mask.trailing_zeros() as usize)
+                .map(|i| if get_bit(mask, 4 - i) == get_bit(firstcond, 0) { 't' } else { 'e' })
+                .collect();
+            (format!("it{}", suffix), CONDITIONS[firstcond as usize].to_string())
+        }
+        BkptT1 => ("bkpt".to_string(), format!("0x{:04x}", imm8)),
+        SvcT1 => (name("svc", "", ""), imm8.to_string()),
+        UdfT1 => ("udf".to_string(), format!("#{}", imm8)),
+        CpsT1Id => ("cpsid".to_string(), "i".to_string()),
+        CpsT1Ie => ("cpsie".to_string(), "i".to_string()),
+        NopT1 => plain("nop", ""),
+        YieldT1 => plain("yield", ""),
+        WfeT1 => plain("wfe", ""),
+        WfiT1 => plain("wfi", ""),
+        SevT1 => plain("sev", ""),
+
+        // 32 bit data processing with a modified immediate
+        AndImmediateT1 | BicImmediateT1 | OrrImmediateT1 | OrnImmediateT1 | EorImmediateT1 | AddImmediateT3
+        | AdcImmediateT1 | SbcImmediateT1 | SubImmediateT3 | RsbImmediateT2 => {
+            let (base, width) = match instruction.instruction {
+                AndImmediateT1 => ("and", ".w"),
+                BicImmediateT1 => ("bic", ""),
+                OrrImmediateT1 => ("orr", ""),
+                OrnImmediateT1 => ("orn", ""),
+                EorImmediateT1 => ("eor", ""),
+                AddImmediateT3 => ("add", ".w"),
+                AdcImmediateT1 => ("adc", ""),
+                SbcImmediateT1 => ("sbc", ""),
+                SubImmediateT3 => ("sub", ".w"),
+                _ => ("rsb", ""),
+            };
+            (name(base, wide_s, width), format!("{}, {}, #{}", r(rd), r(rn), modified))
+        }
+        MovImmediateT2 => (name("mov", wide_s, ".w"), format!("{}, #{}", r(rd), modified)),
+        MvnImmediateT1 => (name("mvn", wide_s, ""), format!("{}, #{}", r(rd), modified)),
+        TstImmediateT1 => (name("tst", "", ""), format!("{}, #{}", r(rn), modified)),
+        TeqImmediateT1 => (name("teq", "", ""), format!("{}, #{}", r(rn), modified)),
+        CmpImmediateT2 => (name("cmp", "", ".w"), format!("{}, #{}", r(rn), modified)),
+        CmnImmediateT1 => (name("cmn", "", ""), format!("{}, #{}", r(rn), modified)),
+
+        // 32 bit data processing with a plain immediate
+        AddImmediateT4 => (name("addw", "", ""), format!("{}, {}, #{}", r(rd), r(rn), imm12)),
+        SubImmediateT4 => (name("subw", "", ""), format!("{}, {}, #{}", r(rd), r(rn), imm12)),
+        AdrT2 => (name("adr", "", ".w"), format!("{}, {}", r(rd), target(aligned_pc.wrapping_sub(imm12 as u32)))),
+        AdrT3 => (name("adr", "", ".w"), format!("{}, {}", r(rd), target(aligned_pc.wrapping_add(imm12 as u32)))),
+        MovImmediateT3 => (name("movw", "", ""), format!("{}, #{}", r(rd), imm16)),
+        MovtT1 => (name("movt", "", ""), format!("{}, #{}", r(rd), imm16)),
+        SsatT1 | UsatT1 => {
+            let (base, saturate) = match instruction.instruction {
+                SsatT1 => ("ssat", get_bits(hw2, 0..=4) + 1),
+                _ => ("usat", get_bits(hw2, 0..=4)),
+            };
+            let shift = match (get_bit(hw1, 5), shift_imm5) {
+                (false, 0) => String::new(),
+                (false, amount) => format!(", lsl #{}", amount),
+                (true, amount) => format!(", asr #{}", amount),
+            };
+            (name(base, "", ""), format!("{}, #{}, {}{}", r(rd), saturate, r(rn), shift))
+        }
+        SbfxT1 | UbfxT1 => {
+            let base = if matches!(instruction.instruction, SbfxT1) { "sbfx" } else { "ubfx" };
+            let width = get_bits(hw2, 0..=4) + 1;
+            (name(base, "", ""), format!("{}, {}, #{}, #{}", r(rd), r(rn), shift_imm5, width))
+        }
+        BfiT1 | BfcT1 => {
+            let width = (get_bits(hw2, 0..=4) + 1).saturating_sub(shift_imm5);
+            match instruction.instruction {
+                BfiT1 => (name("bfi", "", ""), format!("{}, {}, #{}, #{}", r(rd), r(rn), shift_imm5, width)),
+                _ => (name("bfc", "", ""), format!("{}, #{}, #{}", r(rd), shift_imm5, width)),
+            }
+        }
+
+        // 32 bit data processing with a shifted register
+        AndRegisterT2 | BicRegisterT2 | OrrRegisterT2 | OrnRegisterT1 | EorRegisterT2 | AddRegisterT3
+        | AdcRegisterT2 | SbcRegisterT2 | SubRegisterT2 | RsbRegisterT1 => {
+            let (base, width) = match instruction.instruction {
+                AndRegisterT2 => ("and", ".w"),
+                BicRegisterT2 => ("bic", ".w"),
+                OrrRegisterT2 => ("orr", ".w"),
+                OrnRegisterT1 => ("orn", ""),
+                EorRegisterT2 => ("eor", ".w"),
+                AddRegisterT3 => ("add", ".w"),
+                AdcRegisterT2 => ("adc", ".w"),
+                SbcRegisterT2 => ("sbc", ".w"),
+                SubRegisterT2 => ("sub", ".w"),
+                _ => ("rsb", ""),
+            };
+            let shift = shift(shift_type, shift_imm5);
+            (name(base, wide_s, width), format!("{}, {}, {}{}", r(rd), r(rn), r(rm), shift))
+        }
+        MvnRegisterT2 => (name("mvn", wide_s, ".w"), format!("{}, {}{}", r(rd), r(rm), shift(shift_type, shift_imm5))),
+        TstRegisterT2 | TeqRegisterT1 | CmpRegisterT3 | CmnRegisterT2 => {
+            let (base, width) = match instruction.instruction {
+                TstRegisterT2 => ("tst", ".w"),
+                TeqRegisterT1 => ("teq", ""),
+                CmpRegisterT3 => ("cmp", ".w"),
+                _ => ("cmn", ".w"),
+            };
+            (name(base, "", width), format!("{}, {}{}", r(rn), r(rm), shift(shift_type, shift_imm5)))
+        }
+        MovRegisterT3 => (name("mov", wide_s, ".w"), format!("{}, {}", r(rd), r(rm))),
+        LslImmediateT2 | LsrImmediateT2 | AsrImmediateT2 | RorImmediateT1 => {
+            let (base, width) = match instruction.instruction {
+                LslImmediateT2 => ("lsl", ".w"),
+                LsrImmediateT2 => ("lsr", ".w"),
+                AsrImmediateT2 => ("asr", ".w"),
+                _ => ("ror", ""),
+            };
+            let amount = if shift_imm5 == 0 { 32 } else { shift_imm5 };
+            (name(base, wide_s, width), format!("{}, {}, #{}", r(rd), r(rm), amount))
+        }
+        RrxT1 => (name("rrx", wide_s, ""), format!("{}, {}", r(rd), r(rm))),
+
+        // 32 bit data processing with registers
+        LslRegisterT2 | LsrRegisterT2 | AsrRegisterT2 | RorRegisterT2 => {
+            let base = match instruction.instruction {
+                LslRegisterT2 => "lsl",
+                LsrRegisterT2 => "lsr",
+                AsrRegisterT2 => "asr",
+                _ => "ror",
+            };
+            (name(base, wide_s, ".w"), format!("{}, {}, {}", r(rd), r(rn), r(rm)))
+        }
+        SxthT2 | UxthT2 | SxtbT2 | UxtbT2 => {
+            let base = match instruction.instruction {
+                SxthT2 => "sxth",
+                UxthT2 => "uxth",
+                SxtbT2 => "sxtb",
+                _ => "uxtb",
+            };
+            let rotation = get_bits(hw2, 4..=5) * 8;
+            let rotate = if rotation == 0 { String::new() } else { format!(", ror #{}", rotation) };
+            (name(base, "", ".w"), format!("{}, {}{}", r(rd), r(rm), rotate))
+        }
+        RevT2 | Rev16T2 | RevshT2 | RbitT1 | ClzT1 => {
+            let (base, width) = match instruction.instruction {
+                RevT2 => ("rev", ".w"),
+                Rev16T2 => ("rev16", ".w"),
+                RevshT2 => ("revsh", ".w"),
+                RbitT1 => ("rbit", ""),
+                _ => ("clz", ""),
+            };
+            (name(base, "", width), format!("{}, {}", r(rd), r(rm)))
+        }
+
+        // Multiply and divide
+        MulT2 => (name("mul", "", ".w"), format!("{}, {}, {}", r(rd), r(rn), r(rm))),
+        MlaT1 => (name("mla", "", ""), format!("{}, {}, {}, {}", r(rd), r(rn), r(rm), r(rt))),
+        MlsT1 => (name("mls", "", ""), format!("{}, {}, {}, {}", r(rd), r(rn), r(rm), r(rt))),
+        SmullT1 | UmullT1 | SmlalT1 | UmlalT1 => {
+            let base = match instruction.instruction {
+                SmullT1 => "smull",
+                UmullT1 => "umull",
+                SmlalT1 => "smlal",
+                _ => "umlal",
+            };
+            (name(base, "", ""), format!("{}, {}, {}, {}", r(rt), r(rd), r(rn), r(rm)))
+        }
+        SdivT1 => (name("sdiv", "", ""), format!("{}, {}, {}", r(rd), r(rn), r(rm))),
+        UdivT1 => (name("udiv", "", ""), format!("{}, {}, {}", r(rd), r(rn), r(rm))),
+
+        // 32 bit branches and miscellaneous
+        BT3 => {
+            let offset = (get_bits(hw1, 10..=10) as u32) << 20
+                | (get_bits(hw2, 11..=11) as u32) << 19
+                | (get_bits(hw2, 13..=13) as u32) << 18
+                | (get_bits(hw1, 0..=5) as u32) << 12
+                | (get_bits(hw2, 0..=10) as u32) << 1;
+            let address = pc.wrapping_add(sign_extend(offset, 21));
+            (format!("b{}.w", CONDITIONS[get_bits(hw1, 6..=9) as usize]), target(address))
+        }
+        BT4 | BlT1 => {
+            let s = get_bits(hw1, 10..=10) as u32;
+            let i1 = !(get_bits(hw2, 13..=13) as u32 ^ s) & 1;
+            let i2 = !(get_bits(hw2, 11..=11) as u32 ^ s) & 1;
+            let offset = s << 24
+                | i1 << 23
+                | i2 << 22
+                | (get_bits(hw1, 0..=9) as u32) << 12
+                | (get_bits(hw2, 0..=10) as u32) << 1;
+            let address = pc.wrapping_add(sign_extend(offset, 25));
+            match instruction.instruction {
+                BT4 => (name("b", "", ".w"), target(address)),
+                _ => (name("bl", "", ""), target(address)),
+            }
+        }
+        MsrT1 => {
+            let sysm = get_bits(hw2, 0..=7);
+            let special = match (special_register(sysm), sysm) {
+                (Some(special), 0..=3) => {
+                    let suffix = match get_bits(hw2, 10..=11) {
+                        0b01 => "_g",
+                        0b11 => "_nzcvqg",
+                        _ => "_nzcvq",
+                    };
+                    format!("{}{}", special, suffix)
+                }
+                (Some(special), _) => special.to_string(),
+                (None, _) => sysm.to_string(),
+            };
+            (name("msr", "", ""), format!("{}, {}", special, r(rn)))
+        }
+        MrsT1 => {
+            let sysm = get_bits(hw2, 0..=7);
+            let special = special_register(sysm).map_or_else(|| sysm.to_string(), str::to_string);
+            (name("mrs", "", ""), format!("{}, {}", r(rd), special))
+        }
+        NopT2 => plain("nop", ".w"),
+        YieldT2 => plain("yield", ".w"),
+        WfeT2 => plain("wfe", ".w"),
+        WfiT2 => plain("wfi", ".w"),
+        SevT2 => plain("sev", ".w"),
+        ClrexT1 => plain("clrex", ""),
+        DsbT1Sy | DmbT1Sy | IsbT1Sy => {
+            let base = match instruction.instruction {
+                DsbT1Sy => "dsb",
+                DmbT1Sy => "dmb",
+                _ => "isb",
+            };
+            let option = match get_bits(hw2, 0..=3) {
+                0b1111 => "sy".to_string(),
+                option => format!("#{}", option),
+            };
+            (name(base, "", ""), option)
+        }
+        UdfT2 => ("udf.w".to_string(), format!("#{}", (get_bits(hw1, 0..=3) << 12) | get_bits(hw2, 0..=11))),
+
+        // 32 bit load and store multiple
+        LdmT2 | StmT2 | LdmdbT1 | StmdbT1 => {
+            let writeback = get_bit(hw1, 5);
+            let (base, stack) = match instruction.instruction {
+                LdmT2 => ("ldmia", "pop"),
+                StmT2 => ("stmia", ""),
+                LdmdbT1 => ("ldmdb", ""),
+                _ => ("stmdb", "push"),
+            };
+            if rn == 13 && writeback && !stack.is_empty() {
+                (name(stack, "", ".w"), register_list(hw2))
+            } else {
+                let writeback = if writeback { "!" } else { "" };
+                (name(base, "", ".w"), format!("{}{}, {}", r(rn), writeback, register_list(hw2)))
+            }
+        }
+
+        // Load and store dual, exclusive and table branch
+        StrexT1 => {
+            let address = indexed(rn, (get_bits(hw2, 0..=7) << 2) as i64, true, false);
+            (name("strex", "", ""), format!("{}, {}, {}", r(rd), r(rt), address))
+        }
+        LdrexT1 => {
+            let address = indexed(rn, (get_bits(hw2, 0..=7) << 2) as i64, true, false);
+            (name("ldrex", "", ""), format!("{}, {}", r(rt), address))
+        }
+        StrexbT1 | StrexhT1 | StlexT1 | StlexbT1 | StlexhT1 => {
+            let base = match instruction.instruction {
+                StrexbT1 => "strexb",
+                StrexhT1 => "strexh",
+                StlexT1 => "stlex",
+                StlexbT1 => "stlexb",
+                _ => "stlexh",
+            };
+            (name(base, "", ""), format!("{}, {}, [{}]", r(rm), r(rt), r(rn)))
+        }
+        LdrexbT1 | LdrexhT1 | LdaexT1 | LdaexbT1 | LdaexhT1 | LdaT1 | LdabT1 | LdahT1 | StlT1 | StlbT1
+        | StlhT1 => {
+            let base = match instruction.instruction {
+                LdrexbT1 => "ldrexb",
+                LdrexhT1 => "ldrexh",
+                LdaexT1 => "ldaex",
+                LdaexbT1 => "ldaexb",
+                LdaexhT1 => "ldaexh",
+                LdaT1 => "lda",
+                LdabT1 => "ldab",
+                LdahT1 => "ldah",
+                StlT1 => "stl",
+                StlbT1 => "stlb",
+                _ => "stlh",
+            };
+            (name(base, "", ""), format!("{}, [{}]", r(rt), r(rn)))
+        }
+        StrdImmediateT1 | LdrdImmediateT1 => {
+            let base = if matches!(instruction.instruction, StrdImmediateT1) { "strd" } else { "ldrd" };
+            let offset = (get_bits(hw2, 0..=7) << 2) as i64;
+            let offset = if get_bit(hw1, 7) { offset } else { -offset };
+            let address = indexed(rn, offset, get_bit(hw1, 8), get_bit(hw1, 5));
+            (name(base, "", ""), format!("{}, {}, {}", r(rt), r(rd), address))
+        }
+        LdrdLiteralT1 => {
+            let offset = get_bits(hw2, 0..=7) << 2;
+            let address = match get_bit(hw1, 7) {
+                true => aligned_pc.wrapping_add(offset as u32),
+                false => aligned_pc.wrapping_sub(offset as u32),
+            };
+            let sign = if get_bit(hw1, 7) { "" } else { "-" };
+            (name("ldrd", "", ""), format!("{}, {}, [pc, #{}{}] @ ({})", r(rt), r(rd), sign, offset, target(address)))
+        }
+        TbbT1 => (name("tbb", "", ""), format!("[{}, {}]", r(rn), r(rm))),
+        TbhT1 => (name("tbh", "", ""), format!("[{}, {}, lsl #1]", r(rn), r(rm))),
+
+        // 32 bit single loads and stores
+        PldT1 | PliT1 => {
+            let base = if matches!(instruction.instruction, PldT1) { "pld" } else { "pli" };
+            let address = if rn == 15 {
+                let offset = get_bits(hw2, 0..=11) as i64;
+                format!("[pc, #{}]", if get_bit(hw1, 7) { offset } else { -offset })
+            } else if get_bit(hw1, 7) {
+                indexed(rn, get_bits(hw2, 0..=11) as i64, true, false)
+            } else if get_bits(hw2, 8..=11) == 0b1100 {
+                indexed(rn, -(get_bits(hw2, 0..=7) as i64), true, false)
+            } else {
+                format!("[{}, {}{}]", r(rn), r(rm), shift(0b00, get_bits(hw2, 4..=5)))
+            };
+            (name(base, "", ""), address)
+        }
+        LdrLiteralT2 | LdrbLiteralT1 | LdrhLiteralT1 | LdrsbLiteralT1 | LdrshLiteralT1 => {
+            let base = match instruction.instruction {
+                LdrLiteralT2 => "ldr",
+                LdrbLiteralT1 => "ldrb",
+                LdrhLiteralT1 => "ldrh",
+                LdrsbLiteralT1 => "ldrsb",
+                _ => "ldrsh",
+            };
+            let offset = get_bits(hw2, 0..=11);
+            let address = match get_bit(hw1, 7) {
+                true => aligned_pc.wrapping_add(offset as u32),
+                false => aligned_pc.wrapping_sub(offset as u32),
+            };
+            let sign = if get_bit(hw1, 7) { "" } else { "-" };
+            (name(base, "", ".w"), format!("{}, [pc, #{}{}] @ ({})", r(rt), sign, offset, target(address)))
+        }
+        LdrImmediateT3 | LdrbImmediateT2 | LdrhImmediateT2 | LdrsbImmediateT1 | LdrshImmediateT1
+        | StrImmediateT3 | StrbImmediateT2 | StrhImmediateT2 => {
+            let base = single_name(&instruction.instruction);
+            let address = indexed(rn, get_bits(hw2, 0..=11) as i64, true, false);
+            (name(base, "", ".w"), format!("{}, {}", r(rt), address))
+        }
+        LdrImmediateT4 | LdrbImmediateT3 | LdrhImmediateT3 | LdrsbImmediateT2 | LdrshImmediateT2
+        | StrImmediateT4 | StrbImmediateT3 | StrhImmediateT3 => {
+            let base = single_name(&instruction.instruction);
+            let offset = get_bits(hw2, 0..=7) as i64;
+            let offset = if get_bit(hw2, 9) { offset } else { -offset };
+            let address = indexed(rn, offset, get_bit(hw2, 10), get_bit(hw2, 8));
+            (name(base, "", ""), format!("{}, {}", r(rt), address))
+        }
+        LdrtT1 | LdrbtT1 | LdrhtT1 | LdrsbtT1 | LdrshtT1 | StrtT1 | StrbtT1 | StrhtT1 => {
+            let base = single_name(&instruction.instruction);
+            let address = indexed(rn, get_bits(hw2, 0..=7) as i64, true, false);
+            (name(base, "", ""), format!("{}, {}", r(rt), address))
+        }
+        LdrRegisterT2 | LdrbRegisterT2 | LdrhRegisterT2 | LdrsbRegisterT2 | LdrshRegisterT2 | StrRegisterT2
+        | StrbRegisterT2 | StrhRegisterT2 => {
+            let base = single_name(&instruction.instruction);
+            let shift = shift(0b00, get_bits(hw2, 4..=5));
+            (name(base, "", ".w"), format!("{}, [{}, {}{}]", r(rt), r(rn), r(rm), shift))
+        }
+    }
+}
+
+/// The mnemonic of a 32 bit single load or store
+fn single_name(instruction: &InstructionType) -> &'static str {
+    match instruction {
+        LdrImmediateT3 | LdrImmediateT4 | LdrRegisterT2 => "ldr",
+        LdrbImmediateT2 | LdrbImmediateT3 | LdrbRegisterT2 => "ldrb",
+        LdrhImmediateT2 | LdrhImmediateT3 | LdrhRegisterT2 => "ldrh",
+        LdrsbImmediateT1 | LdrsbImmediateT2 | LdrsbRegisterT2 => "ldrsb",
+        LdrshImmediateT1 | LdrshImmediateT2 | LdrshRegisterT2 => "ldrsh",
+        StrImmediateT3 | StrImmediateT4 | StrRegisterT2 => "str",
+        StrbImmediateT2 | StrbImmediateT3 | StrbRegisterT2 => "strb",
+        StrhImmediateT2 | StrhImmediateT3 | StrhRegisterT2 => "strh",
+        LdrtT1 => "ldrt",
+        LdrbtT1 => "ldrbt",
+        LdrhtT1 => "ldrht",
+        LdrsbtT1 => "ldrsbt",
+        LdrshtT1 => "ldrsht",
+        StrtT1 => "strt",
+        StrbtT1 => "strbt",
+        _ => "strht",
+    }
+}
+
+/// The name of a special register for MRS and MSR
+fn special_register(sysm: u16) -> Option<&'static str> {
+    Some(match sysm {
+        0 => "APSR",
+        1 => "IAPSR",
+        2 => "EAPSR",
+        3 => "PSR",
+        5 => "IPSR",
+        6 => "EPSR",
+        7 => "IEPSR",
+        8 => "MSP",
+        9 => "PSP",
+        10 => "MSPLIM",
+        11 => "PSPLIM",
+        16 => "PRIMASK",
+        17 => "BASEPRI",
+        18 => "BASEPRI_MAX",
+        19 => "FAULTMASK",
+        20 => "CONTROL",
+        _ => return None,
+    })
+}
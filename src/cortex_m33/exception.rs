@@ -1,8 +1,14 @@
-use crate::cortex_m33::operation::get_bits;
 use std::collections::HashMap;
 
+use super::nvic::IRQ_COUNT;
 use super::CortexM33;
 
+/// The priority bits implemented for each configurable exception, the rest read as zero
+pub const PRIORITY_MASK: u8 = 0xf0;
+
+/// The execution priority with no exception active and nothing masked, below every configurable priority
+pub const THREAD_PRIORITY: i16 = 256;
+
 #[derive(Debug, Clone, Copy)]
 pub enum InterruptException {
     PendSV,
@@ -15,6 +21,9 @@ pub enum Exception {
     Reset,
     NMI,
     HardFault,
+    MemManage,
+    BusFault,
+    UsageFault,
     SVCall,
     Interrupt(InterruptException),
 }
@@ -25,6 +34,9 @@ impl Exception {
             Exception::Reset => 1,
             Exception::NMI => 2,
             Exception::HardFault => 3,
+            Exception::MemManage => 4,
+            Exception::BusFault => 5,
+            Exception::UsageFault => 6,
             Exception::SVCall => 11,
             Exception::Interrupt(interrupt) => match interrupt {
                 InterruptException::PendSV => 14,
@@ -37,17 +49,26 @@ impl Exception {
 
 pub struct Exceptions {
     pub active: HashMap<u8, Exception>,
+    pub pending: HashMap<u8, Exception>,
+}
+
+impl Default for Exceptions {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Exceptions {
     pub fn new() -> Self {
         Self {
             active: HashMap::new(),
+            pending: HashMap::new(),
         }
     }
 
-    pub fn priority(cortex: &CortexM33, n: u8) -> i8 {
-        assert!(n >= 1 && n <= 48);
+    /// The configured priority of exception n, negative for the fixed priority exceptions
+    pub fn priority(cortex: &CortexM33, n: u8) -> i16 {
+        assert!((1..16 + IRQ_COUNT).contains(&n));
 
         let result = if n == Exception::Reset.number() {
             -3
@@ -55,26 +76,65 @@ impl Exceptions {
             -2
         } else if n == Exception::HardFault.number() {
             -1
+        } else if n == Exception::MemManage.number() {
+            cortex.shpr.pri_4() as i16
+        } else if n == Exception::BusFault.number() {
+            cortex.shpr.pri_5() as i16
+        } else if n == Exception::UsageFault.number() {
+            cortex.shpr.pri_6() as i16
         } else if n == Exception::SVCall.number() {
-            cortex.shpr.pri_11() as i8
+            cortex.shpr.pri_11() as i16
         } else if n == Exception::Interrupt(InterruptException::PendSV).number() {
-            cortex.shpr.pri_14() as i8
+            cortex.shpr.pri_14() as i16
         } else if n == Exception::Interrupt(InterruptException::SysTick).number() {
-            cortex.shpr.pri_15() as i8
+            cortex.shpr.pri_15() as i16
         } else if n >= 16 {
-            let r = ((n - 16) / 4) as usize;
-            let v = n as usize % 4;
-            let v = v * 8;
-
-            let bits = get_bits(cortex.nvic[r], (v + 6)..=(v + 7)) as i8;
-
-            bits
+            cortex.nvic.priority(n - 16) as i16
         } else {
-            4
+            THREAD_PRIORITY
         };
 
-        assert!(-3 <= result && result <= 4);
+        assert!((-3..=THREAD_PRIORITY).contains(&result));
 
         result
     }
+
+    /// Clears the subpriority bits selected by AIRCR.PRIGROUP, leaving the part of the priority that decides
+    /// preemption
+    pub fn group(cortex: &CortexM33, priority: i16) -> i16 {
+        if priority < 0 {
+            return priority;
+        }
+
+        let group_value = 2 << cortex.nvic.prigroup;
+        priority - (priority % group_value)
+    }
+
+    pub fn group_priority(cortex: &CortexM33, n: u8) -> i16 {
+        Self::group(cortex, Self::priority(cortex, n))
+    }
+
+    /// The pending exception that would be taken next. The lowest group priority wins, then the lowest
+    /// subpriority, then the lowest exception number. IRQs that are disabled in the NVIC are never picked.
+    pub fn highest_pending(cortex: &CortexM33) -> Option<Exception> {
+        cortex
+            .exceptions
+            .pending
+            .values()
+            .copied()
+            .filter(|exception| match exception {
+                Exception::Interrupt(InterruptException::ExternalInterrupt(irq)) => cortex.nvic.is_enabled(*irq),
+                _ => true,
+            })
+            .min_by_key(|exception| (Self::priority(cortex, exception.number()), exception.number()))
+    }
+
+    /// Marks the exception as pending, it is taken once its priority allows
+    pub fn set_pending(&mut self, exception: Exception) {
+        self.pending.insert(exception.number(), exception);
+    }
+
+    pub fn is_pending(&self, exception: Exception) -> bool {
+        self.pending.contains_key(&exception.number())
+    }
 }
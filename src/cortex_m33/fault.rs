@@ -0,0 +1,101 @@
+use crate::cortex_m33::exception::Exception;
+use crate::BusError;
+
+// CFSR.UFSR, bits 16 to 31
+pub const CFSR_UNDEFINSTR: u32 = 1 << 16;
+pub const CFSR_INVSTATE: u32 = 1 << 17;
+pub const CFSR_INVPC: u32 = 1 << 18;
+pub const CFSR_DIVBYZERO: u32 = 1 << 25;
+
+// CFSR.BFSR, bits 8 to 15
+pub const CFSR_IBUSERR: u32 = 1 << 8;
+pub const CFSR_PRECISERR: u32 = 1 << 9;
+pub const CFSR_UNSTKERR: u32 = 1 << 11;
+pub const CFSR_STKERR: u32 = 1 << 12;
+pub const CFSR_BFARVALID: u32 = 1 << 15;
+
+pub const HFSR_VECTTBL: u32 = 1 << 1;
+pub const HFSR_FORCED: u32 = 1 << 30;
+
+// SHCSR fault enable bits
+pub const SHCSR_MEMFAULTENA: u32 = 1 << 16;
+pub const SHCSR_BUSFAULTENA: u32 = 1 << 17;
+pub const SHCSR_USGFAULTENA: u32 = 1 << 18;
+
+/// A synchronous fault raised while fetching or executing an instruction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fault {
+    /// A load or store to an address the bus rejected
+    PreciseBus { address: u32, error: BusError },
+    /// An instruction fetch from an address the bus rejected
+    InstructionBus { address: u32, error: BusError },
+    /// A write rejected by the bus while pushing an exception frame
+    Stacking { address: u32, error: BusError },
+    /// A read rejected by the bus while popping an exception frame on exception return
+    Unstacking { address: u32, error: BusError },
+    /// UDF, or an encoding that does not decode to any instruction
+    UndefinedInstruction,
+    /// Execution was attempted with EPSR.T clear
+    InvalidState,
+    /// An exception return with an illegal EXC_RETURN value, or one that does not match the active exceptions
+    InvalidPc,
+    /// SDIV or UDIV by zero while CCR.DIV_0_TRP is set
+    DivideByZero,
+}
+
+impl Fault {
+    /// The configurable fault exception this fault is reported through before any escalation
+    pub fn exception(&self) -> Exception {
+        match self {
+            Fault::PreciseBus { .. }
+            | Fault::InstructionBus { .. }
+            | Fault::Stacking { .. }
+            | Fault::Unstacking { .. } => Exception::BusFault,
+            Fault::UndefinedInstruction
+            | Fault::InvalidState
+            | Fault::InvalidPc
+            | Fault::DivideByZero => Exception::UsageFault,
+        }
+    }
+
+    /// The SHCSR bit that enables the fault's own handler
+    pub fn enable_bit(&self) -> u32 {
+        match self.exception() {
+            Exception::BusFault => SHCSR_BUSFAULTENA,
+            Exception::UsageFault => SHCSR_USGFAULTENA,
+            _ => SHCSR_MEMFAULTENA,
+        }
+    }
+}
+
+/// The fault status and address registers from the System Control Block
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct FaultStatus {
+    pub cfsr: u32,
+    pub hfsr: u32,
+    pub mmfar: u32,
+    pub bfar: u32,
+}
+
+impl FaultStatus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the CFSR syndrome bits for the fault, and BFAR for precise data faults.
+    pub fn record(&mut self, fault: Fault) {
+        match fault {
+            Fault::PreciseBus { address, .. } => {
+                self.cfsr |= CFSR_PRECISERR | CFSR_BFARVALID;
+                self.bfar = address;
+            }
+            Fault::InstructionBus { .. } => self.cfsr |= CFSR_IBUSERR,
+            Fault::Stacking { .. } => self.cfsr |= CFSR_STKERR,
+            Fault::Unstacking { .. } => self.cfsr |= CFSR_UNSTKERR,
+            Fault::UndefinedInstruction => self.cfsr |= CFSR_UNDEFINSTR,
+            Fault::InvalidState => self.cfsr |= CFSR_INVSTATE,
+            Fault::InvalidPc => self.cfsr |= CFSR_INVPC,
+            Fault::DivideByZero => self.cfsr |= CFSR_DIVBYZERO,
+        }
+    }
+}
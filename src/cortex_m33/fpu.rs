@@ -0,0 +1,23 @@
+/// The floating point register file, S0 to S31 and FPSCR. No floating point instructions are decoded yet, so
+/// only a debugger reads and writes it.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Fpu {
+    pub s: [u32; 32],
+    pub fpscr: u32,
+}
+
+impl Fpu {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// D<n> is the pair S<2n> and S<2n+1>, with S<2n> as the low word
+    pub fn d(&self, n: usize) -> u64 {
+        (self.s[2 * n + 1] as u64) << 32 | self.s[2 * n] as u64
+    }
+
+    pub fn set_d(&mut self, n: usize, value: u64) {
+        self.s[2 * n] = value as u32;
+        self.s[2 * n + 1] = (value >> 32) as u32;
+    }
+}
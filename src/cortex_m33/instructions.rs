@@ -1,6 +1,7 @@
 use crate::cortex_m33::operation::{
-    add_with_carry, branch_write_pc, condition_passed, decode_imm_shift, in_it_block,
-    last_in_it_block, sign_extend, SignExtended,
+    add_with_carry, branch_write_pc, bx_write_pc, condition_passed, decode_imm_shift,
+    decode_reg_shift, in_it_block, it_advance, last_in_it_block, signed_sat_q,
+    thumb_expand_imm_c, unsigned_sat_q,
 };
 use crate::cortex_m33::operation::{get_bit, get_bits, is_zero_bit, shift_c, SRType};
 use crate::cortex_m33::registers::Register;
@@ -14,29 +15,100 @@ pub struct OpCode {
 }
 
 impl OpCode {
-    pub fn from_address(cortex: &CortexM33, address: u32) -> Self {
-        let code = cortex.memory.read_u16(address);
-        Self {
+    pub fn from_address(cortex: &CortexM33, address: u32) -> Result<Self, Fault> {
+        let code = cortex
+            .memory
+            .read_u16(address)
+            .map_err(|error| Fault::InstructionBus { address, error })?;
+        Ok(Self {
             address,
             code,
+        })
+    }
+
+    /// Executes the instruction. On a fault the PC is left pointing at the instruction, so it is the return
+    /// address stacked for the fault handler.
+    pub fn execute(&self, cortex: &mut CortexM33) -> Result<(), Fault> {
+        let result = self.try_execute(cortex);
+        if result.is_err() {
+            cortex.registers.pc.set(self.address);
         }
+
+        result
     }
 
-    pub fn execute(&self, cortex: &mut CortexM33) {
-        let op_code_2 = Self::from_address(cortex, self.address + 2);
-        Instruction::new(self, &op_code_2).execute(cortex);
+    pub fn is_32bit(&self) -> bool {
+        Instruction::is_32bit(self.code)
     }
+
+    fn try_execute(&self, cortex: &mut CortexM33) -> Result<(), Fault> {
+        if !cortex.xpsr.epsr.t() {
+            return Err(Fault::InvalidState);
+        }
+
+        let op_code_2 = if Instruction::is_32bit(self.code) {
+            Self::from_address(cortex, self.address + 2)?
+        } else {
+            Self { code: 0, address: self.address + 2 }
+        };
+        Instruction::new(self, &op_code_2)?.execute(cortex)
+    }
+}
+
+/// A decoded instruction, shared by execution, the decode cache and the disassembler
+#[derive(Clone, Copy)]
+pub(super) struct Instruction {
+    pub(super) opcode: OpCode,
+    pub(super) opcode_2: OpCode,
+    pub(super) instruction: InstructionType,
+    pub(super) operands: Operands,
+}
+
+/// When an instruction updates the condition flags
+#[derive(Clone, Copy, Default, PartialEq)]
+pub(super) enum SetFlags {
+    #[default]
+    Never,
+    Always,
+    /// The 16-bit data processing instructions only set them outside an IT block
+    OutsideItBlock,
 }
 
-struct Instruction {
-    opcode: OpCode,
-    opcode_2: OpCode,
-    instruction: InstructionType,
+/// The operand fields of an instruction, taken out of its opcode once when it is decoded so an instruction run
+/// from the decode cache doesn't extract them again. Fields an encoding doesn't have are left at zero.
+#[derive(Clone, Copy, Default)]
+pub(super) struct Operands {
+    /// Rd, which is also Rdn, RdHi, Rt2 and the status register of the store exclusives
+    pub(super) rd: u16,
+    pub(super) rn: u16,
+    pub(super) rm: u16,
+    /// Rt, which is also Ra and RdLo
+    pub(super) rt: u16,
+    /// The immediate, already extended and scaled. Branch offsets are sign extended, and register lists, SYSm, the
+    /// IT bits, the saturation bit position and the bitfield msb or width are kept here too.
+    pub(super) imm32: u32,
+    /// The carry out of an expanded modified immediate, `None` where it is APSR.C
+    pub(super) imm_carry: Option<bool>,
+    /// Whether the second operand, or the offset of a load or store, is Rm shifted rather than imm32
+    pub(super) register: bool,
+    pub(super) shift_t: SRType,
+    /// The shift amount, which is also the bitfield lsb and the rotation of the extends
+    pub(super) shift_n: u16,
+    pub(super) setflags: SetFlags,
+    pub(super) cond: u16,
+    pub(super) index: bool,
+    pub(super) add: bool,
+    pub(super) wback: bool,
 }
 
-#[derive(Debug)]
-enum InstructionType {
+#[derive(Debug, Clone, Copy)]
+pub(super) enum InstructionType {
+    AdcImmediateT1,
+    AdcRegisterT2,
     AdcT1,
+    AddImmediateT3,
+    AddImmediateT4,
+    AddRegisterT3,
     AddSpPlusImmediateT1,
     AddSpPlusImmediateT2,
     AddsT1,
@@ -44,91 +116,239 @@ enum InstructionType {
     AddRegisterT1,
     AddRegisterT2,
     AdrT1,
+    AdrT2,
+    AdrT3,
+    AndImmediateT1,
     AndRegisterT1,
+    AndRegisterT2,
     AsrImmediateT1,
+    AsrImmediateT2,
     AsrRegisterT1,
+    AsrRegisterT2,
+    BfcT1,
+    BfiT1,
+    BicImmediateT1,
+    BicRegisterT2,
     BT1,
     BT2,
     BicRegisterT1,
     BkptT1,
     BlT1,
     BlxT1,
+    BT3,
+    BT4,
     BxT1,
+    CbnzT1,
+    CbzT1,
+    ClrexT1,
+    ClzT1,
+    CmnImmediateT1,
     CmnRegisterT1,
+    CmnRegisterT2,
     CmpImmediateT1,
+    CmpImmediateT2,
     CmpRegisterT1,
     CmpRegisterT2,
+    CmpRegisterT3,
     CpsT1Id,
     CpsT1Ie,
     DmbT1Sy,
     DsbT1Sy,
+    EorImmediateT1,
     EorRegisterT1,
+    EorRegisterT2,
     IsbT1Sy,
+    ItT1,
+    LdabT1,
+    LdaexbT1,
+    LdaexhT1,
+    LdaexT1,
+    LdahT1,
+    LdaT1,
+    LdmdbT1,
     LdmiaT1,
+    LdmT2,
+    LdrbImmediateT2,
+    LdrbImmediateT3,
+    LdrbLiteralT1,
+    LdrbRegisterT2,
+    LdrbtT1,
+    LdrdImmediateT1,
+    LdrdLiteralT1,
+    LdrexbT1,
+    LdrexhT1,
+    LdrexT1,
+    LdrhImmediateT2,
+    LdrhImmediateT3,
+    LdrhLiteralT1,
+    LdrhRegisterT2,
+    LdrhtT1,
     LdrImmediateT1,
     LdrImmediateT2,
+    LdrImmediateT3,
+    LdrImmediateT4,
     LdrLiteralT1,
+    LdrLiteralT2,
     LdrRegisterT1,
     LdrbImmediateT1,
     LdrbRegisterT1,
     LdrhImmediateT1,
     LdrhRegisterT1,
+    LdrRegisterT2,
+    LdrsbImmediateT1,
+    LdrsbImmediateT2,
+    LdrsbLiteralT1,
     LdrsbRegisterT1,
+    LdrsbRegisterT2,
+    LdrsbtT1,
+    LdrshImmediateT1,
+    LdrshImmediateT2,
+    LdrshLiteralT1,
     LdrshRegisterT1,
+    LdrshRegisterT2,
+    LdrshtT1,
+    LdrtT1,
     LslImmediateT1,
+    LslImmediateT2,
     LslRegisterT1,
+    LslRegisterT2,
     LsrImmediateT1,
+    LsrImmediateT2,
     LsrRegisterT1,
+    LsrRegisterT2,
+    MlaT1,
+    MlsT1,
+    MovImmediateT2,
+    MovImmediateT3,
     MovRegisterT1,
     MovImmediateT1,
+    MovRegisterT3,
+    MovtT1,
     MrsT1,
     MsrT1,
     MulT1,
+    MulT2,
+    MvnImmediateT1,
+    MvnRegisterT2,
     MvnT1,
+    NopT2,
+    OrnImmediateT1,
+    OrnRegisterT1,
+    OrrImmediateT1,
     OrrRegisterT1,
+    OrrRegisterT2,
+    PldT1,
+    PliT1,
     PopT1,
     PushT1,
+    RbitT1,
+    Rev16T2,
+    RevshT2,
     RevT1,
     Rev16T1,
     RevshT1,
+    RevT2,
+    RorImmediateT1,
     RorRegisterT1,
+    RorRegisterT2,
+    RrxT1,
     RsbImmediateT1,
     NopT1,
+    RsbImmediateT2,
+    RsbRegisterT1,
+    SbcImmediateT1,
     SbcRegisterT1,
+    SbcRegisterT2,
+    SbfxT1,
+    SdivT1,
     SevT1,
+    SevT2,
+    SmlalT1,
+    SmullT1,
+    SsatT1,
+    StlbT1,
+    StlexbT1,
+    StlexhT1,
+    StlexT1,
+    StlhT1,
+    StlT1,
+    StmdbT1,
     StmiaT1,
+    StmT2,
+    StrbImmediateT2,
+    StrbImmediateT3,
+    StrbRegisterT2,
+    StrbtT1,
+    StrdImmediateT1,
+    StrexbT1,
+    StrexhT1,
+    StrexT1,
+    StrhImmediateT2,
+    StrhImmediateT3,
+    StrhRegisterT2,
+    StrhtT1,
     StrImmediateT1,
     StrImmediateT2,
+    StrImmediateT3,
+    StrImmediateT4,
     StrRegisterT1,
     StrbImmediateT1,
     StrbRegisterT1,
     StrhImmediateT1,
     StrhRegisterT1,
+    StrRegisterT2,
+    StrtT1,
+    SubImmediateT3,
+    SubImmediateT4,
+    SubRegisterT2,
     SubSpMinusImmediateT1,
     SubT1,
     SubT2,
     SubRegisterT1,
     SvcT1,
     SxtbT1,
+    SxtbT2,
     SxthT1,
+    SxthT2,
+    TbbT1,
+    TbhT1,
+    TeqImmediateT1,
+    TeqRegisterT1,
+    TstImmediateT1,
     TstRegisterT1,
+    TstRegisterT2,
+    UbfxT1,
     UdfT1,
     UdfT2,
+    UdivT1,
+    UmlalT1,
+    UmullT1,
+    UsatT1,
     UxtbT1,
+    UxtbT2,
     UxthT1,
+    UxthT2,
     WfeT1,
+    WfeT2,
     WfiT1,
+    WfiT2,
     YieldT1,
+    YieldT2,
 }
 
 use InstructionType::*;
 
 use super::apsr::Apsr;
-use super::CortexM33;
+use super::control::{NPriv, SpSel};
+use super::fault::Fault;
+use super::scb::CCR_DIV_0_TRP;
+use super::{CortexM33, Mode};
 
 impl Instruction {
-    pub fn new(opcode: &OpCode, opcode_2: &OpCode) -> Self {
-        let instruction = if opcode.code >> 6 == 0b0100000101 {
+    pub fn new(opcode: &OpCode, opcode_2: &OpCode) -> Result<Self, Fault> {
+        let instruction = if Self::is_32bit(opcode.code) {
+            Self::decode_32bit(opcode.code, opcode_2.code).ok_or(Fault::UndefinedInstruction)?
+        } else if opcode.code >> 6 == 0b0100000101 {
             AdcT1
         } else if opcode.code >> 11 == 0b10101 {
             AddSpPlusImmediateT1
@@ -158,15 +378,14 @@ impl Instruction {
             BicRegisterT1
         } else if opcode.code >> 8 == 0b10111110 {
             BkptT1
-        } else if opcode.code >> 11 == 0b11110
-            && opcode_2.code >> 14 == 0b11
-            && ((opcode_2.code >> 12) & 0x1) == 1
-        {
-            BlT1
         } else if opcode.code >> 7 == 0b010001111 && (opcode.code & 0x7) == 0 {
             BlxT1
         } else if opcode.code >> 7 == 0b010001110 && (opcode.code & 0x7) == 0 {
             BxT1
+        } else if opcode.code & 0xfd00 == 0xb900 {
+            CbnzT1
+        } else if opcode.code & 0xfd00 == 0xb100 {
+            CbzT1
         } else if opcode.code >> 6 == 0b0100001011 {
             CmnRegisterT1
         } else if opcode.code >> 11 == 0b00101 {
@@ -179,14 +398,10 @@ impl Instruction {
             CpsT1Id
         } else if opcode.code == 0xb662 {
             CpsT1Ie
-        } else if opcode.code == 0xf3bf && (opcode_2.code & 0xfff0) == 0x8f50 {
-            DmbT1Sy
-        } else if opcode.code == 0xf3bf && (opcode_2.code & 0xfff0) == 0x8f40 {
-            DsbT1Sy
         } else if opcode.code >> 6 == 0b0100000001 {
             EorRegisterT1
-        } else if opcode.code == 0xf3bf && (opcode_2.code & 0xfff0) == 0x8f60 {
-            IsbT1Sy
+        } else if opcode.code >> 8 == 0b10111111 && opcode.code & 0xf != 0 {
+            ItT1
         } else if opcode.code >> 11 == 0b11001 {
             LdmiaT1
         } else if opcode.code >> 11 == 0b01101 {
@@ -221,10 +436,6 @@ impl Instruction {
             MovRegisterT1
         } else if opcode.code >> 11 == 0b00100 {
             MovImmediateT1
-        } else if opcode.code == 0b1111001111101111 && opcode_2.code >> 12 == 0b1000 {
-            MrsT1
-        } else if opcode.code >> 4 == 0b111100111000 && opcode_2.code >> 8 == 0b10001000 {
-            MsrT1
         } else if opcode.code >> 6 == 0b0100001101 {
             MulT1
         } else if opcode.code >> 6 == 0b0100001111 {
@@ -285,8 +496,6 @@ impl Instruction {
             TstRegisterT1
         } else if opcode.code >> 8 == 0b11011110 {
             UdfT1
-        } else if opcode.code >> 4 == 0b111101111111 && opcode_2.code >> 12 == 0b1010 {
-            UdfT2
         } else if opcode.code >> 6 == 0b1011001011 {
             UxtbT1
         } else if opcode.code >> 6 == 0b1011001010 {
@@ -298,106 +507,484 @@ impl Instruction {
         } else if opcode.code == 0b1011111100010000 {
             YieldT1
         } else {
-            println!("opcode.code: {:#x}", opcode.code);
-            unimplemented!("Instruction not implemented, file a github issue.");
+            return Err(Fault::UndefinedInstruction);
         };
 
-        Self {
+        Ok(Self {
             opcode: *opcode,
             opcode_2: *opcode_2,
             instruction,
+            operands: Operands::new(instruction, opcode.code, opcode_2.code),
+        })
+    }
+
+    /// Returns whether the halfword is the first half of a 32-bit Thumb-2 instruction.
+    fn is_32bit(hw1: u16) -> bool {
+        matches!(hw1 >> 11, 0b11101..=0b11111)
+    }
+
+    /// Decodes a 32-bit Thumb-2 instruction, returning `None` for encodings that are not implemented.
+    fn decode_32bit(hw1: u16, hw2: u16) -> Option<InstructionType> {
+        let op1 = get_bits(hw1, 11..=12);
+        let op2 = get_bits(hw1, 4..=10);
+
+        if op1 == 0b01 {
+            if op2 >> 5 == 0b00 && get_bit(op2, 2) {
+                Self::decode_load_store_dual_exclusive(hw1, hw2)
+            } else if op2 >> 5 == 0b00 {
+                Self::decode_load_store_multiple(hw1)
+            } else if op2 >> 5 == 0b01 {
+                Self::decode_data_processing_shifted_register(hw1, hw2)
+            } else {
+                None
+            }
+        } else if op1 == 0b10 {
+            if get_bit(hw2, 15) {
+                Self::decode_branch_misc_control(hw1, hw2)
+            } else if get_bit(op2, 5) {
+                Self::decode_data_processing_plain_immediate(hw1, hw2)
+            } else {
+                Self::decode_data_processing_modified_immediate(hw1, hw2)
+            }
+        } else if op2 >> 5 == 0b00 && get_bits(op2, 0..=2) != 0b111 {
+            Self::decode_load_store_single(hw1, hw2)
+        } else if op2 >> 4 == 0b010 {
+            Self::decode_data_processing_register(hw1, hw2)
+        } else if op2 >> 3 == 0b0110 {
+            Self::decode_multiply(hw1, hw2)
+        } else if op2 >> 3 == 0b0111 {
+            Self::decode_long_multiply_divide(hw1, hw2)
+        } else {
+            None
+        }
+    }
+
+    fn decode_load_store_multiple(hw1: u16) -> Option<InstructionType> {
+        let op = get_bits(hw1, 7..=8);
+        let load = get_bit(hw1, 4);
+
+        if op == 0b01 && load {
+            Some(LdmT2)
+        } else if op == 0b01 {
+            Some(StmT2)
+        } else if op == 0b10 && load {
+            Some(LdmdbT1)
+        } else if op == 0b10 {
+            Some(StmdbT1)
+        } else {
+            None
+        }
+    }
+
+    fn decode_load_store_dual_exclusive(hw1: u16, hw2: u16) -> Option<InstructionType> {
+        let op1 = get_bits(hw1, 7..=8);
+        let op2 = get_bits(hw1, 4..=5);
+        let op3 = get_bits(hw2, 4..=7);
+        let rn = get_bits(hw1, 0..=3);
+
+        if op1 == 0b00 && op2 == 0b00 {
+            Some(StrexT1)
+        } else if op1 == 0b00 && op2 == 0b01 {
+            Some(LdrexT1)
+        } else if (op1 >> 1 == 0 && op2 == 0b10) || (op1 >> 1 == 1 && op2 & 0b1 == 0) {
+            Some(StrdImmediateT1)
+        } else if (op1 >> 1 == 0 && op2 == 0b11) || (op1 >> 1 == 1 && op2 & 0b1 == 1) {
+            if rn == 0b1111 {
+                Some(LdrdLiteralT1)
+            } else {
+                Some(LdrdImmediateT1)
+            }
+        } else if op1 == 0b01 && op2 == 0b00 {
+            match op3 {
+                0b0100 => Some(StrexbT1),
+                0b0101 => Some(StrexhT1),
+                0b1000 => Some(StlbT1),
+                0b1001 => Some(StlhT1),
+                0b1010 => Some(StlT1),
+                0b1100 => Some(StlexbT1),
+                0b1101 => Some(StlexhT1),
+                0b1110 => Some(StlexT1),
+                _ => None,
+            }
+        } else if op1 == 0b01 && op2 == 0b01 {
+            match op3 {
+                0b0000 => Some(TbbT1),
+                0b0001 => Some(TbhT1),
+                0b0100 => Some(LdrexbT1),
+                0b0101 => Some(LdrexhT1),
+                0b1000 => Some(LdabT1),
+                0b1001 => Some(LdahT1),
+                0b1010 => Some(LdaT1),
+                0b1100 => Some(LdaexbT1),
+                0b1101 => Some(LdaexhT1),
+                0b1110 => Some(LdaexT1),
+                _ => None,
+            }
+        } else {
+            None
+        }
+    }
+
+    fn decode_data_processing_shifted_register(hw1: u16, hw2: u16) -> Option<InstructionType> {
+        let op = get_bits(hw1, 5..=8);
+        let s = get_bit(hw1, 4);
+        let rn = get_bits(hw1, 0..=3);
+        let rd = get_bits(hw2, 8..=11);
+        let imm5 = (get_bits(hw2, 12..=14) << 2) | get_bits(hw2, 6..=7);
+        let shift_type = get_bits(hw2, 4..=5);
+
+        match op {
+            0b0000 if rd == 0b1111 && s => Some(TstRegisterT2),
+            0b0000 => Some(AndRegisterT2),
+            0b0001 => Some(BicRegisterT2),
+            0b0010 if rn == 0b1111 => match shift_type {
+                0b00 if imm5 == 0 => Some(MovRegisterT3),
+                0b00 => Some(LslImmediateT2),
+                0b01 => Some(LsrImmediateT2),
+                0b10 => Some(AsrImmediateT2),
+                _ if imm5 == 0 => Some(RrxT1),
+                _ => Some(RorImmediateT1),
+            },
+            0b0010 => Some(OrrRegisterT2),
+            0b0011 if rn == 0b1111 => Some(MvnRegisterT2),
+            0b0011 => Some(OrnRegisterT1),
+            0b0100 if rd == 0b1111 && s => Some(TeqRegisterT1),
+            0b0100 => Some(EorRegisterT2),
+            0b1000 if rd == 0b1111 && s => Some(CmnRegisterT2),
+            0b1000 => Some(AddRegisterT3),
+            0b1010 => Some(AdcRegisterT2),
+            0b1011 => Some(SbcRegisterT2),
+            0b1101 if rd == 0b1111 && s => Some(CmpRegisterT3),
+            0b1101 => Some(SubRegisterT2),
+            0b1110 => Some(RsbRegisterT1),
+            _ => None,
+        }
+    }
+
+    fn decode_data_processing_modified_immediate(hw1: u16, hw2: u16) -> Option<InstructionType> {
+        let op = get_bits(hw1, 5..=8);
+        let s = get_bit(hw1, 4);
+        let rn = get_bits(hw1, 0..=3);
+        let rd = get_bits(hw2, 8..=11);
+
+        match op {
+            0b0000 if rd == 0b1111 && s => Some(TstImmediateT1),
+            0b0000 => Some(AndImmediateT1),
+            0b0001 => Some(BicImmediateT1),
+            0b0010 if rn == 0b1111 => Some(MovImmediateT2),
+            0b0010 => Some(OrrImmediateT1),
+            0b0011 if rn == 0b1111 => Some(MvnImmediateT1),
+            0b0011 => Some(OrnImmediateT1),
+            0b0100 if rd == 0b1111 && s => Some(TeqImmediateT1),
+            0b0100 => Some(EorImmediateT1),
+            0b1000 if rd == 0b1111 && s => Some(CmnImmediateT1),
+            0b1000 => Some(AddImmediateT3),
+            0b1010 => Some(AdcImmediateT1),
+            0b1011 => Some(SbcImmediateT1),
+            0b1101 if rd == 0b1111 && s => Some(CmpImmediateT2),
+            0b1101 => Some(SubImmediateT3),
+            0b1110 => Some(RsbImmediateT2),
+            _ => None,
+        }
+    }
+
+    fn decode_data_processing_plain_immediate(hw1: u16, hw2: u16) -> Option<InstructionType> {
+        let op = get_bits(hw1, 4..=8);
+        let rn = get_bits(hw1, 0..=3);
+        let shift = (get_bits(hw2, 12..=14) << 2) | get_bits(hw2, 6..=7);
+
+        match op {
+            0b00000 if rn == 0b1111 => Some(AdrT3),
+            0b00000 => Some(AddImmediateT4),
+            0b00100 => Some(MovImmediateT3),
+            0b01010 if rn == 0b1111 => Some(AdrT2),
+            0b01010 => Some(SubImmediateT4),
+            0b01100 => Some(MovtT1),
+            // SSAT16 and USAT16 share these encodings with a zero shift, and are not implemented
+            0b10000 => Some(SsatT1),
+            0b10010 if shift != 0 => Some(SsatT1),
+            0b10100 => Some(SbfxT1),
+            0b10110 if rn == 0b1111 => Some(BfcT1),
+            0b10110 => Some(BfiT1),
+            0b11000 => Some(UsatT1),
+            0b11010 if shift != 0 => Some(UsatT1),
+            0b11100 => Some(UbfxT1),
+            _ => None,
         }
     }
 
-    pub fn execute(&self, cortex_m33: &mut CortexM33) {
-        println!("Instruction: {:?}", self.instruction);
+    fn decode_branch_misc_control(hw1: u16, hw2: u16) -> Option<InstructionType> {
+        let op = get_bits(hw1, 4..=10);
+        let op1 = get_bits(hw2, 12..=14);
+
+        if op1 == 0b010 && op == 0b1111111 {
+            Some(UdfT2)
+        } else if op1 & 0b101 == 0b000 {
+            if get_bits(op, 3..=5) != 0b111 {
+                Some(BT3)
+            } else if op >> 1 == 0b011100 {
+                Some(MsrT1)
+            } else if op == 0b0111010 {
+                match get_bits(hw2, 0..=10) {
+                    0b000_0000_0000 => Some(NopT2),
+                    0b000_0000_0001 => Some(YieldT2),
+                    0b000_0000_0010 => Some(WfeT2),
+                    0b000_0000_0011 => Some(WfiT2),
+                    0b000_0000_0100 => Some(SevT2),
+                    _ => None,
+                }
+            } else if op == 0b0111011 {
+                match get_bits(hw2, 4..=7) {
+                    0b0010 => Some(ClrexT1),
+                    0b0100 => Some(DsbT1Sy),
+                    0b0101 => Some(DmbT1Sy),
+                    0b0110 => Some(IsbT1Sy),
+                    _ => None,
+                }
+            } else if op >> 1 == 0b011111 {
+                Some(MrsT1)
+            } else {
+                None
+            }
+        } else if op1 & 0b101 == 0b001 {
+            Some(BT4)
+        } else if op1 & 0b101 == 0b101 {
+            Some(BlT1)
+        } else {
+            None
+        }
+    }
+
+    fn decode_load_store_single(hw1: u16, hw2: u16) -> Option<InstructionType> {
+        let load = get_bit(hw1, 4);
+        let signed = get_bit(hw1, 8);
+        let size = get_bits(hw1, 5..=6);
+        let imm12 = get_bit(hw1, 7);
+        let rn = get_bits(hw1, 0..=3);
+        let rt = get_bits(hw2, 12..=15);
+        let op2 = get_bits(hw2, 6..=11);
+
+        // The P, U and W bits of the 8 bit immediate forms, 0b110 being the unprivileged form
+        let puw = get_bits(hw2, 8..=10);
+        let imm8 = op2 >> 5 == 1;
+        let unprivileged = imm8 && puw == 0b110;
+        let register = !imm12 && op2 == 0;
+
+        if !load {
+            if signed {
+                return None;
+            }
+
+            return match size {
+                0b00 if imm12 => Some(StrbImmediateT2),
+                0b00 if unprivileged => Some(StrbtT1),
+                0b00 if imm8 => Some(StrbImmediateT3),
+                0b00 if register => Some(StrbRegisterT2),
+                0b01 if imm12 => Some(StrhImmediateT2),
+                0b01 if unprivileged => Some(StrhtT1),
+                0b01 if imm8 => Some(StrhImmediateT3),
+                0b01 if register => Some(StrhRegisterT2),
+                0b10 if imm12 => Some(StrImmediateT3),
+                0b10 if unprivileged => Some(StrtT1),
+                0b10 if imm8 => Some(StrImmediateT4),
+                0b10 if register => Some(StrRegisterT2),
+                _ => None,
+            };
+        }
+
+        // Loads to the PC from byte and halfword addresses are the preload hints
+        if rt == 0b1111 && size != 0b10 {
+            return if signed { Some(PliT1) } else { Some(PldT1) };
+        }
+
+        match (signed, size) {
+            (false, 0b00) if rn == 0b1111 => Some(LdrbLiteralT1),
+            (false, 0b00) if imm12 => Some(LdrbImmediateT2),
+            (false, 0b00) if unprivileged => Some(LdrbtT1),
+            (false, 0b00) if imm8 => Some(LdrbImmediateT3),
+            (false, 0b00) if register => Some(LdrbRegisterT2),
+            (false, 0b01) if rn == 0b1111 => Some(LdrhLiteralT1),
+            (false, 0b01) if imm12 => Some(LdrhImmediateT2),
+            (false, 0b01) if unprivileged => Some(LdrhtT1),
+            (false, 0b01) if imm8 => Some(LdrhImmediateT3),
+            (false, 0b01) if register => Some(LdrhRegisterT2),
+            (false, 0b10) if rn == 0b1111 => Some(LdrLiteralT2),
+            (false, 0b10) if imm12 => Some(LdrImmediateT3),
+            (false, 0b10) if unprivileged => Some(LdrtT1),
+            (false, 0b10) if imm8 => Some(LdrImmediateT4),
+            (false, 0b10) if register => Some(LdrRegisterT2),
+            (true, 0b00) if rn == 0b1111 => Some(LdrsbLiteralT1),
+            (true, 0b00) if imm12 => Some(LdrsbImmediateT1),
+            (true, 0b00) if unprivileged => Some(LdrsbtT1),
+            (true, 0b00) if imm8 => Some(LdrsbImmediateT2),
+            (true, 0b00) if register => Some(LdrsbRegisterT2),
+            (true, 0b01) if rn == 0b1111 => Some(LdrshLiteralT1),
+            (true, 0b01) if imm12 => Some(LdrshImmediateT1),
+            (true, 0b01) if unprivileged => Some(LdrshtT1),
+            (true, 0b01) if imm8 => Some(LdrshImmediateT2),
+            (true, 0b01) if register => Some(LdrshRegisterT2),
+            _ => None,
+        }
+    }
+
+    fn decode_data_processing_register(hw1: u16, hw2: u16) -> Option<InstructionType> {
+        let op1 = get_bits(hw1, 4..=7);
+        let op2 = get_bits(hw2, 4..=7);
+        let rn = get_bits(hw1, 0..=3);
+
+        if get_bits(hw2, 12..=15) != 0b1111 {
+            return None;
+        }
+
+        if op2 == 0b0000 && op1 >> 3 == 0 {
+            match op1 >> 1 {
+                0b000 => Some(LslRegisterT2),
+                0b001 => Some(LsrRegisterT2),
+                0b010 => Some(AsrRegisterT2),
+                _ => Some(RorRegisterT2),
+            }
+        } else if op2 >> 3 == 1 && rn == 0b1111 {
+            // The extend and add forms with a real Rn are part of the DSP extension
+            match op1 {
+                0b0000 => Some(SxthT2),
+                0b0001 => Some(UxthT2),
+                0b0100 => Some(SxtbT2),
+                0b0101 => Some(UxtbT2),
+                _ => None,
+            }
+        } else if op1 >> 2 == 0b10 && op2 >> 2 == 0b10 {
+            match (get_bits(op1, 0..=1), get_bits(op2, 0..=1)) {
+                (0b01, 0b00) => Some(RevT2),
+                (0b01, 0b01) => Some(Rev16T2),
+                (0b01, 0b10) => Some(RbitT1),
+                (0b01, 0b11) => Some(RevshT2),
+                (0b11, 0b00) => Some(ClzT1),
+                _ => None,
+            }
+        } else {
+            None
+        }
+    }
+
+    fn decode_multiply(hw1: u16, hw2: u16) -> Option<InstructionType> {
+        let op1 = get_bits(hw1, 4..=6);
+        let op2 = get_bits(hw2, 4..=5);
+        let ra = get_bits(hw2, 12..=15);
+
+        if get_bits(hw2, 6..=7) != 0b00 || op1 != 0b000 {
+            return None;
+        }
+
+        match op2 {
+            0b00 if ra == 0b1111 => Some(MulT2),
+            0b00 => Some(MlaT1),
+            0b01 => Some(MlsT1),
+            _ => None,
+        }
+    }
+
+    fn decode_long_multiply_divide(hw1: u16, hw2: u16) -> Option<InstructionType> {
+        let op1 = get_bits(hw1, 4..=6);
+        let op2 = get_bits(hw2, 4..=7);
+
+        match (op1, op2) {
+            (0b000, 0b0000) => Some(SmullT1),
+            (0b001, 0b1111) => Some(SdivT1),
+            (0b010, 0b0000) => Some(UmullT1),
+            (0b011, 0b1111) => Some(UdivT1),
+            (0b100, 0b0000) => Some(SmlalT1),
+            (0b110, 0b0000) => Some(UmlalT1),
+            _ => None,
+        }
+    }
+
+    pub fn execute(&self, cortex_m33: &mut CortexM33) -> Result<(), Fault> {
         let opcode_pc = cortex_m33.registers.pc.get() & !1;
-        let opcode = self.opcode.code;
-        let opcode_2 = self.opcode_2;
+        let size = if Self::is_32bit(self.opcode.code) { 4 } else { 2 };
 
         cortex_m33
             .registers
             .pc
-            .set(cortex_m33.registers.pc.get() + 2);
+            .set(cortex_m33.registers.pc.get() + size);
+
+        cortex_m33.exception_returned = false;
+
+        // Instructions inside an IT block that fail their condition still move ITSTATE along
+        if in_it_block(&cortex_m33.xpsr.epsr) && !matches!(self.instruction, BkptT1) {
+            let cond = get_bits(cortex_m33.xpsr.epsr.it(), 4..=7) as u16;
+            if !condition_passed(&cortex_m33.xpsr.apsr, cond) {
+                it_advance(&mut cortex_m33.xpsr.epsr);
+                return Ok(());
+            }
+        }
+
+        let Operands { rd, rn, rm, rt, imm32, .. } = self.operands;
+        let setflags = match self.operands.setflags {
+            SetFlags::Never => false,
+            SetFlags::Always => true,
+            SetFlags::OutsideItBlock => !in_it_block(&cortex_m33.xpsr.epsr),
+        };
 
         match self.instruction {
-            AdcT1 => {
-                let rm = (opcode >> 3) & 0x7;
-                let rdn = opcode & 0x7;
+            AdcImmediateT1 | AdcRegisterT2 | AdcT1 | AddImmediateT3 | AddRegisterT1 | AddRegisterT3 | AddsT1
+            | AddsT2 | CmnImmediateT1 | CmnRegisterT1 | CmnRegisterT2 | CmpImmediateT1 | CmpImmediateT2
+            | CmpRegisterT1 | CmpRegisterT2 | CmpRegisterT3 | RsbImmediateT1 | RsbImmediateT2 | RsbRegisterT1
+            | SbcImmediateT1 | SbcRegisterT1 | SbcRegisterT2 | SubImmediateT3 | SubRegisterT1 | SubRegisterT2
+            | SubT1 | SubT2 => {
+                if matches!(self.instruction, CmpRegisterT2) && ((rn < 8 && rm < 8) || rn == 15 || rm == 15) {
+                    unpredictable!();
+                }
+
+                let carry = cortex_m33.xpsr.apsr.c();
+                let (operand, _) = self.shifted_operand_c(cortex_m33, opcode_pc, carry);
+                let rn_value = read_register(cortex_m33, opcode_pc, rn);
+
+                let (x, y, carry_in) = match self.instruction {
+                    AdcImmediateT1 | AdcRegisterT2 | AdcT1 => (rn_value, operand, carry),
+                    AddImmediateT3 | AddRegisterT1 | AddRegisterT3 | AddsT1 | AddsT2 | CmnImmediateT1
+                    | CmnRegisterT1 | CmnRegisterT2 => (rn_value, operand, false),
+                    RsbImmediateT1 | RsbImmediateT2 | RsbRegisterT1 => (!rn_value, operand, true),
+                    SbcImmediateT1 | SbcRegisterT1 | SbcRegisterT2 => (rn_value, !operand, carry),
+                    _ => (rn_value, !operand, true),
+                };
 
-                let rm_value = cortex_m33.get_register_from_number(rm).get();
-                let rdn_value = cortex_m33.get_register_from_number(rdn).get()
-                    + cortex_m33.xpsr.apsr.c() as u32;
                 let result = add_instruction_update_flags(
                     &mut cortex_m33.xpsr.apsr,
-                    rm_value,
-                    rdn_value,
-                    false,
+                    x,
+                    y,
+                    carry_in,
+                    setflags,
                 );
-                cortex_m33.get_register_from_number(rdn).set(result);
+
+                if !matches!(
+                    self.instruction,
+                    CmnImmediateT1 | CmnRegisterT1 | CmnRegisterT2 | CmpImmediateT1 | CmpImmediateT2
+                        | CmpRegisterT1 | CmpRegisterT2 | CmpRegisterT3
+                ) {
+                    cortex_m33.get_register_from_number(rd).set(result);
+                }
+            }
+            AddImmediateT4 | SubImmediateT4 => {
+                let rn_value = cortex_m33.get_register_from_number(rn).get();
+                let result = match self.instruction {
+                    AddImmediateT4 => rn_value.wrapping_add(imm32),
+                    _ => rn_value.wrapping_sub(imm32),
+                };
+                cortex_m33.get_register_from_number(rd).set(result);
             }
             AddSpPlusImmediateT1 => {
-                let imm8: u32 = opcode as u32 & 0xff;
-                let rd = (opcode >> 8) & 0x7;
-
                 let sp = cortex_m33.registers.sp.get();
-                cortex_m33
-                    .get_register_from_number(rd)
-                    .set(sp + (imm8 << 2))
+                cortex_m33.get_register_from_number(rd).set(sp + imm32)
             }
             AddSpPlusImmediateT2 => {
-                let imm32 = (opcode as u32 & 0x7f) << 2;
                 cortex_m33
                     .registers
                     .sp
                     .set(cortex_m33.registers.sp.get() + imm32);
             }
-            AddsT1 => {
-                let imm3 = (opcode >> 6) & 0x7;
-                let rn = (opcode >> 3) & 0x7;
-                let rd = opcode & 0x7;
-
-                let rn_value = cortex_m33.get_register_from_number(rn).get();
-                let result = add_instruction_update_flags(
-                    &mut cortex_m33.xpsr.apsr,
-                    rn_value,
-                    imm3 as u32,
-                    false,
-                );
-                cortex_m33.get_register_from_number(rd).set(result);
-            }
-            AddsT2 => {
-                let imm8 = opcode & 0xff;
-                let rdn = (opcode >> 8) & 0x7;
-
-                let rdn_value = cortex_m33.get_register_from_number(rdn).get();
-                let result = add_instruction_update_flags(
-                    &mut cortex_m33.xpsr.apsr,
-                    rdn_value,
-                    imm8 as u32,
-                    false,
-                );
-                cortex_m33.get_register_from_number(rdn).set(result);
-            }
-            AddRegisterT1 => {
-                let rm = (opcode >> 6) & 0x7;
-                let rn = (opcode >> 3) & 0x7;
-                let rd = opcode & 0x7;
-
-                let rn_value = cortex_m33.get_register_from_number(rn).get();
-                let rm_value = cortex_m33.get_register_from_number(rm).get();
-                let result = add_instruction_update_flags(
-                    &mut cortex_m33.xpsr.apsr,
-                    rn_value,
-                    rm_value,
-                    false,
-                );
-                cortex_m33.get_register_from_number(rd).set(result);
-            }
             AddRegisterT2 => {
-                let rdn = ((opcode & 0x80) >> 4) | (opcode & 0x7);
                 let left_value = {
-                    let rdn = cortex_m33.get_register_from_number(rdn);
+                    let rdn = cortex_m33.get_register_from_number(rd);
                     if rdn.is_pc() {
                         cortex_m33.registers.pc.get() + 2
                     } else {
@@ -405,12 +992,11 @@ impl Instruction {
                     }
                 };
 
-                let rm = (opcode >> 3) & 0xf;
                 let right_value = cortex_m33.get_register_from_number(rm).get();
 
                 let result = left_value + right_value;
 
-                let rdn = cortex_m33.get_register_from_number(rdn);
+                let rdn = cortex_m33.get_register_from_number(rd);
 
                 if !rdn.is_sp() && !rdn.is_pc() {
                     rdn.set(result);
@@ -421,159 +1007,114 @@ impl Instruction {
                 }
             }
             AdrT1 => {
-                let imm8 = opcode as u32 & 0xff;
-                let rd = (opcode >> 8) & 0x7;
-
                 cortex_m33
                     .get_register_from_number(rd)
-                    .set((opcode_pc & 0xfffffffc) + 4 + (imm8 << 2));
+                    .set((opcode_pc & 0xfffffffc) + 4 + imm32);
             }
-            AndRegisterT1 => {
-                let rdn = get_bits(opcode, 0..3);
-                let rm = get_bits(opcode, 3..6);
-                let rm_value = cortex_m33.get_register_from_number(rm).get();
-                let (shifted, carry) =
-                    shift_c(rm_value, SRType::Lsl, 0, cortex_m33.xpsr.apsr.c());
-
-                let rdn = cortex_m33.get_register_from_number(rdn);
-                let result = rdn.get() & shifted;
-                rdn.set(result);
-
-                cortex_m33.xpsr.apsr.set_n(get_bit(result, 31));
-                cortex_m33.xpsr.apsr.set_z(is_zero_bit(result));
-                cortex_m33.xpsr.apsr.set_c(carry);
+            AdrT2 | AdrT3 => {
+                let base = (opcode_pc + 4) & 0xfffffffc;
+                let result = match self.instruction {
+                    AdrT2 => base.wrapping_sub(imm32),
+                    _ => base.wrapping_add(imm32),
+                };
+                cortex_m33.get_register_from_number(rd).set(result);
             }
-            AsrImmediateT1 => {
-                let rd = get_bits(opcode, 0..3);
-                let rm = get_bits(opcode, 3..6);
-                let imm5 = get_bits(opcode, 6..11);
-
-                let (_, shift_n) = decode_imm_shift(u2::new(0b10), imm5);
+            AndImmediateT1 | AndRegisterT1 | AndRegisterT2 | AsrImmediateT1 | AsrImmediateT2 | BicImmediateT1
+            | BicRegisterT1 | BicRegisterT2 | EorImmediateT1 | EorRegisterT1 | EorRegisterT2 | LslImmediateT1
+            | LslImmediateT2 | LsrImmediateT1 | LsrImmediateT2 | MovImmediateT1 | MovImmediateT2 | MovRegisterT3
+            | MvnImmediateT1 | MvnRegisterT2 | MvnT1 | OrnImmediateT1 | OrnRegisterT1 | OrrImmediateT1
+            | OrrRegisterT1 | OrrRegisterT2 | RorImmediateT1 | RrxT1 | TeqImmediateT1 | TeqRegisterT1
+            | TstImmediateT1 | TstRegisterT1 | TstRegisterT2 => {
+                let carry_in = cortex_m33.xpsr.apsr.c();
+                let (operand, carry) = self.shifted_operand_c(cortex_m33, opcode_pc, carry_in);
+                let rn_value = cortex_m33.get_register_from_number(rn).get();
 
-                let rm = cortex_m33.get_register_from_number(rm).get();
+                let result = match self.instruction {
+                    AndImmediateT1 | AndRegisterT1 | AndRegisterT2 | TstImmediateT1 | TstRegisterT1
+                    | TstRegisterT2 => rn_value & operand,
+                    BicImmediateT1 | BicRegisterT1 | BicRegisterT2 => rn_value & !operand,
+                    EorImmediateT1 | EorRegisterT1 | EorRegisterT2 | TeqImmediateT1 | TeqRegisterT1 => {
+                        rn_value ^ operand
+                    }
+                    MvnImmediateT1 | MvnRegisterT2 | MvnT1 => !operand,
+                    OrnImmediateT1 | OrnRegisterT1 => rn_value | !operand,
+                    OrrImmediateT1 | OrrRegisterT1 | OrrRegisterT2 => rn_value | operand,
+                    // MOV and the immediate shifts only use the shifted operand
+                    _ => operand,
+                };
 
-                let (result, carry) = shift_c(rm, SRType::Asr, shift_n, cortex_m33.xpsr.apsr.c());
-                cortex_m33.get_register_from_number(rd).set(result);
+                if !matches!(
+                    self.instruction,
+                    TeqImmediateT1 | TeqRegisterT1 | TstImmediateT1 | TstRegisterT1 | TstRegisterT2
+                ) {
+                    cortex_m33.get_register_from_number(rd).set(result);
+                }
 
-                cortex_m33.xpsr.apsr.set_n(get_bit(result, 31));
-                cortex_m33.xpsr.apsr.set_z(is_zero_bit(result));
-                cortex_m33.xpsr.apsr.set_c(carry);
+                logical_instruction_update_flags(&mut cortex_m33.xpsr.apsr, result, carry, setflags);
             }
-            AsrRegisterT1 => {
-                let rdn = get_bits(opcode, 0..=2);
-                let rm = get_bits(opcode, 3..=5);
-
-                let rm = cortex_m33.get_register_from_number(rm).get();
-
-                let setflags = !in_it_block();
+            AsrRegisterT1 | AsrRegisterT2 | LslRegisterT1 | LslRegisterT2 | LsrRegisterT1 | LsrRegisterT2
+            | RorRegisterT1 | RorRegisterT2 => {
+                let shift_n = get_bits(cortex_m33.get_register_from_number(rm).get(), 0..=7) as u16;
+                let rn_value = cortex_m33.get_register_from_number(rn).get();
 
-                let shift_n = get_bits(rm as u16, 0..=7);
-                let (result, carry) = shift_c(
-                    cortex_m33.get_register_from_number(rdn).get(),
-                    SRType::Asr,
-                    shift_n,
-                    cortex_m33.xpsr.apsr.c(),
-                );
-                cortex_m33.get_register_from_number(rdn).set(result);
+                let (result, carry) =
+                    shift_c(rn_value, self.operands.shift_t, shift_n, cortex_m33.xpsr.apsr.c());
+                cortex_m33.get_register_from_number(rd).set(result);
 
-                if setflags {
-                    cortex_m33.xpsr.apsr.set_n(get_bit(result, 31));
-                    cortex_m33.xpsr.apsr.set_z(is_zero_bit(result));
-                    cortex_m33.xpsr.apsr.set_c(carry);
-                }
+                logical_instruction_update_flags(&mut cortex_m33.xpsr.apsr, result, carry, setflags);
             }
-            BT1 => {
-                let cond = get_bits(opcode, 8..=11);
-                let imm8 = get_bits(opcode, 0..=7) << 1;
-
-                if in_it_block() {
+            BT1 | BT3 => {
+                if in_it_block(&cortex_m33.xpsr.epsr) {
                     unpredictable!();
-                };
-
-                let imm32 = match sign_extend(imm8, 8, 32) {
-                    SignExtended::U32(val) => val,
-                    _ => unreachable!(),
-                } as i32;
-
-                if condition_passed(&cortex_m33.xpsr.apsr, cond) {
-                    let pc_value = cortex_m33.registers.pc.get();
-                    branch_write_pc(
-                        &mut cortex_m33.registers.pc,
-                        (pc_value as i32 + imm32) as u32,
-                    );
                 }
 
-                cortex_m33
-                    .registers
-                    .pc
-                    .set(cortex_m33.registers.pc.get() + 2);
+                if condition_passed(&cortex_m33.xpsr.apsr, self.operands.cond) {
+                    branch_write_pc(&mut cortex_m33.registers.pc, (opcode_pc + 4).wrapping_add(imm32));
+                }
             }
             BT2 => {
-                let opcode = opcode as i32;
-                let mut imm11 = (opcode & 0x7ff) << 1;
-                if imm11 & (1 << 11) > 0 {
-                    imm11 = (imm11 & 0x7ff) - 0x800;
+                cortex_m33.registers.pc.set((opcode_pc + 4).wrapping_add(imm32));
+            }
+            BT4 => {
+                if in_it_block(&cortex_m33.xpsr.epsr) && !last_in_it_block(&cortex_m33.xpsr.epsr) {
+                    unpredictable!();
                 }
 
-                let pc_value = cortex_m33.registers.pc.get() as i32;
-                let value = pc_value + imm11 + 2;
-
-                cortex_m33.registers.pc.set(value as u32);
+                branch_write_pc(&mut cortex_m33.registers.pc, (opcode_pc + 4).wrapping_add(imm32));
             }
-            BicRegisterT1 => {
-                let rdn = get_bits(opcode, 0..=2);
-                let rm = get_bits(opcode, 3..=5);
-
-                let rm = cortex_m33.get_register_from_number(rm).get();
-
-                let setflags = !in_it_block();
-                let shift_t = SRType::Lsl;
-                let shift_n = 0;
-
-                let (shifted, carry) = shift_c(rm, shift_t, shift_n, cortex_m33.xpsr.apsr.c());
+            BfcT1 | BfiT1 => {
+                let msbit = imm32;
+                let lsbit = self.operands.shift_n as u32;
 
-                let rdn = cortex_m33.get_register_from_number(rdn);
-                let result = rdn.get() & !shifted;
-                rdn.set(result);
+                if msbit < lsbit {
+                    unpredictable!();
+                } else {
+                    let mask = (((1u64 << (msbit - lsbit + 1)) - 1) as u32) << lsbit;
+                    let source = match self.instruction {
+                        BfiT1 => cortex_m33.get_register_from_number(rn).get() << lsbit,
+                        _ => 0,
+                    };
 
-                if setflags {
-                    cortex_m33.xpsr.apsr.set_n(get_bit(result, 31));
-                    cortex_m33.xpsr.apsr.set_z(is_zero_bit(result));
-                    cortex_m33.xpsr.apsr.set_c(carry);
+                    let rd = cortex_m33.get_register_from_number(rd);
+                    rd.set((rd.get() & !mask) | (source & mask));
                 }
             }
             BkptT1 => {
-                let imm8 = get_bits(opcode, 0..=7);
-                let _imm32 = imm8 as u32;
-
-                todo!();
+                // A debug halt, the instruction doesn't complete until whoever handles it steps over it
+                cortex_m33.registers.pc.set(opcode_pc);
+                cortex_m33.breakpoint = Some(imm32 as u8);
+                return Ok(());
             }
             BlT1 => {
-                let opcode = opcode as i32;
-                let opcode_2 = opcode_2.code as i32;
-
-                let imm11 = opcode_2 & 0x7ff;
-                let j2 = (opcode_2 >> 11) & 0x1;
-                let j1 = (opcode_2 >> 13) & 0x1;
-                let imm10 = opcode & 0x3ff;
-                let s = (opcode >> 10) & 0x1;
-                let i1 = 1 - (s ^ j1);
-                let i2 = 1 - (s ^ j2);
-
-                let s = if s > 0 { 0b11111111 } else { 0 };
-
-                let imm32: i32 =
-                    (s << 24) | ((i1 << 23) | (i2 << 22) | (imm10 << 12) | (imm11 << 1));
                 cortex_m33
                     .registers
                     .lr
-                    .set(cortex_m33.registers.pc.get() + 2 | 0x1);
+                    .set(cortex_m33.registers.pc.get() | 0x1);
 
-                let pc_value = cortex_m33.registers.pc.get() as i32 + 2 + imm32;
-                cortex_m33.registers.pc.set(pc_value as u32);
+                let pc_value = cortex_m33.registers.pc.get().wrapping_add(imm32);
+                cortex_m33.registers.pc.set(pc_value);
             }
             BlxT1 => {
-                let rm = (opcode >> 3) & 0xf;
                 cortex_m33
                     .registers
                     .lr
@@ -582,62 +1123,129 @@ impl Instruction {
                 cortex_m33.registers.pc.set(rm_value & !1);
             }
             BxT1 => {
-                let rm = get_bits(opcode, 3..=6);
-                if in_it_block() && !last_in_it_block() {
+                if in_it_block(&cortex_m33.xpsr.epsr) && !last_in_it_block(&cortex_m33.xpsr.epsr) {
                     unpredictable!();
                 }
 
-                let rm_value = cortex_m33.get_register_from_number(rm).get();
-                if rm_value == 15 {
+                let rm_value = read_register(cortex_m33, opcode_pc, rm);
+                bx_write_pc(cortex_m33, rm_value)?;
+            }
+            CbnzT1 | CbzT1 => {
+                let nonzero = matches!(self.instruction, CbnzT1);
+
+                if in_it_block(&cortex_m33.xpsr.epsr) {
                     unpredictable!();
                 }
 
-                todo!();
-            }
-            CmnRegisterT1 => {
-                todo!();
-            }
-            CmpImmediateT1 => {
-                todo!();
+                let rn_value = cortex_m33.get_register_from_number(rn).get();
+                if (rn_value != 0) == nonzero {
+                    branch_write_pc(&mut cortex_m33.registers.pc, opcode_pc + 4 + imm32);
+                }
             }
-            CmpRegisterT1 => {
-                todo!();
+            ClrexT1 => {
+                cortex_m33.exclusive_monitor = None;
             }
-            CmpRegisterT2 => {
-                todo!();
+            ClzT1 | RbitT1 | RevT1 | RevT2 | Rev16T1 | Rev16T2 | RevshT1 | RevshT2 => {
+                let rm_value = cortex_m33.get_register_from_number(rm).get();
+
+                let result = match self.instruction {
+                    ClzT1 => rm_value.leading_zeros(),
+                    RbitT1 => rm_value.reverse_bits(),
+                    RevT1 | RevT2 => rm_value.swap_bytes(),
+                    Rev16T1 | Rev16T2 => ((rm_value & 0x00ff00ff) << 8) | ((rm_value >> 8) & 0x00ff00ff),
+                    _ => (rm_value as u16).swap_bytes() as i16 as u32,
+                };
+                cortex_m33.get_register_from_number(rd).set(result);
             }
             CpsT1Id => {
-                todo!();
+                // Unprivileged code can't mask interrupts, so the instruction does nothing there
+                if cortex_m33.is_privileged() {
+                    cortex_m33.primask = true;
+                }
             }
             CpsT1Ie => {
-                todo!();
+                if cortex_m33.is_privileged() {
+                    cortex_m33.primask = false;
+                }
             }
-            DmbT1Sy => {
-                cortex_m33
-                    .registers
-                    .pc
-                    .set(cortex_m33.registers.pc.get() + 2);
+            DmbT1Sy | DsbT1Sy => {
+                // Memory accesses are performed in program order, so there is nothing to wait for
             }
-            DsbT1Sy => {
-                cortex_m33
-                    .registers
-                    .pc
-                    .set(cortex_m33.registers.pc.get() + 2);
-            }
-            EorRegisterT1 => {}
             IsbT1Sy => {
-                cortex_m33
-                    .registers
-                    .pc
-                    .set(cortex_m33.registers.pc.get() + 2);
+                // There is no prefetch pipeline to flush, and the decode cache is already invalidated on code
+                // writes, so the next instruction is fetched with everything before it in effect
+            }
+            ItT1 => {
+                let firstcond = get_bits(imm32, 4..=7);
+                let mask = get_bits(imm32, 0..=3);
+
+                if firstcond == 0b1111 || (firstcond == 0b1110 && mask.count_ones() != 1) {
+                    unpredictable!();
+                }
+                if in_it_block(&cortex_m33.xpsr.epsr) {
+                    unpredictable!();
+                }
+
+                cortex_m33.xpsr.epsr.set_it(imm32 as u8);
+            }
+            LdaT1 | LdabT1 | LdahT1 => {
+                let address = cortex_m33.get_register_from_number(rn).get();
+                let value = match self.instruction {
+                    LdabT1 => cortex_m33.read_u8(address)? as u32,
+                    LdahT1 => cortex_m33.read_u16(address)? as u32,
+                    _ => cortex_m33.read_u32(address)?,
+                };
+                cortex_m33.get_register_from_number(rt).set(value);
+            }
+            LdaexT1 | LdaexbT1 | LdaexhT1 | LdrexT1 | LdrexbT1 | LdrexhT1 => {
+                let address = cortex_m33.get_register_from_number(rn).get().wrapping_add(imm32);
+                let value = match self.instruction {
+                    LdaexbT1 | LdrexbT1 => cortex_m33.read_u8(address)? as u32,
+                    LdaexhT1 | LdrexhT1 => cortex_m33.read_u16(address)? as u32,
+                    _ => cortex_m33.read_u32(address)?,
+                };
+
+                cortex_m33.exclusive_monitor = Some(address);
+                cortex_m33.get_register_from_number(rt).set(value);
+            }
+            LdmT2 | LdmdbT1 => {
+                let registers = imm32;
+                let bitcount = registers.count_ones();
+
+                if bitcount < 2 || get_bit(registers, 13) {
+                    unpredictable!();
+                }
+
+                let rn_value = cortex_m33.get_register_from_number(rn).get();
+                let (start_address, end_address) = match self.instruction {
+                    LdmT2 => (rn_value, rn_value.wrapping_add(4 * bitcount)),
+                    _ => (rn_value.wrapping_sub(4 * bitcount), rn_value.wrapping_sub(4 * bitcount)),
+                };
+
+                let mut address = start_address;
+                for i in 0..=14 {
+                    if registers & (1 << i) > 0 {
+                        let value = cortex_m33.read_u32(address)?;
+                        cortex_m33.get_register_from_number(i).set(value);
+                        address += 4;
+                    }
+                }
+
+                if self.operands.wback && registers & (1 << rn) == 0 {
+                    cortex_m33.get_register_from_number(rn).set(end_address);
+                }
+
+                if get_bit(registers, 15) {
+                    let value = cortex_m33.read_u32(address)?;
+                    bx_write_pc(cortex_m33, value)?;
+                }
             }
             LdmiaT1 => {
-                let rn = (opcode >> 8) & 0x7;
-                let registers = opcode & 0xff;
+                let registers = imm32;
                 let mut address = cortex_m33.get_register_from_number(rn).get();
                 for i in 0..8 {
                     if registers & (1 << i) > 0 {
-                        let address_value = cortex_m33.memory.read_u32(address);
+                        let address_value = cortex_m33.read_u32(address)?;
                         cortex_m33
                             .get_register_from_number(i)
                             .set(address_value);
@@ -646,56 +1254,61 @@ impl Instruction {
                 }
 
                 // Write back
-                if !(registers & (1 << rn) > 0) {
+                if registers & (1 << rn) == 0 {
                     cortex_m33.get_register_from_number(rn).set(address);
                 }
             }
-            LdrImmediateT1 => {
-                todo!();
-            }
-            LdrImmediateT2 => {
-                todo!();
-            }
-            LdrLiteralT1 => {
-                todo!();
-            }
-            LdrRegisterT1 => {
-                todo!();
-            }
-            LdrbImmediateT1 => {
-                todo!();
-            }
-            LdrbRegisterT1 => {
-                todo!();
-            }
-            LdrhImmediateT1 => {
-                todo!();
-            }
-            LdrhRegisterT1 => {
-                todo!();
-            }
-            LdrsbRegisterT1 => {
-                todo!();
-            }
-            LdrshRegisterT1 => {
-                todo!();
-            }
-            LslImmediateT1 => {
-                todo!();
-            }
-            LslRegisterT1 => {
-                todo!();
-            }
-            LsrImmediateT1 => {
-                todo!();
+            LdrImmediateT1 | LdrImmediateT2 | LdrImmediateT3 | LdrImmediateT4 | LdrLiteralT1 | LdrLiteralT2
+            | LdrRegisterT1 | LdrRegisterT2 | LdrtT1 | LdrbImmediateT1 | LdrbImmediateT2 | LdrbImmediateT3
+            | LdrbLiteralT1 | LdrbRegisterT1 | LdrbRegisterT2 | LdrbtT1 | LdrhImmediateT1 | LdrhImmediateT2
+            | LdrhImmediateT3 | LdrhLiteralT1 | LdrhRegisterT1 | LdrhRegisterT2 | LdrhtT1 | LdrsbImmediateT1
+            | LdrsbImmediateT2 | LdrsbLiteralT1 | LdrsbRegisterT1 | LdrsbRegisterT2 | LdrsbtT1
+            | LdrshImmediateT1 | LdrshImmediateT2 | LdrshLiteralT1 | LdrshRegisterT1 | LdrshRegisterT2
+            | LdrshtT1 => {
+                let (address, writeback) = self.load_store_address(cortex_m33, opcode_pc);
+                let value = match self.instruction {
+                    LdrbImmediateT1 | LdrbImmediateT2 | LdrbImmediateT3 | LdrbLiteralT1 | LdrbRegisterT1
+                    | LdrbRegisterT2 | LdrbtT1 => cortex_m33.read_u8(address)? as u32,
+                    LdrhImmediateT1 | LdrhImmediateT2 | LdrhImmediateT3 | LdrhLiteralT1 | LdrhRegisterT1
+                    | LdrhRegisterT2 | LdrhtT1 => cortex_m33.read_u16(address)? as u32,
+                    LdrsbImmediateT1 | LdrsbImmediateT2 | LdrsbLiteralT1 | LdrsbRegisterT1 | LdrsbRegisterT2
+                    | LdrsbtT1 => cortex_m33.read_u8(address)? as i8 as u32,
+                    LdrshImmediateT1 | LdrshImmediateT2 | LdrshLiteralT1 | LdrshRegisterT1 | LdrshRegisterT2
+                    | LdrshtT1 => cortex_m33.read_u16(address)? as i16 as u32,
+                    _ => cortex_m33.read_u32(address)?,
+                };
+
+                if let Some(offset_address) = writeback {
+                    cortex_m33.get_register_from_number(rn).set(offset_address);
+                }
+
+                if rt == 15 {
+                    bx_write_pc(cortex_m33, value)?;
+                } else {
+                    cortex_m33.get_register_from_number(rt).set(value);
+                }
             }
-            LsrRegisterT1 => {
-                todo!();
+            LdrdImmediateT1 | LdrdLiteralT1 | StrdImmediateT1 => {
+                let rt2 = rd;
+                let (address, writeback) = self.load_store_address(cortex_m33, opcode_pc);
+
+                if let StrdImmediateT1 = self.instruction {
+                    let value = cortex_m33.get_register_from_number(rt).get();
+                    cortex_m33.write_u32(address, value)?;
+                    let value = cortex_m33.get_register_from_number(rt2).get();
+                    cortex_m33.write_u32(address + 4, value)?;
+                } else {
+                    let value = cortex_m33.read_u32(address)?;
+                    cortex_m33.get_register_from_number(rt).set(value);
+                    let value = cortex_m33.read_u32(address + 4)?;
+                    cortex_m33.get_register_from_number(rt2).set(value);
+                }
+
+                if let Some(offset_address) = writeback {
+                    cortex_m33.get_register_from_number(rn).set(offset_address);
+                }
             }
             MovRegisterT1 => {
-                let rm = (opcode >> 3) & 0xf;
-                let rd = ((opcode >> 4) & 0x8) | (opcode & 0x7);
-
                 let rm = cortex_m33.get_register_from_number(rm);
                 let mut value = if rm.is_pc() {
                     cortex_m33.registers.pc.get() + 2
@@ -713,48 +1326,162 @@ impl Instruction {
 
                 rd.set(value);
             }
-            MovImmediateT1 => {
-                todo!();
+            MovImmediateT3 | MovtT1 => {
+                let rd = cortex_m33.get_register_from_number(rd);
+                match self.instruction {
+                    MovImmediateT3 => rd.set(imm32),
+                    _ => rd.set((imm32 << 16) | (rd.get() & 0xffff)),
+                }
             }
             MrsT1 => {
-                todo!();
+                let sysm = imm32;
+                let privileged = cortex_m33.is_privileged();
+
+                let value = match sysm {
+                    // The xPSR views, where the EPSR always reads as zero
+                    0..=7 => {
+                        let mut value = 0;
+                        if get_bit(sysm, 0) {
+                            value |= cortex_m33.ipsr as u32;
+                        }
+                        if !get_bit(sysm, 2) {
+                            value |= cortex_m33.xpsr.apsr.into_u32();
+                        }
+                        value
+                    }
+                    8 if privileged => cortex_m33.registers.sp.get_msp(),
+                    9 if privileged => cortex_m33.registers.sp.get_psp(),
+                    10 if privileged => cortex_m33.msplim,
+                    11 if privileged => cortex_m33.psplim,
+                    16 => cortex_m33.primask as u32,
+                    17 | 18 => cortex_m33.basepri as u32,
+                    19 => cortex_m33.faultmask as u32,
+                    20 => {
+                        let npriv = cortex_m33.control.npriv == NPriv::ThreadModeUnprivileged;
+                        let spsel = cortex_m33.control.spsel == SpSel::SpProcess;
+                        ((spsel as u32) << 1) | npriv as u32
+                    }
+                    _ => 0,
+                };
+                cortex_m33.get_register_from_number(rd).set(value);
             }
             MsrT1 => {
-                todo!();
+                let mask = get_bits(imm32, 10..=11);
+                let sysm = get_bits(imm32, 0..=7);
+                let privileged = cortex_m33.is_privileged();
+                let rn_value = cortex_m33.get_register_from_number(rn).get();
+
+                match sysm {
+                    0..=7 if !get_bit(sysm, 2) => {
+                        let mut apsr = cortex_m33.xpsr.apsr.into_u32();
+                        if get_bit(mask, 1) {
+                            apsr = (apsr & !0xf8000000) | (rn_value & 0xf8000000);
+                        }
+                        if get_bit(mask, 0) {
+                            apsr = (apsr & !0x000f0000) | (rn_value & 0x000f0000);
+                        }
+                        cortex_m33.xpsr.apsr.set_from_u32(apsr);
+                    }
+                    8 if privileged => cortex_m33.registers.sp.set_msp(rn_value & 0xfffffffc),
+                    9 if privileged => cortex_m33.registers.sp.set_psp(rn_value & 0xfffffffc),
+                    10 if privileged => cortex_m33.msplim = rn_value & 0xfffffff8,
+                    11 if privileged => cortex_m33.psplim = rn_value & 0xfffffff8,
+                    16 if privileged => cortex_m33.primask = get_bit(rn_value, 0),
+                    17 if privileged => cortex_m33.basepri = rn_value as u8,
+                    18 if privileged => {
+                        let value = rn_value as u8;
+                        if value != 0 && (value < cortex_m33.basepri || cortex_m33.basepri == 0) {
+                            cortex_m33.basepri = value;
+                        }
+                    }
+                    19 if privileged => cortex_m33.faultmask = get_bit(rn_value, 0),
+                    20 if privileged => {
+                        cortex_m33.control.npriv = if get_bit(rn_value, 0) {
+                            NPriv::ThreadModeUnprivileged
+                        } else {
+                            NPriv::ThreadModePrivileged
+                        };
+
+                        // SPSEL can only be changed from Thread mode
+                        if cortex_m33.mode == Mode::Thread {
+                            cortex_m33.control.spsel = if get_bit(rn_value, 1) {
+                                SpSel::SpProcess
+                            } else {
+                                SpSel::SpMain
+                            };
+                        }
+
+                        cortex_m33.update_sp_mode();
+                    }
+                    _ => {}
+                }
             }
             MulT1 => {
-                todo!();
-            }
-            MvnT1 => {
-                todo!();
+                let rn_value = cortex_m33.get_register_from_number(rn).get();
+                let rdm = cortex_m33.get_register_from_number(rd);
+                let result = rn_value.wrapping_mul(rdm.get());
+                rdm.set(result);
+
+                if setflags {
+                    cortex_m33.xpsr.apsr.set_n(get_bit(result, 31));
+                    cortex_m33.xpsr.apsr.set_z(is_zero_bit(result));
+                }
             }
-            OrrRegisterT1 => {
-                todo!();
+            MlaT1 | MlsT1 | MulT2 => {
+                let ra = rt;
+                let rn_value = cortex_m33.get_register_from_number(rn).get();
+                let rm_value = cortex_m33.get_register_from_number(rm).get();
+                let product = rn_value.wrapping_mul(rm_value);
+
+                let result = match self.instruction {
+                    MlaT1 => cortex_m33.get_register_from_number(ra).get().wrapping_add(product),
+                    MlsT1 => cortex_m33.get_register_from_number(ra).get().wrapping_sub(product),
+                    _ => product,
+                };
+                cortex_m33.get_register_from_number(rd).set(result);
             }
             PopT1 => {
-                todo!();
-            }
-            PushT1 => {
-                let mut bitcount = 0;
-                for i in 0..=8 {
-                    if self.opcode.code & (1 << i) > 0 {
-                        bitcount += 1;
+                let registers = get_bits(imm32, 0..=7);
+                let pop_pc = get_bit(imm32, 8);
+
+                let bitcount = registers.count_ones() + pop_pc as u32;
+                if bitcount < 1 {
+                    unpredictable!();
+                }
+
+                let mut address = cortex_m33.registers.sp.get();
+                for i in 0..=7 {
+                    if registers & (1 << i) > 0 {
+                        let value = cortex_m33.read_u32(address)?;
+                        cortex_m33.get_register_from_number(i).set(value);
+                        address += 4;
                     }
                 }
 
+                let current_sp = cortex_m33.registers.sp.get();
+                cortex_m33.registers.sp.set(current_sp + 4 * bitcount);
+
+                if pop_pc {
+                    let value = cortex_m33.read_u32(address)?;
+                    bx_write_pc(cortex_m33, value)?;
+                }
+            }
+            PushT1 => {
+                let bitcount = imm32.count_ones();
+
                 let mut address = cortex_m33.registers.sp.get() - 4 * bitcount;
 
                 for i in 0..=7 {
-                    if self.opcode.code & (1 << i) > 0 {
+                    if imm32 & (1 << i) > 0 {
                         let register = cortex_m33.get_register_from_number(i).get();
 
-                        cortex_m33.memory.write(address, register as u8);
+                        cortex_m33.write_u32(address, register)?;
                         address += 4;
                     }
                 }
 
-                if self.opcode.code & (1 << 8) > 0 {
-                    cortex_m33.memory.write(address, cortex_m33.registers.lr.get() as u8);
+                if imm32 & (1 << 8) > 0 {
+                    cortex_m33.write_u32(address, cortex_m33.registers.lr.get())?;
                 }
 
                 let current_sp = cortex_m33.registers.sp.get();
@@ -763,146 +1490,706 @@ impl Instruction {
                     .sp
                     .set(current_sp - 4 * bitcount);
             }
-            RevT1 => {
-                let rm = (opcode >> 3) & 0x7;
-                let rd = opcode & 0x7;
-                let input = cortex_m33.get_register_from_number(rm).get();
-                cortex_m33.get_register_from_number(rd).set(
-                    ((input & 0xff) << 24)
-                        | (((input >> 8) & 0xff) << 16)
-                        | (((input >> 16) & 0xff) << 8)
-                        | ((input >> 24) & 0xff),
-                )
-            }
-            Rev16T1 => {
-                let rm = (opcode >> 3) & 0x7;
-                let rd = opcode & 0x7;
-                let input = cortex_m33.get_register_from_number(rm).get();
-                cortex_m33.get_register_from_number(rd).set(
-                    (((input >> 16) & 0xff) << 24)
-                        | (((input >> 24) & 0xff) << 16)
-                        | ((input & 0xff) << 8)
-                        | ((input >> 8) & 0xff),
-                )
-            }
-            RevshT1 => {
-                todo!();
-            }
-            RorRegisterT1 => {
-                todo!();
-            }
-            RsbImmediateT1 => {
-                todo!();
-            }
             NopT1 => {
                 // Do nothing
             }
-            SbcRegisterT1 => {}
+            NopT2 | SevT2 | WfeT2 | WfiT2 | YieldT2 => {
+                // Hints have no architecturally visible effect in the simulator
+            }
+            PldT1 | PliT1 => {
+                // Preloads are only hints to the memory system, so there is nothing to do
+            }
+            SbfxT1 | UbfxT1 => {
+                let widthm1 = imm32;
+                let lsbit = self.operands.shift_n as u32;
+                let msbit = lsbit + widthm1;
+
+                if msbit > 31 {
+                    unpredictable!();
+                } else {
+                    let rn_value = cortex_m33.get_register_from_number(rn).get();
+                    let field = rn_value << (31 - msbit);
+                    let result = match self.instruction {
+                        SbfxT1 => ((field as i32) >> (31 - widthm1)) as u32,
+                        _ => field >> (31 - widthm1),
+                    };
+                    cortex_m33.get_register_from_number(rd).set(result);
+                }
+            }
+            SdivT1 | UdivT1 => {
+                let rn_value = cortex_m33.get_register_from_number(rn).get();
+                let rm_value = cortex_m33.get_register_from_number(rm).get();
+
+                // Dividing by zero gives zero unless CCR.DIV_0_TRP is set
+                if rm_value == 0 && cortex_m33.ccr & CCR_DIV_0_TRP != 0 {
+                    return Err(Fault::DivideByZero);
+                }
+
+                let result = if rm_value == 0 {
+                    0
+                } else if let SdivT1 = self.instruction {
+                    (rn_value as i32).wrapping_div(rm_value as i32) as u32
+                } else {
+                    rn_value / rm_value
+                };
+                cortex_m33.get_register_from_number(rd).set(result);
+            }
             SevT1 => {}
+            SmlalT1 | SmullT1 | UmlalT1 | UmullT1 => {
+                let (rdlo, rdhi) = (rt, rd);
+                let rn_value = cortex_m33.get_register_from_number(rn).get();
+                let rm_value = cortex_m33.get_register_from_number(rm).get();
+                let accumulator = ((cortex_m33.get_register_from_number(rdhi).get() as u64) << 32)
+                    | cortex_m33.get_register_from_number(rdlo).get() as u64;
+
+                let signed_product = rn_value as i32 as i64 * rm_value as i32 as i64;
+                let unsigned_product = rn_value as u64 * rm_value as u64;
+                let result = match self.instruction {
+                    SmlalT1 => (signed_product as u64).wrapping_add(accumulator),
+                    SmullT1 => signed_product as u64,
+                    UmlalT1 => unsigned_product.wrapping_add(accumulator),
+                    _ => unsigned_product,
+                };
+
+                cortex_m33.get_register_from_number(rdlo).set(result as u32);
+                cortex_m33.get_register_from_number(rdhi).set((result >> 32) as u32);
+            }
+            SsatT1 | UsatT1 => {
+                let sat_imm = imm32;
+                let rn_value = cortex_m33.get_register_from_number(rn).get();
+                let (operand, _) =
+                    shift_c(rn_value, self.operands.shift_t, self.operands.shift_n, cortex_m33.xpsr.apsr.c());
+
+                let (result, saturated) = match self.instruction {
+                    SsatT1 => signed_sat_q(operand as i32 as i64, sat_imm + 1),
+                    _ => unsigned_sat_q(operand as i32 as i64, sat_imm),
+                };
+
+                cortex_m33.get_register_from_number(rd).set(result);
+                if saturated {
+                    cortex_m33.xpsr.apsr.set_q(true);
+                }
+            }
+            StlT1 | StlbT1 | StlhT1 => {
+                let address = cortex_m33.get_register_from_number(rn).get();
+                let value = cortex_m33.get_register_from_number(rt).get();
+                match self.instruction {
+                    StlbT1 => cortex_m33.write_u8(address, value as u8)?,
+                    StlhT1 => cortex_m33.write_u16(address, value as u16)?,
+                    _ => cortex_m33.write_u32(address, value)?,
+                }
+            }
+            StlexT1 | StlexbT1 | StlexhT1 | StrexT1 | StrexbT1 | StrexhT1 => {
+                let address = cortex_m33.get_register_from_number(rn).get().wrapping_add(imm32);
+                if cortex_m33.exclusive_monitor == Some(address) {
+                    let value = cortex_m33.get_register_from_number(rt).get();
+                    match self.instruction {
+                        StlexbT1 | StrexbT1 => cortex_m33.write_u8(address, value as u8)?,
+                        StlexhT1 | StrexhT1 => cortex_m33.write_u16(address, value as u16)?,
+                        _ => cortex_m33.write_u32(address, value)?,
+                    }
+                    cortex_m33.get_register_from_number(rd).set(0);
+                } else {
+                    cortex_m33.get_register_from_number(rd).set(1);
+                }
+
+                cortex_m33.exclusive_monitor = None;
+            }
+            StmT2 | StmdbT1 => {
+                let registers = imm32;
+                let bitcount = registers.count_ones();
+
+                if bitcount < 2 || get_bit(registers, 13) || get_bit(registers, 15) {
+                    unpredictable!();
+                }
+
+                let rn_value = cortex_m33.get_register_from_number(rn).get();
+                let (start_address, end_address) = match self.instruction {
+                    StmT2 => (rn_value, rn_value.wrapping_add(4 * bitcount)),
+                    _ => (rn_value.wrapping_sub(4 * bitcount), rn_value.wrapping_sub(4 * bitcount)),
+                };
+
+                let mut address = start_address;
+                for i in 0..=14 {
+                    if registers & (1 << i) > 0 {
+                        let value = cortex_m33.get_register_from_number(i).get();
+                        cortex_m33.write_u32(address, value)?;
+                        address += 4;
+                    }
+                }
+
+                if self.operands.wback {
+                    cortex_m33.get_register_from_number(rn).set(end_address);
+                }
+            }
             StmiaT1 => {
-                let rn = (opcode >> 8) & 0x7;
-                let registers = opcode & 0xff;
+                let registers = imm32;
                 let mut address = cortex_m33.get_register_from_number(rn).get();
                 for i in 0..8 {
                     if registers & (1 << i) > 0 {
                         let register_value = cortex_m33.get_register_from_number(i).get();
                         // This is probably not correct, gotta check this again later
-                        cortex_m33.memory.write_u32(address, register_value);
+                        cortex_m33.write_u32(address, register_value)?;
                         address += 4;
                     }
                 }
                 // Write back
-                if !(registers & (1 << rn) > 0) {
+                if registers & (1 << rn) == 0 {
                     cortex_m33.get_register_from_number(rn).set(address);
                 }
             }
-            StrImmediateT1 => {
-                todo!();
-            }
-            StrImmediateT2 => {
-                todo!();
-            }
-            StrRegisterT1 => {
-                todo!();
-            }
-            StrbImmediateT1 => {
-                todo!();
-            }
-            StrbRegisterT1 => {
-                todo!();
-            }
-            StrhImmediateT1 => {
-                todo!();
-            }
-            StrhRegisterT1 => {
-                todo!();
+            StrImmediateT1 | StrImmediateT2 | StrImmediateT3 | StrImmediateT4 | StrRegisterT1 | StrRegisterT2
+            | StrtT1 | StrbImmediateT1 | StrbImmediateT2 | StrbImmediateT3 | StrbRegisterT1 | StrbRegisterT2
+            | StrbtT1 | StrhImmediateT1 | StrhImmediateT2 | StrhImmediateT3 | StrhRegisterT1 | StrhRegisterT2
+            | StrhtT1 => {
+                let (address, writeback) = self.load_store_address(cortex_m33, opcode_pc);
+                let value = cortex_m33.get_register_from_number(rt).get();
+                match self.instruction {
+                    StrbImmediateT1 | StrbImmediateT2 | StrbImmediateT3 | StrbRegisterT1 | StrbRegisterT2
+                    | StrbtT1 => cortex_m33.write_u8(address, value as u8)?,
+                    StrhImmediateT1 | StrhImmediateT2 | StrhImmediateT3 | StrhRegisterT1 | StrhRegisterT2
+                    | StrhtT1 => cortex_m33.write_u16(address, value as u16)?,
+                    _ => cortex_m33.write_u32(address, value)?,
+                }
+
+                if let Some(offset_address) = writeback {
+                    cortex_m33.get_register_from_number(rn).set(offset_address);
+                }
             }
             SubSpMinusImmediateT1 => {
-                let imm32 = (opcode & 0x7f) << 2;
                 cortex_m33
                     .registers
                     .sp
-                    .set(cortex_m33.registers.sp.get() - imm32 as u32);
+                    .set(cortex_m33.registers.sp.get() - imm32);
             }
-            SubT1 => {
-                todo!();
+            SvcT1 => {
+                // The PC already points past the SVC, which is where the handler returns to
+                cortex_m33.call_supervisor();
             }
-            SubT2 => {
-                todo!();
+            SxtbT1 | SxtbT2 | SxthT1 | SxthT2 | UxtbT1 | UxtbT2 | UxthT1 | UxthT2 => {
+                let rotation = self.operands.shift_n as u32;
+
+                let rotated = cortex_m33.get_register_from_number(rm).get().rotate_right(rotation);
+                let result = match self.instruction {
+                    SxtbT1 | SxtbT2 => rotated as u8 as i8 as u32,
+                    SxthT1 | SxthT2 => rotated as u16 as i16 as u32,
+                    UxtbT1 | UxtbT2 => rotated & 0xff,
+                    _ => rotated & 0xffff,
+                };
+                cortex_m33.get_register_from_number(rd).set(result);
             }
-            SubRegisterT1 => {
-                todo!();
+            TbbT1 | TbhT1 => {
+                if in_it_block(&cortex_m33.xpsr.epsr) && !last_in_it_block(&cortex_m33.xpsr.epsr) {
+                    unpredictable!();
+                }
+
+                let rn_value = read_register(cortex_m33, opcode_pc, rn);
+                let rm_value = cortex_m33.get_register_from_number(rm).get();
+                let halfwords = match self.instruction {
+                    TbhT1 => cortex_m33.read_u16(rn_value.wrapping_add(rm_value << 1))? as u32,
+                    _ => cortex_m33.read_u8(rn_value.wrapping_add(rm_value))? as u32,
+                };
+
+                branch_write_pc(&mut cortex_m33.registers.pc, opcode_pc + 4 + 2 * halfwords);
             }
-            SvcT1 => {
-                todo!();
+            UdfT1 | UdfT2 => {
+                return Err(Fault::UndefinedInstruction);
+            }
+            WfeT1 => {
+                // Do nothing, there is no low power state to enter
             }
-            SxtbT1 => {
-                todo!();
+            WfiT1 => {
+                // Do nothing, there is no low power state to enter
+            }
+            YieldT1 => {
+                // Do nothing, wait for an event
             }
-            SxthT1 => {
-                todo!();
+        }
+
+        // The IT instruction sets up ITSTATE for the block that follows, every other instruction consumes it.
+        // An exception return has already restored ITSTATE from the stacked xPSR.
+        if !matches!(self.instruction, ItT1) && !cortex_m33.exception_returned {
+            it_advance(&mut cortex_m33.xpsr.epsr);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the second operand of a data processing instruction along with its carry out, which is either
+    /// imm32 or the shifted Rm depending on the encoding.
+    fn shifted_operand_c(&self, cortex_m33: &mut CortexM33, opcode_pc: u32, carry_in: bool) -> (u32, bool) {
+        let Operands { rm, imm32, imm_carry, register, shift_t, shift_n, .. } = self.operands;
+        if register {
+            let rm_value = read_register(cortex_m33, opcode_pc, rm);
+            shift_c(rm_value, shift_t, shift_n, carry_in)
+        } else {
+            (imm32, imm_carry.unwrap_or(carry_in))
+        }
+    }
+
+    /// Computes the address of a single or dual load or store from Rn, or the aligned PC for the literal forms,
+    /// along with the value to write back to Rn for the pre and post indexed forms.
+    fn load_store_address(&self, cortex_m33: &mut CortexM33, opcode_pc: u32) -> (u32, Option<u32>) {
+        let Operands { rn, rm, imm32, register, shift_n, index, add, wback, .. } = self.operands;
+
+        let base = if rn == 15 {
+            (opcode_pc + 4) & 0xfffffffc
+        } else {
+            cortex_m33.get_register_from_number(rn).get()
+        };
+        let offset = if register {
+            cortex_m33.get_register_from_number(rm).get() << shift_n
+        } else {
+            imm32
+        };
+
+        let offset_address = if add {
+            base.wrapping_add(offset)
+        } else {
+            base.wrapping_sub(offset)
+        };
+        let address = if index { offset_address } else { base };
+        (address, wback.then_some(offset_address))
+    }
+}
+
+impl Operands {
+    /// Takes the operand fields of the instruction out of its opcode
+    fn new(instruction: InstructionType, hw1: u16, hw2: u16) -> Self {
+        let operands = Self {
+            index: true,
+            add: true,
+            ..Self::default()
+        };
+
+        // The register fields of the 16-bit encodings, and those of the 32-bit encodings, which keep Rn in the
+        // first halfword and Rt, Rd and Rm in the second
+        let (low_0, low_3, low_6, low_8) = (
+            get_bits(hw1, 0..=2),
+            get_bits(hw1, 3..=5),
+            get_bits(hw1, 6..=8),
+            get_bits(hw1, 8..=10),
+        );
+        let (t32_rn, t32_rt, t32_rd, t32_rm) = (
+            get_bits(hw1, 0..=3),
+            get_bits(hw2, 12..=15),
+            get_bits(hw2, 8..=11),
+            get_bits(hw2, 0..=3),
+        );
+        let t32_setflags = if get_bit(hw1, 4) { SetFlags::Always } else { SetFlags::Never };
+        // imm3:imm2, the shift amount of the shifted register forms and the lsb of the bitfields
+        let imm3_imm2 = (get_bits(hw2, 12..=14) << 2) | get_bits(hw2, 6..=7);
+        let imm12 = (get_bits(hw1, 10..=10) << 11) | (get_bits(hw2, 12..=14) << 8) | get_bits(hw2, 0..=7);
+        // S:J2:J1:imm6:imm11:'0' and S:I1:I2:imm10:imm11:'0' of the 32-bit branches
+        let (s, j1, j2) = (
+            get_bits(hw1, 10..=10) as u32,
+            get_bits(hw2, 13..=13) as u32,
+            get_bits(hw2, 11..=11) as u32,
+        );
+        let imm11 = get_bits(hw2, 0..=10) as u32;
+
+        match instruction {
+            AddRegisterT1 | SubRegisterT1 => Self {
+                rd: low_0,
+                rn: low_3,
+                rm: low_6,
+                register: true,
+                setflags: SetFlags::OutsideItBlock,
+                ..operands
+            },
+            AddsT1 | SubT1 => Self {
+                rd: low_0,
+                rn: low_3,
+                imm32: low_6 as u32,
+                setflags: SetFlags::OutsideItBlock,
+                ..operands
+            },
+            AddsT2 | SubT2 => Self {
+                rd: low_8,
+                rn: low_8,
+                imm32: get_bits(hw1, 0..=7) as u32,
+                setflags: SetFlags::OutsideItBlock,
+                ..operands
+            },
+            CmpImmediateT1 => Self {
+                rn: low_8,
+                imm32: get_bits(hw1, 0..=7) as u32,
+                setflags: SetFlags::Always,
+                ..operands
+            },
+            MovImmediateT1 => Self {
+                rd: low_8,
+                imm32: get_bits(hw1, 0..=7) as u32,
+                setflags: SetFlags::OutsideItBlock,
+                ..operands
+            },
+            AdcT1 | AndRegisterT1 | BicRegisterT1 | EorRegisterT1 | MvnT1 | OrrRegisterT1 | SbcRegisterT1 => Self {
+                rd: low_0,
+                rn: low_0,
+                rm: low_3,
+                register: true,
+                setflags: SetFlags::OutsideItBlock,
+                ..operands
+            },
+            AsrRegisterT1 | LslRegisterT1 | LsrRegisterT1 | RorRegisterT1 => Self {
+                rd: low_0,
+                rn: low_0,
+                rm: low_3,
+                shift_t: match instruction {
+                    AsrRegisterT1 => SRType::Asr,
+                    LslRegisterT1 => SRType::Lsl,
+                    LsrRegisterT1 => SRType::Lsr,
+                    _ => SRType::Ror,
+                },
+                setflags: SetFlags::OutsideItBlock,
+                ..operands
+            },
+            AsrImmediateT1 | LslImmediateT1 | LsrImmediateT1 => {
+                let (shift_t, shift_n) =
+                    decode_imm_shift(u2::new(get_bits(hw1, 11..=12) as u8), get_bits(hw1, 6..=10));
+                Self {
+                    rd: low_0,
+                    rm: low_3,
+                    register: true,
+                    shift_t,
+                    shift_n,
+                    setflags: SetFlags::OutsideItBlock,
+                    ..operands
+                }
             }
-            TstRegisterT1 => {
-                todo!();
+            CmnRegisterT1 | CmpRegisterT1 | TstRegisterT1 => Self {
+                rn: low_0,
+                rm: low_3,
+                register: true,
+                setflags: SetFlags::Always,
+                ..operands
+            },
+            RsbImmediateT1 => Self {
+                rd: low_0,
+                rn: low_3,
+                setflags: SetFlags::OutsideItBlock,
+                ..operands
+            },
+            MulT1 => Self {
+                rd: low_0,
+                rn: low_3,
+                rm: low_0,
+                setflags: SetFlags::OutsideItBlock,
+                ..operands
+            },
+            RevT1 | Rev16T1 | RevshT1 | SxtbT1 | SxthT1 | UxtbT1 | UxthT1 => Self {
+                rd: low_0,
+                rm: low_3,
+                ..operands
+            },
+            LdrRegisterT1 | LdrbRegisterT1 | LdrhRegisterT1 | LdrsbRegisterT1 | LdrshRegisterT1 | StrRegisterT1
+            | StrbRegisterT1 | StrhRegisterT1 => Self {
+                rt: low_0,
+                rn: low_3,
+                rm: low_6,
+                register: true,
+                ..operands
+            },
+            LdrImmediateT1 | LdrbImmediateT1 | LdrhImmediateT1 | StrImmediateT1 | StrbImmediateT1
+            | StrhImmediateT1 => {
+                let scale = match instruction {
+                    LdrImmediateT1 | StrImmediateT1 => 2,
+                    LdrhImmediateT1 | StrhImmediateT1 => 1,
+                    _ => 0,
+                };
+                Self {
+                    rt: low_0,
+                    rn: low_3,
+                    imm32: (get_bits(hw1, 6..=10) as u32) << scale,
+                    ..operands
+                }
             }
-            UdfT1 => {
-                todo!();
+            LdrImmediateT2 | LdrLiteralT1 | StrImmediateT2 => Self {
+                rt: low_8,
+                rn: if let LdrLiteralT1 = instruction { 15 } else { 13 },
+                imm32: (get_bits(hw1, 0..=7) as u32) << 2,
+                ..operands
+            },
+            AddSpPlusImmediateT1 | AdrT1 => Self {
+                rd: low_8,
+                imm32: (get_bits(hw1, 0..=7) as u32) << 2,
+                ..operands
+            },
+            AddSpPlusImmediateT2 | SubSpMinusImmediateT1 => Self {
+                imm32: (get_bits(hw1, 0..=6) as u32) << 2,
+                ..operands
+            },
+            AddRegisterT2 | CmpRegisterT2 | MovRegisterT1 => {
+                let rdn = (get_bits(hw1, 7..=7) << 3) | low_0;
+                Self {
+                    rd: rdn,
+                    rn: rdn,
+                    rm: get_bits(hw1, 3..=6),
+                    register: true,
+                    setflags: if let CmpRegisterT2 = instruction { SetFlags::Always } else { SetFlags::Never },
+                    ..operands
+                }
             }
-            UdfT2 => {
-                todo!();
+            BlxT1 | BxT1 => Self {
+                rm: get_bits(hw1, 3..=6),
+                ..operands
+            },
+            BT1 => Self {
+                cond: get_bits(hw1, 8..=11),
+                imm32: (((hw1 as u32) << 24) as i32 >> 23) as u32,
+                ..operands
+            },
+            BT2 => Self {
+                imm32: (((hw1 as u32) << 21) as i32 >> 20) as u32,
+                ..operands
+            },
+            CbnzT1 | CbzT1 => Self {
+                rn: low_0,
+                imm32: ((get_bits(hw1, 9..=9) << 6) | (get_bits(hw1, 3..=7) << 1)) as u32,
+                ..operands
+            },
+            BkptT1 | ItT1 => Self {
+                imm32: get_bits(hw1, 0..=7) as u32,
+                ..operands
+            },
+            PopT1 | PushT1 => Self {
+                imm32: get_bits(hw1, 0..=8) as u32,
+                ..operands
+            },
+            LdmiaT1 | StmiaT1 => Self {
+                rn: low_8,
+                imm32: get_bits(hw1, 0..=7) as u32,
+                ..operands
+            },
+            AdcImmediateT1 | AddImmediateT3 | AndImmediateT1 | BicImmediateT1 | CmnImmediateT1 | CmpImmediateT2
+            | EorImmediateT1 | MovImmediateT2 | MvnImmediateT1 | OrnImmediateT1 | OrrImmediateT1
+            | RsbImmediateT2 | SbcImmediateT1 | SubImmediateT3 | TeqImmediateT1 | TstImmediateT1 => {
+                // The carry out of the expansion only depends on the constant when it is rotated
+                let (imm32, carry) = thumb_expand_imm_c(imm12, false);
+                Self {
+                    rd: t32_rd,
+                    rn: t32_rn,
+                    imm32,
+                    imm_carry: (get_bits(imm12, 10..=11) != 0b00).then_some(carry),
+                    setflags: t32_setflags,
+                    ..operands
+                }
             }
-            UxtbT1 => {
-                let rm = (opcode >> 3) & 0x7;
-                let rd = opcode & 0x7;
-                let value = cortex_m33.get_register_from_number(rm).get() & 0xff;
-                cortex_m33.get_register_from_number(rd).set(value);
+            AdcRegisterT2 | AddRegisterT3 | AndRegisterT2 | AsrImmediateT2 | BicRegisterT2 | CmnRegisterT2
+            | CmpRegisterT3 | EorRegisterT2 | LslImmediateT2 | LsrImmediateT2 | MovRegisterT3 | MvnRegisterT2
+            | OrnRegisterT1 | OrrRegisterT2 | RorImmediateT1 | RrxT1 | RsbRegisterT1 | SbcRegisterT2
+            | SubRegisterT2 | TeqRegisterT1 | TstRegisterT2 => {
+                let (shift_t, shift_n) = decode_imm_shift(u2::new(get_bits(hw2, 4..=5) as u8), imm3_imm2);
+                Self {
+                    rd: t32_rd,
+                    rn: t32_rn,
+                    rm: t32_rm,
+                    register: true,
+                    shift_t,
+                    shift_n,
+                    setflags: t32_setflags,
+                    ..operands
+                }
             }
-            UxthT1 => {
-                let rm = (opcode >> 3) & 0x7;
-                let rd = opcode & 0x7;
-                let value = cortex_m33.get_register_from_number(rm).get() & 0xffff;
-                cortex_m33.get_register_from_number(rd).set(value);
+            AddImmediateT4 | AdrT2 | AdrT3 | SubImmediateT4 => Self {
+                rd: t32_rd,
+                rn: t32_rn,
+                imm32: imm12 as u32,
+                ..operands
+            },
+            MovImmediateT3 | MovtT1 => Self {
+                rd: t32_rd,
+                imm32: ((get_bits(hw1, 0..=3) << 12) | imm12) as u32,
+                ..operands
+            },
+            AsrRegisterT2 | LslRegisterT2 | LsrRegisterT2 | RorRegisterT2 => Self {
+                rd: t32_rd,
+                rn: t32_rn,
+                rm: t32_rm,
+                shift_t: decode_reg_shift(get_bits(hw1, 5..=6)),
+                setflags: t32_setflags,
+                ..operands
+            },
+            BfcT1 | BfiT1 | SbfxT1 | UbfxT1 => Self {
+                rd: t32_rd,
+                rn: t32_rn,
+                imm32: get_bits(hw2, 0..=4) as u32,
+                shift_n: imm3_imm2,
+                ..operands
+            },
+            SsatT1 | UsatT1 => {
+                let (shift_t, shift_n) = decode_imm_shift(u2::new((get_bits(hw1, 5..=5) << 1) as u8), imm3_imm2);
+                Self {
+                    rd: t32_rd,
+                    rn: t32_rn,
+                    imm32: get_bits(hw2, 0..=4) as u32,
+                    shift_t,
+                    shift_n,
+                    ..operands
+                }
             }
-            WfeT1 => {
-                todo!();
+            SxtbT2 | SxthT2 | UxtbT2 | UxthT2 => Self {
+                rd: t32_rd,
+                rm: t32_rm,
+                shift_n: get_bits(hw2, 4..=5) * 8,
+                ..operands
+            },
+            ClzT1 | RbitT1 | RevT2 | Rev16T2 | RevshT2 => Self {
+                rd: t32_rd,
+                rm: t32_rm,
+                ..operands
+            },
+            MlaT1 | MlsT1 | MulT2 | SdivT1 | SmlalT1 | SmullT1 | UdivT1 | UmlalT1 | UmullT1 => Self {
+                rd: t32_rd,
+                rn: t32_rn,
+                rm: t32_rm,
+                rt: t32_rt,
+                ..operands
+            },
+            TbbT1 | TbhT1 => Self {
+                rn: t32_rn,
+                rm: t32_rm,
+                ..operands
+            },
+            BT3 => {
+                let imm21 = (s << 20) | (j2 << 19) | (j1 << 18) | ((get_bits(hw1, 0..=5) as u32) << 12) | (imm11 << 1);
+                Self {
+                    cond: get_bits(hw1, 6..=9),
+                    imm32: ((imm21 << 11) as i32 >> 11) as u32,
+                    ..operands
+                }
             }
-            WfiT1 => {
-                todo!();
+            BT4 | BlT1 => {
+                let (i1, i2) = (1 - (j1 ^ s), 1 - (j2 ^ s));
+                let imm10 = get_bits(hw1, 0..=9) as u32;
+                let imm25 = (s << 24) | (i1 << 23) | (i2 << 22) | (imm10 << 12) | (imm11 << 1);
+                Self {
+                    imm32: ((imm25 << 7) as i32 >> 7) as u32,
+                    ..operands
+                }
             }
-            YieldT1 => {
-                // Do nothing, wait for an event
+            MrsT1 => Self {
+                rd: t32_rd,
+                imm32: get_bits(hw2, 0..=7) as u32,
+                ..operands
+            },
+            // SYSm, with the mask above it where it is in the encoding
+            MsrT1 => Self {
+                rn: t32_rn,
+                imm32: get_bits(hw2, 0..=11) as u32,
+                ..operands
+            },
+            LdaT1 | LdabT1 | LdahT1 | LdaexT1 | LdaexbT1 | LdaexhT1 | LdrexbT1 | LdrexhT1 | StlT1 | StlbT1
+            | StlhT1 => Self {
+                rn: t32_rn,
+                rt: t32_rt,
+                ..operands
+            },
+            LdrexT1 | StrexT1 => Self {
+                rd: t32_rd,
+                rn: t32_rn,
+                rt: t32_rt,
+                imm32: (get_bits(hw2, 0..=7) as u32) << 2,
+                ..operands
+            },
+            StlexT1 | StlexbT1 | StlexhT1 | StrexbT1 | StrexhT1 => Self {
+                rd: t32_rm,
+                rn: t32_rn,
+                rt: t32_rt,
+                ..operands
+            },
+            LdmT2 | LdmdbT1 | StmT2 | StmdbT1 => Self {
+                rn: t32_rn,
+                imm32: hw2 as u32,
+                wback: get_bit(hw1, 5),
+                ..operands
+            },
+            LdrdImmediateT1 | LdrdLiteralT1 | StrdImmediateT1 => Self {
+                rd: t32_rd,
+                rn: t32_rn,
+                rt: t32_rt,
+                imm32: (get_bits(hw2, 0..=7) as u32) << 2,
+                index: get_bit(hw1, 8),
+                add: get_bit(hw1, 7),
+                wback: get_bit(hw1, 5),
+                ..operands
+            },
+            LdrImmediateT3 | LdrImmediateT4 | LdrLiteralT2 | LdrRegisterT2 | LdrtT1 | LdrbImmediateT2
+            | LdrbImmediateT3 | LdrbLiteralT1 | LdrbRegisterT2 | LdrbtT1 | LdrhImmediateT2 | LdrhImmediateT3
+            | LdrhLiteralT1 | LdrhRegisterT2 | LdrhtT1 | LdrsbImmediateT1 | LdrsbImmediateT2 | LdrsbLiteralT1
+            | LdrsbRegisterT2 | LdrsbtT1 | LdrshImmediateT1 | LdrshImmediateT2 | LdrshLiteralT1
+            | LdrshRegisterT2 | LdrshtT1 | StrImmediateT3 | StrImmediateT4 | StrRegisterT2 | StrtT1
+            | StrbImmediateT2 | StrbImmediateT3 | StrbRegisterT2 | StrbtT1 | StrhImmediateT2 | StrhImmediateT3
+            | StrhRegisterT2 | StrhtT1 => {
+                let operands = Self {
+                    rn: t32_rn,
+                    rt: t32_rt,
+                    ..operands
+                };
+
+                if t32_rn == 0b1111 || get_bit(hw1, 7) {
+                    // The literal forms, where U picks the direction, and the positive 12 bit offsets
+                    Self {
+                        imm32: get_bits(hw2, 0..=11) as u32,
+                        add: get_bit(hw1, 7),
+                        ..operands
+                    }
+                } else if get_bit(hw2, 11) {
+                    Self {
+                        imm32: get_bits(hw2, 0..=7) as u32,
+                        index: get_bit(hw2, 10),
+                        add: get_bit(hw2, 9),
+                        wback: get_bit(hw2, 8),
+                        ..operands
+                    }
+                } else {
+                    Self {
+                        rm: t32_rm,
+                        register: true,
+                        shift_n: get_bits(hw2, 4..=5),
+                        ..operands
+                    }
+                }
             }
+            ClrexT1 | CpsT1Id | CpsT1Ie | DmbT1Sy | DsbT1Sy | IsbT1Sy | NopT1 | NopT2 | PldT1 | PliT1 | SevT1
+            | SevT2 | SvcT1 | UdfT1 | UdfT2 | WfeT1 | WfeT2 | WfiT1 | WfiT2 | YieldT1 | YieldT2 => operands,
         }
     }
 }
 
-fn add_instruction_update_flags(apsr: &mut Apsr, x: u32, y: u32, carry_in: bool) -> u32 {
+/// Reads a register as an instruction operand, where the PC reads as the current instruction address plus 4.
+fn read_register(cortex_m33: &mut CortexM33, opcode_pc: u32, n: u16) -> u32 {
+    let register = cortex_m33.get_register_from_number(n);
+    if register.is_pc() {
+        opcode_pc + 4
+    } else {
+        register.get()
+    }
+}
+
+fn add_instruction_update_flags(
+    apsr: &mut Apsr,
+    x: u32,
+    y: u32,
+    carry_in: bool,
+    setflags: bool,
+) -> u32 {
     let (result, carry, overflow) = add_with_carry(x, y, carry_in);
 
-    apsr.set_n(get_bit(result, 31));
-    apsr.set_z(is_zero_bit(result));
-    apsr.set_c(carry);
-    apsr.set_v(overflow);
+    if setflags {
+        apsr.set_n(get_bit(result, 31));
+        apsr.set_z(is_zero_bit(result));
+        apsr.set_c(carry);
+        apsr.set_v(overflow);
+    }
 
     result
 }
+
+fn logical_instruction_update_flags(apsr: &mut Apsr, result: u32, carry: bool, setflags: bool) {
+    if setflags {
+        apsr.set_n(get_bit(result, 31));
+        apsr.set_z(is_zero_bit(result));
+        apsr.set_c(carry);
+    }
+}
@@ -1,24 +1,46 @@
 mod apsr;
+pub mod decode_cache;
+pub mod disassembler;
 pub mod exception;
+pub mod fault;
 mod instructions;
 pub mod nvic;
 pub mod opcodes;
 mod operation;
 pub mod registers;
-mod control;
+pub mod scb;
+pub mod systick;
+pub mod control;
+pub mod fpu;
+pub mod watchpoint;
 mod shpr;
 
-use crate::cortex_m33::apsr::Apsr;
-use crate::cortex_m33::nvic::Nvic;
-use crate::cortex_m33::registers::{CortexM33Registers, Register};
-use crate::MemoryInterface;
+use crate::cortex_m33::nvic::{Nvic, IRQ_COUNT, NVIC_END_ADDRESS, NVIC_START_ADDRESS};
+use crate::cortex_m33::scb::{CCR_RESET, ICTR_ADDRESS, SCB_END_ADDRESS, SCB_START_ADDRESS};
+use crate::cortex_m33::systick::{SysTick, SYSTICK_END_ADDRESS, SYSTICK_START_ADDRESS};
+use crate::cortex_m33::registers::{CortexM33Registers, Register, SpMode};
+use crate::trace::MemoryAccess;
+use crate::{BusError, MemoryInterface};
+use exception::{Exception, InterruptException, PRIORITY_MASK, THREAD_PRIORITY};
+use fault::{Fault, FaultStatus, HFSR_FORCED};
 use apsr::Xpsr;
-use control::Control;
+use fpu::Fpu;
+use decode_cache::DecodeCache;
+use watchpoint::{Watchpoint, WatchpointHit};
+use operation::exception_entry;
+use control::{Control, NPriv, SpSel};
 pub use instructions::OpCode;
 use shpr::Shpr;
 
 use self::exception::Exceptions;
 
+pub const PPB_START_ADDRESS: u32 = 0xE0000000;
+pub const PPB_END_ADDRESS: u32 = 0xE0100000;
+
+fn is_ppb(address: u32) -> bool {
+    (PPB_START_ADDRESS..PPB_END_ADDRESS).contains(&address)
+}
+
 #[derive(Debug, PartialEq)]
 pub enum Mode {
     Thread,
@@ -33,7 +55,47 @@ pub struct CortexM33 {
     pub exceptions: Exceptions,
     pub shpr: Shpr,
     pub nvic: Nvic,
+    pub systick: SysTick,
+    /// Core clock cycles since power on
+    pub cycles: u64,
     pub control: Control,
+    pub primask: bool,
+    pub basepri: u8,
+    pub faultmask: bool,
+    pub msplim: u32,
+    pub psplim: u32,
+    /// Address tagged by the local exclusive monitor, set by LDREX and cleared by STREX or CLREX
+    pub exclusive_monitor: Option<u32>,
+    /// System Handler Control and State Register, holds the enables for the configurable faults
+    pub shcsr: u32,
+    pub fault_status: FaultStatus,
+    /// Set when a fault is raised at HardFault priority or above, the core stops executing
+    pub locked_up: bool,
+    /// Vector Table Offset Register, the base address handlers are fetched from on exception entry
+    pub vtor: u32,
+    /// System Control Register, the sleep and event controls
+    pub scr: u32,
+    /// Configuration and Control Register
+    pub ccr: u32,
+    /// Coprocessor Access Control Register, which the startup code writes to enable the FPU and coprocessors
+    pub cpacr: u32,
+    /// Non-secure Access Control Register
+    pub nsacr: u32,
+    /// Set by AIRCR.SYSRESETREQ, the chip resets before the next instruction
+    pub reset_requested: bool,
+    /// Set by an exception return during the current instruction, which restores ITSTATE rather than advancing it
+    pub exception_returned: bool,
+    pub fpu: Fpu,
+    /// Data watchpoints set by a debugger, they survive reset
+    pub watchpoints: Vec<Watchpoint>,
+    /// The first watchpoint triggered since the debugger last cleared it
+    pub watchpoint_hit: Option<WatchpointHit>,
+    /// The immediate of the BKPT the core is halted on, the PC is left pointing at it
+    pub breakpoint: Option<u8>,
+    /// Data accesses made by the current instruction, only collected while it is being traced
+    pub traced_accesses: Option<Vec<MemoryAccess>>,
+    /// Instructions decoded by earlier executions, kept across reset like memory
+    pub decode_cache: DecodeCache,
     pub memory: Box<dyn MemoryInterface<u32>>,
 }
 
@@ -41,19 +103,381 @@ impl CortexM33 {
     pub fn deafult_exceptions() {}
 
     pub fn new(memory: Box<dyn MemoryInterface<u32>>) -> Self {
+        let mut xpsr = Xpsr::new();
+        xpsr.epsr.set_t(true);
+
         Self {
             registers: CortexM33Registers::new(),
-            xpsr: Xpsr::new(),
+            xpsr,
             mode: Mode::Thread,
             ipsr: 0,
             exceptions: Exceptions::new(),
             shpr: Shpr::new(),
             nvic: Nvic::new(),
+            systick: SysTick::new(),
+            cycles: 0,
             control: Control::new(),
+            primask: false,
+            basepri: 0,
+            faultmask: false,
+            msplim: 0,
+            psplim: 0,
+            exclusive_monitor: None,
+            shcsr: 0,
+            fault_status: FaultStatus::new(),
+            locked_up: false,
+            vtor: 0,
+            scr: 0,
+            ccr: CCR_RESET,
+            cpacr: 0,
+            nsacr: 0,
+            reset_requested: false,
+            exception_returned: false,
+            fpu: Fpu::new(),
+            watchpoints: Vec::new(),
+            watchpoint_hit: None,
+            breakpoint: None,
+            traced_accesses: None,
+            decode_cache: DecodeCache::new(),
             memory,
         }
     }
 
+    /// Puts the core back to its reset state. Memory and the cycle counter are left alone.
+    pub fn reset(&mut self) {
+        self.registers = CortexM33Registers::new();
+        self.xpsr = Xpsr::new();
+        self.xpsr.epsr.set_t(true);
+        self.mode = Mode::Thread;
+        self.ipsr = 0;
+        self.exceptions = Exceptions::new();
+        self.shpr = Shpr::new();
+        self.nvic = Nvic::new();
+        self.systick = SysTick::new();
+        self.control = Control::new();
+        self.primask = false;
+        self.basepri = 0;
+        self.faultmask = false;
+        self.msplim = 0;
+        self.psplim = 0;
+        self.exclusive_monitor = None;
+        self.shcsr = 0;
+        self.fault_status = FaultStatus::new();
+        self.locked_up = false;
+        self.vtor = 0;
+        self.scr = 0;
+        self.ccr = CCR_RESET;
+        self.cpacr = 0;
+        self.nsacr = 0;
+        self.reset_requested = false;
+        self.exception_returned = false;
+        self.fpu = Fpu::new();
+        self.breakpoint = None;
+    }
+
+    /// Selects the stack pointer the SP register banks to, from the current mode and CONTROL.SPSEL.
+    pub fn update_sp_mode(&mut self) {
+        if self.mode == Mode::Thread && self.control.spsel == SpSel::SpProcess {
+            self.registers.sp.set_mode(SpMode::Process);
+        } else {
+            self.registers.sp.set_mode(SpMode::Main);
+        }
+    }
+
+    /// Completes the BKPT the core is halted on and moves past it, as a debugger resuming after handling it would.
+    pub fn step_over_breakpoint(&mut self) {
+        if self.breakpoint.take().is_some() {
+            self.registers.pc.set(self.registers.pc.get() + 2);
+            operation::it_advance(&mut self.xpsr.epsr);
+        }
+    }
+
+    /// Returns from a function call as BX LR would, which in handler mode can be an exception return.
+    pub fn return_from_call(&mut self) -> Result<(), Fault> {
+        let lr = self.registers.lr.get();
+        operation::bx_write_pc(self, lr)
+    }
+
+    pub fn is_privileged(&self) -> bool {
+        self.mode == Mode::Handler || self.control.npriv == NPriv::ThreadModePrivileged
+    }
+
+    pub fn read_u8(&mut self, address: u32) -> Result<u8, Fault> {
+        self.watch(address, 1, false);
+
+        let value = if is_ppb(address) {
+            (self.read_ppb(address & !3)? >> (8 * (address & 3))) as u8
+        } else {
+            self.memory
+                .read(address)
+                .map_err(|error| Fault::PreciseBus { address, error })?
+        };
+
+        self.trace_access(address, 1, false, value as u32);
+        Ok(value)
+    }
+
+    pub fn read_u16(&mut self, address: u32) -> Result<u16, Fault> {
+        self.watch(address, 2, false);
+
+        let value = if is_ppb(address) {
+            (self.read_ppb(address & !3)? >> (8 * (address & 2))) as u16
+        } else {
+            self.memory
+                .read_u16(address)
+                .map_err(|error| Fault::PreciseBus { address, error })?
+        };
+
+        self.trace_access(address, 2, false, value as u32);
+        Ok(value)
+    }
+
+    pub fn read_u32(&mut self, address: u32) -> Result<u32, Fault> {
+        self.watch(address, 4, false);
+
+        let value = if is_ppb(address) {
+            self.read_ppb(address & !3)?
+        } else {
+            self.memory
+                .read_u32(address)
+                .map_err(|error| Fault::PreciseBus { address, error })?
+        };
+
+        self.trace_access(address, 4, false, value);
+        Ok(value)
+    }
+
+    pub fn write_u8(&mut self, address: u32, value: u8) -> Result<(), Fault> {
+        self.watch(address, 1, true);
+
+        if is_ppb(address) {
+            let shift = 8 * (address & 3);
+            self.write_ppb(address & !3, (value as u32) << shift, 0xff << shift)?;
+        } else {
+            self.memory
+                .write(address, value)
+                .map_err(|error| Fault::PreciseBus { address, error })?;
+        }
+
+        self.trace_access(address, 1, true, value as u32);
+        Ok(())
+    }
+
+    pub fn write_u16(&mut self, address: u32, value: u16) -> Result<(), Fault> {
+        self.watch(address, 2, true);
+
+        if is_ppb(address) {
+            let shift = 8 * (address & 2);
+            self.write_ppb(address & !3, (value as u32) << shift, 0xffff << shift)?;
+        } else {
+            self.memory
+                .write_u16(address, value)
+                .map_err(|error| Fault::PreciseBus { address, error })?;
+        }
+
+        self.trace_access(address, 2, true, value as u32);
+        Ok(())
+    }
+
+    pub fn write_u32(&mut self, address: u32, value: u32) -> Result<(), Fault> {
+        self.watch(address, 4, true);
+
+        if is_ppb(address) {
+            self.write_ppb(address & !3, value, u32::MAX)?;
+        } else {
+            self.memory
+                .write_u32(address, value)
+                .map_err(|error| Fault::PreciseBus { address, error })?;
+        }
+
+        self.trace_access(address, 4, true, value);
+        Ok(())
+    }
+
+    /// Keeps a data access for the trace while one is being collected.
+    fn trace_access(&mut self, address: u32, size: u8, write: bool, value: u32) {
+        if let Some(accesses) = &mut self.traced_accesses {
+            accesses.push(MemoryAccess {
+                address,
+                size,
+                write,
+                value,
+            });
+        }
+    }
+
+    /// Records the first watchpoint a data access triggers.
+    fn watch(&mut self, address: u32, size: u32, write: bool) {
+        if self.watchpoint_hit.is_some() {
+            return;
+        }
+
+        if let Some(watchpoint) = self.watchpoints.iter().find(|watchpoint| watchpoint.matches(address, size, write)) {
+            self.watchpoint_hit = Some(WatchpointHit {
+                watchpoint: *watchpoint,
+                address,
+            });
+        }
+    }
+
+    /// Reads a word from the Private Peripheral Bus, the core's own registers. Only privileged code has access.
+    fn read_ppb(&mut self, address: u32) -> Result<u32, Fault> {
+        let unmapped = Fault::PreciseBus { address, error: BusError::Unmapped };
+        if !self.is_privileged() {
+            return Err(unmapped);
+        }
+
+        match address {
+            ICTR_ADDRESS => Ok(self.read_ictr()),
+            SYSTICK_START_ADDRESS..SYSTICK_END_ADDRESS => Ok(self.systick.read(address - SYSTICK_START_ADDRESS)),
+            NVIC_START_ADDRESS..NVIC_END_ADDRESS => {
+                Ok(self.nvic.read(address - NVIC_START_ADDRESS, &self.exceptions))
+            }
+            SCB_START_ADDRESS..SCB_END_ADDRESS => Ok(self.read_scb(address - SCB_START_ADDRESS)),
+            _ => Err(unmapped),
+        }
+    }
+
+    /// Writes the bits selected by mask to a word on the Private Peripheral Bus.
+    fn write_ppb(&mut self, address: u32, value: u32, mask: u32) -> Result<(), Fault> {
+        let unmapped = Fault::PreciseBus { address, error: BusError::Unmapped };
+        if !self.is_privileged() {
+            return Err(unmapped);
+        }
+
+        match address {
+            SYSTICK_START_ADDRESS..SYSTICK_END_ADDRESS => {
+                self.systick.write(address - SYSTICK_START_ADDRESS, value, mask);
+                Ok(())
+            }
+            NVIC_START_ADDRESS..NVIC_END_ADDRESS => {
+                self.nvic
+                    .write(address - NVIC_START_ADDRESS, value, mask, &mut self.exceptions);
+                Ok(())
+            }
+            ICTR_ADDRESS => Ok(()),
+            SCB_START_ADDRESS..SCB_END_ADDRESS => {
+                self.write_scb(address - SCB_START_ADDRESS, value, mask);
+                Ok(())
+            }
+            _ => Err(unmapped),
+        }
+    }
+
+    /// Advances the cycle counter, clocking SysTick when it counts the processor clock.
+    pub fn advance_cycles(&mut self, cycles: u64) {
+        self.cycles += cycles;
+
+        if self.systick.uses_processor_clock() && self.systick.count(cycles) {
+            self.exceptions.set_pending(Exception::Interrupt(InterruptException::SysTick));
+        }
+    }
+
+    /// Clocks SysTick from the external reference tick, which it counts when CSR.CLKSOURCE is clear. The RP2350
+    /// takes the tick from its TICKS proc0 generator.
+    pub fn reference_tick(&mut self) {
+        if !self.systick.uses_processor_clock() && self.systick.count(1) {
+            self.exceptions.set_pending(Exception::Interrupt(InterruptException::SysTick));
+        }
+    }
+
+    /// Drives an external IRQ line into the NVIC. Lines are level sensitive, so an IRQ asserted when its
+    /// handler returns pends again.
+    pub fn set_irq_line(&mut self, irq: u8, level: bool) {
+        assert!(irq < IRQ_COUNT, "IRQ {} does not exist", irq);
+
+        self.nvic.set_line(irq, level);
+        if level {
+            self.exceptions.set_pending(Nvic::exception(irq));
+        }
+    }
+
+    /// The group priority the core is currently executing at, from the active exceptions and the mask registers.
+    /// Lower numbers are higher priority.
+    pub fn execution_priority(&self) -> i16 {
+        let mut priority = self
+            .exceptions
+            .active
+            .keys()
+            .map(|&n| Exceptions::group_priority(self, n))
+            .min()
+            .unwrap_or(THREAD_PRIORITY);
+
+        let basepri = self.basepri & PRIORITY_MASK;
+        if basepri != 0 {
+            priority = priority.min(Exceptions::group(self, basepri as i16));
+        }
+
+        if self.primask {
+            priority = priority.min(0);
+        }
+
+        if self.faultmask {
+            priority = priority.min(-1);
+        }
+
+        priority
+    }
+
+    /// Records a synchronous fault and pends the exception that handles it. Faults that are disabled, or that
+    /// can't preempt the current execution priority, escalate to HardFault. A fault that can't be taken even as
+    /// HardFault locks the core up.
+    pub fn raise_fault(&mut self, fault: Fault) {
+        self.fault_status.record(fault);
+
+        let exception = fault.exception();
+        let enabled = self.shcsr & fault.enable_bit() != 0;
+        let execution_priority = self.execution_priority();
+
+        if enabled && Exceptions::group_priority(self, exception.number()) < execution_priority {
+            self.exceptions.set_pending(exception);
+            return;
+        }
+
+        self.escalate_to_hard_fault(execution_priority);
+    }
+
+    /// Pends SVCall for an SVC instruction. The call is synchronous, so if SVCall can't preempt the current
+    /// execution priority it escalates to HardFault like a fault would.
+    pub fn call_supervisor(&mut self) {
+        let execution_priority = self.execution_priority();
+
+        if Exceptions::group_priority(self, Exception::SVCall.number()) < execution_priority {
+            self.exceptions.set_pending(Exception::SVCall);
+            return;
+        }
+
+        self.escalate_to_hard_fault(execution_priority);
+    }
+
+    fn escalate_to_hard_fault(&mut self, execution_priority: i16) {
+        if Exceptions::group_priority(self, Exception::HardFault.number()) < execution_priority {
+            self.fault_status.hfsr |= HFSR_FORCED;
+            self.exceptions.set_pending(Exception::HardFault);
+        } else {
+            self.locked_up = true;
+        }
+    }
+
+    /// Takes the highest priority pending exception if it can preempt the current execution priority.
+    pub fn take_pending_exception(&mut self) -> bool {
+        // Checked before every instruction, when there is almost never anything pending
+        if self.exceptions.pending.is_empty() {
+            return false;
+        }
+
+        let Some(exception) = Exceptions::highest_pending(self) else {
+            return false;
+        };
+
+        if Exceptions::group_priority(self, exception.number()) >= self.execution_priority() {
+            return false;
+        }
+
+        exception_entry(self, exception);
+        true
+    }
+
     pub fn get_register_from_number(&mut self, i: u16) -> &mut dyn Register {
         match i {
             0 => &mut self.registers.r0,
@@ -1,27 +1,163 @@
 use std::ops::{Index, IndexMut};
 
+use crate::cortex_m33::exception::{Exception, Exceptions, InterruptException, PRIORITY_MASK};
+
+pub const NVIC_START_ADDRESS: u32 = 0xE000E100;
+pub const NVIC_END_ADDRESS: u32 = 0xE000E500;
+
+/// External interrupt lines wired into the NVIC on the RP2350
+pub const IRQ_COUNT: u8 = 52;
+
+const BIT_WORDS: usize = IRQ_COUNT.div_ceil(32) as usize;
+const IPR_WORDS: usize = IRQ_COUNT.div_ceil(4) as usize;
+
+// Register offsets from NVIC_START_ADDRESS, each is a block of 16 words apart from IPR
+const ISER: u32 = 0x000;
+const ICER: u32 = 0x080;
+const ISPR: u32 = 0x100;
+const ICPR: u32 = 0x180;
+const IABR: u32 = 0x200;
+const ITNS: u32 = 0x280;
+const IPR: u32 = 0x300;
+
 pub struct Nvic {
-    ipr0: u32,
-    ipr1: u32,
-    ipr2: u32,
-    ipr3: u32,
-    ipr4: u32,
-    ipr5: u32,
-    ipr6: u32,
-    ipr7: u32,
+    iser: [u32; BIT_WORDS],
+    itns: [u32; BIT_WORDS],
+    ipr: [u32; IPR_WORDS],
+    /// The level each peripheral is driving its IRQ line at
+    lines: [u32; BIT_WORDS],
+    /// AIRCR.PRIGROUP, the split between group priority and subpriority
+    pub prigroup: u8,
+}
+
+impl Default for Nvic {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Nvic {
     pub fn new() -> Self {
         Self {
-            ipr0: 0,
-            ipr1: 0,
-            ipr2: 0,
-            ipr3: 0,
-            ipr4: 0,
-            ipr5: 0,
-            ipr6: 0,
-            ipr7: 0,
+            iser: [0; BIT_WORDS],
+            itns: [0; BIT_WORDS],
+            ipr: [0; IPR_WORDS],
+            lines: [0; BIT_WORDS],
+            prigroup: 0,
+        }
+    }
+
+    pub fn is_enabled(&self, irq: u8) -> bool {
+        self.iser[irq as usize / 32] & (1 << (irq % 32)) != 0
+    }
+
+    pub fn set_enabled(&mut self, irq: u8, enabled: bool) {
+        let bit = 1 << (irq % 32);
+        if enabled {
+            self.iser[irq as usize / 32] |= bit;
+        } else {
+            self.iser[irq as usize / 32] &= !bit;
+        }
+    }
+
+    pub fn priority(&self, irq: u8) -> u8 {
+        (self.ipr[irq as usize / 4] >> (8 * (irq % 4))) as u8 & PRIORITY_MASK
+    }
+
+    pub fn set_priority(&mut self, irq: u8, priority: u8) {
+        let shift = 8 * (irq % 4);
+        let word = &mut self.ipr[irq as usize / 4];
+        *word = (*word & !(0xff << shift)) | ((priority & PRIORITY_MASK) as u32) << shift;
+    }
+
+    pub fn line(&self, irq: u8) -> bool {
+        self.lines[irq as usize / 32] & (1 << (irq % 32)) != 0
+    }
+
+    pub fn set_line(&mut self, irq: u8, level: bool) {
+        let bit = 1 << (irq % 32);
+        if level {
+            self.lines[irq as usize / 32] |= bit;
+        } else {
+            self.lines[irq as usize / 32] &= !bit;
+        }
+    }
+
+    /// Reads the register at the offset from NVIC_START_ADDRESS. Pending and active state lives in Exceptions.
+    pub fn read(&self, offset: u32, exceptions: &Exceptions) -> u32 {
+        let block = offset & !0x7f;
+        let word = (offset & 0x7f) as usize / 4;
+
+        match block {
+            ISER | ICER if word < BIT_WORDS => self.iser[word],
+            ISPR | ICPR if word < BIT_WORDS => Self::exception_bits(word, |n| exceptions.pending.contains_key(&n)),
+            IABR if word < BIT_WORDS => Self::exception_bits(word, |n| exceptions.active.contains_key(&n)),
+            ITNS if word < BIT_WORDS => self.itns[word],
+            _ if (IPR..IPR + 4 * IPR_WORDS as u32).contains(&offset) => self.ipr[(offset - IPR) as usize / 4],
+            _ => 0,
+        }
+    }
+
+    /// Writes the bits of value selected by mask to the register at the offset from NVIC_START_ADDRESS.
+    /// Registers that don't exist, and bits for IRQs past IRQ_COUNT, are ignored.
+    pub fn write(&mut self, offset: u32, value: u32, mask: u32, exceptions: &mut Exceptions) {
+        let block = offset & !0x7f;
+        let word = (offset & 0x7f) as usize / 4;
+
+        if (IPR..IPR + 4 * IPR_WORDS as u32).contains(&offset) {
+            let ipr = &mut self.ipr[(offset - IPR) as usize / 4];
+            let mask = mask & 0xf0f0f0f0;
+            *ipr = (*ipr & !mask) | (value & mask);
+            return;
+        }
+
+        if word >= BIT_WORDS {
+            return;
+        }
+
+        let bits = value & mask & Self::implemented_bits(word);
+        match block {
+            ISER => self.iser[word] |= bits,
+            ICER => self.iser[word] &= !bits,
+            ISPR => Self::for_each_irq(word, bits, |irq| exceptions.set_pending(Self::exception(irq))),
+            ICPR => Self::for_each_irq(word, bits, |irq| {
+                exceptions.pending.remove(&Self::exception(irq).number());
+            }),
+            ITNS => self.itns[word] = (self.itns[word] & !(mask & Self::implemented_bits(word))) | bits,
+            _ => {}
+        }
+    }
+
+    pub fn exception(irq: u8) -> Exception {
+        Exception::Interrupt(InterruptException::ExternalInterrupt(irq))
+    }
+
+    fn implemented_bits(word: usize) -> u32 {
+        let irqs = IRQ_COUNT as usize - 32 * word;
+        if irqs >= 32 {
+            u32::MAX
+        } else {
+            (1 << irqs) - 1
+        }
+    }
+
+    fn exception_bits(word: usize, is_set: impl Fn(u8) -> bool) -> u32 {
+        let mut bits = 0;
+        for bit in 0..32 {
+            let irq = 32 * word + bit;
+            if irq < IRQ_COUNT as usize && is_set(16 + irq as u8) {
+                bits |= 1 << bit;
+            }
+        }
+
+        bits
+    }
+
+    fn for_each_irq(word: usize, bits: u32, mut f: impl FnMut(u8)) {
+        for bit in 0..32 {
+            if bits & (1 << bit) != 0 {
+                f((32 * word + bit) as u8);
+            }
         }
     }
 }
@@ -30,32 +166,16 @@ impl Index<usize> for Nvic {
     type Output = u32;
 
     fn index(&self, index: usize) -> &Self::Output {
-        match index {
-            0 => &self.ipr0,
-            1 => &self.ipr1,
-            2 => &self.ipr2,
-            3 => &self.ipr3,
-            4 => &self.ipr4,
-            5 => &self.ipr5,
-            6 => &self.ipr6,
-            7 => &self.ipr7,
-            _ => panic!("Specified nvic of '{}' does not exist", index),
-        }
+        self.ipr
+            .get(index)
+            .unwrap_or_else(|| panic!("Specified nvic of '{}' does not exist", index))
     }
 }
 
 impl IndexMut<usize> for Nvic {
     fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-        match index {
-            0 => &mut self.ipr0,
-            1 => &mut self.ipr1,
-            2 => &mut self.ipr2,
-            3 => &mut self.ipr3,
-            4 => &mut self.ipr4,
-            5 => &mut self.ipr5,
-            6 => &mut self.ipr6,
-            7 => &mut self.ipr7,
-            _ => panic!("Specified nvic of '{}' does not exist", index),
-        }
+        self.ipr
+            .get_mut(index)
+            .unwrap_or_else(|| panic!("Specified nvic of '{}' does not exist", index))
     }
 }
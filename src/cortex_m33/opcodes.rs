@@ -25,31 +25,31 @@ impl Registers {
     pub fn binary(&self) -> u16 {
         let mut binary = 0;
         for number in self.register_numbers.iter() {
-            binary = binary | (1 << number);
+            binary |= 1 << number;
         }
 
-        return binary;
+        binary
     }
 }
 
 pub struct PushT1;
 impl PushT1 {
     pub fn opcode(push_to_lr: bool, registers: Registers) -> u16 {
-        return (0b1011010 << 9) | ((push_to_lr as u16) << 8) | registers.binary();
+        (0b1011010 << 9) | ((push_to_lr as u16) << 8) | registers.binary()
     }
 }
 
 pub struct AdcT1;
 impl AdcT1 {
     pub fn opcode(rdn: &dyn Register, rm: &dyn Register) -> u16 {
-        return (0b0100000101 << 6) | ((rm.number() & 7) << 3) | (rdn.number() & 7);
+        (0b0100000101 << 6) | ((rm.number() & 7) << 3) | (rdn.number() & 7)
     }
 }
 
 pub struct AddSpPlusImmediateT2;
 impl AddSpPlusImmediateT2 {
     pub fn opcode(imm: u16) -> u16 {
-        return (0b101100000 << 7) | ((imm >> 2) & 0x7f);
+        (0b101100000 << 7) | ((imm >> 2) & 0x7f)
     }
 }
 
@@ -57,93 +57,107 @@ pub struct AddSpPlusImmediateT1;
 impl AddSpPlusImmediateT1 {
     // rd is the register number
     pub fn opcode(rd: &dyn Register, imm8: u16) -> u16 {
-        return (0b10101 << 11) | ((rd.number() & 7) << 8) | ((imm8 >> 2) & 0xff);
+        (0b10101 << 11) | ((rd.number() & 7) << 8) | ((imm8 >> 2) & 0xff)
     }
 }
 
 pub struct AddsT1;
 impl AddsT1 {
     pub fn opcode(rd: &dyn Register, rn: &dyn Register, imm3: u16) -> u16 {
-        return (0b0001110 << 9)
+        (0b0001110 << 9)
             | ((imm3 & 0x7) << 6)
             | ((rn.number() & 7) << 3)
-            | (rd.number() & 7);
+            | (rd.number() & 7)
     }
 }
 
 pub struct AddsT2;
 impl AddsT2 {
     pub fn opcode(rdn: &dyn Register, imm8: u16) -> u16 {
-        return (0b00110 << 11) | ((rdn.number() & 7) << 8) | (imm8 & 0xff);
+        (0b00110 << 11) | ((rdn.number() & 7) << 8) | (imm8 & 0xff)
     }
 }
 
 pub struct AddsRegisterT1;
 impl AddsRegisterT1 {
     pub fn opcode(rd: &dyn Register, rn: &dyn Register, rm: &dyn Register) -> u16 {
-        return (0b0001100 << 9)
+        (0b0001100 << 9)
             | ((rm.number() & 0x7) << 6)
             | ((rn.number() & 7) << 3)
-            | (rd.number() & 7);
+            | (rd.number() & 7)
     }
 }
 
 pub struct AddRegisterT2;
 impl AddRegisterT2 {
     pub fn opcode(rdn: &dyn Register, rm: &dyn Register) -> u16 {
-        return (0b01000100 << 8)
+        (0b01000100 << 8)
             | ((rdn.number() & 0x8) << 4)
             | ((rm.number() & 0xf) << 3)
-            | (rdn.number() & 0x7);
+            | (rdn.number() & 0x7)
     }
 }
 
 pub struct AdrT1;
 impl AdrT1 {
     pub fn opcode(rd: &dyn Register, imm8: u16) -> u16 {
-        return (0b10100 << 11) | ((rd.number() & 7) << 8) | ((imm8 >> 2) & 0xff);
+        (0b10100 << 11) | ((rd.number() & 7) << 8) | ((imm8 >> 2) & 0xff)
     }
 }
 
 pub struct AndRegisterT1;
 impl AndRegisterT1 {
     pub fn opcode(rn: &dyn Register, rm: &dyn Register) -> u16 {
-        return (0b0100000000 << 6) | ((rm.number() & 7) << 3) | (rn.number() & 0x7);
+        (0b0100000000 << 6) | ((rm.number() & 7) << 3) | (rn.number() & 0x7)
     }
 }
 
 pub struct BT2;
 impl BT2 {
     pub fn opcode(imm11: u16) -> u16 {
-        return (0b11100 << 11) | ((imm11 >> 1) & 0x7ff);
+        (0b11100 << 11) | ((imm11 >> 1) & 0x7ff)
     }
 }
 
 pub struct BT1;
 impl BT1 {
     pub fn opcode(cond: u16, imm8: u16) -> u16 {
-        return (0b1101 << 12) | ((cond & 0xf) << 8) | ((imm8 >> 1) & 0x1ff);
+        (0b1101 << 12) | ((cond & 0xf) << 8) | ((imm8 >> 1) & 0x1ff)
+    }
+}
+
+pub struct BkptT1;
+impl BkptT1 {
+    pub fn opcode(imm8: u16) -> u16 {
+        (0b10111110 << 8) | (imm8 & 0xff)
     }
 }
 
 pub struct DmbT1Sy;
 impl DmbT1Sy {
     pub fn opcode() -> u32 {
-        return 0x8f50f3bf;
+        0x8f50f3bf
     }
 }
 
 pub struct DsbT1Sy;
 impl DsbT1Sy {
     pub fn opcode() -> u32 {
-        return 0x8f4ff3bf;
+        0x8f4ff3bf
     }
 }
 
 pub struct IsbT1Sy;
 impl IsbT1Sy {
     pub fn opcode() -> u32 {
-        return 0x8f6ff3bf;
+        0x8f6ff3bf
+    }
+}
+
+pub struct ItT1;
+impl ItT1 {
+    pub fn opcode(firstcond: u16, mask: u16) -> u16 {
+        (0b10111111 << 8) | ((firstcond & 0xf) << 4) | (mask & 0xf)
     }
 }
 
@@ -152,63 +166,77 @@ impl MovRegisterT1 {
     pub fn opcode(rd: &dyn Register, rm: &dyn Register) -> u16 {
         let bit = if rd.number() & 0x8 > 0 { 1 } else { 0 };
 
-        return (0b01000110 << 8) | (bit << 7) | (rm.number() << 3) | (rd.number() & 0x7);
+        (0b01000110 << 8) | (bit << 7) | (rm.number() << 3) | (rd.number() & 0x7)
     }
 }
 
 pub struct LdmiaT1;
 impl LdmiaT1 {
     pub fn opcode(rn: &dyn Register, registers: Registers) -> u16 {
-        return (0b11001 << 11) | ((rn.number() & 0x7) << 8) | (registers.binary() & 0xff);
+        (0b11001 << 11) | ((rn.number() & 0x7) << 8) | (registers.binary() & 0xff)
     }
 }
 
 pub struct RevT1;
 impl RevT1 {
     pub fn opcode(rd: &dyn Register, rn: &dyn Register) -> u16 {
-        return (0b1011101000 << 6) | ((rn.number() & 0x7) << 3) | (rd.number() & 0x7);
+        (0b1011101000 << 6) | ((rn.number() & 0x7) << 3) | (rd.number() & 0x7)
     }
 }
 
 pub struct Rev16T1;
 impl Rev16T1 {
     pub fn opcode(rd: &dyn Register, rn: &dyn Register) -> u16 {
-        return (0b1011101001 << 6) | ((rn.number() & 0x7) << 3) | (rd.number() & 0x7);
+        (0b1011101001 << 6) | ((rn.number() & 0x7) << 3) | (rd.number() & 0x7)
     }
 }
 
 pub struct StmiaT1;
 impl StmiaT1 {
     pub fn opcode(rn: &dyn Register, registers: Registers) -> u16 {
-        return (0b11000 << 11) | ((rn.number() & 0x7) << 8) | (registers.binary() & 0xff);
+        (0b11000 << 11) | ((rn.number() & 0x7) << 8) | (registers.binary() & 0xff)
     }
 }
 
 pub struct SubSpMinusImmediateT1;
 impl SubSpMinusImmediateT1 {
     pub fn opcode(imm: u16) -> u16 {
-        return (0b101100001 << 7) | ((imm >> 2) & 0x7f);
+        (0b101100001 << 7) | ((imm >> 2) & 0x7f)
     }
 }
 
 pub struct UxtbT1;
 impl UxtbT1 {
     pub fn opcode(rd: &dyn Register, rm: &dyn Register) -> u16 {
-        return (0b1011001011 << 6) | ((rm.number() & 7) << 3) | (rd.number() & 7);
+        (0b1011001011 << 6) | ((rm.number() & 7) << 3) | (rd.number() & 7)
     }
 }
 
 pub struct UxthT1;
 impl UxthT1 {
     pub fn opcode(rd: &dyn Register, rm: &dyn Register) -> u16 {
-        return (0b1011001010 << 6) | ((rm.number() & 7) << 3) | (rd.number() & 7);
+        (0b1011001010 << 6) | ((rm.number() & 7) << 3) | (rd.number() & 7)
     }
 }
 
 pub struct YieldT1;
 impl YieldT1 {
     pub fn opcode() -> u16 {
-        return 0b1011111100010000;
+        0b1011111100010000
+    }
+}
+
+pub struct CpsT1Id;
+impl CpsT1Id {
+    pub fn opcode() -> u16 {
+        0b1011011001110010
+    }
+}
+
+pub struct CpsT1Ie;
+impl CpsT1Ie {
+    pub fn opcode() -> u16 {
+        0b1011011001100010
     }
 }
 
@@ -227,33 +255,951 @@ impl BlT1 {
             | (0b11110 << 11)
             | (s << 10)
             | imm10;
-        return opcode as u32 >> 0;
+        opcode as u32
     }
 }
 
 pub struct BlxT1;
 impl BlxT1 {
     pub fn opcode(rm: &dyn Register) -> u16 {
-        return (0b010001111 << 7) | (rm.number() << 3);
+        (0b010001111 << 7) | (rm.number() << 3)
     }
 }
 
 pub struct AsrImmediateT1;
 impl AsrImmediateT1 {
     pub fn opcode(rd: &dyn Register, rm: &dyn Register, imm5: u16) -> u16 {
-        return (0b00010 << 11)
+        (0b00010 << 11)
             | ((imm5 & 0x1f) << 6)
             | ((rm.number() & 0x7) << 3)
-            | (rd.number() & 0x7);
+            | (rd.number() & 0x7)
     }
 }
 
 pub struct AsrRegisterT1;
 impl AsrRegisterT1 {
     pub fn opcode(rdn: &dyn Register, rm: &dyn Register) -> u16 {
-        return (0b0100000100 << 6)
+        (0b0100000100 << 6)
             | ((rm.number() & 0x7) << 3)
             | ((rm.number() & 0x7) << 3)
-            | (rdn.number() & 0x7);
+            | (rdn.number() & 0x7)
+    }
+}
+
+pub struct BxT1;
+impl BxT1 {
+    pub fn opcode(rm: &dyn Register) -> u16 {
+        (0b010001110 << 7) | ((rm.number() & 0xf) << 3)
+    }
+}
+
+pub struct CmnRegisterT1;
+impl CmnRegisterT1 {
+    pub fn opcode(rn: &dyn Register, rm: &dyn Register) -> u16 {
+        (0b0100001011 << 6) | ((rm.number() & 0x7) << 3) | (rn.number() & 0x7)
+    }
+}
+
+pub struct CmpImmediateT1;
+impl CmpImmediateT1 {
+    pub fn opcode(rn: &dyn Register, imm8: u16) -> u16 {
+        (0b00101 << 11) | ((rn.number() & 0x7) << 8) | (imm8 & 0xff)
+    }
+}
+
+pub struct CmpRegisterT1;
+impl CmpRegisterT1 {
+    pub fn opcode(rn: &dyn Register, rm: &dyn Register) -> u16 {
+        (0b0100001010 << 6) | ((rm.number() & 0x7) << 3) | (rn.number() & 0x7)
+    }
+}
+
+pub struct CmpRegisterT2;
+impl CmpRegisterT2 {
+    pub fn opcode(rn: &dyn Register, rm: &dyn Register) -> u16 {
+        (0b01000101 << 8)
+            | ((rn.number() & 0x8) << 4)
+            | ((rm.number() & 0xf) << 3)
+            | (rn.number() & 0x7)
+    }
+}
+
+pub struct EorRegisterT1;
+impl EorRegisterT1 {
+    pub fn opcode(rdn: &dyn Register, rm: &dyn Register) -> u16 {
+        (0b0100000001 << 6) | ((rm.number() & 0x7) << 3) | (rdn.number() & 0x7)
+    }
+}
+
+pub struct LdrImmediateT1;
+impl LdrImmediateT1 {
+    pub fn opcode(rt: &dyn Register, rn: &dyn Register, imm: u16) -> u16 {
+        (0b01101 << 11)
+            | (((imm >> 2) & 0x1f) << 6)
+            | ((rn.number() & 0x7) << 3)
+            | (rt.number() & 0x7)
+    }
+}
+
+pub struct LdrImmediateT2;
+impl LdrImmediateT2 {
+    pub fn opcode(rt: &dyn Register, imm: u16) -> u16 {
+        (0b10011 << 11) | ((rt.number() & 0x7) << 8) | ((imm >> 2) & 0xff)
+    }
+}
+
+pub struct LdrLiteralT1;
+impl LdrLiteralT1 {
+    pub fn opcode(rt: &dyn Register, imm: u16) -> u16 {
+        (0b01001 << 11) | ((rt.number() & 0x7) << 8) | ((imm >> 2) & 0xff)
+    }
+}
+
+pub struct LdrRegisterT1;
+impl LdrRegisterT1 {
+    pub fn opcode(rt: &dyn Register, rn: &dyn Register, rm: &dyn Register) -> u16 {
+        (0b0101100 << 9)
+            | ((rm.number() & 0x7) << 6)
+            | ((rn.number() & 0x7) << 3)
+            | (rt.number() & 0x7)
+    }
+}
+
+pub struct LdrbImmediateT1;
+impl LdrbImmediateT1 {
+    pub fn opcode(rt: &dyn Register, rn: &dyn Register, imm5: u16) -> u16 {
+        (0b01111 << 11)
+            | ((imm5 & 0x1f) << 6)
+            | ((rn.number() & 0x7) << 3)
+            | (rt.number() & 0x7)
+    }
+}
+
+pub struct LdrbRegisterT1;
+impl LdrbRegisterT1 {
+    pub fn opcode(rt: &dyn Register, rn: &dyn Register, rm: &dyn Register) -> u16 {
+        (0b0101110 << 9)
+            | ((rm.number() & 0x7) << 6)
+            | ((rn.number() & 0x7) << 3)
+            | (rt.number() & 0x7)
+    }
+}
+
+pub struct LdrhImmediateT1;
+impl LdrhImmediateT1 {
+    pub fn opcode(rt: &dyn Register, rn: &dyn Register, imm: u16) -> u16 {
+        (0b10001 << 11)
+            | (((imm >> 1) & 0x1f) << 6)
+            | ((rn.number() & 0x7) << 3)
+            | (rt.number() & 0x7)
+    }
+}
+
+pub struct LdrhRegisterT1;
+impl LdrhRegisterT1 {
+    pub fn opcode(rt: &dyn Register, rn: &dyn Register, rm: &dyn Register) -> u16 {
+        (0b0101101 << 9)
+            | ((rm.number() & 0x7) << 6)
+            | ((rn.number() & 0x7) << 3)
+            | (rt.number() & 0x7)
+    }
+}
+
+pub struct LdrsbRegisterT1;
+impl LdrsbRegisterT1 {
+    pub fn opcode(rt: &dyn Register, rn: &dyn Register, rm: &dyn Register) -> u16 {
+        (0b0101011 << 9)
+            | ((rm.number() & 0x7) << 6)
+            | ((rn.number() & 0x7) << 3)
+            | (rt.number() & 0x7)
+    }
+}
+
+pub struct LdrshRegisterT1;
+impl LdrshRegisterT1 {
+    pub fn opcode(rt: &dyn Register, rn: &dyn Register, rm: &dyn Register) -> u16 {
+        (0b0101111 << 9)
+            | ((rm.number() & 0x7) << 6)
+            | ((rn.number() & 0x7) << 3)
+            | (rt.number() & 0x7)
+    }
+}
+
+pub struct LslImmediateT1;
+impl LslImmediateT1 {
+    pub fn opcode(rd: &dyn Register, rm: &dyn Register, imm5: u16) -> u16 {
+        ((imm5 & 0x1f) << 6) | ((rm.number() & 0x7) << 3) | (rd.number() & 0x7)
+    }
+}
+
+pub struct LslRegisterT1;
+impl LslRegisterT1 {
+    pub fn opcode(rdn: &dyn Register, rm: &dyn Register) -> u16 {
+        (0b0100000010 << 6) | ((rm.number() & 0x7) << 3) | (rdn.number() & 0x7)
+    }
+}
+
+pub struct LsrImmediateT1;
+impl LsrImmediateT1 {
+    pub fn opcode(rd: &dyn Register, rm: &dyn Register, imm5: u16) -> u16 {
+        (0b00001 << 11)
+            | ((imm5 & 0x1f) << 6)
+            | ((rm.number() & 0x7) << 3)
+            | (rd.number() & 0x7)
+    }
+}
+
+pub struct LsrRegisterT1;
+impl LsrRegisterT1 {
+    pub fn opcode(rdn: &dyn Register, rm: &dyn Register) -> u16 {
+        (0b0100000011 << 6) | ((rm.number() & 0x7) << 3) | (rdn.number() & 0x7)
+    }
+}
+
+pub struct MovImmediateT1;
+impl MovImmediateT1 {
+    pub fn opcode(rd: &dyn Register, imm8: u16) -> u16 {
+        (0b00100 << 11) | ((rd.number() & 0x7) << 8) | (imm8 & 0xff)
+    }
+}
+
+pub struct MulT1;
+impl MulT1 {
+    pub fn opcode(rdm: &dyn Register, rn: &dyn Register) -> u16 {
+        (0b0100001101 << 6) | ((rn.number() & 0x7) << 3) | (rdm.number() & 0x7)
+    }
+}
+
+pub struct MvnT1;
+impl MvnT1 {
+    pub fn opcode(rd: &dyn Register, rm: &dyn Register) -> u16 {
+        (0b0100001111 << 6) | ((rm.number() & 0x7) << 3) | (rd.number() & 0x7)
+    }
+}
+
+pub struct OrrRegisterT1;
+impl OrrRegisterT1 {
+    pub fn opcode(rdn: &dyn Register, rm: &dyn Register) -> u16 {
+        (0b0100001100 << 6) | ((rm.number() & 0x7) << 3) | (rdn.number() & 0x7)
+    }
+}
+
+pub struct PopT1;
+impl PopT1 {
+    pub fn opcode(pop_to_pc: bool, registers: Registers) -> u16 {
+        (0b1011110 << 9) | ((pop_to_pc as u16) << 8) | (registers.binary() & 0xff)
+    }
+}
+
+pub struct RevshT1;
+impl RevshT1 {
+    pub fn opcode(rd: &dyn Register, rm: &dyn Register) -> u16 {
+        (0b1011101011 << 6) | ((rm.number() & 0x7) << 3) | (rd.number() & 0x7)
+    }
+}
+
+pub struct RorRegisterT1;
+impl RorRegisterT1 {
+    pub fn opcode(rdn: &dyn Register, rm: &dyn Register) -> u16 {
+        (0b0100000111 << 6) | ((rm.number() & 0x7) << 3) | (rdn.number() & 0x7)
+    }
+}
+
+pub struct RsbImmediateT1;
+impl RsbImmediateT1 {
+    pub fn opcode(rd: &dyn Register, rn: &dyn Register) -> u16 {
+        (0b0100001001 << 6) | ((rn.number() & 0x7) << 3) | (rd.number() & 0x7)
+    }
+}
+
+pub struct SbcRegisterT1;
+impl SbcRegisterT1 {
+    pub fn opcode(rdn: &dyn Register, rm: &dyn Register) -> u16 {
+        (0b0100000110 << 6) | ((rm.number() & 0x7) << 3) | (rdn.number() & 0x7)
+    }
+}
+
+pub struct StrImmediateT1;
+impl StrImmediateT1 {
+    pub fn opcode(rt: &dyn Register, rn: &dyn Register, imm: u16) -> u16 {
+        (0b01100 << 11)
+            | (((imm >> 2) & 0x1f) << 6)
+            | ((rn.number() & 0x7) << 3)
+            | (rt.number() & 0x7)
+    }
+}
+
+pub struct StrImmediateT2;
+impl StrImmediateT2 {
+    pub fn opcode(rt: &dyn Register, imm: u16) -> u16 {
+        (0b10010 << 11) | ((rt.number() & 0x7) << 8) | ((imm >> 2) & 0xff)
+    }
+}
+
+pub struct StrRegisterT1;
+impl StrRegisterT1 {
+    pub fn opcode(rt: &dyn Register, rn: &dyn Register, rm: &dyn Register) -> u16 {
+        (0b0101000 << 9)
+            | ((rm.number() & 0x7) << 6)
+            | ((rn.number() & 0x7) << 3)
+            | (rt.number() & 0x7)
+    }
+}
+
+pub struct StrbImmediateT1;
+impl StrbImmediateT1 {
+    pub fn opcode(rt: &dyn Register, rn: &dyn Register, imm5: u16) -> u16 {
+        (0b01110 << 11)
+            | ((imm5 & 0x1f) << 6)
+            | ((rn.number() & 0x7) << 3)
+            | (rt.number() & 0x7)
+    }
+}
+
+pub struct StrbRegisterT1;
+impl StrbRegisterT1 {
+    pub fn opcode(rt: &dyn Register, rn: &dyn Register, rm: &dyn Register) -> u16 {
+        (0b0101010 << 9)
+            | ((rm.number() & 0x7) << 6)
+            | ((rn.number() & 0x7) << 3)
+            | (rt.number() & 0x7)
+    }
+}
+
+pub struct StrhImmediateT1;
+impl StrhImmediateT1 {
+    pub fn opcode(rt: &dyn Register, rn: &dyn Register, imm: u16) -> u16 {
+        (0b10000 << 11)
+            | (((imm >> 1) & 0x1f) << 6)
+            | ((rn.number() & 0x7) << 3)
+            | (rt.number() & 0x7)
+    }
+}
+
+pub struct StrhRegisterT1;
+impl StrhRegisterT1 {
+    pub fn opcode(rt: &dyn Register, rn: &dyn Register, rm: &dyn Register) -> u16 {
+        (0b0101001 << 9)
+            | ((rm.number() & 0x7) << 6)
+            | ((rn.number() & 0x7) << 3)
+            | (rt.number() & 0x7)
+    }
+}
+
+pub struct SubT1;
+impl SubT1 {
+    pub fn opcode(rd: &dyn Register, rn: &dyn Register, imm3: u16) -> u16 {
+        (0b0001111 << 9)
+            | ((imm3 & 0x7) << 6)
+            | ((rn.number() & 0x7) << 3)
+            | (rd.number() & 0x7)
+    }
+}
+
+pub struct SubT2;
+impl SubT2 {
+    pub fn opcode(rdn: &dyn Register, imm8: u16) -> u16 {
+        (0b00111 << 11) | ((rdn.number() & 0x7) << 8) | (imm8 & 0xff)
+    }
+}
+
+pub struct SubRegisterT1;
+impl SubRegisterT1 {
+    pub fn opcode(rd: &dyn Register, rn: &dyn Register, rm: &dyn Register) -> u16 {
+        (0b0001101 << 9)
+            | ((rm.number() & 0x7) << 6)
+            | ((rn.number() & 0x7) << 3)
+            | (rd.number() & 0x7)
+    }
+}
+
+pub struct SxtbT1;
+impl SxtbT1 {
+    pub fn opcode(rd: &dyn Register, rm: &dyn Register) -> u16 {
+        (0b1011001001 << 6) | ((rm.number() & 0x7) << 3) | (rd.number() & 0x7)
+    }
+}
+
+pub struct SxthT1;
+impl SxthT1 {
+    pub fn opcode(rd: &dyn Register, rm: &dyn Register) -> u16 {
+        (0b1011001000 << 6) | ((rm.number() & 0x7) << 3) | (rd.number() & 0x7)
+    }
+}
+
+pub struct TstRegisterT1;
+impl TstRegisterT1 {
+    pub fn opcode(rn: &dyn Register, rm: &dyn Register) -> u16 {
+        (0b0100001000 << 6) | ((rm.number() & 0x7) << 3) | (rn.number() & 0x7)
+    }
+}
+
+/// Packs the two halfwords of a 32-bit instruction, so writing the result with `write_u32` stores them in order.
+fn t32(hw1: u16, hw2: u16) -> u32 {
+    ((hw2 as u32) << 16) | hw1 as u32
+}
+
+fn modified_immediate(op: u16, setflags: bool, rn: u16, rd: u16, imm12: u16) -> u32 {
+    t32(
+        0b11110 << 11
+            | ((imm12 >> 11) & 0x1) << 10
+            | op << 5
+            | (setflags as u16) << 4
+            | rn,
+        ((imm12 >> 8) & 0x7) << 12 | rd << 8 | (imm12 & 0xff),
+    )
+}
+
+fn shifted_register(
+    op: u16,
+    setflags: bool,
+    rn: u16,
+    rd: u16,
+    rm: u16,
+    shift_type: u16,
+    imm5: u16,
+) -> u32 {
+    t32(
+        0b1110101 << 9 | op << 5 | (setflags as u16) << 4 | rn,
+        ((imm5 >> 2) & 0x7) << 12 | rd << 8 | (imm5 & 0x3) << 6 | (shift_type & 0x3) << 4 | rm,
+    )
+}
+
+fn load_store_imm8(hw1: u16, rt: u16, imm8: u16, index: bool, add: bool, wback: bool) -> u32 {
+    t32(
+        hw1,
+        rt << 12 | 1 << 11 | (index as u16) << 10 | (add as u16) << 9 | (wback as u16) << 8 | (imm8 & 0xff),
+    )
+}
+
+pub struct AndImmediateT1;
+impl AndImmediateT1 {
+    pub fn opcode(setflags: bool, rd: &dyn Register, rn: &dyn Register, imm12: u16) -> u32 {
+        modified_immediate(0b0000, setflags, rn.number(), rd.number(), imm12)
+    }
+}
+
+pub struct AddImmediateT3;
+impl AddImmediateT3 {
+    pub fn opcode(setflags: bool, rd: &dyn Register, rn: &dyn Register, imm12: u16) -> u32 {
+        modified_immediate(0b1000, setflags, rn.number(), rd.number(), imm12)
+    }
+}
+
+pub struct AddImmediateT4;
+impl AddImmediateT4 {
+    pub fn opcode(rd: &dyn Register, rn: &dyn Register, imm12: u16) -> u32 {
+        t32(
+            0b11110 << 11 | ((imm12 >> 11) & 0x1) << 10 | 0b100000 << 4 | rn.number(),
+            ((imm12 >> 8) & 0x7) << 12 | rd.number() << 8 | (imm12 & 0xff),
+        )
+    }
+}
+
+pub struct AddRegisterT3;
+impl AddRegisterT3 {
+    pub fn opcode(
+        setflags: bool,
+        rd: &dyn Register,
+        rn: &dyn Register,
+        rm: &dyn Register,
+        shift_type: u16,
+        imm5: u16,
+    ) -> u32 {
+        shifted_register(0b1000, setflags, rn.number(), rd.number(), rm.number(), shift_type, imm5)
+    }
+}
+
+pub struct SubImmediateT3;
+impl SubImmediateT3 {
+    pub fn opcode(setflags: bool, rd: &dyn Register, rn: &dyn Register, imm12: u16) -> u32 {
+        modified_immediate(0b1101, setflags, rn.number(), rd.number(), imm12)
+    }
+}
+
+pub struct CmpImmediateT2;
+impl CmpImmediateT2 {
+    pub fn opcode(rn: &dyn Register, imm12: u16) -> u32 {
+        modified_immediate(0b1101, true, rn.number(), 0b1111, imm12)
+    }
+}
+
+pub struct MovImmediateT2;
+impl MovImmediateT2 {
+    pub fn opcode(setflags: bool, rd: &dyn Register, imm12: u16) -> u32 {
+        modified_immediate(0b0010, setflags, 0b1111, rd.number(), imm12)
+    }
+}
+
+pub struct MovImmediateT3;
+impl MovImmediateT3 {
+    pub fn opcode(rd: &dyn Register, imm16: u16) -> u32 {
+        t32(
+            0b11110 << 11 | ((imm16 >> 11) & 0x1) << 10 | 0b100100 << 4 | (imm16 >> 12),
+            ((imm16 >> 8) & 0x7) << 12 | rd.number() << 8 | (imm16 & 0xff),
+        )
+    }
+}
+
+pub struct MovtT1;
+impl MovtT1 {
+    pub fn opcode(rd: &dyn Register, imm16: u16) -> u32 {
+        t32(
+            0b11110 << 11 | ((imm16 >> 11) & 0x1) << 10 | 0b101100 << 4 | (imm16 >> 12),
+            ((imm16 >> 8) & 0x7) << 12 | rd.number() << 8 | (imm16 & 0xff),
+        )
+    }
+}
+
+pub struct OrrRegisterT2;
+impl OrrRegisterT2 {
+    pub fn opcode(
+        setflags: bool,
+        rd: &dyn Register,
+        rn: &dyn Register,
+        rm: &dyn Register,
+        shift_type: u16,
+        imm5: u16,
+    ) -> u32 {
+        shifted_register(0b0010, setflags, rn.number(), rd.number(), rm.number(), shift_type, imm5)
+    }
+}
+
+pub struct LslRegisterT2;
+impl LslRegisterT2 {
+    pub fn opcode(setflags: bool, rd: &dyn Register, rn: &dyn Register, rm: &dyn Register) -> u32 {
+        t32(
+            0b111110100000 << 4 | (setflags as u16) << 4 | rn.number(),
+            0b1111 << 12 | rd.number() << 8 | rm.number(),
+        )
+    }
+}
+
+pub struct BfiT1;
+impl BfiT1 {
+    pub fn opcode(rd: &dyn Register, rn: &dyn Register, lsb: u16, width: u16) -> u32 {
+        let msb = lsb + width - 1;
+        t32(
+            0b1111001101100000 | rn.number(),
+            (lsb >> 2) << 12 | rd.number() << 8 | (lsb & 0x3) << 6 | msb,
+        )
+    }
+}
+
+pub struct BfcT1;
+impl BfcT1 {
+    pub fn opcode(rd: &dyn Register, lsb: u16, width: u16) -> u32 {
+        let msb = lsb + width - 1;
+        t32(
+            0b1111001101101111,
+            (lsb >> 2) << 12 | rd.number() << 8 | (lsb & 0x3) << 6 | msb,
+        )
+    }
+}
+
+pub struct SbfxT1;
+impl SbfxT1 {
+    pub fn opcode(rd: &dyn Register, rn: &dyn Register, lsb: u16, width: u16) -> u32 {
+        t32(
+            0b1111001101000000 | rn.number(),
+            (lsb >> 2) << 12 | rd.number() << 8 | (lsb & 0x3) << 6 | (width - 1),
+        )
+    }
+}
+
+pub struct UbfxT1;
+impl UbfxT1 {
+    pub fn opcode(rd: &dyn Register, rn: &dyn Register, lsb: u16, width: u16) -> u32 {
+        t32(
+            0b1111001111000000 | rn.number(),
+            (lsb >> 2) << 12 | rd.number() << 8 | (lsb & 0x3) << 6 | (width - 1),
+        )
+    }
+}
+
+pub struct SsatT1;
+impl SsatT1 {
+    pub fn opcode(rd: &dyn Register, saturate_to: u16, rn: &dyn Register) -> u32 {
+        t32(0b1111001100000000 | rn.number(), rd.number() << 8 | (saturate_to - 1))
+    }
+}
+
+pub struct UsatT1;
+impl UsatT1 {
+    pub fn opcode(rd: &dyn Register, saturate_to: u16, rn: &dyn Register) -> u32 {
+        t32(0b1111001110000000 | rn.number(), rd.number() << 8 | saturate_to)
+    }
+}
+
+pub struct ClzT1;
+impl ClzT1 {
+    pub fn opcode(rd: &dyn Register, rm: &dyn Register) -> u32 {
+        t32(
+            0b1111101010110000 | rm.number(),
+            0b1111 << 12 | rd.number() << 8 | 0b1000 << 4 | rm.number(),
+        )
+    }
+}
+
+pub struct RbitT1;
+impl RbitT1 {
+    pub fn opcode(rd: &dyn Register, rm: &dyn Register) -> u32 {
+        t32(
+            0b1111101010010000 | rm.number(),
+            0b1111 << 12 | rd.number() << 8 | 0b1010 << 4 | rm.number(),
+        )
+    }
+}
+
+pub struct UxtbT2;
+impl UxtbT2 {
+    pub fn opcode(rd: &dyn Register, rm: &dyn Register, rotation: u16) -> u32 {
+        t32(
+            0b1111101001011111,
+            0b1111 << 12 | rd.number() << 8 | 0b10 << 6 | (rotation / 8) << 4 | rm.number(),
+        )
+    }
+}
+
+pub struct MlaT1;
+impl MlaT1 {
+    pub fn opcode(rd: &dyn Register, rn: &dyn Register, rm: &dyn Register, ra: &dyn Register) -> u32 {
+        t32(
+            0b1111101100000000 | rn.number(),
+            ra.number() << 12 | rd.number() << 8 | rm.number(),
+        )
+    }
+}
+
+pub struct MlsT1;
+impl MlsT1 {
+    pub fn opcode(rd: &dyn Register, rn: &dyn Register, rm: &dyn Register, ra: &dyn Register) -> u32 {
+        t32(
+            0b1111101100000000 | rn.number(),
+            ra.number() << 12 | rd.number() << 8 | 0b0001 << 4 | rm.number(),
+        )
+    }
+}
+
+pub struct SmullT1;
+impl SmullT1 {
+    pub fn opcode(
+        rdlo: &dyn Register,
+        rdhi: &dyn Register,
+        rn: &dyn Register,
+        rm: &dyn Register,
+    ) -> u32 {
+        t32(
+            0b1111101110000000 | rn.number(),
+            rdlo.number() << 12 | rdhi.number() << 8 | rm.number(),
+        )
+    }
+}
+
+pub struct UmullT1;
+impl UmullT1 {
+    pub fn opcode(
+        rdlo: &dyn Register,
+        rdhi: &dyn Register,
+        rn: &dyn Register,
+        rm: &dyn Register,
+    ) -> u32 {
+        t32(
+            0b1111101110100000 | rn.number(),
+            rdlo.number() << 12 | rdhi.number() << 8 | rm.number(),
+        )
+    }
+}
+
+pub struct UmlalT1;
+impl UmlalT1 {
+    pub fn opcode(
+        rdlo: &dyn Register,
+        rdhi: &dyn Register,
+        rn: &dyn Register,
+        rm: &dyn Register,
+    ) -> u32 {
+        t32(
+            0b1111101111100000 | rn.number(),
+            rdlo.number() << 12 | rdhi.number() << 8 | rm.number(),
+        )
+    }
+}
+
+pub struct SdivT1;
+impl SdivT1 {
+    pub fn opcode(rd: &dyn Register, rn: &dyn Register, rm: &dyn Register) -> u32 {
+        t32(
+            0b1111101110010000 | rn.number(),
+            0b1111 << 12 | rd.number() << 8 | 0b1111 << 4 | rm.number(),
+        )
+    }
+}
+
+pub struct UdivT1;
+impl UdivT1 {
+    pub fn opcode(rd: &dyn Register, rn: &dyn Register, rm: &dyn Register) -> u32 {
+        t32(
+            0b1111101110110000 | rn.number(),
+            0b1111 << 12 | rd.number() << 8 | 0b1111 << 4 | rm.number(),
+        )
+    }
+}
+
+pub struct LdrImmediateT3;
+impl LdrImmediateT3 {
+    pub fn opcode(rt: &dyn Register, rn: &dyn Register, imm12: u16) -> u32 {
+        t32(0b1111100011010000 | rn.number(), rt.number() << 12 | (imm12 & 0xfff))
+    }
+}
+
+pub struct LdrImmediateT4;
+impl LdrImmediateT4 {
+    pub fn opcode(
+        rt: &dyn Register,
+        rn: &dyn Register,
+        imm8: u16,
+        index: bool,
+        add: bool,
+        wback: bool,
+    ) -> u32 {
+        load_store_imm8(0b1111100001010000 | rn.number(), rt.number(), imm8, index, add, wback)
+    }
+}
+
+pub struct LdrLiteralT2;
+impl LdrLiteralT2 {
+    pub fn opcode(rt: &dyn Register, imm: i32) -> u32 {
+        let add = (imm >= 0) as u16;
+        t32(
+            0b1111100001011111 | add << 7,
+            rt.number() << 12 | (imm.unsigned_abs() as u16 & 0xfff),
+        )
+    }
+}
+
+pub struct LdrRegisterT2;
+impl LdrRegisterT2 {
+    pub fn opcode(rt: &dyn Register, rn: &dyn Register, rm: &dyn Register, shift: u16) -> u32 {
+        t32(
+            0b1111100001010000 | rn.number(),
+            rt.number() << 12 | (shift & 0x3) << 4 | rm.number(),
+        )
+    }
+}
+
+pub struct LdrshImmediateT1;
+impl LdrshImmediateT1 {
+    pub fn opcode(rt: &dyn Register, rn: &dyn Register, imm12: u16) -> u32 {
+        t32(0b1111100110110000 | rn.number(), rt.number() << 12 | (imm12 & 0xfff))
+    }
+}
+
+pub struct StrImmediateT4;
+impl StrImmediateT4 {
+    pub fn opcode(
+        rt: &dyn Register,
+        rn: &dyn Register,
+        imm8: u16,
+        index: bool,
+        add: bool,
+        wback: bool,
+    ) -> u32 {
+        load_store_imm8(0b1111100001000000 | rn.number(), rt.number(), imm8, index, add, wback)
+    }
+}
+
+pub struct StrbImmediateT2;
+impl StrbImmediateT2 {
+    pub fn opcode(rt: &dyn Register, rn: &dyn Register, imm12: u16) -> u32 {
+        t32(0b1111100010000000 | rn.number(), rt.number() << 12 | (imm12 & 0xfff))
+    }
+}
+
+pub struct LdrdImmediateT1;
+impl LdrdImmediateT1 {
+    pub fn opcode(
+        rt: &dyn Register,
+        rt2: &dyn Register,
+        rn: &dyn Register,
+        imm: u16,
+        index: bool,
+        add: bool,
+        wback: bool,
+    ) -> u32 {
+        t32(
+            0b1110100001010000
+                | (index as u16) << 8
+                | (add as u16) << 7
+                | (wback as u16) << 5
+                | rn.number(),
+            rt.number() << 12 | rt2.number() << 8 | ((imm >> 2) & 0xff),
+        )
+    }
+}
+
+pub struct StrdImmediateT1;
+impl StrdImmediateT1 {
+    pub fn opcode(
+        rt: &dyn Register,
+        rt2: &dyn Register,
+        rn: &dyn Register,
+        imm: u16,
+        index: bool,
+        add: bool,
+        wback: bool,
+    ) -> u32 {
+        t32(
+            0b1110100001000000
+                | (index as u16) << 8
+                | (add as u16) << 7
+                | (wback as u16) << 5
+                | rn.number(),
+            rt.number() << 12 | rt2.number() << 8 | ((imm >> 2) & 0xff),
+        )
+    }
+}
+
+pub struct LdrexT1;
+impl LdrexT1 {
+    pub fn opcode(rt: &dyn Register, rn: &dyn Register, imm: u16) -> u32 {
+        t32(
+            0b1110100001010000 | rn.number(),
+            rt.number() << 12 | 0b1111 << 8 | ((imm >> 2) & 0xff),
+        )
+    }
+}
+
+pub struct StrexT1;
+impl StrexT1 {
+    pub fn opcode(rd: &dyn Register, rt: &dyn Register, rn: &dyn Register, imm: u16) -> u32 {
+        t32(
+            0b1110100001000000 | rn.number(),
+            rt.number() << 12 | rd.number() << 8 | ((imm >> 2) & 0xff),
+        )
+    }
+}
+
+pub struct ClrexT1;
+impl ClrexT1 {
+    pub fn opcode() -> u32 {
+        0x8f2ff3bf
+    }
+}
+
+pub struct TbbT1;
+impl TbbT1 {
+    pub fn opcode(rn: &dyn Register, rm: &dyn Register) -> u32 {
+        t32(0b1110100011010000 | rn.number(), 0b1111 << 12 | rm.number())
+    }
+}
+
+pub struct TbhT1;
+impl TbhT1 {
+    pub fn opcode(rn: &dyn Register, rm: &dyn Register) -> u32 {
+        t32(0b1110100011010000 | rn.number(), 0b1111 << 12 | 0b0001 << 4 | rm.number())
+    }
+}
+
+pub struct CbzT1;
+impl CbzT1 {
+    pub fn opcode(rn: &dyn Register, imm: u16) -> u16 {
+        0b1011000100000000 | ((imm >> 6) & 0x1) << 9 | ((imm >> 1) & 0x1f) << 3 | (rn.number() & 0x7)
+    }
+}
+
+pub struct CbnzT1;
+impl CbnzT1 {
+    pub fn opcode(rn: &dyn Register, imm: u16) -> u16 {
+        0b1011100100000000 | ((imm >> 6) & 0x1) << 9 | ((imm >> 1) & 0x1f) << 3 | (rn.number() & 0x7)
+    }
+}
+
+pub struct LdmT2;
+impl LdmT2 {
+    pub fn opcode(wback: bool, rn: &dyn Register, registers: Registers) -> u32 {
+        t32(0b1110100010010000 | (wback as u16) << 5 | rn.number(), registers.binary())
+    }
+}
+
+pub struct StmdbT1;
+impl StmdbT1 {
+    pub fn opcode(wback: bool, rn: &dyn Register, registers: Registers) -> u32 {
+        t32(0b1110100100000000 | (wback as u16) << 5 | rn.number(), registers.binary())
+    }
+}
+
+pub struct MrsT1;
+impl MrsT1 {
+    pub fn opcode(rd: &dyn Register, sysm: u16) -> u32 {
+        t32(0b1111001111101111, 0b1000 << 12 | rd.number() << 8 | (sysm & 0xff))
+    }
+}
+
+pub struct MsrT1;
+impl MsrT1 {
+    pub fn opcode(mask: u16, sysm: u16, rn: &dyn Register) -> u32 {
+        t32(
+            0b1111001110000000 | rn.number(),
+            0b1000 << 12 | (mask & 0x3) << 10 | (sysm & 0xff),
+        )
+    }
+}
+
+pub struct BT3;
+impl BT3 {
+    pub fn opcode(cond: u16, imm: i32) -> u32 {
+        let imm = imm as u32;
+        let s = ((imm >> 20) & 0x1) as u16;
+        let j2 = ((imm >> 19) & 0x1) as u16;
+        let j1 = ((imm >> 18) & 0x1) as u16;
+        let imm6 = ((imm >> 12) & 0x3f) as u16;
+        let imm11 = ((imm >> 1) & 0x7ff) as u16;
+        t32(
+            0b11110 << 11 | s << 10 | (cond & 0xf) << 6 | imm6,
+            0b10 << 14 | j1 << 13 | j2 << 11 | imm11,
+        )
+    }
+}
+
+pub struct BT4;
+impl BT4 {
+    pub fn opcode(imm: i32) -> u32 {
+        let imm = imm as u32;
+        let s = ((imm >> 24) & 0x1) as u16;
+        let i1 = ((imm >> 23) & 0x1) as u16;
+        let i2 = ((imm >> 22) & 0x1) as u16;
+        let imm10 = ((imm >> 12) & 0x3ff) as u16;
+        let imm11 = ((imm >> 1) & 0x7ff) as u16;
+        let j1 = 1 - (i1 ^ s);
+        let j2 = 1 - (i2 ^ s);
+        t32(
+            0b11110 << 11 | s << 10 | imm10,
+            0b10 << 14 | j1 << 13 | 1 << 12 | j2 << 11 | imm11,
+        )
+    }
+}
+
+pub struct SvcT1;
+impl SvcT1 {
+    pub fn opcode(imm: u8) -> u16 {
+        0b11011111 << 8 | imm as u16
+    }
+}
+
+pub struct UdfT1;
+impl UdfT1 {
+    pub fn opcode(imm: u8) -> u16 {
+        0b11011110 << 8 | imm as u16
+    }
+}
+
+pub struct UdfT2;
+impl UdfT2 {
+    pub fn opcode(imm: u16) -> u32 {
+        t32(
+            0b111101111111 << 4 | ((imm >> 12) & 0xf),
+            0b1010 << 12 | (imm & 0xfff),
+        )
     }
 }
@@ -3,21 +3,23 @@ use core::ops::Range;
 use std::ops::{Bound, RangeBounds};
 
 use crate::cortex_m33::control::SpSel;
+use crate::cortex_m33::exception::Exception;
+use crate::cortex_m33::fault::{Fault, HFSR_VECTTBL};
+use crate::cortex_m33::nvic::Nvic;
 
 use super::{
-    apsr::{Apsr, Ipsr}, control::NPriv, exception::Exceptions, registers::{PcRegister, Register}, CortexM33, Mode
+    apsr::{Apsr, Epsr}, exception::Exceptions, registers::{PcRegister, Register}, CortexM33, Mode
 };
 
 pub fn add_with_carry(x: u32, y: u32, carry_in: bool) -> (u32, bool, bool) {
-    let overflow_x_and_y = x.overflowing_add(y);
-    let unsigned_sum = (carry_in as u32).overflowing_add(overflow_x_and_y.0);
-    let carry_out = overflow_x_and_y.1 || unsigned_sum.1;
+    let unsigned_sum = x as u64 + y as u64 + carry_in as u64;
+    let signed_sum = x as i32 as i64 + y as i32 as i64 + carry_in as i64;
+    let result = unsigned_sum as u32;
 
-    let overflow_x_and_y = (x as i32).overflowing_add(y as i32);
-    let signed_sum = (carry_in as i32).overflowing_add(overflow_x_and_y.0);
-    let overflow = overflow_x_and_y.1 || signed_sum.1;
+    let carry_out = result as u64 != unsigned_sum;
+    let overflow = result as i32 as i64 != signed_sum;
 
-    (unsigned_sum.0, carry_out, overflow)
+    (result, carry_out, overflow)
 }
 
 pub fn unwind_bounds<V: num_traits::PrimInt, R: RangeBounds<usize>>(
@@ -71,7 +73,6 @@ pub fn set_bits<V: num_traits::PrimInt>(
     bits: u32,
     bits_len: usize,
 ) {
-    let range = range;
     let start = range.start;
     for i in range {
         set_bit(value, i, get_bit(bits, (i - start) % bits_len));
@@ -86,16 +87,16 @@ pub fn is_zero_bit(x: u32) -> bool {
 pub fn get_bit<V: num_traits::PrimInt>(value: V, bit: usize) -> bool {
     assert!(bit < get_size_of_number(value));
 
-    let mask = V::one() << bit.into();
-    if (mask & value) > V::zero() {
-        true
-    } else {
-        false
-    }
+    let mask = V::one() << bit;
+    (mask & value) > V::zero()
 }
 
 pub fn lsl_c(value: u32, amount: usize) -> (u32, bool) {
     assert!(amount > 0);
+    if amount > 32 {
+        return (0, false);
+    }
+
     let value = value as u64;
     let value = value << amount;
     let carry_out = get_bit(value, 32);
@@ -106,6 +107,9 @@ pub fn lsl_c(value: u32, amount: usize) -> (u32, bool) {
 
 pub fn lsr_c(value: u32, amount: usize) -> (u32, bool) {
     assert!(amount > 0);
+    if amount > 32 {
+        return (0, false);
+    }
 
     let value = value as u64;
     let result = get_bits(value, amount..amount + 32) as u32;
@@ -114,78 +118,60 @@ pub fn lsr_c(value: u32, amount: usize) -> (u32, bool) {
     (result, carry_out)
 }
 
+/// Marks where the architecture calls behaviour UNPREDICTABLE. It can't be relied on, so the simulator carries on
+/// with whatever the pseudocode does next rather than reporting it.
 #[macro_export]
 macro_rules! unpredictable {
 	() => {
-        {
-    		println!("Unpredictable behavior occured!");
-    		println!("What does this mean? It means according to the documentation...");
-    		println!("Means the behavior cannot be relied on. UNPREDICTABLE behavior must not represent a security hole. UNPREDICTABLE behavior must not hang the processor, or any parts of the system. UNPREDICTABLE behavior must not be documented or promoted as having a defined effect.");
-        }
+        {}
 	};
 }
 
-pub fn in_it_block() -> bool {
-    return false;
+pub fn in_it_block(epsr: &Epsr) -> bool {
+    get_bits(epsr.it(), 0..=3) != 0b0000
+}
+
+pub fn last_in_it_block(epsr: &Epsr) -> bool {
+    get_bits(epsr.it(), 0..=3) == 0b1000
+}
+
+/// Moves ITSTATE on to the next instruction of the IT block, clearing it after the last one.
+pub fn it_advance(epsr: &mut Epsr) {
+    let it = epsr.it();
+    if get_bits(it, 0..=2) == 0b000 {
+        epsr.set_it(0);
+    } else {
+        epsr.set_it((it & 0b11100000) | ((it << 1) & 0b00011111));
+    }
 }
 
 pub fn condition_passed(apsr: &Apsr, cond: u16) -> bool {
-    println!("looking at condition");
-    println!("cond as came in: {:#b}", cond);
     let cond = get_bits(cond, 0..4);
-    println!("cond: {:#b}", cond);
-    let mut result = match get_bits(cond, 0..3) {
-        0b000 => {
-            println!("case 1");
-            apsr.z() == true
-        }
-        0b001 => {
-            println!("case 2");
-            apsr.c() == true
-        }
-        0b010 => {
-            println!("case 3");
-            apsr.n() == true
-        }
-        0b011 => {
-            println!("case 4");
-            apsr.v() == true
-        }
-        0b100 => {
-            println!("case 5");
-            apsr.c() == true && apsr.z() == false
-        }
-        0b101 => {
-            println!("case 6");
-            apsr.n() == apsr.v()
-        }
-        0b110 => {
-            println!("case 7");
-            apsr.n() == apsr.v() && apsr.z() == false
-        }
-        0b111 => {
-            println!("case 8");
-            true
-        }
+    let mut result = match get_bits(cond, 1..=3) {
+        0b000 => apsr.z(),
+        0b001 => apsr.c(),
+        0b010 => apsr.n(),
+        0b011 => apsr.v(),
+        0b100 => apsr.c() && !apsr.z(),
+        0b101 => apsr.n() == apsr.v(),
+        0b110 => apsr.n() == apsr.v() && !apsr.z(),
+        0b111 => true,
         _ => unreachable!(),
     };
 
-    println!("result: {result}");
-
-    if get_bit(cond, 0) == true && cond != 0b1111 {
+    if get_bit(cond, 0) && cond != 0b1111 {
         result = !result;
     }
 
-    println!("final result: {result}");
-
     result
 }
 
 pub fn asr_c(value: u32, shift: usize) -> (u32, bool) {
-    println!("value: {:#x}", value);
-    println!("value: {:?}", shift);
     assert!(shift > 0);
 
+    // Every bit shifted in past the 32nd is a copy of the sign bit, so larger shifts behave like 32
+    let shift = shift.min(32);
+
     let extended = match sign_extend(value, 31, 32 + shift) {
         SignExtended::U64(val) => val as u128,
         SignExtended::U128(val) => val,
@@ -198,6 +184,7 @@ pub fn asr_c(value: u32, shift: usize) -> (u32, bool) {
     (result, carry)
 }
 
+#[allow(dead_code)]
 pub fn get_lsb<N: num_traits::PrimInt>(n: N) -> N {
     n & N::one()
 }
@@ -206,24 +193,17 @@ pub fn get_size_of_number<N: num_traits::PrimInt>(_n: N) -> usize {
     std::mem::size_of::<N>() * 8
 }
 
+#[allow(dead_code)]
 pub fn get_msb<N: num_traits::PrimInt>(n: N) -> bool {
     let shift = get_size_of_number(n) - 1;
-    if (n >> shift) & N::one() == N::zero() {
-        false
-    } else {
-        true
-    }
-}
-
-pub fn last_in_it_block() -> bool {
-    false
+    (n >> shift) & N::one() != N::zero()
 }
 
 pub fn is_ones<N: num_traits::PrimInt, R: RangeBounds<usize>>(value: N, range: R) -> bool {
     let (start, end) = unwind_bounds(value, range);
 
     for i in start..end {
-        if get_bit(value, i) == false {
+        if !get_bit(value, i) {
             return false;
         }
     }
@@ -235,107 +215,219 @@ pub fn exception_active_bit_count(exceptions: &Exceptions) -> usize {
     exceptions.active.len()
 }
 
-pub fn exception_return(cortex: &mut CortexM33, ipsr: u8, current_mode: Mode, exc_return: u32) {
+/// Returns from the active exception in IPSR. A pending exception that can preempt the context being returned
+/// to is tail-chained, taking it with the stacked frame left in place.
+pub fn exception_return(cortex: &mut CortexM33, ipsr: u8, current_mode: Mode, exc_return: u32) -> Result<(), Fault> {
     assert_eq!(current_mode, Mode::Handler);
-    if !is_ones(get_bits(exc_return, 4..=27), 0..23) {
+    if !is_ones(get_bits(exc_return, 4..=27), 0..24) {
         unpredictable!();
     }
 
-    let returning_exception_number = get_bits(ipsr, 0..=5);
+    let returning_exception_number = ipsr;
     let nested_activation = exception_active_bit_count(&cortex.exceptions);
 
-    if cortex
+    if !cortex
         .exceptions
         .active
         .contains_key(&returning_exception_number) {
-        unpredictable!();
+        return Err(Fault::InvalidPc);
     }
 
-    match get_bits(exc_return, 0..=3) {
-        0b0001 => {
-            if nested_activation == 1 {
-                unpredictable!();
-            }
+    let (frameptr, mode, spsel) = match get_bits(exc_return, 0..=3) {
+        0b0001 if nested_activation != 1 => (cortex.registers.sp.get_msp(), Mode::Handler, SpSel::SpMain),
+        0b1001 if nested_activation == 1 => (cortex.registers.sp.get_msp(), Mode::Thread, SpSel::SpMain),
+        0b1101 if nested_activation == 1 => (cortex.registers.sp.get_psp(), Mode::Thread, SpSel::SpProcess),
+        _ => return Err(Fault::InvalidPc),
+    };
 
-            let frameptr = cortex.registers.sp.get_msp();
-            let current_mode = Mode::Handler;
-            cortex.control.spsel = SpSel::SpMain;
-        },
-        0b1001 => {
-            if nested_activation != 1 {
-                unpredictable!();
-            }
+    deactivate(cortex, returning_exception_number);
+    cortex.exception_returned = true;
 
-            let frameptr = cortex.registers.sp.get_msp();
-            let current_mode = Mode::Thread;
-            cortex.control.spsel = SpSel::SpMain;
-        },
-        0b1101 => {
-            if nested_activation != 1 {
-                unpredictable!();
-            }
-
-            let frameptr = cortex.registers.sp.get_psp();
-            let current_mode = Mode::Thread;
-            cortex.control.spsel = SpSel::SpProcess;
-        },
-        _ => unpredictable!(),
+    if let Some(exception) = Exceptions::highest_pending(cortex) {
+        if Exceptions::group_priority(cortex, exception.number()) < cortex.execution_priority() {
+            exception_taken(cortex, exception);
+            cortex.registers.lr.set(exc_return);
+            return Ok(());
+        }
     }
 
+    popstack(cortex, frameptr, exc_return)?;
+    cortex.mode = mode;
+    cortex.control.spsel = spsel;
+    cortex.update_sp_mode();
 
+    Ok(())
 }
 
 pub fn deactivate(cortex: &mut CortexM33, returning_exception_number: u8) {
     cortex.exceptions.active.remove(&returning_exception_number);
-}
 
-pub fn popstack(cortex: &mut CortexM33, frameptr: u32, exc_return: u32) {
-    cortex.registers[0] = cortex.memory.read_u32(frameptr);
-    cortex.registers[1] = cortex.memory.read_u32(frameptr + 0x04);
-    cortex.registers[2] = cortex.memory.read_u32(frameptr + 0x08);
-    cortex.registers[3] = cortex.memory.read_u32(frameptr + 0x0C);
-    cortex.registers[12] = cortex.memory.read_u32(frameptr + 0x10);
-    cortex.registers.lr.set(frameptr + 0x14);
-    cortex.registers.pc.set(frameptr + 0x18);
-    let psr = frameptr + 0x1C;
+    // A level sensitive IRQ that is still asserted pends again
+    if returning_exception_number >= 16 && cortex.nvic.line(returning_exception_number - 16) {
+        cortex
+            .exceptions
+            .set_pending(Nvic::exception(returning_exception_number - 16));
+    }
 
-    if get_bit(psr, 0) == true { unpredictable!() }
-    branch_to(cortex, cortex.registers.pc.get());
+    if returning_exception_number != Exception::NMI.number() {
+        cortex.faultmask = false;
+    }
+    cortex.exclusive_monitor = None;
+}
+
+/// Restores the basic frame at frameptr. The whole frame is read before any state changes, so a failed unstack
+/// leaves the core as it was.
+pub fn popstack(cortex: &mut CortexM33, frameptr: u32, exc_return: u32) -> Result<(), Fault> {
+    let mut frame = [0; 8];
+    for (i, value) in frame.iter_mut().enumerate() {
+        let address = frameptr.wrapping_add(4 * i as u32);
+        *value = cortex
+            .memory
+            .read_u32(address)
+            .map_err(|error| Fault::Unstacking { address, error })?;
+    }
+
+    let [r0, r1, r2, r3, r12, lr, pc, psr] = frame;
+    cortex.registers[0] = r0;
+    cortex.registers[1] = r1;
+    cortex.registers[2] = r2;
+    cortex.registers[3] = r3;
+    cortex.registers[12] = r12;
+    cortex.registers.lr.set(lr);
+
+    if get_bit(pc, 0) { unpredictable!() }
+    branch_to(cortex, pc & !1);
 
     let sp_mask = (get_bit(psr, 9) as u32) << 2;
     match get_bits(exc_return, 0..=3) {
         0b0001 | 0b1001 => {
-            let msp = cortex.registers.sp.get_msp() + 0x20;
+            let msp = cortex.registers.sp.get_msp().wrapping_add(0x20);
             cortex.registers.sp.set_msp(msp | sp_mask);
         },
         0b1101 => {
-            let psp = cortex.registers.sp.get_psp() + 0x20;
-            cortex.registers.sp.set_msp(psp | sp_mask);
+            let psp = cortex.registers.sp.get_psp().wrapping_add(0x20);
+            cortex.registers.sp.set_psp(psp | sp_mask);
         },
         _ => unpredictable!(),
     }
 
-    let mut aspr_values = Apsr::new();
-    aspr_values.set_from_u32(psr);
+    // APSR, and EPSR including ITSTATE and the T bit
+    cortex.xpsr.set_from_u32(psr);
+    cortex.ipsr = get_bits(psr, 0..=8) as u8;
+
+    Ok(())
+}
+
+/// Pushes the basic frame onto the stack in use, aligned to 8 bytes, and sets LR to the EXC_RETURN value that
+/// returns to the current context. Stacking carries on past a failed write, the first failure is returned.
+pub fn pushstack(cortex: &mut CortexM33) -> Result<(), Fault> {
+    let process = cortex.mode == Mode::Thread && cortex.control.spsel == SpSel::SpProcess;
+    let sp = if process {
+        cortex.registers.sp.get_psp()
+    } else {
+        cortex.registers.sp.get_msp()
+    };
+
+    let frameptralign = get_bit(sp, 2);
+    let frameptr = sp.wrapping_sub(0x20) & !0b100;
+    if process {
+        cortex.registers.sp.set_psp(frameptr);
+    } else {
+        cortex.registers.sp.set_msp(frameptr);
+    }
+
+    let psr = (cortex.xpsr.into_u32() & !0x3ff) | cortex.ipsr as u32 | (frameptralign as u32) << 9;
+    let frame = [
+        cortex.registers[0],
+        cortex.registers[1],
+        cortex.registers[2],
+        cortex.registers[3],
+        cortex.registers[12],
+        cortex.registers.lr.get(),
+        cortex.registers.pc.get() & !1,
+        psr,
+    ];
+
+    let mut result = Ok(());
+    for (i, value) in frame.into_iter().enumerate() {
+        let address = frameptr.wrapping_add(4 * i as u32);
+        if let Err(error) = cortex.memory.write_u32(address, value) {
+            result = result.and(Err(Fault::Stacking { address, error }));
+        }
+    }
+
+    let exc_return = if cortex.mode == Mode::Handler {
+        0xFFFFFFF1
+    } else if process {
+        0xFFFFFFFD
+    } else {
+        0xFFFFFFF9
+    };
+    cortex.registers.lr.set(exc_return);
+
+    result
+}
 
-    cortex.xpsr.apsr.set_n(aspr_values.n());
-    cortex.xpsr.apsr.set_z(aspr_values.z());
-    cortex.xpsr.apsr.set_c(aspr_values.c());
-    cortex.xpsr.apsr.set_v(aspr_values.v());
+/// Fetches the handler from the vector table and makes the exception active. A vector that can't be read
+/// escalates to HardFault, or locks up if HardFault itself was being taken.
+pub fn exception_taken(cortex: &mut CortexM33, exception: Exception) {
+    let number = exception.number();
+    let vector = match cortex.memory.read_u32(cortex.vtor.wrapping_add(4 * number as u32)) {
+        Ok(vector) => vector,
+        Err(_) => {
+            cortex.fault_status.hfsr |= HFSR_VECTTBL;
+            if matches!(exception, Exception::HardFault | Exception::NMI) {
+                cortex.locked_up = true;
+            } else {
+                exception_taken(cortex, Exception::HardFault);
+            }
+            return;
+        }
+    };
 
-    let force_thread = cortex.mode == Mode::Thread && cortex.control.npriv == NPriv::ThreadModeUnprivileged;
+    cortex.exceptions.pending.remove(&number);
+    cortex.exceptions.active.insert(number, exception);
+    cortex.mode = Mode::Handler;
+    cortex.ipsr = number;
+    cortex.xpsr.epsr.set_it(0);
+    cortex.xpsr.epsr.set_t(get_bit(vector, 0));
+    cortex.control.spsel = SpSel::SpMain;
+    cortex.update_sp_mode();
+    cortex.exclusive_monitor = None;
+    branch_to(cortex, vector & !1);
+}
+
+/// Stacks the current context and takes the exception. Exceptions that arrive while stacking, such as a fault on
+/// the stack itself, are taken first if they have higher priority, using the same frame.
+pub fn exception_entry(cortex: &mut CortexM33, exception: Exception) {
+    if let Err(fault) = pushstack(cortex) {
+        cortex.raise_fault(fault);
+        if cortex.locked_up {
+            return;
+        }
+    }
 
-    // let Ipsr
+    let exception = match Exceptions::highest_pending(cortex) {
+        Some(late) if Exceptions::group_priority(cortex, late.number()) < Exceptions::group_priority(cortex, exception.number()) => late,
+        _ => exception,
+    };
 
+    exception_taken(cortex, exception);
 }
 
 pub fn branch_to(cortex: &mut CortexM33, address: u32) {
     cortex.registers.pc.set(address);
 }
 
-pub fn bx_write_pc(pc: &mut PcRegister, current_mode: Mode, address: u32) {
-    if current_mode == Mode::Handler && get_bits(address, 28..=31) == 0b1111 {
-        // exception_return()
+pub fn bx_write_pc(cortex: &mut CortexM33, address: u32) -> Result<(), Fault> {
+    if cortex.mode == Mode::Handler && get_bits(address, 28..=31) == 0b1111 {
+        let ipsr = cortex.ipsr;
+        exception_return(cortex, ipsr, Mode::Handler, address)
+    } else {
+        cortex.xpsr.epsr.set_t(get_bit(address, 0));
+        // If EPSR.T == 0, a UsageFault('Invalid State') is taken on the next instruction
+        cortex.registers.pc.set(address & 0xFFFFFFFE);
+        Ok(())
     }
 }
 
@@ -394,6 +486,11 @@ pub fn sign_extend<
 pub fn ror_c(value: u32, amount: usize) -> (u32, bool) {
     assert!(amount > 0);
 
+    let amount = amount % 32;
+    if amount == 0 {
+        return (value, get_bit(value, 31));
+    }
+
     let value = (value as u64) << 32 >> (amount as u64);
     let result: u32 = (value as u32) | (value >> 32) as u32;
     let carry_out = (result & (1 << 31)) != 0;
@@ -403,13 +500,14 @@ pub fn ror_c(value: u32, amount: usize) -> (u32, bool) {
 
 pub fn rrx_c(value: u32, carry_in: bool) -> (u32, bool) {
     let carry_out = get_bit(value, 0);
-    let result = value >> 1 + (carry_in as u32) << 31;
+    let result = (value >> 1) | ((carry_in as u32) << 31);
 
     (result, carry_out)
 }
 
-#[derive(PartialEq, Clone, Copy)]
+#[derive(PartialEq, Clone, Copy, Default)]
 pub enum SRType {
+    #[default]
     Lsl,
     Lsr,
     Asr,
@@ -439,7 +537,7 @@ pub fn decode_imm_shift(imm_type: u2, imm5: u16) -> (SRType, u16) {
         (SRType::Lsl, imm5)
     } else if imm_type == u2::new(0b01) {
         let imm5 = if imm5 == 0b00000 { 32 } else { imm5 };
-        (SRType::Lsl, imm5)
+        (SRType::Lsr, imm5)
     } else if imm_type == u2::new(0b10) {
         let imm5 = if imm5 == 0b00000 { 32 } else { imm5 };
         (SRType::Asr, imm5)
@@ -451,3 +549,61 @@ pub fn decode_imm_shift(imm_type: u2, imm5: u16) -> (SRType, u16) {
         }
     }
 }
+
+pub fn decode_reg_shift(shift_type: u16) -> SRType {
+    match get_bits(shift_type, 0..=1) {
+        0b00 => SRType::Lsl,
+        0b01 => SRType::Lsr,
+        0b10 => SRType::Asr,
+        _ => SRType::Ror,
+    }
+}
+
+/// Expands the 12 bit modified immediate constant used by 32-bit data processing instructions.
+pub fn thumb_expand_imm_c(imm12: u16, carry_in: bool) -> (u32, bool) {
+    if get_bits(imm12, 10..=11) == 0b00 {
+        let imm8 = get_bits(imm12, 0..=7) as u32;
+        let imm32 = match get_bits(imm12, 8..=9) {
+            0b00 => imm8,
+            0b01 => (imm8 << 16) | imm8,
+            0b10 => (imm8 << 24) | (imm8 << 8),
+            _ => (imm8 << 24) | (imm8 << 16) | (imm8 << 8) | imm8,
+        };
+
+        if get_bits(imm12, 8..=9) != 0b00 && imm8 == 0 {
+            unpredictable!();
+        }
+
+        (imm32, carry_in)
+    } else {
+        let unrotated = (1 << 7) | get_bits(imm12, 0..=6) as u32;
+        ror_c(unrotated, get_bits(imm12, 7..=11) as usize)
+    }
+}
+
+/// Saturates a signed value into an `n` bit signed range, returning whether saturation occurred.
+pub fn signed_sat_q(value: i64, n: u32) -> (u32, bool) {
+    let max = (1i64 << (n - 1)) - 1;
+    let min = -(1i64 << (n - 1));
+
+    if value > max {
+        (max as u32, true)
+    } else if value < min {
+        (min as u32, true)
+    } else {
+        (value as u32, false)
+    }
+}
+
+/// Saturates a signed value into an `n` bit unsigned range, returning whether saturation occurred.
+pub fn unsigned_sat_q(value: i64, n: u32) -> (u32, bool) {
+    let max = (1i64 << n) - 1;
+
+    if value > max {
+        (max as u32, true)
+    } else if value < 0 {
+        (0, true)
+    } else {
+        (value as u32, false)
+    }
+}
@@ -201,6 +201,12 @@ impl Sp {
     }
 }
 
+impl Default for CortexM33Registers {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl CortexM33Registers {
     pub fn new() -> Self {
         Self {
@@ -0,0 +1,231 @@
+use crate::cortex_m33::exception::{Exception, Exceptions, InterruptException};
+use crate::cortex_m33::fault::{SHCSR_BUSFAULTENA, SHCSR_MEMFAULTENA, SHCSR_USGFAULTENA};
+use crate::cortex_m33::nvic::IRQ_COUNT;
+use crate::cortex_m33::{CortexM33, Mode};
+
+pub const SCB_START_ADDRESS: u32 = 0xE000ED00;
+pub const SCB_END_ADDRESS: u32 = 0xE000ED90;
+
+/// Interrupt Controller Type Register, the one System Control Space register ahead of SysTick
+pub const ICTR_ADDRESS: u32 = 0xE000E004;
+
+// Register offsets from SCB_START_ADDRESS
+const CPUID: u32 = 0x00;
+const ICSR: u32 = 0x04;
+const VTOR: u32 = 0x08;
+const AIRCR: u32 = 0x0c;
+const SCR: u32 = 0x10;
+const CCR: u32 = 0x14;
+const SHPR1: u32 = 0x18;
+const SHPR3: u32 = 0x20;
+const SHCSR: u32 = 0x24;
+const CFSR: u32 = 0x28;
+const HFSR: u32 = 0x2c;
+const MMFAR: u32 = 0x34;
+const BFAR: u32 = 0x38;
+const CPACR: u32 = 0x88;
+const NSACR: u32 = 0x8c;
+
+/// Arm Cortex-M33 r1p0, as on the RP2350
+pub const CPUID_VALUE: u32 = 0x411FD210;
+
+pub const ICSR_RETTOBASE: u32 = 1 << 11;
+pub const ICSR_ISRPENDING: u32 = 1 << 22;
+pub const ICSR_PENDSTCLR: u32 = 1 << 25;
+pub const ICSR_PENDSTSET: u32 = 1 << 26;
+pub const ICSR_PENDSVCLR: u32 = 1 << 27;
+pub const ICSR_PENDSVSET: u32 = 1 << 28;
+pub const ICSR_PENDNMICLR: u32 = 1 << 30;
+pub const ICSR_PENDNMISET: u32 = 1 << 31;
+
+const VTOR_MASK: u32 = 0xFFFFFF80;
+
+/// Writes to AIRCR are ignored unless this key is in the top halfword
+pub const AIRCR_VECTKEY: u32 = 0x05FA;
+const AIRCR_VECTKEYSTAT: u32 = 0xFA05;
+pub const AIRCR_SYSRESETREQ: u32 = 1 << 2;
+
+pub const SCR_SLEEPONEXIT: u32 = 1 << 1;
+pub const SCR_SLEEPDEEP: u32 = 1 << 2;
+pub const SCR_SEVONPEND: u32 = 1 << 4;
+
+pub const CCR_USERSETMPEND: u32 = 1 << 1;
+pub const CCR_UNALIGN_TRP: u32 = 1 << 3;
+pub const CCR_DIV_0_TRP: u32 = 1 << 4;
+pub const CCR_BFHFNMIGN: u32 = 1 << 8;
+pub const CCR_STKOFHFNMIGN: u32 = 1 << 10;
+/// STKALIGN and bit 0 are RES1 on ARMv8-M
+pub const CCR_RESET: u32 = (1 << 9) | 1;
+
+/// The two bit access fields of CP0 to CP7 and of the FPU's CP10 and CP11
+pub const CPACR_MASK: u32 = 0x00F0FFFF;
+/// The Non-secure access bits of CP0 to CP7, CP10 and CP11
+pub const NSACR_MASK: u32 = 0x00000CFF;
+
+const SYSTICK: Exception = Exception::Interrupt(InterruptException::SysTick);
+const PENDSV: Exception = Exception::Interrupt(InterruptException::PendSV);
+
+// SHCSR active and pended bits, views of the exception state
+const SHCSR_ACTIVE: [(u32, Exception); 8] = [
+    (1 << 0, Exception::MemManage),
+    (1 << 1, Exception::BusFault),
+    (1 << 2, Exception::HardFault),
+    (1 << 3, Exception::UsageFault),
+    (1 << 5, Exception::NMI),
+    (1 << 7, Exception::SVCall),
+    (1 << 10, PENDSV),
+    (1 << 11, SYSTICK),
+];
+const SHCSR_PENDED: [(u32, Exception); 5] = [
+    (1 << 12, Exception::UsageFault),
+    (1 << 13, Exception::MemManage),
+    (1 << 14, Exception::BusFault),
+    (1 << 15, Exception::SVCall),
+    (1 << 21, Exception::HardFault),
+];
+const SHCSR_ENABLES: u32 = SHCSR_MEMFAULTENA | SHCSR_BUSFAULTENA | SHCSR_USGFAULTENA;
+
+impl CortexM33 {
+    pub(crate) fn read_ictr(&self) -> u32 {
+        (IRQ_COUNT as u32).div_ceil(32) - 1
+    }
+
+    /// Reads the register at the offset from SCB_START_ADDRESS. ICSR and SHCSR are built from the exception
+    /// state, the rest are plain registers.
+    pub(crate) fn read_scb(&self, offset: u32) -> u32 {
+        match offset {
+            CPUID => CPUID_VALUE,
+            ICSR => self.icsr(),
+            VTOR => self.vtor,
+            AIRCR => AIRCR_VECTKEYSTAT << 16 | (self.nvic.prigroup as u32) << 8,
+            SCR => self.scr,
+            CCR => self.ccr,
+            SHPR1..=SHPR3 => self.shpr.read(((offset - SHPR1) / 4) as usize),
+            SHCSR => self.read_shcsr(),
+            CFSR => self.fault_status.cfsr,
+            HFSR => self.fault_status.hfsr,
+            MMFAR => self.fault_status.mmfar,
+            BFAR => self.fault_status.bfar,
+            CPACR => self.cpacr,
+            NSACR => self.nsacr,
+            _ => 0,
+        }
+    }
+
+    /// Writes the bits of value selected by mask to the register at the offset from SCB_START_ADDRESS.
+    /// CFSR and HFSR are write one to clear.
+    pub(crate) fn write_scb(&mut self, offset: u32, value: u32, mask: u32) {
+        let masked = value & mask;
+
+        match offset {
+            ICSR => self.write_icsr(masked),
+            VTOR => self.vtor = (self.vtor & !(mask & VTOR_MASK)) | (masked & VTOR_MASK),
+            AIRCR => {
+                // The key has to be written along with the register, so only word writes can get through
+                if mask != u32::MAX || value >> 16 != AIRCR_VECTKEY {
+                    return;
+                }
+
+                self.nvic.prigroup = ((value >> 8) & 0b111) as u8;
+                if value & AIRCR_SYSRESETREQ != 0 {
+                    self.reset_requested = true;
+                }
+            }
+            SCR => {
+                let writable = mask & (SCR_SLEEPONEXIT | SCR_SLEEPDEEP | SCR_SEVONPEND);
+                self.scr = (self.scr & !writable) | (value & writable);
+            }
+            CCR => {
+                let writable = mask
+                    & (CCR_USERSETMPEND | CCR_UNALIGN_TRP | CCR_DIV_0_TRP | CCR_BFHFNMIGN | CCR_STKOFHFNMIGN);
+                self.ccr = (self.ccr & !writable) | (value & writable);
+            }
+            SHPR1..=SHPR3 => self.shpr.write(((offset - SHPR1) / 4) as usize, value, mask),
+            SHCSR => self.write_shcsr(value, mask),
+            CFSR => self.fault_status.cfsr &= !masked,
+            HFSR => self.fault_status.hfsr &= !masked,
+            MMFAR => self.fault_status.mmfar = (self.fault_status.mmfar & !mask) | masked,
+            BFAR => self.fault_status.bfar = (self.fault_status.bfar & !mask) | masked,
+            CPACR => self.cpacr = (self.cpacr & !(mask & CPACR_MASK)) | (masked & CPACR_MASK),
+            NSACR => self.nsacr = (self.nsacr & !(mask & NSACR_MASK)) | (masked & NSACR_MASK),
+            _ => {}
+        }
+    }
+
+    fn icsr(&self) -> u32 {
+        let exceptions = &self.exceptions;
+        let mut icsr = self.ipsr as u32;
+
+        if self.mode == Mode::Handler && exceptions.active.len() == 1 {
+            icsr |= ICSR_RETTOBASE;
+        }
+        if let Some(exception) = Exceptions::highest_pending(self) {
+            icsr |= (exception.number() as u32) << 12;
+        }
+        if exceptions.pending.keys().any(|&n| n >= 16) {
+            icsr |= ICSR_ISRPENDING;
+        }
+        if exceptions.is_pending(SYSTICK) {
+            icsr |= ICSR_PENDSTSET;
+        }
+        if exceptions.is_pending(PENDSV) {
+            icsr |= ICSR_PENDSVSET;
+        }
+        if exceptions.is_pending(Exception::NMI) {
+            icsr |= ICSR_PENDNMISET;
+        }
+
+        icsr
+    }
+
+    fn write_icsr(&mut self, value: u32) {
+        let requests = [
+            (ICSR_PENDNMISET, ICSR_PENDNMICLR, Exception::NMI),
+            (ICSR_PENDSVSET, ICSR_PENDSVCLR, PENDSV),
+            (ICSR_PENDSTSET, ICSR_PENDSTCLR, SYSTICK),
+        ];
+
+        for (set, clear, exception) in requests {
+            if value & set != 0 {
+                self.exceptions.set_pending(exception);
+            } else if value & clear != 0 {
+                self.exceptions.pending.remove(&exception.number());
+            }
+        }
+    }
+
+    fn read_shcsr(&self) -> u32 {
+        let mut shcsr = self.shcsr & SHCSR_ENABLES;
+
+        for (bit, exception) in SHCSR_ACTIVE {
+            if self.exceptions.active.contains_key(&exception.number()) {
+                shcsr |= bit;
+            }
+        }
+        for (bit, exception) in SHCSR_PENDED {
+            if self.exceptions.is_pending(exception) {
+                shcsr |= bit;
+            }
+        }
+
+        shcsr
+    }
+
+    /// Writes the fault enables and the pended bits. The active bits are left to exception entry and return.
+    fn write_shcsr(&mut self, value: u32, mask: u32) {
+        let enables = mask & SHCSR_ENABLES;
+        self.shcsr = (self.shcsr & !enables) | (value & enables);
+
+        for (bit, exception) in SHCSR_PENDED {
+            if mask & bit == 0 {
+                continue;
+            }
+
+            if value & bit != 0 {
+                self.exceptions.set_pending(exception);
+            } else {
+                self.exceptions.pending.remove(&exception.number());
+            }
+        }
+    }
+}
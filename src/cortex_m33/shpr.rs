@@ -1,24 +1,61 @@
+use crate::cortex_m33::exception::PRIORITY_MASK;
 use crate::cortex_m33::operation::get_bits;
 
 pub struct Shpr {
+    shpr1: u32,
     shpr2: u32,
     shpr3: u32,
 }
 
 impl Shpr {
     pub fn new() -> Self {
-        Self { shpr2: 0, shpr3: 0 }
+        Self { shpr1: 0, shpr2: 0, shpr3: 0 }
+    }
+
+    /// Reads SHPR1, SHPR2 or SHPR3, selected by index 0 to 2
+    pub fn read(&self, index: usize) -> u32 {
+        match index {
+            0 => self.shpr1,
+            1 => self.shpr2,
+            2 => self.shpr3,
+            _ => 0,
+        }
+    }
+
+    /// Writes the bits of value selected by mask, keeping only the implemented priority fields
+    pub fn write(&mut self, index: usize, value: u32, mask: u32) {
+        let (register, implemented) = match index {
+            0 => (&mut self.shpr1, 0x00f0f0f0),
+            1 => (&mut self.shpr2, 0xf0000000),
+            2 => (&mut self.shpr3, 0xf0f00000),
+            _ => return,
+        };
+
+        let mask = mask & implemented;
+        *register = (*register & !mask) | (value & mask);
+    }
+
+    pub fn pri_4(&self) -> u8 {
+        get_bits(self.shpr1, 0..=7) as u8 & PRIORITY_MASK
+    }
+
+    pub fn pri_5(&self) -> u8 {
+        get_bits(self.shpr1, 8..=15) as u8 & PRIORITY_MASK
+    }
+
+    pub fn pri_6(&self) -> u8 {
+        get_bits(self.shpr1, 16..=23) as u8 & PRIORITY_MASK
     }
 
     pub fn pri_11(&self) -> u8 {
-        get_bits(self.shpr2, 30..=31) as u8
+        get_bits(self.shpr2, 24..=31) as u8 & PRIORITY_MASK
     }
 
     pub fn pri_15(&self) -> u8 {
-        get_bits(self.shpr3, 30..=31) as u8
+        get_bits(self.shpr3, 24..=31) as u8 & PRIORITY_MASK
     }
 
     pub fn pri_14(&self) -> u8 {
-        get_bits(self.shpr3, 22..=23) as u8
+        get_bits(self.shpr3, 16..=23) as u8 & PRIORITY_MASK
     }
 }
@@ -0,0 +1,111 @@
+pub const SYSTICK_START_ADDRESS: u32 = 0xE000E010;
+pub const SYSTICK_END_ADDRESS: u32 = 0xE000E020;
+
+// Register offsets from SYSTICK_START_ADDRESS
+const CSR: u32 = 0x0;
+const RVR: u32 = 0x4;
+const CVR: u32 = 0x8;
+const CALIB: u32 = 0xc;
+
+pub const CSR_ENABLE: u32 = 1 << 0;
+pub const CSR_TICKINT: u32 = 1 << 1;
+/// Set to count the processor clock, clear to count the external reference tick
+pub const CSR_CLKSOURCE: u32 = 1 << 2;
+pub const CSR_COUNTFLAG: u32 = 1 << 16;
+
+const COUNTER_MASK: u32 = 0x00ff_ffff;
+
+/// The 24-bit SysTick down counter. It reloads from RVR on the tick after reaching zero, and reaching zero
+/// sets COUNTFLAG and, with TICKINT set, pends the SysTick exception.
+pub struct SysTick {
+    csr: u32,
+    rvr: u32,
+    cvr: u32,
+    calib: u32,
+}
+
+impl Default for SysTick {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SysTick {
+    pub fn new() -> Self {
+        Self {
+            csr: 0,
+            rvr: 0,
+            cvr: 0,
+            calib: 0,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.csr & CSR_ENABLE != 0
+    }
+
+    pub fn uses_processor_clock(&self) -> bool {
+        self.csr & CSR_CLKSOURCE != 0
+    }
+
+    pub fn current_value(&self) -> u32 {
+        self.cvr
+    }
+
+    /// Reads the register at the offset from SYSTICK_START_ADDRESS. Reading CSR clears COUNTFLAG.
+    pub fn read(&mut self, offset: u32) -> u32 {
+        match offset {
+            CSR => {
+                let value = self.csr;
+                self.csr &= !CSR_COUNTFLAG;
+                value
+            }
+            RVR => self.rvr,
+            CVR => self.cvr,
+            CALIB => self.calib,
+            _ => 0,
+        }
+    }
+
+    /// Writes the bits of value selected by mask to the register at the offset from SYSTICK_START_ADDRESS.
+    /// Any write to CVR clears the counter and COUNTFLAG.
+    pub fn write(&mut self, offset: u32, value: u32, mask: u32) {
+        match offset {
+            CSR => {
+                let writable = mask & (CSR_ENABLE | CSR_TICKINT | CSR_CLKSOURCE);
+                self.csr = (self.csr & !writable) | (value & writable);
+            }
+            RVR => {
+                let writable = mask & COUNTER_MASK;
+                self.rvr = (self.rvr & !writable) | (value & writable);
+            }
+            CVR => {
+                self.cvr = 0;
+                self.csr &= !CSR_COUNTFLAG;
+            }
+            _ => {}
+        }
+    }
+
+    /// Counts ticks of the selected clock, returning whether the SysTick exception should be pended.
+    pub fn count(&mut self, ticks: u64) -> bool {
+        if !self.is_enabled() {
+            return false;
+        }
+
+        let mut pend = false;
+        for _ in 0..ticks {
+            if self.cvr == 0 {
+                self.cvr = self.rvr;
+            } else {
+                self.cvr -= 1;
+                if self.cvr == 0 {
+                    self.csr |= CSR_COUNTFLAG;
+                    pend |= self.csr & CSR_TICKINT != 0;
+                }
+            }
+        }
+
+        pend
+    }
+}
@@ -0,0 +1,35 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchKind {
+    Read,
+    Write,
+    Access,
+}
+
+/// A data watchpoint over length bytes from address, like a DWT comparator
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Watchpoint {
+    pub address: u32,
+    pub length: u32,
+    pub kind: WatchKind,
+}
+
+impl Watchpoint {
+    /// Whether an access of size bytes at the address overlaps the watched range and is of a kind it watches
+    pub fn matches(&self, address: u32, size: u32, write: bool) -> bool {
+        let kind = match self.kind {
+            WatchKind::Read => !write,
+            WatchKind::Write => write,
+            WatchKind::Access => true,
+        };
+
+        let end = self.address as u64 + self.length.max(1) as u64;
+        kind && (address as u64) < end && (address as u64 + size as u64) > self.address as u64
+    }
+}
+
+/// The watchpoint an instruction triggered, along with the address it accessed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WatchpointHit {
+    pub watchpoint: Watchpoint,
+    pub address: u32,
+}
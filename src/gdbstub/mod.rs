@@ -0,0 +1,507 @@
+pub mod packet;
+
+use std::collections::BTreeSet;
+use std::io;
+use std::net::TcpListener;
+
+use crate::cortex_m33::control::{NPriv, SpSel};
+use crate::cortex_m33::disassembler::disassemble;
+use crate::cortex_m33::registers::Register;
+use crate::cortex_m33::watchpoint::{WatchKind, Watchpoint};
+use crate::{BusError, RP2350};
+use packet::{from_hex, read_packet, to_hex, write_packet, Connection, Packet};
+
+pub const DEFAULT_PORT: u16 = 3333;
+
+/// The registers GDB sees, described for arm-none-eabi-gdb in the numbering used by `g` and `p`
+pub const TARGET_XML: &str = include_str!("target.xml");
+
+const R12: usize = 12;
+const SP: usize = 13;
+const LR: usize = 14;
+const PC: usize = 15;
+const XPSR: usize = 16;
+const MSP: usize = 17;
+const PSP: usize = 18;
+const PRIMASK: usize = 19;
+const BASEPRI: usize = 20;
+const FAULTMASK: usize = 21;
+const CONTROL: usize = 22;
+const D0: usize = 23;
+const D15: usize = 38;
+const FPSCR: usize = 39;
+pub const REGISTER_COUNT: usize = 40;
+
+const SIGINT: u8 = 2;
+const SIGTRAP: u8 = 5;
+const SIGSEGV: u8 = 11;
+
+/// How often a running target checks for GDB interrupting it, in instructions
+const INTERRUPT_POLL_INTERVAL: u64 = 4096;
+
+/// Why the target stopped running
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopReason {
+    Step,
+    Breakpoint,
+    Watchpoint { kind: WatchKind, address: u32 },
+    Interrupted,
+    /// The core halted on a BKPT in the firmware
+    Bkpt(u8),
+    /// The firmware exited through semihosting with the status
+    Exited(u32),
+    /// The core locked up and can't run any further
+    LockedUp,
+}
+
+impl StopReason {
+    /// The stop reply packet GDB expects
+    pub fn reply(&self) -> String {
+        match self {
+            StopReason::Step => format!("S{:02x}", SIGTRAP),
+            StopReason::Breakpoint => format!("T{:02x}hwbreak:;", SIGTRAP),
+            StopReason::Watchpoint { kind, address } => {
+                let name = match kind {
+                    WatchKind::Write => "watch",
+                    WatchKind::Read => "rwatch",
+                    WatchKind::Access => "awatch",
+                };
+                format!("T{:02x}{}:{:x};", SIGTRAP, name, address)
+            }
+            StopReason::Interrupted => format!("S{:02x}", SIGINT),
+            StopReason::Bkpt(_) => format!("S{:02x}", SIGTRAP),
+            StopReason::Exited(status) => format!("W{:02x}", *status as u8),
+            StopReason::LockedUp => format!("S{:02x}", SIGSEGV),
+        }
+    }
+}
+
+/// What the connection loop does for a packet
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Response {
+    Reply(Vec<u8>),
+    Step,
+    Continue,
+    /// Reply OK and close the connection
+    Detach,
+    /// Close the connection without a reply
+    Kill,
+    /// Packets that don't get a reply, like an interrupt while already stopped
+    None,
+}
+
+impl Response {
+    fn ok() -> Self {
+        Response::Reply(b"OK".to_vec())
+    }
+
+    fn error(code: u8) -> Self {
+        Response::Reply(format!("E{:02x}", code).into_bytes())
+    }
+
+    fn text(text: impl Into<String>) -> Self {
+        Response::Reply(text.into().into_bytes())
+    }
+}
+
+/// A GDB Remote Serial Protocol server for one RP2350. It runs the chip itself with execute_instruction, and
+/// keeps the breakpoints, which are all treated as hardware breakpoints.
+#[derive(Debug)]
+pub struct GdbStub {
+    pub breakpoints: BTreeSet<u32>,
+    ack: bool,
+}
+
+impl Default for GdbStub {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GdbStub {
+    pub fn new() -> Self {
+        Self {
+            breakpoints: BTreeSet::new(),
+            ack: true,
+        }
+    }
+
+    /// Waits for GDB to connect on the local port, then serves it until it detaches or disconnects.
+    pub fn listen(&mut self, mcu: &mut RP2350, port: u16) -> io::Result<()> {
+        let listener = TcpListener::bind(("127.0.0.1", port))?;
+        let (mut stream, _) = listener.accept()?;
+        stream.set_nodelay(true)?;
+        self.serve(mcu, &mut stream)
+    }
+
+    pub fn serve(&mut self, mcu: &mut RP2350, connection: &mut impl Connection) -> io::Result<()> {
+        self.ack = true;
+
+        while let Some(packet) = read_packet(connection, self.ack)? {
+            let response = match &packet {
+                Packet::Interrupt => Response::None,
+                Packet::Command(command) => self.handle_packet(mcu, command),
+            };
+
+            let no_ack = response == Response::Reply(b"OK".to_vec()) && packet_is(&packet, b"QStartNoAckMode");
+            let reply = match response {
+                Response::Reply(reply) => reply,
+                Response::Step => self.step(mcu).reply().into_bytes(),
+                Response::Continue => self
+                    .resume(mcu, || connection.poll_interrupt().unwrap_or(true))
+                    .reply()
+                    .into_bytes(),
+                Response::Detach => {
+                    write_packet(connection, b"OK", self.ack)?;
+                    return Ok(());
+                }
+                Response::Kill => return Ok(()),
+                Response::None => continue,
+            };
+
+            write_packet(connection, &reply, self.ack)?;
+            // Acks stop once the OK for QStartNoAckMode has gone out
+            if no_ack {
+                self.ack = false;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Executes one instruction.
+    pub fn step(&mut self, mcu: &mut RP2350) -> StopReason {
+        mcu.cortex_m33.watchpoint_hit = None;
+        mcu.execute_instruction();
+
+        self.stop_after_instruction(mcu).unwrap_or(StopReason::Step)
+    }
+
+    /// Runs until a breakpoint or watchpoint is hit, the core locks up, or interrupted says to stop. A breakpoint
+    /// at the PC the target resumes from doesn't stop it straight away.
+    pub fn resume(&mut self, mcu: &mut RP2350, mut interrupted: impl FnMut() -> bool) -> StopReason {
+        mcu.cortex_m33.watchpoint_hit = None;
+
+        for count in 0u64.. {
+            if count > 0 && self.breakpoints.contains(&mcu.cortex_m33.registers.pc.get()) {
+                return StopReason::Breakpoint;
+            }
+            if count % INTERRUPT_POLL_INTERVAL == INTERRUPT_POLL_INTERVAL - 1 && interrupted() {
+                return StopReason::Interrupted;
+            }
+
+            mcu.execute_instruction();
+
+            if let Some(reason) = self.stop_after_instruction(mcu) {
+                return reason;
+            }
+        }
+
+        unreachable!()
+    }
+
+    fn stop_after_instruction(&self, mcu: &mut RP2350) -> Option<StopReason> {
+        if let Some(hit) = mcu.cortex_m33.watchpoint_hit.take() {
+            return Some(StopReason::Watchpoint {
+                kind: hit.watchpoint.kind,
+                address: hit.address,
+            });
+        }
+        if let Some(imm) = mcu.cortex_m33.breakpoint {
+            return Some(StopReason::Bkpt(imm));
+        }
+        if let Some(status) = mcu.semihosting.exit {
+            return Some(StopReason::Exited(status));
+        }
+        if mcu.cortex_m33.locked_up {
+            return Some(StopReason::LockedUp);
+        }
+
+        None
+    }
+
+    /// Works out the response to a packet's payload. Packets the stub doesn't know get an empty reply, which tells
+    /// GDB they aren't supported.
+    pub fn handle_packet(&mut self, mcu: &mut RP2350, packet: &[u8]) -> Response {
+        let Some((&command, args)) = packet.split_first() else {
+            return Response::text("");
+        };
+
+        match command {
+            b'?' => Response::text(format!("S{:02x}", SIGTRAP)),
+            b'g' => {
+                let registers: Vec<u8> = (0..REGISTER_COUNT)
+                    .flat_map(|n| read_register(mcu, n).unwrap_or_default())
+                    .collect();
+                Response::text(to_hex(&registers))
+            }
+            b'G' => {
+                let Some(mut bytes) = from_hex(args) else {
+                    return Response::error(1);
+                };
+                for n in 0..REGISTER_COUNT {
+                    let size = register_size(n).min(bytes.len());
+                    let value: Vec<u8> = bytes.drain(..size).collect();
+                    if value.len() == register_size(n) {
+                        write_register(mcu, n, &value);
+                    }
+                }
+                Response::ok()
+            }
+            b'p' => match parse_hex(args).and_then(|n| read_register(mcu, n as usize)) {
+                Some(value) => Response::text(to_hex(&value)),
+                None => Response::error(1),
+            },
+            b'P' => {
+                let written = split(args, b'=').and_then(|(n, value)| {
+                    let n = parse_hex(n)? as usize;
+                    let value = from_hex(value)?;
+                    (value.len() == register_size(n)).then_some(())?;
+                    write_register(mcu, n, &value)
+                });
+                written.map_or(Response::error(1), |_| Response::ok())
+            }
+            b'm' => {
+                let Some((address, length)) = parse_address_length(args) else {
+                    return Response::error(1);
+                };
+                let memory = &mcu.cortex_m33.memory;
+                let bytes: Vec<u8> = (0..length)
+                    .map_while(|i| memory.read(address.wrapping_add(i)).ok())
+                    .collect();
+                if bytes.is_empty() && length > 0 {
+                    Response::error(14)
+                } else {
+                    Response::text(to_hex(&bytes))
+                }
+            }
+            b'M' | b'X' => {
+                let Some((range, data)) = split(args, b':') else {
+                    return Response::error(1);
+                };
+                let Some((address, length)) = parse_address_length(range) else {
+                    return Response::error(1);
+                };
+                let data = if command == b'M' { from_hex(data) } else { Some(data.to_vec()) };
+                match data {
+                    Some(data) if data.len() == length as usize => match write_memory(mcu, address, &data) {
+                        Ok(()) => Response::ok(),
+                        Err(_) => Response::error(14),
+                    },
+                    _ => Response::error(1),
+                }
+            }
+            b's' => Response::Step,
+            b'c' => Response::Continue,
+            b'Z' | b'z' => self.handle_breakpoint(mcu, command == b'Z', args),
+            b'D' => Response::Detach,
+            b'k' => Response::Kill,
+            b'H' => Response::ok(),
+            b'T' => Response::ok(),
+            b'q' | b'Q' => self.handle_query(mcu, packet),
+            _ => Response::text(""),
+        }
+    }
+
+    fn handle_breakpoint(&mut self, mcu: &mut RP2350, insert: bool, args: &[u8]) -> Response {
+        let fields: Vec<&[u8]> = args.split(|&byte| byte == b',').collect();
+        let [kind, address, length] = fields[..] else {
+            return Response::error(1);
+        };
+        let (Some(address), Some(length)) = (parse_hex(address), parse_hex(length)) else {
+            return Response::error(1);
+        };
+
+        let watch_kind = match kind {
+            b"0" | b"1" => {
+                if insert {
+                    self.breakpoints.insert(address & !1);
+                } else {
+                    self.breakpoints.remove(&(address & !1));
+                }
+                return Response::ok();
+            }
+            b"2" => WatchKind::Write,
+            b"3" => WatchKind::Read,
+            b"4" => WatchKind::Access,
+            _ => return Response::text(""),
+        };
+
+        let watchpoint = Watchpoint { address, length, kind: watch_kind };
+        let watchpoints = &mut mcu.cortex_m33.watchpoints;
+        if insert {
+            watchpoints.push(watchpoint);
+        } else if let Some(index) = watchpoints.iter().position(|existing| *existing == watchpoint) {
+            watchpoints.remove(index);
+        }
+        Response::ok()
+    }
+
+    fn handle_query(&mut self, mcu: &mut RP2350, packet: &[u8]) -> Response {
+        let query = String::from_utf8_lossy(packet);
+
+        if query.starts_with("qSupported") {
+            return Response::text("PacketSize=4000;qXfer:features:read+;hwbreak+;QStartNoAckMode+");
+        }
+        if let Some(annex) = query.strip_prefix("qXfer:features:read:") {
+            let Some(("target.xml", range)) = annex.split_once(':') else {
+                return Response::error(0);
+            };
+            let Some((offset, length)) = parse_address_length(range.as_bytes()) else {
+                return Response::error(1);
+            };
+
+            let xml = TARGET_XML.as_bytes();
+            let start = (offset as usize).min(xml.len());
+            let end = start.saturating_add(length as usize).min(xml.len());
+            let marker = if end == xml.len() { b'l' } else { b'm' };
+            let mut reply = vec![marker];
+            reply.extend(&xml[start..end]);
+            return Response::Reply(reply);
+        }
+        if let Some(command) = query.strip_prefix("qRcmd,") {
+            return match from_hex(command.as_bytes()).as_deref() {
+                Some(b"reset") | Some(b"reset halt") => {
+                    mcu.reset();
+                    Response::ok()
+                }
+                Some(command) if command == b"disas" || command.starts_with(b"disas ") => match disassembly(mcu, &command[5..]) {
+                    Some(listing) => Response::text(to_hex(listing.as_bytes())),
+                    None => Response::error(1),
+                },
+                _ => Response::text(""),
+            };
+        }
+
+        match query.as_ref() {
+            "QStartNoAckMode" => Response::ok(),
+            "qAttached" => Response::text("1"),
+            "qC" => Response::text("QC1"),
+            "qfThreadInfo" => Response::text("m1"),
+            "qsThreadInfo" => Response::text("l"),
+            _ => Response::text(""),
+        }
+    }
+}
+
+/// The listing for `monitor disas [address] [count]`, from the PC and 8 instructions by default
+fn disassembly(mcu: &RP2350, args: &[u8]) -> Option<String> {
+    let args = std::str::from_utf8(args).ok()?;
+    let mut args = args.split_whitespace();
+    let address = match args.next() {
+        Some(address) => u32::from_str_radix(address.trim_start_matches("0x"), 16).ok()?,
+        None => mcu.cortex_m33.registers.pc.get(),
+    };
+    let count = match args.next() {
+        Some(count) => count.parse().ok()?,
+        None => 8,
+    };
+
+    let lines = disassemble(&mcu.cortex_m33, &mcu.symbols, address, count);
+    Some(lines.iter().map(|line| format!("{}\n", line)).collect())
+}
+
+fn packet_is(packet: &Packet, command: &[u8]) -> bool {
+    matches!(packet, Packet::Command(payload) if payload == command)
+}
+
+pub fn register_size(n: usize) -> usize {
+    if (D0..=D15).contains(&n) {
+        8
+    } else {
+        4
+    }
+}
+
+/// Reads register n in GDB's numbering as little endian bytes.
+pub fn read_register(mcu: &mut RP2350, n: usize) -> Option<Vec<u8>> {
+    let cortex = &mut mcu.cortex_m33;
+
+    let value = match n {
+        0..=PC => cortex.get_register_from_number(n as u16).get(),
+        XPSR => (cortex.xpsr.into_u32() & !0x1ff) | cortex.ipsr as u32,
+        MSP => cortex.registers.sp.get_msp(),
+        PSP => cortex.registers.sp.get_psp(),
+        PRIMASK => cortex.primask as u32,
+        BASEPRI => cortex.basepri as u32,
+        FAULTMASK => cortex.faultmask as u32,
+        CONTROL => {
+            let npriv = cortex.control.npriv == NPriv::ThreadModeUnprivileged;
+            let spsel = cortex.control.spsel == SpSel::SpProcess;
+            ((spsel as u32) << 1) | npriv as u32
+        }
+        D0..=D15 => return Some(cortex.fpu.d(n - D0).to_le_bytes().to_vec()),
+        FPSCR => cortex.fpu.fpscr,
+        _ => return None,
+    };
+
+    Some(value.to_le_bytes().to_vec())
+}
+
+/// Writes register n in GDB's numbering from little endian bytes. IPSR is left alone, it follows the exception
+/// state rather than being set.
+pub fn write_register(mcu: &mut RP2350, n: usize, bytes: &[u8]) -> Option<()> {
+    let cortex = &mut mcu.cortex_m33;
+
+    if (D0..=D15).contains(&n) {
+        cortex.fpu.set_d(n - D0, u64::from_le_bytes(bytes.try_into().ok()?));
+        return Some(());
+    }
+
+    let value = u32::from_le_bytes(bytes.try_into().ok()?);
+    match n {
+        0..=R12 | LR => cortex.get_register_from_number(n as u16).set(value),
+        SP => cortex.registers.sp.set(value & !0b11),
+        PC => cortex.registers.pc.set(value & !1),
+        XPSR => {
+            let ipsr = cortex.ipsr;
+            cortex.xpsr.set_from_u32(value);
+            cortex.ipsr = ipsr;
+        }
+        MSP => cortex.registers.sp.set_msp(value & !0b11),
+        PSP => cortex.registers.sp.set_psp(value & !0b11),
+        PRIMASK => cortex.primask = value & 1 != 0,
+        BASEPRI => cortex.basepri = value as u8,
+        FAULTMASK => cortex.faultmask = value & 1 != 0,
+        CONTROL => {
+            cortex.control.npriv = if value & 1 != 0 {
+                NPriv::ThreadModeUnprivileged
+            } else {
+                NPriv::ThreadModePrivileged
+            };
+            cortex.control.spsel = if value & 2 != 0 { SpSel::SpProcess } else { SpSel::SpMain };
+            cortex.update_sp_mode();
+        }
+        FPSCR => cortex.fpu.fpscr = value,
+        _ => return None,
+    }
+
+    Some(())
+}
+
+/// Writes memory through the bus, falling back to programming for flash so `load` works as with a probe.
+pub fn write_memory(mcu: &mut RP2350, address: u32, data: &[u8]) -> Result<(), BusError> {
+    for (i, byte) in data.iter().enumerate() {
+        let address = address.wrapping_add(i as u32);
+        match mcu.cortex_m33.memory.write(address, *byte) {
+            Err(BusError::ReadOnly) => mcu.memory_mut().program(address, &[*byte])?,
+            result => result?,
+        }
+    }
+
+    Ok(())
+}
+
+fn split(bytes: &[u8], separator: u8) -> Option<(&[u8], &[u8])> {
+    let index = bytes.iter().position(|&byte| byte == separator)?;
+    Some((&bytes[..index], &bytes[index + 1..]))
+}
+
+fn parse_hex(digits: &[u8]) -> Option<u32> {
+    u32::from_str_radix(std::str::from_utf8(digits).ok()?, 16).ok()
+}
+
+fn parse_address_length(args: &[u8]) -> Option<(u32, u32)> {
+    let (address, length) = split(args, b',')?;
+    Some((parse_hex(address)?, parse_hex(length)?))
+}
@@ -0,0 +1,156 @@
+use std::io::{self, ErrorKind, Read, Write};
+use std::net::TcpStream;
+
+/// The byte GDB sends outside of a packet to interrupt a running target
+pub const INTERRUPT: u8 = 0x03;
+
+/// A connection to GDB. Besides reading and writing it has to be able to check for an interrupt without
+/// blocking, so a running target can be stopped.
+pub trait Connection: Read + Write {
+    /// Consumes and reports an interrupt byte if one is waiting
+    fn poll_interrupt(&mut self) -> io::Result<bool>;
+}
+
+impl Connection for TcpStream {
+    fn poll_interrupt(&mut self) -> io::Result<bool> {
+        self.set_nonblocking(true)?;
+        let mut byte = [0];
+        let result = match self.peek(&mut byte) {
+            Ok(1) if byte[0] == INTERRUPT => self.read_exact(&mut byte).map(|_| true),
+            Ok(_) => Ok(false),
+            Err(error) if error.kind() == ErrorKind::WouldBlock => Ok(false),
+            Err(error) => Err(error),
+        };
+        self.set_nonblocking(false)?;
+        result
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Packet {
+    /// The payload of a `$...#xx` packet with escapes removed
+    Command(Vec<u8>),
+    Interrupt,
+}
+
+pub fn checksum(data: &[u8]) -> u8 {
+    data.iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte))
+}
+
+fn read_byte(connection: &mut impl Read) -> io::Result<Option<u8>> {
+    let mut byte = [0];
+    match connection.read(&mut byte)? {
+        0 => Ok(None),
+        _ => Ok(Some(byte[0])),
+    }
+}
+
+/// Reads the next packet, acknowledging it unless acks are off. A packet with a bad checksum is refused so GDB
+/// sends it again. None once the connection is closed.
+pub fn read_packet(connection: &mut impl Connection, ack: bool) -> io::Result<Option<Packet>> {
+    loop {
+        let Some(byte) = read_byte(connection)? else {
+            return Ok(None);
+        };
+
+        match byte {
+            INTERRUPT => return Ok(Some(Packet::Interrupt)),
+            b'$' => {}
+            // Acks for our own packets, or noise between packets
+            _ => continue,
+        }
+
+        let mut raw = Vec::new();
+        loop {
+            match read_byte(connection)? {
+                None => return Ok(None),
+                Some(b'#') => break,
+                Some(byte) => raw.push(byte),
+            }
+        }
+
+        let mut digits = [0; 2];
+        connection.read_exact(&mut digits)?;
+        let expected = std::str::from_utf8(&digits)
+            .ok()
+            .and_then(|digits| u8::from_str_radix(digits, 16).ok());
+
+        if expected != Some(checksum(&raw)) {
+            if ack {
+                connection.write_all(b"-")?;
+            }
+            continue;
+        }
+        if ack {
+            connection.write_all(b"+")?;
+        }
+
+        return Ok(Some(Packet::Command(unescape(&raw))));
+    }
+}
+
+/// Sends a packet, escaping the payload, and waits for GDB to acknowledge it unless acks are off.
+pub fn write_packet(connection: &mut impl Connection, payload: &[u8], ack: bool) -> io::Result<()> {
+    let escaped = escape(payload);
+    let mut packet = Vec::with_capacity(escaped.len() + 4);
+    packet.push(b'$');
+    packet.extend(&escaped);
+    packet.extend(format!("#{:02x}", checksum(&escaped)).as_bytes());
+
+    loop {
+        connection.write_all(&packet)?;
+        connection.flush()?;
+
+        if !ack {
+            return Ok(());
+        }
+
+        match read_byte(connection)? {
+            Some(b'-') => continue,
+            _ => return Ok(()),
+        }
+    }
+}
+
+/// Binary data escapes `#`, `$`, `}` and `*` as `}` followed by the byte xor 0x20
+pub fn escape(data: &[u8]) -> Vec<u8> {
+    let mut escaped = Vec::with_capacity(data.len());
+    for &byte in data {
+        if matches!(byte, b'#' | b'$' | b'}' | b'*') {
+            escaped.extend([b'}', byte ^ 0x20]);
+        } else {
+            escaped.push(byte);
+        }
+    }
+    escaped
+}
+
+pub fn unescape(data: &[u8]) -> Vec<u8> {
+    let mut unescaped = Vec::with_capacity(data.len());
+    let mut bytes = data.iter();
+    while let Some(&byte) = bytes.next() {
+        if byte == b'}' {
+            if let Some(&next) = bytes.next() {
+                unescaped.push(next ^ 0x20);
+            }
+        } else {
+            unescaped.push(byte);
+        }
+    }
+    unescaped
+}
+
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+pub fn from_hex(digits: &[u8]) -> Option<Vec<u8>> {
+    if !digits.len().is_multiple_of(2) {
+        return None;
+    }
+
+    digits
+        .chunks(2)
+        .map(|pair| u8::from_str_radix(std::str::from_utf8(pair).ok()?, 16).ok())
+        .collect()
+}
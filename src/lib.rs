@@ -1,43 +1,89 @@
+pub mod bootrom;
 pub mod cortex_m33;
+pub mod gdbstub;
+pub mod loader;
+pub mod peripherals;
+pub mod pins;
 mod rp2350;
+pub mod runner;
+pub mod semihosting;
+pub mod trace;
 
 use std::any::Any;
 
 use byteorder::{ByteOrder, LittleEndian};
 pub use rp2350::*;
 
+/// Why a bus access could not be completed. The core turns these into a BusFault.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BusError {
+    /// Nothing responds at the address
+    Unmapped,
+    /// The address is mapped, but the region cannot be written
+    ReadOnly,
+}
+
+impl std::fmt::Display for BusError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BusError::Unmapped => write!(f, "no memory is mapped at the address"),
+            BusError::ReadOnly => write!(f, "the address is read only"),
+        }
+    }
+}
+
+impl std::error::Error for BusError {}
+
+pub type AccessResult<T> = Result<T, BusError>;
+
 pub trait MemoryInterface<AddressType: num_traits::Unsigned + Copy> {
-    fn read(&self, address: AddressType) -> u8;
-    fn write(&mut self, address: AddressType, value: u8);
+    fn read(&self, address: AddressType) -> AccessResult<u8>;
+    fn write(&mut self, address: AddressType, value: u8) -> AccessResult<()>;
 
 
-    fn read_u16(&self, address: AddressType) -> u16 {
-        LittleEndian::read_u16(&[self.read(address), self.read(address + AddressType::one())])
+    fn read_u16(&self, address: AddressType) -> AccessResult<u16> {
+        Ok(LittleEndian::read_u16(&[self.read(address)?, self.read(address + AddressType::one())?]))
     }
 
-    fn read_u32(&self, address: AddressType) -> u32 {
+    fn read_u32(&self, address: AddressType) -> AccessResult<u32> {
         let one = AddressType::one();
-        LittleEndian::read_u32(&[self.read(address), self.read(address + one), self.read(address + one + one), self.read(address + one + one + one)])
+        Ok(LittleEndian::read_u32(&[self.read(address)?, self.read(address + one)?, self.read(address + one + one)?, self.read(address + one + one + one)?]))
     }
 
 
-    fn write_u16(&mut self, address: AddressType, value: u16) {
+    fn write_u16(&mut self, address: AddressType, value: u16) -> AccessResult<()> {
         let bytes = value.to_le_bytes();
         for (i, byte) in bytes.into_iter().enumerate() {
             let mut address = address;
             for _ in 0..i { address = address + AddressType::one(); };
-            self.write(address, byte);
+            self.write(address, byte)?;
         }
+
+        Ok(())
     }
 
-    fn write_u32(&mut self, address: AddressType, value: u32) {
+    fn write_u32(&mut self, address: AddressType, value: u32) -> AccessResult<()> {
         let bytes = value.to_le_bytes();
         for (i, byte) in bytes.into_iter().enumerate() {
             let mut address = address;
             for _ in 0..i { address = address + AddressType::one(); };
-            self.write(address, byte);
+            self.write(address, byte)?;
         }
+
+        Ok(())
+    }
+
+    /// Asks to be told of writes to the code page holding the address, returning false for memory that can't
+    /// tell. Only memory that can has its instructions kept in the decode cache.
+    fn watch_code(&mut self, _address: AddressType) -> bool {
+        false
+    }
+
+    /// The code pages written since they were watched, each by its start address. They stop being watched.
+    fn take_code_writes(&mut self) -> Vec<AddressType> {
+        Vec::new()
     }
 
     fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
 }
@@ -0,0 +1,228 @@
+use byteorder::{ByteOrder, LittleEndian};
+
+use crate::BusError;
+
+const ELF_MAGIC: [u8; 4] = [0x7f, b'E', b'L', b'F'];
+const ELFCLASS32: u8 = 1;
+const ELFDATA2LSB: u8 = 1;
+const EM_ARM: u16 = 40;
+
+const PT_LOAD: u32 = 1;
+const SHT_SYMTAB: u32 = 2;
+
+const STT_NOTYPE: u8 = 0;
+const STT_OBJECT: u8 = 1;
+const STT_FUNC: u8 = 2;
+
+const PROGRAM_HEADER_SIZE: usize = 32;
+const SECTION_HEADER_SIZE: usize = 40;
+const SYMBOL_SIZE: usize = 16;
+
+/// Why an ELF file could not be loaded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ElfError {
+    /// The file does not start with the ELF magic
+    NotElf,
+    /// The file is not a 32-bit little endian Arm image
+    Unsupported(&'static str),
+    /// A header, segment or table extends past the end of the file
+    Truncated,
+    /// A segment that falls outside memory the loader can program
+    Program { address: u32, error: BusError },
+}
+
+impl std::fmt::Display for ElfError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ElfError::NotElf => write!(f, "not an ELF file"),
+            ElfError::Unsupported(reason) => write!(f, "unsupported ELF file, {}", reason),
+            ElfError::Truncated => write!(f, "ELF file is truncated"),
+            ElfError::Program { address, error } => write!(f, "cannot program {:#010x}, {}", address, error),
+        }
+    }
+}
+
+impl std::error::Error for ElfError {}
+
+/// A PT_LOAD segment. It is loaded at its physical address, the LMA, which is where initialised data sits in
+/// flash until the startup code copies it to its virtual address.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Segment {
+    pub physical_address: u32,
+    pub virtual_address: u32,
+    /// The bytes from the file, memory_size can be larger with the rest zeroed
+    pub data: Vec<u8>,
+    pub memory_size: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SymbolKind {
+    Function,
+    Object,
+    Other,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Symbol {
+    pub name: String,
+    /// With the Thumb bit cleared for functions
+    pub address: u32,
+    pub size: u32,
+    pub kind: SymbolKind,
+}
+
+impl Symbol {
+    pub fn contains(&self, address: u32) -> bool {
+        if self.size == 0 {
+            address == self.address
+        } else {
+            address.wrapping_sub(self.address) < self.size
+        }
+    }
+}
+
+/// Named symbols from an image, ordered by address
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SymbolTable {
+    symbols: Vec<Symbol>,
+}
+
+impl SymbolTable {
+    pub fn new(mut symbols: Vec<Symbol>) -> Self {
+        symbols.sort_by_key(|symbol| symbol.address);
+        Self { symbols }
+    }
+
+    pub fn symbols(&self) -> &[Symbol] {
+        &self.symbols
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.symbols.is_empty()
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Symbol> {
+        self.symbols.iter().find(|symbol| symbol.name == name)
+    }
+
+    /// The symbol covering the address, preferring functions so a PC resolves to the function it is in
+    pub fn resolve(&self, address: u32) -> Option<&Symbol> {
+        let candidates = self.symbols.iter().filter(|symbol| symbol.contains(address));
+
+        candidates
+            .clone()
+            .find(|symbol| symbol.kind == SymbolKind::Function)
+            .or_else(|| candidates.clone().next())
+    }
+}
+
+/// The contents of an ELF image
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ElfFile {
+    pub entry: u32,
+    pub segments: Vec<Segment>,
+    pub symbols: SymbolTable,
+}
+
+/// Parses a 32-bit little endian Arm ELF file, keeping its PT_LOAD segments and the symbols from its symbol
+/// table. Section, file and Arm mapping symbols are left out.
+pub fn parse(bytes: &[u8]) -> Result<ElfFile, ElfError> {
+    if bytes.get(..4) != Some(&ELF_MAGIC[..]) {
+        return Err(ElfError::NotElf);
+    }
+
+    let header = slice(bytes, 0, 52)?;
+    if header[4] != ELFCLASS32 {
+        return Err(ElfError::Unsupported("not 32-bit"));
+    }
+    if header[5] != ELFDATA2LSB {
+        return Err(ElfError::Unsupported("not little endian"));
+    }
+    if LittleEndian::read_u16(&header[18..]) != EM_ARM {
+        return Err(ElfError::Unsupported("not an Arm image"));
+    }
+
+    let entry = LittleEndian::read_u32(&header[24..]);
+    let program_headers = LittleEndian::read_u32(&header[28..]) as usize;
+    let section_headers = LittleEndian::read_u32(&header[32..]) as usize;
+    let program_header_count = LittleEndian::read_u16(&header[44..]) as usize;
+    let section_header_count = LittleEndian::read_u16(&header[48..]) as usize;
+
+    let mut segments = Vec::new();
+    for i in 0..program_header_count {
+        let header = slice(bytes, program_headers + i * PROGRAM_HEADER_SIZE, PROGRAM_HEADER_SIZE)?;
+        if LittleEndian::read_u32(header) != PT_LOAD {
+            continue;
+        }
+
+        let offset = LittleEndian::read_u32(&header[4..]) as usize;
+        let file_size = LittleEndian::read_u32(&header[16..]) as usize;
+        segments.push(Segment {
+            physical_address: LittleEndian::read_u32(&header[12..]),
+            virtual_address: LittleEndian::read_u32(&header[8..]),
+            data: slice(bytes, offset, file_size)?.to_vec(),
+            memory_size: LittleEndian::read_u32(&header[20..]),
+        });
+    }
+
+    let mut symbols = Vec::new();
+    for i in 0..section_header_count {
+        let header = slice(bytes, section_headers + i * SECTION_HEADER_SIZE, SECTION_HEADER_SIZE)?;
+        if LittleEndian::read_u32(&header[4..]) != SHT_SYMTAB {
+            continue;
+        }
+
+        let table = section(bytes, header)?;
+        let string_index = LittleEndian::read_u32(&header[24..]) as usize;
+        let string_header = slice(bytes, section_headers + string_index * SECTION_HEADER_SIZE, SECTION_HEADER_SIZE)?;
+        let strings = section(bytes, string_header)?;
+
+        // The first entry is the reserved undefined symbol
+        for entry in table.chunks_exact(SYMBOL_SIZE).skip(1) {
+            let name = name(strings, LittleEndian::read_u32(entry) as usize);
+            let value = LittleEndian::read_u32(&entry[4..]);
+            let kind = match entry[12] & 0xf {
+                STT_NOTYPE => SymbolKind::Other,
+                STT_OBJECT => SymbolKind::Object,
+                STT_FUNC => SymbolKind::Function,
+                _ => continue,
+            };
+
+            let undefined = LittleEndian::read_u16(&entry[14..]) == 0;
+            if undefined || name.is_empty() || name.starts_with('$') {
+                continue;
+            }
+
+            symbols.push(Symbol {
+                name,
+                address: if kind == SymbolKind::Function { value & !1 } else { value },
+                size: LittleEndian::read_u32(&entry[8..]),
+                kind,
+            });
+        }
+    }
+
+    Ok(ElfFile {
+        entry,
+        segments,
+        symbols: SymbolTable::new(symbols),
+    })
+}
+
+fn slice(bytes: &[u8], offset: usize, length: usize) -> Result<&[u8], ElfError> {
+    bytes
+        .get(offset..offset.checked_add(length).ok_or(ElfError::Truncated)?)
+        .ok_or(ElfError::Truncated)
+}
+
+fn section<'a>(bytes: &'a [u8], header: &[u8]) -> Result<&'a [u8], ElfError> {
+    let offset = LittleEndian::read_u32(&header[16..]) as usize;
+    let size = LittleEndian::read_u32(&header[20..]) as usize;
+    slice(bytes, offset, size)
+}
+
+fn name(strings: &[u8], offset: usize) -> String {
+    let bytes = strings.get(offset..).unwrap_or_default();
+    let end = bytes.iter().position(|&byte| byte == 0).unwrap_or(bytes.len());
+    String::from_utf8_lossy(&bytes[..end]).into_owned()
+}
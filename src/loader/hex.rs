@@ -0,0 +1,142 @@
+use crate::BusError;
+
+const DATA: u8 = 0x00;
+const END_OF_FILE: u8 = 0x01;
+const EXTENDED_SEGMENT_ADDRESS: u8 = 0x02;
+const START_SEGMENT_ADDRESS: u8 = 0x03;
+const EXTENDED_LINEAR_ADDRESS: u8 = 0x04;
+const START_LINEAR_ADDRESS: u8 = 0x05;
+
+/// Why an Intel HEX file could not be loaded, along with the 1-based line it happened on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HexError {
+    pub line: usize,
+    pub kind: HexErrorKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HexErrorKind {
+    /// The record does not start with ':'
+    MissingStartCode,
+    /// The record has characters that are not hex digits, or an odd number of them
+    InvalidDigits,
+    /// The byte count does not match the length of the record
+    LengthMismatch,
+    /// The record's checksum byte does not match the sum of its other bytes
+    Checksum { expected: u8, actual: u8 },
+    UnknownRecordType(u8),
+    /// An address or end of file record with the wrong byte count
+    InvalidRecord(u8),
+    /// Data that falls outside memory the loader can program
+    Program { address: u32, error: BusError },
+}
+
+impl std::fmt::Display for HexError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: ", self.line)?;
+        match self.kind {
+            HexErrorKind::MissingStartCode => write!(f, "record does not start with ':'"),
+            HexErrorKind::InvalidDigits => write!(f, "record is not made of hex digit pairs"),
+            HexErrorKind::LengthMismatch => write!(f, "byte count does not match the record length"),
+            HexErrorKind::Checksum { expected, actual } => {
+                write!(f, "checksum is {:#04x}, expected {:#04x}", actual, expected)
+            }
+            HexErrorKind::UnknownRecordType(record_type) => write!(f, "unknown record type {:02}", record_type),
+            HexErrorKind::InvalidRecord(record_type) => {
+                write!(f, "wrong byte count for a type {:02} record", record_type)
+            }
+            HexErrorKind::Program { address, error } => write!(f, "cannot program {:#010x}, {}", address, error),
+        }
+    }
+}
+
+impl std::error::Error for HexError {}
+
+/// A run of bytes from one data record, placed at its absolute address
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HexData {
+    pub line: usize,
+    pub address: u32,
+    pub bytes: Vec<u8>,
+}
+
+/// The contents of an Intel HEX file
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct HexFile {
+    pub data: Vec<HexData>,
+    /// From a start linear address record, or CS:IP as a linear address from a start segment address record
+    pub start_address: Option<u32>,
+}
+
+/// Parses record types 00 to 05, verifying each record's checksum. Blank lines are skipped, and anything after
+/// the end of file record is ignored.
+pub fn parse(source: &str) -> Result<HexFile, HexError> {
+    let mut file = HexFile::default();
+    let mut base_address = 0u32;
+
+    for (index, line) in source.lines().enumerate() {
+        let line_number = index + 1;
+        let error = |kind| HexError { line: line_number, kind };
+
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let digits = line.strip_prefix(':').ok_or(error(HexErrorKind::MissingStartCode))?;
+        let record = decode(digits).ok_or(error(HexErrorKind::InvalidDigits))?;
+
+        // Byte count, two address bytes, record type and checksum
+        if record.len() < 5 || record.len() != record[0] as usize + 5 {
+            return Err(error(HexErrorKind::LengthMismatch));
+        }
+
+        let (body, checksum) = record.split_at(record.len() - 1);
+        let expected = body.iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte)).wrapping_neg();
+        if checksum[0] != expected {
+            return Err(error(HexErrorKind::Checksum { expected, actual: checksum[0] }));
+        }
+
+        let offset = u16::from_be_bytes([record[1], record[2]]) as u32;
+        let record_type = record[3];
+        let data = &body[4..];
+
+        match record_type {
+            DATA => file.data.push(HexData {
+                line: line_number,
+                address: base_address.wrapping_add(offset),
+                bytes: data.to_vec(),
+            }),
+            END_OF_FILE if data.is_empty() => break,
+            EXTENDED_SEGMENT_ADDRESS if data.len() == 2 => {
+                base_address = (u16::from_be_bytes([data[0], data[1]]) as u32) << 4;
+            }
+            START_SEGMENT_ADDRESS if data.len() == 4 => {
+                let segment = u16::from_be_bytes([data[0], data[1]]) as u32;
+                let offset = u16::from_be_bytes([data[2], data[3]]) as u32;
+                file.start_address = Some((segment << 4) + offset);
+            }
+            EXTENDED_LINEAR_ADDRESS if data.len() == 2 => {
+                base_address = (u16::from_be_bytes([data[0], data[1]]) as u32) << 16;
+            }
+            START_LINEAR_ADDRESS if data.len() == 4 => {
+                file.start_address = Some(u32::from_be_bytes([data[0], data[1], data[2], data[3]]));
+            }
+            END_OF_FILE..=START_LINEAR_ADDRESS => return Err(error(HexErrorKind::InvalidRecord(record_type))),
+            _ => return Err(error(HexErrorKind::UnknownRecordType(record_type))),
+        }
+    }
+
+    Ok(file)
+}
+
+fn decode(digits: &str) -> Option<Vec<u8>> {
+    if !digits.len().is_multiple_of(2) || !digits.bytes().all(|digit| digit.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    (0..digits.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&digits[i..i + 2], 16).ok())
+        .collect()
+}
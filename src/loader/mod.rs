@@ -0,0 +1,3 @@
+pub mod elf;
+pub mod hex;
+pub mod uf2;
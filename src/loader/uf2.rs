@@ -0,0 +1,195 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use byteorder::{ByteOrder, LittleEndian};
+
+use crate::BusError;
+
+pub const BLOCK_SIZE: usize = 512;
+const MAGIC_START0: u32 = 0x0A324655;
+const MAGIC_START1: u32 = 0x9E5D5157;
+const MAGIC_END: u32 = 0x0AB16F30;
+const MAX_PAYLOAD: usize = 476;
+/// The 256 byte blocks it takes to fill the 4 MB of flash, which no image the simulator can load needs more than
+pub const MAX_BLOCK_COUNT: u32 = 4 * 1024 * 1024 / 256;
+
+pub const FLAG_NOT_MAIN_FLASH: u32 = 0x0000_0001;
+pub const FLAG_FILE_CONTAINER: u32 = 0x0000_1000;
+pub const FLAG_FAMILY_ID_PRESENT: u32 = 0x0000_2000;
+
+/// Addresses are absolute, whatever partitions the flash has
+pub const ABSOLUTE_FAMILY_ID: u32 = 0xe48bff57;
+/// Data for whichever partition accepts it, which the loader writes at its target address like any other
+pub const DATA_FAMILY_ID: u32 = 0xe48bff58;
+pub const RP2350_ARM_S_FAMILY_ID: u32 = 0xe48bff59;
+pub const RP2350_RISCV_FAMILY_ID: u32 = 0xe48bff5a;
+pub const RP2350_ARM_NS_FAMILY_ID: u32 = 0xe48bff5b;
+
+/// The families the bootrom takes when booting Arm secure code, which is how the simulator runs
+pub const ARM_SECURE_FAMILY_IDS: [u32; 3] = [RP2350_ARM_S_FAMILY_ID, ABSOLUTE_FAMILY_ID, DATA_FAMILY_ID];
+
+/// Why a UF2 file could not be loaded, along with the index of the block in the file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Uf2Error {
+    pub block: usize,
+    pub kind: Uf2ErrorKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Uf2ErrorKind {
+    /// The file ends part way through a block
+    Truncated,
+    /// One of the three magic numbers is wrong
+    BadMagic,
+    /// The payload size is larger than fits in a block
+    PayloadTooLarge(u32),
+    /// The block count is more than the blocks it takes to fill flash
+    TooManyBlocks(u32),
+    /// The block number is not below the block count
+    BlockNumberOutOfRange { number: u32, count: u32 },
+    /// A payload that falls outside memory the loader can program
+    Program { address: u32, error: BusError },
+}
+
+impl std::fmt::Display for Uf2Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "block {}: ", self.block)?;
+        match self.kind {
+            Uf2ErrorKind::Truncated => write!(f, "file ends part way through the block"),
+            Uf2ErrorKind::BadMagic => write!(f, "bad magic number"),
+            Uf2ErrorKind::PayloadTooLarge(size) => write!(f, "payload of {} bytes does not fit in a block", size),
+            Uf2ErrorKind::TooManyBlocks(count) => write!(f, "{} blocks are more than fit in flash", count),
+            Uf2ErrorKind::BlockNumberOutOfRange { number, count } => {
+                write!(f, "block number {} is not below the block count of {}", number, count)
+            }
+            Uf2ErrorKind::Program { address, error } => write!(f, "cannot program {:#010x}, {}", address, error),
+        }
+    }
+}
+
+impl std::error::Error for Uf2Error {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Uf2Block {
+    pub flags: u32,
+    pub target_address: u32,
+    pub block_number: u32,
+    pub block_count: u32,
+    /// Only present with FLAG_FAMILY_ID_PRESENT, the word holds the file size otherwise
+    pub family_id: Option<u32>,
+    pub payload: Vec<u8>,
+}
+
+impl Uf2Block {
+    /// Whether the block is meant to be written, rather than skipped as a file container or not for main flash
+    pub fn is_written(&self) -> bool {
+        self.flags & (FLAG_NOT_MAIN_FLASH | FLAG_FILE_CONTAINER) == 0
+    }
+}
+
+/// A block number within the blocks of one family
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct BlockNumber {
+    pub family_id: Option<u32>,
+    pub number: u32,
+}
+
+/// What loading a UF2 file did. Blocks are counted per family, since a file can hold several images that each
+/// number their blocks from zero.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Uf2Report {
+    pub blocks_written: usize,
+    /// Blocks for other families, or flagged as not for main flash
+    pub blocks_skipped: usize,
+    /// Block numbers below a family's block count that never appeared
+    pub missing: Vec<BlockNumber>,
+    /// Block numbers that appeared more than once, the later block wins
+    pub duplicated: Vec<BlockNumber>,
+}
+
+impl Uf2Report {
+    pub fn is_complete(&self) -> bool {
+        self.missing.is_empty() && self.duplicated.is_empty()
+    }
+}
+
+/// Parses a UF2 file into its blocks, checking the magic numbers, payload sizes and block numbers. Block counts
+/// are capped at MAX_BLOCK_COUNT, which keeps the blocks reported missing to a bounded list.
+pub fn parse(bytes: &[u8]) -> Result<Vec<Uf2Block>, Uf2Error> {
+    bytes
+        .chunks(BLOCK_SIZE)
+        .enumerate()
+        .map(|(index, block)| {
+            let error = |kind| Uf2Error { block: index, kind };
+
+            if block.len() != BLOCK_SIZE {
+                return Err(error(Uf2ErrorKind::Truncated));
+            }
+
+            let word = |offset: usize| LittleEndian::read_u32(&block[offset..]);
+            if word(0) != MAGIC_START0 || word(4) != MAGIC_START1 || word(BLOCK_SIZE - 4) != MAGIC_END {
+                return Err(error(Uf2ErrorKind::BadMagic));
+            }
+
+            let flags = word(8);
+            let payload_size = word(16);
+            if payload_size as usize > MAX_PAYLOAD {
+                return Err(error(Uf2ErrorKind::PayloadTooLarge(payload_size)));
+            }
+
+            let (block_number, block_count) = (word(20), word(24));
+            if block_count > MAX_BLOCK_COUNT {
+                return Err(error(Uf2ErrorKind::TooManyBlocks(block_count)));
+            }
+            if block_number >= block_count {
+                return Err(error(Uf2ErrorKind::BlockNumberOutOfRange { number: block_number, count: block_count }));
+            }
+
+            Ok(Uf2Block {
+                flags,
+                target_address: word(12),
+                block_number,
+                block_count,
+                family_id: (flags & FLAG_FAMILY_ID_PRESENT != 0).then(|| word(28)),
+                payload: block[32..32 + payload_size as usize].to_vec(),
+            })
+        })
+        .collect()
+}
+
+/// Picks out the blocks to write, those for main flash in one of the families, along with their index in the
+/// file, and reports on the rest. Blocks without a family ID are always taken.
+///
+/// Partition targets are not supported. The simulator has no partition table to place a block relative to, so
+/// every block is written at its target address as it is, including those for the data family.
+pub fn select<'a>(blocks: &'a [Uf2Block], family_ids: &[u32]) -> (Vec<(usize, &'a Uf2Block)>, Uf2Report) {
+    let mut report = Uf2Report::default();
+    let mut selected = Vec::new();
+    let mut seen: BTreeMap<Option<u32>, (u32, BTreeSet<u32>)> = BTreeMap::new();
+
+    for (index, block) in blocks.iter().enumerate() {
+        let wanted = block.family_id.is_none_or(|family_id| family_ids.contains(&family_id));
+        if !wanted || !block.is_written() {
+            report.blocks_skipped += 1;
+            continue;
+        }
+
+        let (count, numbers) = seen.entry(block.family_id).or_default();
+        *count = (*count).max(block.block_count);
+        if !numbers.insert(block.block_number) {
+            report.duplicated.push(BlockNumber { family_id: block.family_id, number: block.block_number });
+        }
+
+        selected.push((index, block));
+    }
+
+    for (family_id, (count, numbers)) in seen {
+        report.missing.extend(
+            (0..count)
+                .filter(|number| !numbers.contains(number))
+                .map(|number| BlockNumber { family_id, number }),
+        );
+    }
+
+    report.blocks_written = selected.len();
+    (selected, report)
+}
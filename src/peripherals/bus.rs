@@ -0,0 +1,255 @@
+use std::any::Any;
+use std::cell::{Cell, Ref, RefCell};
+
+use crate::peripherals::{Peripheral, Width};
+use crate::{AccessResult, BusError, APB_START_ADDRESS, SIO_START_ADDRESS};
+
+/// A write at this offset from a register flips the bits set in the value
+pub const ATOMIC_XOR: u32 = 0x1000;
+/// A write at this offset from a register sets the bits set in the value
+pub const ATOMIC_SET: u32 = 0x2000;
+/// A write at this offset from a register clears the bits set in the value
+pub const ATOMIC_CLEAR: u32 = 0x3000;
+/// The address space taken by a peripheral with atomic aliases, its registers followed by the three aliases
+pub const ATOMIC_BLOCK_SIZE: u32 = 0x4000;
+
+struct Region {
+    base: u32,
+    size: u32,
+    atomic: bool,
+    /// Behind a cell so a read, which only borrows the bus, can tick it up to now first
+    peripheral: RefCell<Box<dyn Peripheral>>,
+    /// The bus time the peripheral was last ticked up to
+    ticked_to: Cell<u64>,
+}
+
+impl Region {
+    fn end(&self) -> u32 {
+        self.base + if self.atomic { ATOMIC_BLOCK_SIZE } else { self.size }
+    }
+
+    /// Ticks the peripheral up to the time, returning whether it needed it
+    fn catch_up(&self, now: u64) -> bool {
+        let ticked_to = self.ticked_to.get();
+        if now <= ticked_to {
+            return false;
+        }
+
+        self.peripheral.borrow_mut().tick(now - ticked_to);
+        self.ticked_to.set(now);
+        true
+    }
+
+    /// The bus time the peripheral next needs a tick at
+    fn next_tick(&self) -> u64 {
+        self.peripheral
+            .borrow()
+            .next_tick()
+            .map_or(u64::MAX, |cycles| self.ticked_to.get().saturating_add(cycles))
+    }
+}
+
+/// The peripherals on the system bus, each at the base address and size it was registered with. Those on the APB
+/// and AHB segments also answer at the XOR, SET and CLR aliases of their registers, which the bus carries out as a
+/// masked write of the register.
+///
+/// Peripherals are ticked when they ask to be, or are read or written, rather than every cycle, and what they ask
+/// for and the IRQs they raise are only looked at again after a tick or a change from outside, so a bus where nothing
+/// is happening costs a comparison a cycle.
+#[derive(Default)]
+pub struct Bus {
+    /// Ordered by base address
+    regions: Vec<Region>,
+    /// System clock cycles since the bus was made
+    now: u64,
+    /// The earliest time a peripheral needs a tick at
+    next_tick: u64,
+    /// The IRQ lines asserted as of the last look
+    irqs: u64,
+    /// Whether a peripheral may have changed since the last look
+    changed: Cell<bool>,
+    /// Whether the last tick took another look at the peripherals
+    updated: bool,
+}
+
+impl Bus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Maps a peripheral's registers at base..base + size. Peripherals on the APB and AHB segments take the
+    /// 0x4000 bytes from their base, for the atomic aliases, so there they can't be more than 0x1000 bytes.
+    pub fn register(&mut self, base: u32, size: u32, peripheral: impl Peripheral + 'static) {
+        let atomic = has_atomic_aliases(base);
+        assert!(
+            !atomic || size <= ATOMIC_XOR,
+            "peripheral at {:#010x} is too large for its atomic aliases",
+            base
+        );
+
+        let region = Region {
+            base,
+            size,
+            atomic,
+            peripheral: RefCell::new(Box::new(peripheral)),
+            ticked_to: Cell::new(self.now),
+        };
+        assert!(
+            self.regions
+                .iter()
+                .all(|other| region.end() <= other.base || other.end() <= base),
+            "peripheral at {:#010x} overlaps another",
+            base
+        );
+
+        let index = self.regions.partition_point(|other| other.base < base);
+        self.regions.insert(index, region);
+        self.changed.set(true);
+    }
+
+    /// The peripheral registered at the base address, if it is a T, as of its last tick
+    pub fn get<T: Any>(&self, base: u32) -> Option<Ref<'_, T>> {
+        let region = self.regions.iter().find(|region| region.base == base)?;
+        Ref::filter_map(region.peripheral.borrow(), |peripheral| peripheral.as_any().downcast_ref()).ok()
+    }
+
+    /// The peripheral registered at the base address, if it is a T, ticked up to now
+    pub fn get_mut<T: Any>(&mut self, base: u32) -> Option<&mut T> {
+        let region = self.regions.iter_mut().find(|region| region.base == base)?;
+        region.catch_up(self.now);
+        self.changed.set(true);
+        region.peripheral.get_mut().as_any_mut().downcast_mut()
+    }
+
+    /// System clock cycles since the bus was made
+    pub fn now(&self) -> u64 {
+        self.now
+    }
+
+    /// Whether a peripheral answers at the address
+    pub fn contains(&self, address: u32) -> bool {
+        self.region(address).is_some()
+    }
+
+    fn region(&self, address: u32) -> Option<usize> {
+        let index = self.regions.partition_point(|region| region.base <= address).checked_sub(1)?;
+        (address < self.regions[index].end()).then_some(index)
+    }
+
+    /// Splits an address into its region, the atomic alias it is in and the offset of the register. Accesses
+    /// that aren't aligned to their width, or go past the registers, aren't answered.
+    fn decode(&self, address: u32, width: Width) -> AccessResult<(usize, u32, u32)> {
+        let index = self.region(address).ok_or(BusError::Unmapped)?;
+        let region = &self.regions[index];
+
+        let mut offset = address - region.base;
+        let mut alias = 0;
+        if region.atomic {
+            alias = offset & !(ATOMIC_XOR - 1);
+            offset &= ATOMIC_XOR - 1;
+        }
+
+        if !offset.is_multiple_of(width.bytes()) || offset + width.bytes() > region.size {
+            return Err(BusError::Unmapped);
+        }
+
+        Ok((index, alias, offset))
+    }
+
+    /// Reads a register, once the peripheral is ticked up to now. Reads through the atomic aliases read the
+    /// register itself.
+    pub fn read(&self, address: u32, width: Width) -> AccessResult<u32> {
+        let (index, _, offset) = self.decode(address, width)?;
+        let region = &self.regions[index];
+        if region.catch_up(self.now) {
+            self.changed.set(true);
+        }
+
+        region.peripheral.borrow().read(offset, width)
+    }
+
+    pub fn write(&mut self, address: u32, width: Width, value: u32) -> AccessResult<()> {
+        let (index, alias, offset) = self.decode(address, width)?;
+        let region = &mut self.regions[index];
+        region.catch_up(self.now);
+        self.changed.set(true);
+
+        let peripheral = region.peripheral.get_mut();
+        if alias == 0 {
+            return peripheral.write(offset, width, value);
+        }
+
+        // Narrow writes are replicated across the word, so an alias changes the same bits in every byte lane. Only
+        // the bits set in the value are written.
+        let mask = width.replicate(value);
+        let offset = offset & !3;
+        let value = match alias {
+            ATOMIC_XOR => !peripheral.read(offset, Width::Word)?,
+            ATOMIC_SET => u32::MAX,
+            _ => 0,
+        };
+
+        peripheral.write_masked(offset, mask, value)
+    }
+
+    /// Moves the bus on by a number of system clock cycles, ticking the peripherals that are due, and returns the
+    /// NVIC IRQ lines asserted
+    pub fn tick(&mut self, cycles: u64) -> u64 {
+        self.now += cycles;
+        self.updated = self.changed.get() || self.now >= self.next_tick;
+        if self.updated {
+            self.update();
+        }
+
+        self.irqs
+    }
+
+    /// Whether the last tick took another look at the peripherals, without which nothing about them has changed
+    pub fn updated(&self) -> bool {
+        self.updated
+    }
+
+    /// Ticks the peripherals that are due, then takes another look at the ticks and IRQs they ask for
+    fn update(&mut self) {
+        self.next_tick = u64::MAX;
+        self.irqs = 0;
+
+        for region in &mut self.regions {
+            if region.next_tick() <= self.now {
+                region.catch_up(self.now);
+            }
+            self.next_tick = self.next_tick.min(region.next_tick());
+            self.irqs |= region.peripheral.get_mut().irqs();
+        }
+
+        self.changed.set(false);
+    }
+
+    /// The NVIC IRQ lines any peripheral is asserting, by bit
+    pub fn irqs(&self) -> u64 {
+        self.regions.iter().fold(0, |irqs, region| irqs | region.peripheral.borrow().irqs())
+    }
+
+    /// Puts the peripheral registered at the base address back to its reset values
+    pub fn reset_peripheral(&mut self, base: u32) {
+        if let Some(region) = self.regions.iter_mut().find(|region| region.base == base) {
+            region.ticked_to.set(self.now);
+            region.peripheral.get_mut().reset();
+            self.changed.set(true);
+        }
+    }
+
+    /// Puts every peripheral back to its reset values
+    pub fn reset(&mut self) {
+        for region in &mut self.regions {
+            region.ticked_to.set(self.now);
+            region.peripheral.get_mut().reset();
+        }
+        self.changed.set(true);
+    }
+}
+
+/// Whether a peripheral at the base is on the APB or AHB segments, which have atomic register aliases
+fn has_atomic_aliases(base: u32) -> bool {
+    (APB_START_ADDRESS..SIO_START_ADDRESS).contains(&base)
+}
@@ -0,0 +1,98 @@
+use std::cell::Ref;
+use std::time::Duration;
+
+use crate::peripherals::clocks::{Clock, Clocks, Sources, CLOCKS_BASE, LPOSC_HZ};
+use crate::peripherals::pll::{Pll, PLL_SYS_BASE, PLL_USB_BASE};
+use crate::peripherals::resets::{Resets, RESETS_BASE};
+use crate::peripherals::rosc::{Rosc, ROSC_BASE};
+use crate::peripherals::ticks::{Ticks, TICKS_BASE};
+use crate::peripherals::xosc::{Xosc, XOSC_BASE};
+use crate::RP2350Memory;
+
+/// The oscillators and PLLs through the clock generators, and the time the simulation has run for
+impl RP2350Memory {
+    pub fn resets(&mut self) -> &mut Resets {
+        self.peripherals.get_mut(RESETS_BASE).expect("RESETS is always on the bus")
+    }
+
+    pub fn clocks(&mut self) -> &mut Clocks {
+        self.peripherals.get_mut(CLOCKS_BASE).expect("CLOCKS is always on the bus")
+    }
+
+    pub fn xosc(&mut self) -> &mut Xosc {
+        self.peripherals.get_mut(XOSC_BASE).expect("The XOSC is always on the bus")
+    }
+
+    pub fn rosc(&mut self) -> &mut Rosc {
+        self.peripherals.get_mut(ROSC_BASE).expect("The ROSC is always on the bus")
+    }
+
+    pub fn pll_sys(&mut self) -> &mut Pll {
+        self.peripherals.get_mut(PLL_SYS_BASE).expect("PLL_SYS is always on the bus")
+    }
+
+    pub fn pll_usb(&mut self) -> &mut Pll {
+        self.peripherals.get_mut(PLL_USB_BASE).expect("PLL_USB is always on the bus")
+    }
+
+    pub fn ticks(&mut self) -> &mut Ticks {
+        self.peripherals.get_mut(TICKS_BASE).expect("TICKS is always on the bus")
+    }
+
+    fn clock_generators(&self) -> Ref<'_, Clocks> {
+        self.peripherals.get(CLOCKS_BASE).expect("CLOCKS is always on the bus")
+    }
+
+    /// The frequency of a clock in Hz, 0 while it is stopped
+    pub fn frequency(&self, clock: Clock) -> u32 {
+        self.clock_generators().frequency(clock)
+    }
+
+    /// The frequency the core and the bus run at
+    pub fn clk_sys(&self) -> u32 {
+        self.frequency(Clock::Sys)
+    }
+
+    /// The frequency the UARTs and SPIs run at
+    pub fn clk_peri(&self) -> u32 {
+        self.frequency(Clock::Peri)
+    }
+
+    /// The simulated time since power on, from the cycles run at the clk_sys frequency of the time
+    pub fn elapsed(&self) -> Duration {
+        self.clock_generators().elapsed(self.peripherals.now())
+    }
+
+    /// Works the clock frequencies out again after anything they depend on changed, then passes clk_sys on to the
+    /// blocks that time themselves in it
+    pub fn update_clocks(&mut self) {
+        // The PLLs run from the crystal
+        let xosc = self.xosc().frequency();
+        let clk_sys = self.clk_sys();
+        self.pll_sys().set_clocks(xosc, clk_sys);
+        self.pll_usb().set_clocks(xosc, clk_sys);
+
+        let sources = Sources {
+            xosc,
+            rosc: self.rosc().frequency(),
+            lposc: LPOSC_HZ,
+            pll_sys: self.pll_sys().frequency(),
+            pll_usb: self.pll_usb().frequency(),
+            gpin0: 0,
+            gpin1: 0,
+        };
+        let now = self.peripherals.now();
+        self.clocks().update(sources, now);
+
+        let clk_sys = self.clk_sys();
+        self.xosc().set_clk_sys(clk_sys);
+        self.rosc().set_clk_sys(clk_sys);
+        self.pll_sys().set_clocks(xosc, clk_sys);
+        self.pll_usb().set_clocks(xosc, clk_sys);
+        let clk_ref = self.frequency(Clock::Ref);
+        self.ticks().set_clocks(clk_ref, clk_sys);
+        let clk_peri = self.clk_peri();
+        self.uart0().set_clocks(clk_peri, clk_sys);
+        self.uart1().set_clocks(clk_peri, clk_sys);
+    }
+}
@@ -0,0 +1,588 @@
+use std::any::Any;
+use std::time::Duration;
+
+use crate::peripherals::rosc::ROSC_HZ;
+use crate::peripherals::{Peripheral, Width};
+use crate::{AccessResult, BusError};
+
+pub const CLOCKS_BASE: u32 = 0x40010000;
+pub const CLOCKS_SIZE: u32 = 0xd4;
+
+/// Each clock has a CTRL, DIV and SELECTED register in turn, at 0xc times its number
+pub const CLK_CTRL: u32 = 0x0;
+pub const CLK_DIV: u32 = 0x4;
+pub const CLK_SELECTED: u32 = 0x8;
+const CLK_REGISTERS: u32 = 0xc;
+
+pub const CLOCKS_DFTCLK_XOSC_CTRL: u32 = 0x78;
+pub const CLOCKS_DFTCLK_ROSC_CTRL: u32 = 0x7c;
+pub const CLOCKS_DFTCLK_LPOSC_CTRL: u32 = 0x80;
+pub const CLOCKS_CLK_SYS_RESUS_CTRL: u32 = 0x84;
+pub const CLOCKS_CLK_SYS_RESUS_STATUS: u32 = 0x88;
+pub const CLOCKS_FC0_REF_KHZ: u32 = 0x8c;
+pub const CLOCKS_FC0_MIN_KHZ: u32 = 0x90;
+pub const CLOCKS_FC0_MAX_KHZ: u32 = 0x94;
+pub const CLOCKS_FC0_DELAY: u32 = 0x98;
+pub const CLOCKS_FC0_INTERVAL: u32 = 0x9c;
+pub const CLOCKS_FC0_SRC: u32 = 0xa0;
+pub const CLOCKS_FC0_STATUS: u32 = 0xa4;
+pub const CLOCKS_FC0_RESULT: u32 = 0xa8;
+pub const CLOCKS_WAKE_EN0: u32 = 0xac;
+pub const CLOCKS_WAKE_EN1: u32 = 0xb0;
+pub const CLOCKS_SLEEP_EN0: u32 = 0xb4;
+pub const CLOCKS_SLEEP_EN1: u32 = 0xb8;
+pub const CLOCKS_ENABLED0: u32 = 0xbc;
+pub const CLOCKS_ENABLED1: u32 = 0xc0;
+pub const CLOCKS_INTR: u32 = 0xc4;
+pub const CLOCKS_INTE: u32 = 0xc8;
+pub const CLOCKS_INTF: u32 = 0xcc;
+pub const CLOCKS_INTS: u32 = 0xd0;
+
+/// The glitchless source of clk_ref and clk_sys, which SELECTED shows one hot
+pub const CLK_CTRL_SRC_MASK: u32 = 0x3;
+pub const CLK_CTRL_AUXSRC_SHIFT: u32 = 5;
+/// Four bits wide for the GPOUTs, three for the rest
+pub const CLK_CTRL_AUXSRC_MASK: u32 = 0xf << CLK_CTRL_AUXSRC_SHIFT;
+pub const CLK_CTRL_KILL: u32 = 1 << 10;
+pub const CLK_CTRL_ENABLE: u32 = 1 << 11;
+/// Read only, whether the clock is running
+pub const CLK_CTRL_ENABLED: u32 = 1 << 28;
+/// The integer part of a divider, the fraction is in the bits below
+pub const CLK_DIV_INT_SHIFT: u32 = 16;
+
+pub const CLK_REF_SRC_ROSC: u32 = 0;
+pub const CLK_REF_SRC_AUX: u32 = 1;
+pub const CLK_REF_SRC_XOSC: u32 = 2;
+pub const CLK_REF_SRC_LPOSC: u32 = 3;
+pub const CLK_SYS_SRC_REF: u32 = 0;
+pub const CLK_SYS_SRC_AUX: u32 = 1;
+pub const CLK_SYS_AUXSRC_PLL_SYS: u32 = 0;
+pub const CLK_SYS_AUXSRC_PLL_USB: u32 = 1;
+pub const CLK_SYS_AUXSRC_ROSC: u32 = 2;
+pub const CLK_SYS_AUXSRC_XOSC: u32 = 3;
+pub const CLK_PERI_AUXSRC_CLK_SYS: u32 = 0;
+pub const CLK_PERI_AUXSRC_PLL_SYS: u32 = 1;
+pub const CLK_PERI_AUXSRC_PLL_USB: u32 = 2;
+pub const CLK_PERI_AUXSRC_XOSC: u32 = 4;
+
+pub const FC0_STATUS_PASS: u32 = 1 << 0;
+pub const FC0_STATUS_DONE: u32 = 1 << 4;
+pub const FC0_STATUS_FAIL: u32 = 1 << 16;
+pub const FC0_STATUS_SLOW: u32 = 1 << 20;
+pub const FC0_STATUS_FAST: u32 = 1 << 24;
+pub const FC0_STATUS_DIED: u32 = 1 << 28;
+/// FC0_RESULT holds the frequency in kHz above five bits of fraction
+pub const FC0_RESULT_KHZ_SHIFT: u32 = 5;
+
+/// The low power oscillator, which runs at about 32 kHz and is never stopped
+pub const LPOSC_HZ: u32 = 32_768;
+
+/// The clock generators, by their number in the register map
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Clock {
+    GpOut0 = 0,
+    GpOut1,
+    GpOut2,
+    GpOut3,
+    Ref,
+    Sys,
+    Peri,
+    Hstx,
+    Usb,
+    Adc,
+}
+
+const CLOCK_COUNT: usize = 10;
+
+const CLOCKS: [Clock; CLOCK_COUNT] = [
+    Clock::GpOut0,
+    Clock::GpOut1,
+    Clock::GpOut2,
+    Clock::GpOut3,
+    Clock::Ref,
+    Clock::Sys,
+    Clock::Peri,
+    Clock::Hstx,
+    Clock::Usb,
+    Clock::Adc,
+];
+
+/// The order the clocks are worked out in, so each comes after the clocks it can run from
+const EVALUATION_ORDER: [Clock; CLOCK_COUNT] = [
+    Clock::Ref,
+    Clock::Sys,
+    Clock::Peri,
+    Clock::Hstx,
+    Clock::Usb,
+    Clock::Adc,
+    Clock::GpOut0,
+    Clock::GpOut1,
+    Clock::GpOut2,
+    Clock::GpOut3,
+];
+
+/// The frequencies of the oscillators and PLLs the clocks run from, in Hz, 0 for one that isn't running
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Sources {
+    pub xosc: u32,
+    pub rosc: u32,
+    pub lposc: u32,
+    pub pll_sys: u32,
+    pub pll_usb: u32,
+    /// The clock inputs on GPIO 20 and 22
+    pub gpin0: u32,
+    pub gpin1: u32,
+}
+
+/// Something a clock, or the frequency counter, can run from
+#[derive(Debug, Clone, Copy)]
+enum Input {
+    Xosc,
+    Rosc,
+    Lposc,
+    PllSys,
+    PllUsb,
+    Gpin0,
+    Gpin1,
+    Clock(Clock),
+    /// The auxiliary mux of clk_ref or clk_sys
+    Aux,
+    /// A source that isn't modelled
+    Null,
+}
+
+/// How a clock generator is built
+struct Generator {
+    /// The writable bits of CTRL
+    ctrl_mask: u32,
+    /// The bits of DIV there are, the integer part above the fraction
+    div_mask: u32,
+    /// The glitchless mux, for clk_ref and clk_sys
+    src: &'static [Input],
+    /// The auxiliary mux, by AUXSRC
+    aux: &'static [Input],
+    /// Whether it has ENABLE and KILL, rather than always running
+    gated: bool,
+}
+
+const GPOUT: Generator = Generator {
+    ctrl_mask: 0x00131de0,
+    div_mask: u32::MAX,
+    src: &[],
+    aux: &[
+        Input::PllSys,
+        Input::Gpin0,
+        Input::Gpin1,
+        Input::PllUsb,
+        Input::Null,
+        Input::Rosc,
+        Input::Xosc,
+        Input::Lposc,
+        Input::Clock(Clock::Sys),
+        Input::Clock(Clock::Usb),
+        Input::Clock(Clock::Adc),
+        Input::Clock(Clock::Ref),
+        Input::Clock(Clock::Peri),
+        Input::Clock(Clock::Hstx),
+        Input::Null,
+    ],
+    gated: true,
+};
+
+const GENERATORS: [Generator; CLOCK_COUNT] = [
+    GPOUT,
+    GPOUT,
+    GPOUT,
+    GPOUT,
+    Generator {
+        ctrl_mask: 0x63,
+        div_mask: 0x00ff0000,
+        src: &[Input::Rosc, Input::Aux, Input::Xosc, Input::Lposc],
+        aux: &[Input::PllUsb, Input::Gpin0, Input::Gpin1],
+        gated: false,
+    },
+    Generator {
+        ctrl_mask: 0xe1,
+        div_mask: u32::MAX,
+        src: &[Input::Clock(Clock::Ref), Input::Aux],
+        aux: &[Input::PllSys, Input::PllUsb, Input::Rosc, Input::Xosc, Input::Gpin0, Input::Gpin1],
+        gated: false,
+    },
+    Generator {
+        ctrl_mask: 0xce0,
+        div_mask: 0x00030000,
+        src: &[],
+        aux: &[
+            Input::Clock(Clock::Sys),
+            Input::PllSys,
+            Input::PllUsb,
+            Input::Rosc,
+            Input::Xosc,
+            Input::Gpin0,
+            Input::Gpin1,
+        ],
+        gated: true,
+    },
+    Generator {
+        ctrl_mask: 0x00130ce0,
+        div_mask: 0x00030000,
+        src: &[],
+        aux: &[Input::Clock(Clock::Sys), Input::PllSys, Input::PllUsb, Input::Gpin0, Input::Gpin1],
+        gated: true,
+    },
+    Generator {
+        ctrl_mask: 0x00130ce0,
+        div_mask: 0x000f0000,
+        src: &[],
+        aux: &[Input::PllUsb, Input::PllSys, Input::Rosc, Input::Xosc, Input::Gpin0, Input::Gpin1],
+        gated: true,
+    },
+    Generator {
+        ctrl_mask: 0x00130ce0,
+        div_mask: 0x000f0000,
+        src: &[],
+        aux: &[Input::PllUsb, Input::PllSys, Input::Rosc, Input::Xosc, Input::Gpin0, Input::Gpin1],
+        gated: true,
+    },
+];
+
+/// What the frequency counter measures, by FC0_SRC
+const FC0_SOURCES: [Input; 17] = [
+    Input::Null,
+    Input::PllSys,
+    Input::PllUsb,
+    Input::Rosc,
+    Input::Rosc,
+    Input::Xosc,
+    Input::Gpin0,
+    Input::Gpin1,
+    Input::Clock(Clock::Ref),
+    Input::Clock(Clock::Sys),
+    Input::Clock(Clock::Peri),
+    Input::Clock(Clock::Usb),
+    Input::Clock(Clock::Adc),
+    Input::Clock(Clock::Hstx),
+    Input::Lposc,
+    Input::Null,
+    Input::Null,
+];
+
+const PICOSECONDS: u128 = 1_000_000_000_000;
+
+/// The clock generators. Each runs from a source its CTRL picks, through its divider, and the frequencies are
+/// worked out again whenever a source or a setting changes. A switch of source takes effect at once, so SELECTED
+/// follows SRC straight away. The frequency counter measures as soon as FC0_SRC is written.
+///
+/// clk_sys is what the core and the bus run at, so the cycles the simulator counts become time at its frequency.
+#[derive(Debug, Clone)]
+pub struct Clocks {
+    ctrl: [u32; CLOCK_COUNT],
+    div: [u32; CLOCK_COUNT],
+    dftclk: [u32; 3],
+    resus_ctrl: u32,
+    fc0_ref_khz: u32,
+    fc0_min_khz: u32,
+    fc0_max_khz: u32,
+    fc0_delay: u32,
+    fc0_interval: u32,
+    fc0_src: u32,
+    fc0_status: u32,
+    fc0_result: u32,
+    wake_en: [u32; 2],
+    sleep_en: [u32; 2],
+    inte: u32,
+    intf: u32,
+    sources: Sources,
+    frequencies: [u32; CLOCK_COUNT],
+    /// Simulated picoseconds up to the bus time clk_sys last changed at
+    elapsed: u128,
+    since: u64,
+}
+
+impl Default for Clocks {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clocks {
+    pub fn new() -> Self {
+        let mut clocks = Self {
+            ctrl: [0; CLOCK_COUNT],
+            div: [1 << CLK_DIV_INT_SHIFT; CLOCK_COUNT],
+            dftclk: [0; 3],
+            resus_ctrl: 0xff,
+            fc0_ref_khz: 0,
+            fc0_min_khz: 0,
+            fc0_max_khz: 0x01ffffff,
+            fc0_delay: 1,
+            fc0_interval: 8,
+            fc0_src: 0,
+            fc0_status: 0,
+            fc0_result: 0,
+            wake_en: [u32::MAX, 0x7fffffff],
+            sleep_en: [u32::MAX, 0x7fffffff],
+            inte: 0,
+            intf: 0,
+            sources: Sources::default(),
+            frequencies: [0; CLOCK_COUNT],
+            elapsed: 0,
+            since: 0,
+        };
+
+        // Out of reset everything runs from the ROSC
+        let sources = Sources {
+            rosc: ROSC_HZ,
+            lposc: LPOSC_HZ,
+            ..Sources::default()
+        };
+        clocks.update(sources, 0);
+        clocks
+    }
+
+    /// The frequency of a clock in Hz, 0 while it is stopped
+    pub fn frequency(&self, clock: Clock) -> u32 {
+        self.frequencies[clock as usize]
+    }
+
+    /// The simulated time up to a bus time, each cycle taking as long as clk_sys was running at
+    pub fn elapsed(&self, now: u64) -> Duration {
+        let picoseconds = self.picoseconds(now);
+        Duration::new((picoseconds / PICOSECONDS) as u64, (picoseconds % PICOSECONDS / 1000) as u32)
+    }
+
+    fn picoseconds(&self, now: u64) -> u128 {
+        match self.frequency(Clock::Sys) {
+            0 => self.elapsed,
+            hz => self.elapsed + (now - self.since) as u128 * PICOSECONDS / hz as u128,
+        }
+    }
+
+    /// Works the clock frequencies out again from the sources, at the bus time
+    pub fn update(&mut self, sources: Sources, now: u64) {
+        self.elapsed = self.picoseconds(now);
+        self.since = now;
+        self.sources = sources;
+
+        for clock in EVALUATION_ORDER {
+            self.frequencies[clock as usize] = self.generate(clock);
+        }
+    }
+
+    fn generate(&self, clock: Clock) -> u32 {
+        let index = clock as usize;
+        let (generator, ctrl) = (&GENERATORS[index], self.ctrl[index]);
+        if generator.gated && !self.is_running(clock) {
+            return 0;
+        }
+
+        let aux = generator.aux.get(((ctrl & CLK_CTRL_AUXSRC_MASK) >> CLK_CTRL_AUXSRC_SHIFT) as usize).copied().unwrap_or(Input::Null);
+        let input = match generator.src.get((ctrl & CLK_CTRL_SRC_MASK) as usize) {
+            Some(Input::Aux) => aux,
+            Some(&input) => input,
+            None => aux,
+        };
+
+        // An integer part of 0 is the largest divisor there is room for
+        let mask = generator.div_mask;
+        let mut div = self.div[index] & mask;
+        if div >> CLK_DIV_INT_SHIFT == 0 {
+            div |= (mask >> CLK_DIV_INT_SHIFT).wrapping_add(1) << CLK_DIV_INT_SHIFT;
+        }
+
+        (self.input(input) as u64 * (1 << CLK_DIV_INT_SHIFT) / div as u64) as u32
+    }
+
+    fn input(&self, input: Input) -> u32 {
+        match input {
+            Input::Xosc => self.sources.xosc,
+            Input::Rosc => self.sources.rosc,
+            Input::Lposc => self.sources.lposc,
+            Input::PllSys => self.sources.pll_sys,
+            Input::PllUsb => self.sources.pll_usb,
+            Input::Gpin0 => self.sources.gpin0,
+            Input::Gpin1 => self.sources.gpin1,
+            Input::Clock(clock) => self.frequency(clock),
+            Input::Aux | Input::Null => 0,
+        }
+    }
+
+    fn is_running(&self, clock: Clock) -> bool {
+        let ctrl = self.ctrl[clock as usize];
+        !GENERATORS[clock as usize].gated || ctrl & (CLK_CTRL_ENABLE | CLK_CTRL_KILL) == CLK_CTRL_ENABLE
+    }
+
+    /// Measures the source FC0_SRC picks
+    fn count_frequency(&mut self) {
+        let input = FC0_SOURCES.get(self.fc0_src as usize).copied().unwrap_or(Input::Null);
+        let hz = self.input(input) as u64;
+        let khz = (hz / 1000) as u32;
+
+        self.fc0_result = ((hz * (1 << FC0_RESULT_KHZ_SHIFT) / 1000) as u32) & 0x3fffffff;
+        self.fc0_status = FC0_STATUS_DONE
+            | match khz {
+                0 => FC0_STATUS_DIED | FC0_STATUS_FAIL,
+                khz if khz < self.fc0_min_khz => FC0_STATUS_SLOW | FC0_STATUS_FAIL,
+                khz if khz > self.fc0_max_khz => FC0_STATUS_FAST | FC0_STATUS_FAIL,
+                _ => FC0_STATUS_PASS,
+            };
+    }
+
+    fn register(&self, offset: u32) -> AccessResult<u32> {
+        let value = match offset {
+            0..CLOCKS_DFTCLK_XOSC_CTRL => {
+                let clock = (offset / CLK_REGISTERS) as usize;
+                let generator = &GENERATORS[clock];
+                match offset % CLK_REGISTERS {
+                    CLK_CTRL if generator.gated && self.is_running(CLOCKS[clock]) => self.ctrl[clock] | CLK_CTRL_ENABLED,
+                    CLK_CTRL => self.ctrl[clock],
+                    CLK_DIV => self.div[clock],
+                    // Clocks with no glitchless mux always read as their one source selected
+                    _ if generator.src.is_empty() => 1,
+                    _ => 1 << (self.ctrl[clock] & CLK_CTRL_SRC_MASK),
+                }
+            }
+            CLOCKS_DFTCLK_XOSC_CTRL..=CLOCKS_DFTCLK_LPOSC_CTRL => {
+                self.dftclk[((offset - CLOCKS_DFTCLK_XOSC_CTRL) / 4) as usize]
+            }
+            CLOCKS_CLK_SYS_RESUS_CTRL => self.resus_ctrl,
+            CLOCKS_CLK_SYS_RESUS_STATUS => 0,
+            CLOCKS_FC0_REF_KHZ => self.fc0_ref_khz,
+            CLOCKS_FC0_MIN_KHZ => self.fc0_min_khz,
+            CLOCKS_FC0_MAX_KHZ => self.fc0_max_khz,
+            CLOCKS_FC0_DELAY => self.fc0_delay,
+            CLOCKS_FC0_INTERVAL => self.fc0_interval,
+            CLOCKS_FC0_SRC => self.fc0_src,
+            CLOCKS_FC0_STATUS => self.fc0_status,
+            CLOCKS_FC0_RESULT => self.fc0_result,
+            CLOCKS_WAKE_EN0 | CLOCKS_WAKE_EN1 => self.wake_en[((offset - CLOCKS_WAKE_EN0) / 4) as usize],
+            CLOCKS_SLEEP_EN0 | CLOCKS_SLEEP_EN1 => self.sleep_en[((offset - CLOCKS_SLEEP_EN0) / 4) as usize],
+            // The core never sleeps, so the clocks enabled are those enabled while awake
+            CLOCKS_ENABLED0 | CLOCKS_ENABLED1 => self.wake_en[((offset - CLOCKS_ENABLED0) / 4) as usize],
+            // The clk_sys resus never fires
+            CLOCKS_INTR => 0,
+            CLOCKS_INTE => self.inte,
+            CLOCKS_INTF => self.intf,
+            CLOCKS_INTS => self.intf & self.inte,
+            _ => return Err(BusError::Unmapped),
+        };
+
+        Ok(value)
+    }
+}
+
+impl Peripheral for Clocks {
+    fn read(&self, offset: u32, width: Width) -> AccessResult<u32> {
+        Ok(width.lane(self.register(offset & !3)?, offset))
+    }
+
+    fn write(&mut self, offset: u32, width: Width, value: u32) -> AccessResult<()> {
+        let value = width.replicate(value);
+        match offset & !3 {
+            offset @ 0..CLOCKS_DFTCLK_XOSC_CTRL => {
+                let clock = (offset / CLK_REGISTERS) as usize;
+                let generator = &GENERATORS[clock];
+                match offset % CLK_REGISTERS {
+                    CLK_CTRL => self.ctrl[clock] = value & generator.ctrl_mask,
+                    CLK_DIV => self.div[clock] = value & generator.div_mask,
+                    // SELECTED is read only
+                    _ => {}
+                }
+            }
+            offset @ CLOCKS_DFTCLK_XOSC_CTRL..=CLOCKS_DFTCLK_LPOSC_CTRL => {
+                self.dftclk[((offset - CLOCKS_DFTCLK_XOSC_CTRL) / 4) as usize] = value & 0x3
+            }
+            CLOCKS_CLK_SYS_RESUS_CTRL => self.resus_ctrl = value & 0x000111ff,
+            CLOCKS_FC0_REF_KHZ => self.fc0_ref_khz = value & 0xfffff,
+            CLOCKS_FC0_MIN_KHZ => self.fc0_min_khz = value & 0x1ffffff,
+            CLOCKS_FC0_MAX_KHZ => self.fc0_max_khz = value & 0x1ffffff,
+            CLOCKS_FC0_DELAY => self.fc0_delay = value & 0x7,
+            CLOCKS_FC0_INTERVAL => self.fc0_interval = value & 0xf,
+            CLOCKS_FC0_SRC => {
+                self.fc0_src = value & 0xff;
+                self.count_frequency();
+            }
+            offset @ (CLOCKS_WAKE_EN0 | CLOCKS_WAKE_EN1) => {
+                self.wake_en[((offset - CLOCKS_WAKE_EN0) / 4) as usize] = value
+            }
+            offset @ (CLOCKS_SLEEP_EN0 | CLOCKS_SLEEP_EN1) => {
+                self.sleep_en[((offset - CLOCKS_SLEEP_EN0) / 4) as usize] = value
+            }
+            CLOCKS_INTE => self.inte = value & 1,
+            CLOCKS_INTF => self.intf = value & 1,
+            offset => {
+                // Writes to the read only registers are ignored
+                self.register(offset)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The time kept so far and the frequencies it is counted at carry on, as the clocks are worked out again
+    /// straight after
+    fn reset(&mut self) {
+        *self = Self {
+            sources: self.sources,
+            frequencies: self.frequencies,
+            elapsed: self.elapsed,
+            since: self.since,
+            ..Self::new()
+        };
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// Counts the cycles of a clock in system clock cycles, carrying part cycles over
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ClockDomain {
+    hz: u32,
+    clk_sys_hz: u32,
+    /// The part of a cycle counted so far, in 1 / clk_sys_hz of a cycle
+    remainder: u128,
+}
+
+impl ClockDomain {
+    /// Sets the frequencies to convert between, starting the count again if either changed
+    pub fn set(&mut self, hz: u32, clk_sys_hz: u32) {
+        if (hz, clk_sys_hz) != (self.hz, self.clk_sys_hz) {
+            *self = Self {
+                hz,
+                clk_sys_hz,
+                remainder: 0,
+            };
+        }
+    }
+
+    pub fn hz(&self) -> u32 {
+        self.hz
+    }
+
+    /// The whole cycles of the clock in a number of system clock cycles
+    pub fn count(&mut self, sys_cycles: u64) -> u64 {
+        if self.clk_sys_hz == 0 {
+            return 0;
+        }
+
+        let total = sys_cycles as u128 * self.hz as u128 + self.remainder;
+        self.remainder = total % self.clk_sys_hz as u128;
+        (total / self.clk_sys_hz as u128) as u64
+    }
+
+    /// The system clock cycles until the clock has counted a number of cycles, None if it is stopped
+    pub fn until(&self, cycles: u64) -> Option<u64> {
+        if self.hz == 0 || self.clk_sys_hz == 0 {
+            return None;
+        }
+
+        let needed = (cycles as u128 * self.clk_sys_hz as u128).saturating_sub(self.remainder);
+        Some(needed.div_ceil(self.hz as u128).max(1) as u64)
+    }
+}
@@ -0,0 +1,78 @@
+use crate::peripherals::io_bank0::{IoBank0, IO_BANK0_BASE};
+use crate::peripherals::pads_bank0::{PadsBank0, PADS_BANK0_BASE};
+use crate::peripherals::sio::GPIO_COUNT;
+use crate::RP2350Memory;
+
+/// The bank 0 pins, in the SIO register bits
+const BANK0_PINS: u64 = (1 << GPIO_COUNT) - 1;
+
+/// What the chip drives onto the bank 0 pins, kept to find the changes to pass on to the host
+#[derive(Debug, Default)]
+pub struct PinOutputs {
+    driving: u64,
+    levels: u64,
+    /// The pins whose output changed since the host last took them, with the level the chip now drives them to
+    changes: Vec<(u32, Option<bool>)>,
+}
+
+impl PinOutputs {
+    fn record(&mut self, driving: u64, levels: u64) {
+        let levels = levels & driving;
+        let changed = (driving ^ self.driving) | (levels ^ self.levels);
+
+        for pin in (0..GPIO_COUNT).filter(|pin| changed >> pin & 1 != 0) {
+            let output = (driving >> pin & 1 != 0).then_some(levels >> pin & 1 != 0);
+            self.changes.push((pin, output));
+        }
+
+        self.driving = driving;
+        self.levels = levels;
+    }
+}
+
+/// The path from the SIO through IO_BANK0 and PADS_BANK0 to the pins and back
+impl RP2350Memory {
+    pub fn io_bank0(&mut self) -> &mut IoBank0 {
+        self.peripherals.get_mut(IO_BANK0_BASE).expect("IO_BANK0 is always on the bus")
+    }
+
+    pub fn pads_bank0(&mut self) -> &mut PadsBank0 {
+        self.peripherals.get_mut(PADS_BANK0_BASE).expect("PADS_BANK0 is always on the bus")
+    }
+
+    /// Drives a bank 0 pin from outside the chip to a level, or lets it go with None
+    pub fn drive_pin(&mut self, pin: u32, level: Option<bool>) {
+        self.pads_bank0().drive(pin, level);
+        self.update_pins();
+    }
+
+    /// Holds a bank 0 pin at a voltage from outside the chip
+    pub fn drive_pin_analog(&mut self, pin: u32, volts: f32) {
+        self.pads_bank0().drive_analog(pin, volts);
+        self.update_pins();
+    }
+
+    /// Takes the changes to what the chip drives on the pins since they were last taken, each as the pin and the
+    /// level it is now driven to, or None where the chip stopped driving it
+    pub fn take_output_changes(&mut self) -> Vec<(u32, Option<bool>)> {
+        std::mem::take(&mut self.pin_outputs.changes)
+    }
+
+    pub fn has_output_changes(&self) -> bool {
+        !self.pin_outputs.changes.is_empty()
+    }
+
+    /// Carries the SIO outputs out to the pins and the pin levels back in, after anything on the way changed
+    pub fn update_pins(&mut self) {
+        let sio = self.sio();
+        let (out, oe) = (sio.out(), sio.oe());
+        let (out, oe) = self.io_bank0().to_pads(out, oe);
+        let pads = self.pads_bank0();
+        let from_pads = pads.update(out, oe);
+        let (driving, levels) = (pads.driving(), pads.levels());
+        self.pin_outputs.record(driving, levels);
+
+        let inputs = self.io_bank0().from_pads(from_pads);
+        self.sio().set_inputs(BANK0_PINS, inputs);
+    }
+}
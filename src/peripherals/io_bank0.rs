@@ -0,0 +1,326 @@
+use std::any::Any;
+
+use crate::peripherals::sio::GPIO_COUNT;
+use crate::peripherals::{read_modify_write, Peripheral, Width};
+use crate::{AccessResult, BusError};
+
+pub const IO_BANK0_BASE: u32 = 0x40028000;
+pub const IO_BANK0_SIZE: u32 = 0x320;
+
+/// GPIOn_STATUS is at 8n, with GPIOn_CTRL after it
+pub const GPIO_STATUS: u32 = 0x000;
+pub const GPIO_CTRL: u32 = 0x004;
+pub const IRQSUMMARY_PROC0_SECURE0: u32 = 0x200;
+pub const IRQSUMMARY_PROC0_SECURE1: u32 = 0x204;
+/// INTR0 to INTR5 hold 8 GPIOs each, 4 bits a GPIO. The interrupt enable, force and status registers of each
+/// destination follow the same layout.
+pub const INTR0: u32 = 0x230;
+pub const PROC0_INTE0: u32 = 0x248;
+pub const PROC0_INTF0: u32 = 0x260;
+pub const PROC0_INTS0: u32 = 0x278;
+pub const PROC1_INTE0: u32 = 0x290;
+pub const DORMANT_WAKE_INTE0: u32 = 0x2d8;
+
+pub const GPIO_CTRL_FUNCSEL: u32 = 0x1f;
+pub const GPIO_CTRL_OUTOVER_SHIFT: u32 = 12;
+pub const GPIO_CTRL_OEOVER_SHIFT: u32 = 14;
+pub const GPIO_CTRL_INOVER_SHIFT: u32 = 16;
+pub const GPIO_CTRL_IRQOVER_SHIFT: u32 = 28;
+pub const GPIO_STATUS_OUTTOPAD: u32 = 1 << 9;
+pub const GPIO_STATUS_OETOPAD: u32 = 1 << 13;
+pub const GPIO_STATUS_INFROMPAD: u32 = 1 << 17;
+pub const GPIO_STATUS_IRQTOPROC: u32 = 1 << 26;
+
+/// The interrupts of a GPIO in its 4 bits of INTR
+pub const INT_LEVEL_LOW: u32 = 1 << 0;
+pub const INT_LEVEL_HIGH: u32 = 1 << 1;
+pub const INT_EDGE_LOW: u32 = 1 << 2;
+pub const INT_EDGE_HIGH: u32 = 1 << 3;
+
+/// The NVIC line the GPIO interrupts of core 0 are on
+pub const IO_IRQ_BANK0: u8 = 21;
+
+const CTRL_WRITABLE: u32 = 0x3003f01f;
+const INT_REGISTERS: usize = (GPIO_COUNT / 8) as usize;
+
+/// What a pin is connected to, from GPIOn_CTRL.FUNCSEL
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Function {
+    Hstx = 0,
+    Spi = 1,
+    Uart = 2,
+    I2c = 3,
+    Pwm = 4,
+    Sio = 5,
+    Pio0 = 6,
+    Pio1 = 7,
+    Pio2 = 8,
+    Gpck = 9,
+    Usb = 10,
+    UartAux = 11,
+    Null = 0x1f,
+}
+
+impl Function {
+    /// Function selects that don't name a function leave the pin unconnected
+    pub fn from_funcsel(funcsel: u32) -> Function {
+        match funcsel & GPIO_CTRL_FUNCSEL {
+            0 => Function::Hstx,
+            1 => Function::Spi,
+            2 => Function::Uart,
+            3 => Function::I2c,
+            4 => Function::Pwm,
+            5 => Function::Sio,
+            6 => Function::Pio0,
+            7 => Function::Pio1,
+            8 => Function::Pio2,
+            9 => Function::Gpck,
+            10 => Function::Usb,
+            11 => Function::UartAux,
+            _ => Function::Null,
+        }
+    }
+}
+
+/// How one of the override fields of GPIOn_CTRL changes a signal
+fn apply_override(field: u32, signal: bool) -> bool {
+    match field & 3 {
+        0 => signal,
+        1 => !signal,
+        2 => false,
+        _ => true,
+    }
+}
+
+/// Where GPIO interrupts can go, each with its own enables and forces
+const PROC0: usize = 0;
+const DESTINATIONS: usize = 3;
+
+/// The bank 0 GPIO muxing. Each pin takes its output and output enable from the peripheral its FUNCSEL picks, with
+/// the overrides applied, and passes its input back to every peripheral. Only the SIO drives pins so far, the other
+/// functions leave them undriven. Pin inputs are also watched for the level and edge interrupts of core 0, which
+/// are raised on IO_IRQ_BANK0.
+#[derive(Debug, Clone)]
+pub struct IoBank0 {
+    ctrl: [u32; GPIO_COUNT as usize],
+    /// The signals to and from the pads, by pin bit
+    out_to_pad: u64,
+    oe_to_pad: u64,
+    in_from_pad: u64,
+    /// The inputs after the input overrides, as the peripherals and interrupts see them
+    inputs: u64,
+    /// Edge interrupts latched until they are written to clear
+    edges: [u32; INT_REGISTERS],
+    inte: [[u32; INT_REGISTERS]; DESTINATIONS],
+    intf: [[u32; INT_REGISTERS]; DESTINATIONS],
+    /// The pins interrupting core 0, worked out again whenever anything they depend on changes
+    irq_to_proc: u64,
+}
+
+impl Default for IoBank0 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl IoBank0 {
+    pub fn new() -> Self {
+        Self {
+            ctrl: [Function::Null as u32; GPIO_COUNT as usize],
+            out_to_pad: 0,
+            oe_to_pad: 0,
+            in_from_pad: 0,
+            inputs: 0,
+            edges: [0; INT_REGISTERS],
+            inte: [[0; INT_REGISTERS]; DESTINATIONS],
+            intf: [[0; INT_REGISTERS]; DESTINATIONS],
+            irq_to_proc: 0,
+        }
+    }
+
+    pub fn function(&self, pin: u32) -> Function {
+        Function::from_funcsel(self.ctrl[pin as usize])
+    }
+
+    /// Works out what goes to the pads from what the SIO drives, returning the outputs and output enables
+    pub fn to_pads(&mut self, sio_out: u64, sio_oe: u64) -> (u64, u64) {
+        self.out_to_pad = 0;
+        self.oe_to_pad = 0;
+
+        for pin in 0..GPIO_COUNT {
+            let ctrl = self.ctrl[pin as usize];
+            let (out, oe) = match self.function(pin) {
+                Function::Sio => (sio_out >> pin & 1 != 0, sio_oe >> pin & 1 != 0),
+                _ => (false, false),
+            };
+
+            let out = apply_override(ctrl >> GPIO_CTRL_OUTOVER_SHIFT, out);
+            let oe = apply_override(ctrl >> GPIO_CTRL_OEOVER_SHIFT, oe);
+            self.out_to_pad |= (out as u64) << pin;
+            self.oe_to_pad |= (oe as u64) << pin;
+        }
+
+        (self.out_to_pad, self.oe_to_pad)
+    }
+
+    /// Takes the inputs from the pads, latching any edges, and returns them as the peripherals see them
+    pub fn from_pads(&mut self, in_from_pad: u64) -> u64 {
+        self.in_from_pad = in_from_pad;
+
+        let mut inputs = 0;
+        for pin in 0..GPIO_COUNT {
+            let input = apply_override(self.ctrl[pin as usize] >> GPIO_CTRL_INOVER_SHIFT, in_from_pad >> pin & 1 != 0);
+            inputs |= (input as u64) << pin;
+        }
+
+        let rising = inputs & !self.inputs;
+        let falling = self.inputs & !inputs;
+        for pin in 0..GPIO_COUNT {
+            let shift = 4 * (pin % 8);
+            if rising >> pin & 1 != 0 {
+                self.edges[pin as usize / 8] |= INT_EDGE_HIGH << shift;
+            }
+            if falling >> pin & 1 != 0 {
+                self.edges[pin as usize / 8] |= INT_EDGE_LOW << shift;
+            }
+        }
+
+        self.inputs = inputs;
+        self.update_irqs();
+        inputs
+    }
+
+    /// The raw interrupts of the 8 pins in one INTR register, the levels as they are now and the latched edges
+    fn intr(&self, index: usize) -> u32 {
+        let mut levels = 0;
+        for i in 0..8 {
+            let high = self.inputs >> (8 * index + i) & 1 != 0;
+            levels |= if high { INT_LEVEL_HIGH } else { INT_LEVEL_LOW } << (4 * i);
+        }
+
+        levels | self.edges[index]
+    }
+
+    fn ints(&self, destination: usize, index: usize) -> u32 {
+        (self.intr(index) & self.inte[destination][index]) | self.intf[destination][index]
+    }
+
+    fn update_irqs(&mut self) {
+        self.irq_to_proc = 0;
+        for index in 0..INT_REGISTERS {
+            let ints = self.ints(PROC0, index);
+            for i in 0..8 {
+                let pin = 8 * index + i;
+                let interrupting = apply_override(self.ctrl[pin] >> GPIO_CTRL_IRQOVER_SHIFT, ints >> (4 * i) & 0xf != 0);
+                self.irq_to_proc |= (interrupting as u64) << pin;
+            }
+        }
+    }
+
+    /// Whether the pin is interrupting core 0, after the interrupt override
+    pub fn irq_to_proc(&self, pin: u32) -> bool {
+        self.irq_to_proc >> pin & 1 != 0
+    }
+
+    fn status(&self, pin: u32) -> u32 {
+        let bit = |signals: u64, status: u32| if signals >> pin & 1 != 0 { status } else { 0 };
+
+        bit(self.out_to_pad, GPIO_STATUS_OUTTOPAD)
+            | bit(self.oe_to_pad, GPIO_STATUS_OETOPAD)
+            | bit(self.in_from_pad, GPIO_STATUS_INFROMPAD)
+            | if self.irq_to_proc(pin) { GPIO_STATUS_IRQTOPROC } else { 0 }
+    }
+
+    /// The destination and index of an interrupt enable, force or status register
+    fn int_register(offset: u32) -> Option<(usize, u32, usize)> {
+        let from = offset.checked_sub(PROC0_INTE0)?;
+        let destination = (from / (3 * 4 * INT_REGISTERS as u32)) as usize;
+        let kind = from / (4 * INT_REGISTERS as u32) % 3;
+        let index = (from / 4) as usize % INT_REGISTERS;
+        (destination < DESTINATIONS).then_some((destination, kind, index))
+    }
+
+    fn register(&self, offset: u32) -> AccessResult<u32> {
+        let pins = GPIO_COUNT * 8;
+        let value = match offset {
+            _ if offset < pins && offset & 4 == GPIO_STATUS => self.status(offset / 8),
+            _ if offset < pins => self.ctrl[offset as usize / 8],
+            IRQSUMMARY_PROC0_SECURE0 => self.irq_to_proc as u32,
+            IRQSUMMARY_PROC0_SECURE1 => (self.irq_to_proc >> 32) as u32,
+            // The other summaries, of core 0's non-secure GPIOs, core 1 and dormant wake, are left clear
+            0x208..INTR0 => 0,
+            INTR0..PROC0_INTE0 => self.intr((offset - INTR0) as usize / 4),
+            _ => match Self::int_register(offset) {
+                Some((destination, 0, index)) => self.inte[destination][index],
+                Some((destination, 1, index)) => self.intf[destination][index],
+                Some((destination, _, index)) => self.ints(destination, index),
+                None => return Err(BusError::Unmapped),
+            },
+        };
+
+        Ok(value)
+    }
+}
+
+impl Peripheral for IoBank0 {
+    fn read(&self, offset: u32, width: Width) -> AccessResult<u32> {
+        Ok(width.lane(self.register(offset & !3)?, offset))
+    }
+
+    fn write(&mut self, offset: u32, width: Width, value: u32) -> AccessResult<()> {
+        let value = width.replicate(value);
+        let offset = offset & !3;
+
+        match offset {
+            _ if offset < GPIO_COUNT * 8 && offset & 4 == GPIO_CTRL => {
+                self.ctrl[offset as usize / 8] = value & CTRL_WRITABLE;
+            }
+            // Only the edge interrupts latch, writing a 1 clears them
+            INTR0..PROC0_INTE0 => {
+                self.edges[(offset - INTR0) as usize / 4] &= !value;
+            }
+            _ => match Self::int_register(offset) {
+                Some((destination, 0, index)) => self.inte[destination][index] = value,
+                Some((destination, 1, index)) => self.intf[destination][index] = value,
+                // Status and the other read only registers ignore writes
+                _ => {
+                    self.register(offset)?;
+                }
+            },
+        }
+
+        self.update_irqs();
+        Ok(())
+    }
+
+    /// Writing INTR back whole would clear every latched edge, not just those targeted
+    fn write_masked(&mut self, offset: u32, mask: u32, value: u32) -> AccessResult<()> {
+        match offset {
+            INTR0..PROC0_INTE0 => self.write(offset, Width::Word, value & mask),
+            _ => read_modify_write(self, offset, mask, value),
+        }
+    }
+
+    /// The pad signals are kept, as they are worked out again from the other blocks
+    fn reset(&mut self) {
+        *self = Self {
+            out_to_pad: self.out_to_pad,
+            oe_to_pad: self.oe_to_pad,
+            in_from_pad: self.in_from_pad,
+            inputs: self.inputs,
+            ..Self::new()
+        };
+    }
+
+    fn irqs(&self) -> u64 {
+        ((self.irq_to_proc != 0) as u64) << IO_IRQ_BANK0
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
@@ -0,0 +1,105 @@
+pub mod bus;
+pub mod clock_tree;
+pub mod clocks;
+pub mod gpio;
+pub mod io_bank0;
+pub mod pads_bank0;
+pub mod pll;
+pub mod resets;
+pub mod rosc;
+pub mod sio;
+pub mod ticks;
+pub mod uart;
+pub mod xosc;
+
+use std::any::Any;
+
+use crate::AccessResult;
+
+/// The size of a bus access
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Width {
+    Byte,
+    Halfword,
+    Word,
+}
+
+impl Width {
+    pub fn bytes(self) -> u32 {
+        match self {
+            Width::Byte => 1,
+            Width::Halfword => 2,
+            Width::Word => 4,
+        }
+    }
+
+    /// The bits of a value an access of this width carries
+    pub fn mask(self) -> u32 {
+        match self {
+            Width::Byte => 0xff,
+            Width::Halfword => 0xffff,
+            Width::Word => u32::MAX,
+        }
+    }
+
+    /// The bits of a register a narrow read at the offset sees, in the low bits
+    pub fn lane(self, register: u32, offset: u32) -> u32 {
+        (register >> (8 * (offset & 3))) & self.mask()
+    }
+
+    /// A narrow write as the bus puts it on a 32 bit register, copied into every lane
+    pub fn replicate(self, value: u32) -> u32 {
+        match self {
+            Width::Byte => (value & 0xff) * 0x01010101,
+            Width::Halfword => (value & 0xffff) * 0x00010001,
+            Width::Word => value,
+        }
+    }
+}
+
+/// A block of registers on the bus. Offsets are from the base it is registered at and are always aligned to the
+/// width of the access. A narrow access carries its value in the low bits, not in the lanes it would use on the bus.
+pub trait Peripheral {
+    fn read(&self, offset: u32, width: Width) -> AccessResult<u32>;
+    fn write(&mut self, offset: u32, width: Width, value: u32) -> AccessResult<()>;
+
+    /// Writes the bits of the register at the word offset set in the mask to those of the value, leaving the others,
+    /// as the atomic aliases do. By default the register is read and written back whole, so peripherals with bits
+    /// that clear when a 1 is written to them write only the bits in the mask instead.
+    fn write_masked(&mut self, offset: u32, mask: u32, value: u32) -> AccessResult<()> {
+        read_modify_write(self, offset, mask, value)
+    }
+
+    /// Moves the peripheral on by a number of system clock cycles. The bus only ticks a peripheral when it has asked
+    /// for it, and before it is read or written, so one tick can cover many cycles.
+    fn tick(&mut self, _cycles: u64) {}
+
+    /// The cycles from its last tick until the peripheral next changes by itself, None while nothing it does
+    /// depends on time
+    fn next_tick(&self) -> Option<u64> {
+        None
+    }
+
+    /// Puts the registers back to their reset values
+    fn reset(&mut self) {}
+
+    /// The NVIC IRQ lines the peripheral is asserting, by bit
+    fn irqs(&self) -> u64 {
+        0
+    }
+
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+/// Writes the bits of a register set in the mask by reading it and writing it back whole, which is what
+/// [`Peripheral::write_masked`] does unless a peripheral overrides it
+pub fn read_modify_write<P: Peripheral + ?Sized>(
+    peripheral: &mut P,
+    offset: u32,
+    mask: u32,
+    value: u32,
+) -> AccessResult<()> {
+    let current = peripheral.read(offset, Width::Word)?;
+    peripheral.write(offset, Width::Word, (current & !mask) | (value & mask))
+}
@@ -0,0 +1,246 @@
+use std::any::Any;
+
+use crate::peripherals::sio::GPIO_COUNT;
+use crate::peripherals::{Peripheral, Width};
+use crate::{AccessResult, BusError};
+
+pub const PADS_BANK0_BASE: u32 = 0x40038000;
+pub const PADS_BANK0_SIZE: u32 = 0xcc;
+
+pub const PADS_VOLTAGE_SELECT: u32 = 0x00;
+/// The pad control of GPIOn is at 4 + 4n
+pub const PADS_GPIO0: u32 = 0x04;
+pub const PADS_SWCLK: u32 = 0xc4;
+pub const PADS_SWD: u32 = 0xc8;
+
+pub const PADS_SLEWFAST: u32 = 1 << 0;
+pub const PADS_SCHMITT: u32 = 1 << 1;
+/// Pull down enable
+pub const PADS_PDE: u32 = 1 << 2;
+/// Pull up enable
+pub const PADS_PUE: u32 = 1 << 3;
+pub const PADS_DRIVE_SHIFT: u32 = 4;
+/// Input enable
+pub const PADS_IE: u32 = 1 << 6;
+/// Output disable, which wins over the output enable
+pub const PADS_OD: u32 = 1 << 7;
+/// Pad isolation, holding the pad as it was until software has set it up
+pub const PADS_ISO: u32 = 1 << 8;
+
+const PAD_WRITABLE: u32 = 0x1ff;
+/// Isolated, pulled down with a 4 mA drive and Schmitt trigger, input disabled
+const GPIO_RESET: u32 = PADS_ISO | 1 << PADS_DRIVE_SHIFT | PADS_PDE | PADS_SCHMITT;
+/// The SWD pads are pulled up with their inputs enabled
+const SWD_RESET: u32 = PADS_IE | 1 << PADS_DRIVE_SHIFT | PADS_PUE | PADS_SCHMITT;
+const PAD_COUNT: usize = GPIO_COUNT as usize + 2;
+
+/// How the host drives a pin from outside
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Drive {
+    Digital(bool),
+    /// Held at a voltage, which inputs see as high above half the IO supply
+    Analog(f32),
+}
+
+/// What puts a pin at its level
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Source {
+    /// The chip drives it
+    Output,
+    /// The host drives it
+    Input,
+    /// The host holds it at a voltage
+    Analog(f32),
+    PullUp,
+    PullDown,
+    /// Nothing drives it or pulls it one way, so it stays at the level it was last at
+    Kept,
+}
+
+/// The level of a pin and what puts it there
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PinState {
+    pub level: bool,
+    pub source: Source,
+}
+
+/// The bank 0 pads, where the pins meet the outside. A pad drives its pin when its output is enabled and not
+/// disabled. Otherwise the pin is at the level the host drives it to, or failing that pulled up or down, and with
+/// both pulls on or neither it keeps the level it had. A pin the host holds at a voltage is high above half the IO
+/// supply VOLTAGE_SELECT picks. While isolated, a pad holds the output and output enable it had when it was
+/// isolated. Pin levels only reach the chip through pads with their input enabled.
+#[derive(Debug, Clone)]
+pub struct PadsBank0 {
+    voltage_select: u32,
+    /// The GPIOs, then SWCLK and SWD
+    pads: [u32; PAD_COUNT],
+    /// What the host is driving each pin with
+    external: [Option<Drive>; GPIO_COUNT as usize],
+    /// The pins the chip drives and the pin levels, by bit
+    driving: u64,
+    levels: u64,
+    /// The outputs and output enables isolated pads hold
+    held_out: u64,
+    held_oe: u64,
+}
+
+impl Default for PadsBank0 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PadsBank0 {
+    pub fn new() -> Self {
+        let mut pads = [GPIO_RESET; PAD_COUNT];
+        pads[GPIO_COUNT as usize..].fill(SWD_RESET);
+
+        Self {
+            voltage_select: 0,
+            pads,
+            external: [None; GPIO_COUNT as usize],
+            driving: 0,
+            levels: 0,
+            held_out: 0,
+            held_oe: 0,
+        }
+    }
+
+    /// Drives a pin from outside to a level, or stops driving it
+    pub fn drive(&mut self, pin: u32, level: Option<bool>) {
+        assert!(pin < GPIO_COUNT, "GPIO {} does not exist", pin);
+        self.external[pin as usize] = level.map(Drive::Digital);
+    }
+
+    /// Holds a pin at a voltage from outside
+    pub fn drive_analog(&mut self, pin: u32, volts: f32) {
+        assert!(pin < GPIO_COUNT, "GPIO {} does not exist", pin);
+        self.external[pin as usize] = Some(Drive::Analog(volts));
+    }
+
+    /// The voltage the pads are powered from, by VOLTAGE_SELECT
+    pub fn iovdd(&self) -> f32 {
+        if self.voltage_select & 1 == 0 {
+            3.3
+        } else {
+            1.8
+        }
+    }
+
+    /// The level a pin is at
+    pub fn level(&self, pin: u32) -> bool {
+        self.levels >> pin & 1 != 0
+    }
+
+    /// The pins the chip is driving, by bit
+    pub fn driving(&self) -> u64 {
+        self.driving
+    }
+
+    pub fn levels(&self) -> u64 {
+        self.levels
+    }
+
+    pub fn state(&self, pin: u32) -> PinState {
+        assert!(pin < GPIO_COUNT, "GPIO {} does not exist", pin);
+        let control = self.pads[pin as usize];
+        let source = match self.external[pin as usize] {
+            _ if self.driving >> pin & 1 != 0 => Source::Output,
+            Some(Drive::Digital(_)) => Source::Input,
+            Some(Drive::Analog(volts)) => Source::Analog(volts),
+            None => match (control & PADS_PUE != 0, control & PADS_PDE != 0) {
+                (true, false) => Source::PullUp,
+                (false, true) => Source::PullDown,
+                _ => Source::Kept,
+            },
+        };
+
+        PinState {
+            level: self.level(pin),
+            source,
+        }
+    }
+
+    pub fn control(&self, pin: u32) -> u32 {
+        self.pads[pin as usize]
+    }
+
+    fn pads_with(&self, bit: u32) -> u64 {
+        (0..GPIO_COUNT).fold(0, |pins, pin| pins | ((self.pads[pin as usize] & bit != 0) as u64) << pin)
+    }
+
+    /// Works out the pin levels from the outputs and output enables IO_BANK0 sends to the pads, returning the
+    /// levels the pads pass back to the chip
+    pub fn update(&mut self, out: u64, oe: u64) -> u64 {
+        let isolated = self.pads_with(PADS_ISO);
+        self.held_out = (self.held_out & isolated) | (out & !isolated);
+        self.held_oe = (self.held_oe & isolated) | (oe & !isolated);
+
+        let (mut driven, mut external) = (0, 0);
+        for (pin, drive) in self.external.iter().enumerate() {
+            let level = match *drive {
+                Some(Drive::Digital(level)) => level,
+                Some(Drive::Analog(volts)) => volts > self.iovdd() / 2.0,
+                None => continue,
+            };
+            driven |= 1 << pin;
+            external |= (level as u64) << pin;
+        }
+
+        let driving = self.held_oe & !self.pads_with(PADS_OD);
+        let pull_up = self.pads_with(PADS_PUE);
+        let pull_down = self.pads_with(PADS_PDE);
+        let pulled = (pull_up ^ pull_down) & !driven & !driving;
+        let keeping = !(driving | driven | pulled);
+
+        self.driving = driving;
+        self.levels = (self.held_out & driving)
+            | (external & driven & !driving)
+            | (pull_up & pulled)
+            | (self.levels & keeping);
+
+        self.levels & self.pads_with(PADS_IE)
+    }
+
+    fn register(&self, offset: u32) -> AccessResult<u32> {
+        match offset {
+            PADS_VOLTAGE_SELECT => Ok(self.voltage_select),
+            PADS_GPIO0..PADS_BANK0_SIZE => Ok(self.pads[(offset - PADS_GPIO0) as usize / 4]),
+            _ => Err(BusError::Unmapped),
+        }
+    }
+}
+
+impl Peripheral for PadsBank0 {
+    fn read(&self, offset: u32, width: Width) -> AccessResult<u32> {
+        Ok(width.lane(self.register(offset & !3)?, offset))
+    }
+
+    fn write(&mut self, offset: u32, width: Width, value: u32) -> AccessResult<()> {
+        let value = width.replicate(value);
+        match offset & !3 {
+            PADS_VOLTAGE_SELECT => self.voltage_select = value & 1,
+            offset @ PADS_GPIO0..PADS_BANK0_SIZE => self.pads[(offset - PADS_GPIO0) as usize / 4] = value & PAD_WRITABLE,
+            _ => return Err(BusError::Unmapped),
+        }
+
+        Ok(())
+    }
+
+    /// What the host drives and the pin levels are kept, as they are outside the chip
+    fn reset(&mut self) {
+        *self = Self {
+            external: self.external,
+            levels: self.levels,
+            ..Self::new()
+        };
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
@@ -0,0 +1,205 @@
+use std::any::Any;
+
+use crate::peripherals::clocks::ClockDomain;
+use crate::peripherals::{Peripheral, Width};
+use crate::{AccessResult, BusError};
+
+pub const PLL_SYS_BASE: u32 = 0x40050000;
+pub const PLL_USB_BASE: u32 = 0x40058000;
+pub const PLL_SIZE: u32 = 0x20;
+
+pub const PLL_CS: u32 = 0x00;
+pub const PLL_PWR: u32 = 0x04;
+pub const PLL_FBDIV_INT: u32 = 0x08;
+pub const PLL_PRIM: u32 = 0x0c;
+pub const PLL_INTR: u32 = 0x10;
+pub const PLL_INTE: u32 = 0x14;
+pub const PLL_INTF: u32 = 0x18;
+pub const PLL_INTS: u32 = 0x1c;
+
+pub const PLL_CS_REFDIV_MASK: u32 = 0x3f;
+/// Passes the reference clock straight to the output
+pub const PLL_CS_BYPASS: u32 = 1 << 8;
+pub const PLL_CS_LOCK: u32 = 1 << 31;
+
+/// Powers down the whole PLL
+pub const PLL_PWR_PD: u32 = 1 << 0;
+pub const PLL_PWR_DSMPD: u32 = 1 << 2;
+/// Powers down the post dividers
+pub const PLL_PWR_POSTDIVPD: u32 = 1 << 3;
+/// Powers down the VCO
+pub const PLL_PWR_VCOPD: u32 = 1 << 5;
+
+pub const PLL_FBDIV_INT_MASK: u32 = 0xfff;
+pub const PLL_PRIM_POSTDIV1_SHIFT: u32 = 16;
+pub const PLL_PRIM_POSTDIV2_SHIFT: u32 = 12;
+
+/// The reference clock cycles the PLL takes to lock, about 50 µs from the crystal
+pub const PLL_LOCK_CYCLES: u64 = 600;
+
+const PWR_RESET: u32 = PLL_PWR_VCOPD | PLL_PWR_POSTDIVPD | PLL_PWR_DSMPD | PLL_PWR_PD;
+const PRIM_RESET: u32 = 7 << PLL_PRIM_POSTDIV1_SHIFT | 7 << PLL_PRIM_POSTDIV2_SHIFT;
+
+/// One of the two PLLs, which multiply the crystal up to clk_sys and clk_usb. The VCO runs at the reference divided
+/// by REFDIV times FBDIV_INT, and the output is that divided by both post dividers. Once the PLL and its VCO are
+/// powered up with valid dividers, it takes PLL_LOCK_CYCLES reference cycles to lock. Changing the reference, REFDIV
+/// or FBDIV_INT, or powering it down, loses the lock. It never loses lock by itself, so INTR is always clear.
+#[derive(Debug, Clone)]
+pub struct Pll {
+    cs: u32,
+    pwr: u32,
+    fbdiv_int: u32,
+    prim: u32,
+    inte: u32,
+    intf: u32,
+    /// The reference cycles until the PLL locks, None while it is locked or can't lock
+    locking: Option<u64>,
+    /// The reference clock in system clock cycles
+    reference: ClockDomain,
+}
+
+impl Default for Pll {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Pll {
+    pub fn new() -> Self {
+        Self {
+            cs: 1,
+            pwr: PWR_RESET,
+            fbdiv_int: 0,
+            prim: PRIM_RESET,
+            inte: 0,
+            intf: 0,
+            locking: None,
+            reference: ClockDomain::default(),
+        }
+    }
+
+    pub fn is_locked(&self) -> bool {
+        self.cs & PLL_CS_LOCK != 0
+    }
+
+    /// The frequency the VCO runs at, 0 while it is powered down or its dividers are out of range
+    pub fn vco_frequency(&self) -> u32 {
+        let refdiv = self.cs & PLL_CS_REFDIV_MASK;
+        if self.pwr & (PLL_PWR_PD | PLL_PWR_VCOPD) != 0 || refdiv == 0 || !(16..=320).contains(&self.fbdiv_int) {
+            return 0;
+        }
+
+        (self.reference.hz() as u64 / refdiv as u64 * self.fbdiv_int as u64) as u32
+    }
+
+    /// The frequency of the output, 0 while it is powered down
+    pub fn frequency(&self) -> u32 {
+        if self.cs & PLL_CS_BYPASS != 0 {
+            return self.reference.hz();
+        }
+
+        let postdiv1 = self.prim >> PLL_PRIM_POSTDIV1_SHIFT & 7;
+        let postdiv2 = self.prim >> PLL_PRIM_POSTDIV2_SHIFT & 7;
+        if self.pwr & PLL_PWR_POSTDIVPD != 0 || postdiv1 == 0 || postdiv2 == 0 {
+            return 0;
+        }
+
+        self.vco_frequency() / (postdiv1 * postdiv2)
+    }
+
+    /// Sets the reference and clk_sys frequencies, the lock time is counted in the reference and timed in clk_sys
+    pub fn set_clocks(&mut self, reference_hz: u32, clk_sys_hz: u32) {
+        let relock = reference_hz != self.reference.hz();
+        self.reference.set(reference_hz, clk_sys_hz);
+        if relock {
+            self.relock();
+        }
+    }
+
+    /// Drops the lock and starts locking again, if the PLL can
+    fn relock(&mut self) {
+        self.cs &= !PLL_CS_LOCK;
+        self.locking = (self.vco_frequency() != 0).then_some(PLL_LOCK_CYCLES);
+    }
+
+    /// What the VCO, and so the lock, depends on
+    fn vco_settings(&self) -> (u32, u32, u32) {
+        (self.cs & PLL_CS_REFDIV_MASK, self.pwr & (PLL_PWR_PD | PLL_PWR_VCOPD), self.fbdiv_int)
+    }
+
+    fn register(&self, offset: u32) -> AccessResult<u32> {
+        let value = match offset {
+            PLL_CS => self.cs,
+            PLL_PWR => self.pwr,
+            PLL_FBDIV_INT => self.fbdiv_int,
+            PLL_PRIM => self.prim,
+            PLL_INTR => 0,
+            PLL_INTE => self.inte,
+            PLL_INTF => self.intf,
+            PLL_INTS => self.intf & self.inte,
+            _ => return Err(BusError::Unmapped),
+        };
+
+        Ok(value)
+    }
+}
+
+impl Peripheral for Pll {
+    fn read(&self, offset: u32, width: Width) -> AccessResult<u32> {
+        Ok(width.lane(self.register(offset & !3)?, offset))
+    }
+
+    fn write(&mut self, offset: u32, width: Width, value: u32) -> AccessResult<()> {
+        let value = width.replicate(value);
+        let vco = self.vco_settings();
+        match offset & !3 {
+            PLL_CS => self.cs = (self.cs & PLL_CS_LOCK) | value & (PLL_CS_BYPASS | PLL_CS_REFDIV_MASK),
+            PLL_PWR => self.pwr = value & PWR_RESET,
+            PLL_FBDIV_INT => self.fbdiv_int = value & PLL_FBDIV_INT_MASK,
+            PLL_PRIM => self.prim = value & PRIM_RESET,
+            PLL_INTE => self.inte = value & 1,
+            PLL_INTF => self.intf = value & 1,
+            PLL_INTR | PLL_INTS => {}
+            _ => return Err(BusError::Unmapped),
+        }
+
+        if self.vco_settings() != vco {
+            self.relock();
+        }
+
+        Ok(())
+    }
+
+    fn tick(&mut self, cycles: u64) {
+        let reference_cycles = self.reference.count(cycles);
+        if let Some(locking) = self.locking {
+            match locking.saturating_sub(reference_cycles) {
+                0 => {
+                    self.locking = None;
+                    self.cs |= PLL_CS_LOCK;
+                }
+                left => self.locking = Some(left),
+            }
+        }
+    }
+
+    fn next_tick(&self) -> Option<u64> {
+        self.reference.until(self.locking?)
+    }
+
+    /// The reference and clk_sys frequencies are kept, they are set again as the clocks are worked out after a reset
+    fn reset(&mut self) {
+        *self = Self {
+            reference: self.reference,
+            ..Self::new()
+        };
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
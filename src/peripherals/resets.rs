@@ -0,0 +1,125 @@
+use std::any::Any;
+
+use crate::peripherals::{Peripheral, Width};
+use crate::{AccessResult, BusError};
+
+pub const RESETS_BASE: u32 = 0x40020000;
+pub const RESETS_SIZE: u32 = 0x0c;
+
+pub const RESETS_RESET: u32 = 0x00;
+pub const RESETS_WDSEL: u32 = 0x04;
+pub const RESETS_RESET_DONE: u32 = 0x08;
+
+/// The bit of each block in RESET, WDSEL and RESET_DONE
+pub const RESET_ADC: u32 = 0;
+pub const RESET_BUSCTRL: u32 = 1;
+pub const RESET_DMA: u32 = 2;
+pub const RESET_HSTX: u32 = 3;
+pub const RESET_I2C0: u32 = 4;
+pub const RESET_I2C1: u32 = 5;
+pub const RESET_IO_BANK0: u32 = 6;
+pub const RESET_IO_QSPI: u32 = 7;
+pub const RESET_JTAG: u32 = 8;
+pub const RESET_PADS_BANK0: u32 = 9;
+pub const RESET_PADS_QSPI: u32 = 10;
+pub const RESET_PIO0: u32 = 11;
+pub const RESET_PIO1: u32 = 12;
+pub const RESET_PIO2: u32 = 13;
+pub const RESET_PLL_SYS: u32 = 14;
+pub const RESET_PLL_USB: u32 = 15;
+pub const RESET_PWM: u32 = 16;
+pub const RESET_SHA256: u32 = 17;
+pub const RESET_SPI0: u32 = 18;
+pub const RESET_SPI1: u32 = 19;
+pub const RESET_SYSCFG: u32 = 20;
+pub const RESET_SYSINFO: u32 = 21;
+pub const RESET_TBMAN: u32 = 22;
+pub const RESET_TIMER0: u32 = 23;
+pub const RESET_TIMER1: u32 = 24;
+pub const RESET_TRNG: u32 = 25;
+pub const RESET_UART0: u32 = 26;
+pub const RESET_UART1: u32 = 27;
+pub const RESET_USBCTRL: u32 = 28;
+
+/// Every block RESETS controls, all of which start held in reset
+pub const RESET_ALL: u32 = (1 << (RESET_USBCTRL + 1)) - 1;
+
+/// The subsystem resets. A block comes out of reset at once when its RESET bit is cleared, so RESET_DONE is always
+/// the inverse of RESET. Setting a bit puts the block back to its reset values, which the bus carries out for the
+/// blocks that are modelled. Blocks held in reset still answer on the bus.
+#[derive(Debug, Clone)]
+pub struct Resets {
+    reset: u32,
+    wdsel: u32,
+    /// The blocks put into reset since they were last taken
+    asserted: u32,
+}
+
+impl Default for Resets {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Resets {
+    pub fn new() -> Self {
+        Self {
+            reset: RESET_ALL,
+            wdsel: 0,
+            asserted: 0,
+        }
+    }
+
+    /// Whether a block is held in reset
+    pub fn in_reset(&self, block: u32) -> bool {
+        self.reset >> block & 1 != 0
+    }
+
+    /// Takes the blocks put into reset since they were last taken, by bit
+    pub fn take_asserted(&mut self) -> u32 {
+        std::mem::take(&mut self.asserted)
+    }
+
+    fn register(&self, offset: u32) -> AccessResult<u32> {
+        match offset {
+            RESETS_RESET => Ok(self.reset),
+            RESETS_WDSEL => Ok(self.wdsel),
+            RESETS_RESET_DONE => Ok(!self.reset & RESET_ALL),
+            _ => Err(BusError::Unmapped),
+        }
+    }
+}
+
+impl Peripheral for Resets {
+    fn read(&self, offset: u32, width: Width) -> AccessResult<u32> {
+        Ok(width.lane(self.register(offset & !3)?, offset))
+    }
+
+    fn write(&mut self, offset: u32, width: Width, value: u32) -> AccessResult<()> {
+        let value = width.replicate(value) & RESET_ALL;
+        match offset & !3 {
+            RESETS_RESET => {
+                self.asserted |= value & !self.reset;
+                self.reset = value;
+            }
+            RESETS_WDSEL => self.wdsel = value,
+            // RESET_DONE is read only
+            RESETS_RESET_DONE => {}
+            _ => return Err(BusError::Unmapped),
+        }
+
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
@@ -0,0 +1,217 @@
+use std::any::Any;
+use std::cell::Cell;
+
+use crate::peripherals::clocks::ClockDomain;
+use crate::peripherals::{read_modify_write, Peripheral, Width};
+use crate::{AccessResult, BusError};
+
+pub const ROSC_BASE: u32 = 0x400e8000;
+pub const ROSC_SIZE: u32 = 0x28;
+
+pub const ROSC_CTRL: u32 = 0x00;
+pub const ROSC_FREQA: u32 = 0x04;
+pub const ROSC_FREQB: u32 = 0x08;
+pub const ROSC_RANDOM: u32 = 0x0c;
+pub const ROSC_DORMANT: u32 = 0x10;
+pub const ROSC_DIV: u32 = 0x14;
+pub const ROSC_PHASE: u32 = 0x18;
+pub const ROSC_STATUS: u32 = 0x1c;
+pub const ROSC_RANDOMBIT: u32 = 0x20;
+pub const ROSC_COUNT: u32 = 0x24;
+
+/// What the ring oscillator runs at, nominally, out of reset
+pub const ROSC_HZ: u32 = 11_000_000;
+
+pub const ROSC_CTRL_ENABLE_SHIFT: u32 = 12;
+pub const ROSC_CTRL_ENABLE: u32 = 0xfab;
+pub const ROSC_CTRL_DISABLE: u32 = 0xd1e;
+/// FREQA and FREQB are only written along with this in their top half
+pub const ROSC_FREQ_PASSWD: u32 = 0x9696;
+
+pub const ROSC_STATUS_ENABLED: u32 = 1 << 12;
+pub const ROSC_STATUS_DIV_RUNNING: u32 = 1 << 16;
+/// An invalid value was written to CTRL or FREQA/B, write 1 to clear
+pub const ROSC_STATUS_BADWRITE: u32 = 1 << 24;
+pub const ROSC_STATUS_STABLE: u32 = 1 << 31;
+
+const CTRL_RESET: u32 = ROSC_CTRL_ENABLE << ROSC_CTRL_ENABLE_SHIFT | 0xaa0;
+const RANDOM_RESET: u32 = 0x3f04b16d;
+const PHASE_RESET: u32 = 0x08;
+
+/// The ring oscillator the chip runs from out of reset. Its frequency is taken to be the nominal one whatever the
+/// drive strengths and divider are set to, as on silicon it varies from chip to chip anyway. It is stable as soon as
+/// it is enabled, COUNT counts down at its frequency and RANDOMBIT reads a pseudorandom bit.
+#[derive(Debug, Clone)]
+pub struct Rosc {
+    ctrl: u32,
+    freqa: u32,
+    freqb: u32,
+    random: u32,
+    dormant: u32,
+    div: u32,
+    phase: u32,
+    count: u32,
+    enabled: bool,
+    badwrite: bool,
+    /// The shift register RANDOMBIT reads from, moved on by each read
+    lfsr: Cell<u32>,
+    domain: ClockDomain,
+}
+
+impl Default for Rosc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Rosc {
+    pub fn new() -> Self {
+        Self {
+            ctrl: CTRL_RESET,
+            freqa: 0,
+            freqb: 0,
+            random: RANDOM_RESET,
+            dormant: 0,
+            div: 0,
+            phase: PHASE_RESET,
+            count: 0,
+            enabled: true,
+            badwrite: false,
+            lfsr: Cell::new(RANDOM_RESET),
+            domain: ClockDomain::default(),
+        }
+    }
+
+    /// The frequency the oscillator runs at, 0 while it is disabled
+    pub fn frequency(&self) -> u32 {
+        if self.enabled {
+            ROSC_HZ
+        } else {
+            0
+        }
+    }
+
+    /// Sets the clk_sys frequency COUNT is timed in
+    pub fn set_clk_sys(&mut self, hz: u32) {
+        self.domain.set(self.frequency(), hz);
+    }
+
+    fn write_ctrl(&mut self, value: u32) {
+        match value >> ROSC_CTRL_ENABLE_SHIFT & 0xfff {
+            ROSC_CTRL_ENABLE => self.enabled = true,
+            ROSC_CTRL_DISABLE => self.enabled = false,
+            0 => {}
+            _ => self.badwrite = true,
+        }
+        self.ctrl = value & 0x00ffffff;
+    }
+
+    fn write_freq(&mut self, register: u32, value: u32) -> u32 {
+        if value >> 16 != ROSC_FREQ_PASSWD {
+            self.badwrite = true;
+            return register;
+        }
+        value & 0xffff
+    }
+
+    /// The next bit of a 32 bit Galois LFSR
+    fn random_bit(&self) -> u32 {
+        let state = self.lfsr.get();
+        let bit = state & 1;
+        self.lfsr.set(state >> 1 ^ if bit != 0 { 0x80200003 } else { 0 });
+        bit
+    }
+
+    fn status(&self) -> u32 {
+        let mut status = 0;
+        if self.enabled {
+            status |= ROSC_STATUS_ENABLED | ROSC_STATUS_DIV_RUNNING | ROSC_STATUS_STABLE;
+        }
+        if self.badwrite {
+            status |= ROSC_STATUS_BADWRITE;
+        }
+        status
+    }
+
+    fn register(&self, offset: u32) -> AccessResult<u32> {
+        let value = match offset {
+            ROSC_CTRL => self.ctrl,
+            ROSC_FREQA => self.freqa,
+            ROSC_FREQB => self.freqb,
+            ROSC_RANDOM => self.random,
+            ROSC_DORMANT => self.dormant,
+            ROSC_DIV => self.div,
+            ROSC_PHASE => self.phase,
+            ROSC_STATUS => self.status(),
+            ROSC_RANDOMBIT => self.random_bit(),
+            ROSC_COUNT => self.count,
+            _ => return Err(BusError::Unmapped),
+        };
+
+        Ok(value)
+    }
+}
+
+impl Peripheral for Rosc {
+    fn read(&self, offset: u32, width: Width) -> AccessResult<u32> {
+        Ok(width.lane(self.register(offset & !3)?, offset))
+    }
+
+    fn write(&mut self, offset: u32, width: Width, value: u32) -> AccessResult<()> {
+        let value = width.replicate(value);
+        match offset & !3 {
+            ROSC_CTRL => self.write_ctrl(value),
+            ROSC_FREQA => self.freqa = self.write_freq(self.freqa, value),
+            ROSC_FREQB => self.freqb = self.write_freq(self.freqb, value),
+            ROSC_RANDOM => {
+                self.random = value;
+                self.lfsr.set(value);
+            }
+            ROSC_DORMANT => self.dormant = value,
+            ROSC_DIV => self.div = value & 0xffff,
+            ROSC_PHASE => self.phase = value & 0xfff,
+            ROSC_STATUS if value & ROSC_STATUS_BADWRITE != 0 => self.badwrite = false,
+            ROSC_STATUS | ROSC_RANDOMBIT => {}
+            ROSC_COUNT => self.count = value & 0xffff,
+            _ => return Err(BusError::Unmapped),
+        }
+
+        Ok(())
+    }
+
+    /// Writing STATUS back whole would clear BADWRITE whenever it is set
+    fn write_masked(&mut self, offset: u32, mask: u32, value: u32) -> AccessResult<()> {
+        match offset {
+            ROSC_STATUS => self.write(offset, Width::Word, value & mask),
+            _ => read_modify_write(self, offset, mask, value),
+        }
+    }
+
+    fn tick(&mut self, cycles: u64) {
+        let rosc_cycles = self.domain.count(cycles);
+        self.count = self.count.saturating_sub(rosc_cycles.min(u32::MAX as u64) as u32);
+    }
+
+    /// Ticked at every oscillator cycle while COUNT counts, so it reads right
+    fn next_tick(&self) -> Option<u64> {
+        match self.count {
+            0 => None,
+            _ => self.domain.until(1),
+        }
+    }
+
+    fn reset(&mut self) {
+        *self = Self {
+            domain: self.domain,
+            ..Self::new()
+        };
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
@@ -0,0 +1,176 @@
+use std::any::Any;
+
+use crate::peripherals::{Peripheral, Width};
+use crate::{AccessResult, BusError};
+
+pub const SIO_SIZE: u32 = 0x200;
+
+pub const SIO_CPUID: u32 = 0x000;
+pub const SIO_GPIO_IN: u32 = 0x004;
+pub const SIO_GPIO_HI_IN: u32 = 0x008;
+pub const SIO_GPIO_OUT: u32 = 0x010;
+pub const SIO_GPIO_HI_OUT: u32 = 0x014;
+pub const SIO_GPIO_OUT_SET: u32 = 0x018;
+pub const SIO_GPIO_HI_OUT_SET: u32 = 0x01c;
+pub const SIO_GPIO_OUT_CLR: u32 = 0x020;
+pub const SIO_GPIO_HI_OUT_CLR: u32 = 0x024;
+pub const SIO_GPIO_OUT_XOR: u32 = 0x028;
+pub const SIO_GPIO_HI_OUT_XOR: u32 = 0x02c;
+pub const SIO_GPIO_OE: u32 = 0x030;
+pub const SIO_GPIO_HI_OE: u32 = 0x034;
+pub const SIO_GPIO_OE_SET: u32 = 0x038;
+pub const SIO_GPIO_HI_OE_SET: u32 = 0x03c;
+pub const SIO_GPIO_OE_CLR: u32 = 0x040;
+pub const SIO_GPIO_HI_OE_CLR: u32 = 0x044;
+pub const SIO_GPIO_OE_XOR: u32 = 0x048;
+pub const SIO_GPIO_HI_OE_XOR: u32 = 0x04c;
+
+/// Pins are numbered by their bit in the SIO registers, the low bank then the high bank. Bank 0 GPIOs are 0 to 47,
+/// the rest of the high bank is the USB and QSPI pins.
+pub const GPIO_COUNT: u32 = 48;
+pub const USB_DP: u32 = 32 + 24;
+pub const USB_DM: u32 = 32 + 25;
+pub const QSPI_SCK: u32 = 32 + 26;
+pub const QSPI_CSN: u32 = 32 + 27;
+/// QSPI SD0, SD1 to SD3 follow it
+pub const QSPI_SD0: u32 = 32 + 28;
+pub const PIN_COUNT: u32 = 64;
+
+/// The bits of the high bank registers that have a pin
+const HI_BANK_MASK: u32 = 0xff00ffff;
+
+/// The single-cycle IO block reduced to its GPIO registers. It holds what the core would drive onto each pin and
+/// whether it would drive it, which reaches bank 0 pins through IO_BANK0 when they select the SIO function. GPIO_IN
+/// reads the levels the IO banks feed back. The USB and QSPI pins have no IO bank modelled, so the host sets those.
+#[derive(Debug, Clone)]
+pub struct Sio {
+    /// The core the registers are read by, only core 0 runs
+    pub core: u32,
+    out: u64,
+    oe: u64,
+    /// The levels the pins are seen at
+    inputs: u64,
+}
+
+impl Default for Sio {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Sio {
+    pub fn new() -> Self {
+        Self {
+            core: 0,
+            out: 0,
+            oe: 0,
+            inputs: 0,
+        }
+    }
+
+    /// Sets the level GPIO_IN sees on a pin
+    pub fn set_input(&mut self, pin: u32, level: bool) {
+        assert!(pin < PIN_COUNT, "pin {} does not exist", pin);
+        self.set_inputs(1 << pin, (level as u64) << pin);
+    }
+
+    /// Sets the level GPIO_IN sees on the pins selected by the mask
+    pub fn set_inputs(&mut self, mask: u64, levels: u64) {
+        self.inputs = (self.inputs & !mask) | (levels & mask);
+    }
+
+    /// The level GPIO_IN sees on every pin, by its bit
+    pub fn levels(&self) -> u64 {
+        self.inputs
+    }
+
+    pub fn level(&self, pin: u32) -> bool {
+        self.inputs >> pin & 1 != 0
+    }
+
+    /// What GPIO_OUT drives onto every pin, by its bit
+    pub fn out(&self) -> u64 {
+        self.out
+    }
+
+    /// Which pins GPIO_OE drives, by their bit
+    pub fn oe(&self) -> u64 {
+        self.oe
+    }
+
+    /// Whether the core would drive the pin
+    pub fn is_output(&self, pin: u32) -> bool {
+        self.oe >> pin & 1 != 0
+    }
+
+    fn register(&self, offset: u32) -> AccessResult<u32> {
+        let value = match offset {
+            SIO_CPUID => self.core,
+            SIO_GPIO_IN => self.levels() as u32,
+            SIO_GPIO_HI_IN => (self.levels() >> 32) as u32,
+            SIO_GPIO_OUT => self.out as u32,
+            SIO_GPIO_HI_OUT => (self.out >> 32) as u32,
+            SIO_GPIO_OE => self.oe as u32,
+            SIO_GPIO_HI_OE => (self.oe >> 32) as u32,
+            // The set, clear and xor registers are write only
+            SIO_GPIO_OUT_SET..SIO_GPIO_OE | SIO_GPIO_OE_SET..=SIO_GPIO_HI_OE_XOR => 0,
+            _ => return Err(BusError::Unmapped),
+        };
+
+        Ok(value)
+    }
+}
+
+/// Writes to one of the register groups that follow the GPIO_OUT and GPIO_OE layout: the value, then set, clear and
+/// xor, each for the low bank then the high bank
+fn write_group(bits: &mut u64, offset: u32, value: u32) {
+    let (shift, value) = match offset & 4 {
+        0 => (0, value),
+        _ => (32, value & HI_BANK_MASK),
+    };
+    let bank = (u32::MAX as u64) << shift;
+    let value = (value as u64) << shift;
+
+    *bits = match offset >> 3 {
+        0 => (*bits & !bank) | value,
+        1 => *bits | value,
+        2 => *bits & !value,
+        _ => *bits ^ value,
+    };
+}
+
+impl Peripheral for Sio {
+    fn read(&self, offset: u32, width: Width) -> AccessResult<u32> {
+        Ok(width.lane(self.register(offset & !3)?, offset))
+    }
+
+    fn write(&mut self, offset: u32, width: Width, value: u32) -> AccessResult<()> {
+        let value = width.replicate(value);
+        match offset & !3 {
+            offset @ SIO_GPIO_OUT..SIO_GPIO_OE => write_group(&mut self.out, offset - SIO_GPIO_OUT, value),
+            offset @ SIO_GPIO_OE..=SIO_GPIO_HI_OE_XOR => write_group(&mut self.oe, offset - SIO_GPIO_OE, value),
+            offset => {
+                // Writes to the read only registers are ignored
+                self.register(offset)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The outputs go back to their reset values, the levels pins are seen at are kept
+    fn reset(&mut self) {
+        *self = Self {
+            inputs: self.inputs,
+            ..Self::new()
+        };
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
@@ -0,0 +1,162 @@
+use std::any::Any;
+use std::cell::Cell;
+
+use crate::peripherals::clocks::ClockDomain;
+use crate::peripherals::{Peripheral, Width};
+use crate::AccessResult;
+
+pub const TICKS_BASE: u32 = 0x40108000;
+pub const TICKS_SIZE: u32 = 0x48;
+
+/// Each tick generator has a CTRL, CYCLES and COUNT register in turn, at 0xc times its number
+pub const TICK_CTRL: u32 = 0x0;
+pub const TICK_CYCLES: u32 = 0x4;
+pub const TICK_COUNT: u32 = 0x8;
+const TICK_REGISTERS: u32 = 0xc;
+
+pub const TICK_PROC0: u32 = 0;
+pub const TICK_PROC1: u32 = 1;
+pub const TICK_TIMER0: u32 = 2;
+pub const TICK_TIMER1: u32 = 3;
+pub const TICK_WATCHDOG: u32 = 4;
+pub const TICK_RISCV: u32 = 5;
+const GENERATOR_COUNT: usize = 6;
+
+pub const TICK_CTRL_ENABLE: u32 = 1 << 0;
+/// Read only, whether the generator is running
+pub const TICK_CTRL_RUNNING: u32 = 1 << 1;
+/// The clk_ref cycles between ticks
+pub const TICK_CYCLES_MASK: u32 = 0x1ff;
+
+/// The tick generators, which divide clk_ref down to the ticks the timers, the watchdog and the cores' SysTick
+/// count. A running generator counts COUNT down from CYCLES in clk_ref cycles and ticks as it reaches zero. Only the
+/// proc0 ticks are taken, by core 0's SysTick, the others are counted for nothing.
+#[derive(Debug, Clone, Default)]
+pub struct Ticks {
+    ctrl: [u32; GENERATOR_COUNT],
+    cycles: [u32; GENERATOR_COUNT],
+    /// The clk_ref cycles to the next tick of each running generator
+    count: [u32; GENERATOR_COUNT],
+    /// The ticks of each generator not yet taken, which can be taken through a shared borrow of the bus
+    ticks: [Cell<u64>; GENERATOR_COUNT],
+    /// clk_ref, counted in system clock cycles
+    domain: ClockDomain,
+}
+
+impl Ticks {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether a generator is running
+    pub fn is_running(&self, generator: u32) -> bool {
+        self.ctrl[generator as usize] & TICK_CTRL_ENABLE != 0
+    }
+
+    /// The clk_ref cycles between a generator's ticks
+    pub fn cycles(&self, generator: u32) -> u32 {
+        self.cycles[generator as usize]
+    }
+
+    /// Sets the clk_ref and clk_sys frequencies, for counting clk_ref in system clock cycles
+    pub fn set_clocks(&mut self, clk_ref_hz: u32, clk_sys_hz: u32) {
+        self.domain.set(clk_ref_hz, clk_sys_hz);
+    }
+
+    /// The ticks a generator has made since they were last taken
+    pub fn take_ticks(&self, generator: u32) -> u64 {
+        self.ticks[generator as usize].take()
+    }
+
+    /// Whether a generator is running and ticks, which it can't with CYCLES at 0
+    fn is_ticking(&self, generator: usize) -> bool {
+        self.is_running(generator as u32) && self.cycles[generator] != 0
+    }
+
+    fn register(&self, offset: u32) -> u32 {
+        let generator = (offset / TICK_REGISTERS) as usize;
+        let running = self.is_running(generator as u32);
+        match offset % TICK_REGISTERS {
+            TICK_CTRL if running => self.ctrl[generator] | TICK_CTRL_RUNNING,
+            TICK_CTRL => self.ctrl[generator],
+            TICK_CYCLES => self.cycles[generator],
+            _ => self.count[generator],
+        }
+    }
+}
+
+impl Peripheral for Ticks {
+    fn read(&self, offset: u32, width: Width) -> AccessResult<u32> {
+        Ok(width.lane(self.register(offset & !3), offset))
+    }
+
+    fn write(&mut self, offset: u32, width: Width, value: u32) -> AccessResult<()> {
+        let offset = offset & !3;
+        let generator = (offset / TICK_REGISTERS) as usize;
+        let value = width.replicate(value);
+        match offset % TICK_REGISTERS {
+            TICK_CTRL => {
+                let enable = value & TICK_CTRL_ENABLE;
+                // A generator starts counting from CYCLES when it is enabled, and COUNT reads 0 while it is stopped
+                if enable == 0 {
+                    self.count[generator] = 0;
+                } else if !self.is_running(generator as u32) {
+                    self.count[generator] = self.cycles[generator];
+                }
+                self.ctrl[generator] = enable;
+            }
+            TICK_CYCLES => self.cycles[generator] = value & TICK_CYCLES_MASK,
+            // COUNT is read only
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn tick(&mut self, cycles: u64) {
+        let ref_cycles = self.domain.count(cycles);
+        for generator in 0..GENERATOR_COUNT {
+            if !self.is_ticking(generator) {
+                continue;
+            }
+
+            let count = self.count[generator] as u64;
+            if ref_cycles < count {
+                self.count[generator] -= ref_cycles as u32;
+                continue;
+            }
+
+            // COUNT goes back to CYCLES as it ticks, so any cycles past the first tick count down from there
+            let period = self.cycles[generator] as u64;
+            let past = ref_cycles - count;
+            let ticks = &self.ticks[generator];
+            ticks.set(ticks.get() + 1 + past / period);
+            self.count[generator] = (period - past % period) as u32;
+        }
+    }
+
+    /// Ticked as the first running generator ticks, so the ticks are passed on as they happen
+    fn next_tick(&self) -> Option<u64> {
+        let count = (0..GENERATOR_COUNT)
+            .filter(|&generator| self.is_ticking(generator))
+            .map(|generator| self.count[generator])
+            .min()?;
+        self.domain.until(count as u64)
+    }
+
+    /// The clk_ref and clk_sys frequencies are kept, they are set again as the clocks are worked out after a reset
+    fn reset(&mut self) {
+        *self = Self {
+            domain: self.domain,
+            ..Self::new()
+        };
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
@@ -0,0 +1,238 @@
+use std::any::Any;
+use std::collections::VecDeque;
+
+use crate::peripherals::clocks::ClockDomain;
+use crate::peripherals::{Peripheral, Width};
+use crate::AccessResult;
+
+pub const UART0_BASE: u32 = 0x40070000;
+pub const UART0_END: u32 = UART0_BASE + UART_SIZE;
+pub const UART1_BASE: u32 = 0x40078000;
+pub const UART_SIZE: u32 = 0x1000;
+
+pub const UART_DR: u32 = 0x000;
+pub const UART_FR: u32 = 0x018;
+/// The integer and fractional parts of the baud rate divisor
+pub const UART_IBRD: u32 = 0x024;
+pub const UART_FBRD: u32 = 0x028;
+pub const UART_LCR_H: u32 = 0x02c;
+pub const UART_CR: u32 = 0x030;
+
+/// Transmit FIFO empty
+pub const UART_FR_TXFE: u32 = 1 << 7;
+/// Transmit FIFO full
+pub const UART_FR_TXFF: u32 = 1 << 5;
+/// Receive FIFO empty
+pub const UART_FR_RXFE: u32 = 1 << 4;
+/// Set from a byte going into the transmit FIFO until the last one has been sent, stop bits and all
+pub const UART_FR_BUSY: u32 = 1 << 3;
+pub const UART_LCR_H_PEN: u32 = 1 << 1;
+pub const UART_LCR_H_STP2: u32 = 1 << 3;
+pub const UART_LCR_H_FEN: u32 = 1 << 4;
+/// Five to eight data bits, as 0 to 3
+pub const UART_LCR_H_WLEN_SHIFT: u32 = 5;
+pub const UART_LCR_H_WLEN_MASK: u32 = 0x3 << UART_LCR_H_WLEN_SHIFT;
+pub const UART_CR_UARTEN: u32 = 1 << 0;
+pub const UART_CR_TXE: u32 = 1 << 8;
+pub const UART_CR_RXE: u32 = 1 << 9;
+
+const REGISTER_COUNT: usize = (UART_SIZE / 4) as usize;
+/// Bytes the transmit FIFO holds while LCR_H.FEN is set, without it there is a single holding register
+const FIFO_DEPTH: usize = 32;
+
+/// A PL011 UART reduced to its transmit side. Bytes written to DR while the UART and its transmitter are enabled
+/// go into the transmit FIFO, and are sent one after another at the baud rate the divisors make of clk_peri, with
+/// the frame LCR_H sets up. The host collects each one from the output once its stop bits are sent. Nothing is ever
+/// received.
+#[derive(Debug, Clone)]
+pub struct Uart {
+    registers: [u32; REGISTER_COUNT],
+    /// Bytes transmitted since the host last took them
+    pub output: Vec<u8>,
+    /// Bytes written and waiting for the transmitter
+    tx_fifo: VecDeque<u8>,
+    /// The byte being sent and the bits of its frame still to go
+    transmitting: Option<(u8, u64)>,
+    clk_peri_hz: u32,
+    clk_sys_hz: u32,
+    /// Bit periods at the baud rate, counted in system clock cycles
+    bits: ClockDomain,
+}
+
+impl Default for Uart {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Uart {
+    pub fn new() -> Self {
+        let mut registers = [0; REGISTER_COUNT];
+        registers[(UART_CR / 4) as usize] = UART_CR_TXE | UART_CR_RXE;
+
+        Self {
+            registers,
+            output: Vec::new(),
+            tx_fifo: VecDeque::new(),
+            transmitting: None,
+            clk_peri_hz: 0,
+            clk_sys_hz: 0,
+            bits: ClockDomain::default(),
+        }
+    }
+
+    /// Puts the registers back to their reset values, keeping output the host hasn't taken yet and the clocks
+    pub fn reset(&mut self) {
+        *self = Self {
+            output: std::mem::take(&mut self.output),
+            clk_peri_hz: self.clk_peri_hz,
+            clk_sys_hz: self.clk_sys_hz,
+            ..Self::new()
+        };
+        self.update_baud_rate();
+    }
+
+    /// Sets the clk_peri and clk_sys frequencies, for timing the bits sent in system clock cycles
+    pub fn set_clocks(&mut self, clk_peri_hz: u32, clk_sys_hz: u32) {
+        self.clk_peri_hz = clk_peri_hz;
+        self.clk_sys_hz = clk_sys_hz;
+        self.update_baud_rate();
+    }
+
+    fn update_baud_rate(&mut self) {
+        self.bits.set(self.baud_rate(self.clk_peri_hz), self.clk_sys_hz);
+    }
+
+    /// Takes the bytes transmitted so far
+    pub fn take_output(&mut self) -> Vec<u8> {
+        std::mem::take(&mut self.output)
+    }
+
+    /// The baud rate the divisors give from a clk_peri frequency, 0 until they are set
+    pub fn baud_rate(&self, clk_peri_hz: u32) -> u32 {
+        // The divisor is IBRD + FBRD / 64 of clk_peri / 16
+        let divisor = 64 * self.register(UART_IBRD) as u64 + self.register(UART_FBRD) as u64;
+        match divisor {
+            0 => 0,
+            divisor => (4 * clk_peri_hz as u64 / divisor) as u32,
+        }
+    }
+
+    fn is_tx_enabled(&self) -> bool {
+        let enabled = UART_CR_UARTEN | UART_CR_TXE;
+        self.register(UART_CR) & enabled == enabled
+    }
+
+    fn fifo_depth(&self) -> usize {
+        match self.register(UART_LCR_H) & UART_LCR_H_FEN {
+            0 => 1,
+            _ => FIFO_DEPTH,
+        }
+    }
+
+    /// The bits in a frame: a start bit, five to eight data bits, a parity bit if enabled, and one or two stop bits
+    fn frame_bits(&self) -> u64 {
+        let lcr_h = self.register(UART_LCR_H);
+        let data = 5 + ((lcr_h & UART_LCR_H_WLEN_MASK) >> UART_LCR_H_WLEN_SHIFT);
+        let parity = (lcr_h & UART_LCR_H_PEN != 0) as u32;
+        let stop = 1 + (lcr_h & UART_LCR_H_STP2 != 0) as u32;
+        (1 + data + parity + stop) as u64
+    }
+
+    /// Starts sending the next byte in the FIFO, if the transmitter is idle and enabled
+    fn start_next(&mut self) {
+        if self.transmitting.is_some() || !self.is_tx_enabled() {
+            return;
+        }
+
+        self.transmitting = self.tx_fifo.pop_front().map(|byte| (byte, self.frame_bits()));
+    }
+
+    fn register(&self, offset: u32) -> u32 {
+        match offset & !3 {
+            UART_DR => 0,
+            UART_FR => self.flags(),
+            offset => self.registers[(offset / 4) as usize],
+        }
+    }
+
+    fn flags(&self) -> u32 {
+        let mut flags = UART_FR_RXFE;
+        if self.tx_fifo.is_empty() {
+            flags |= UART_FR_TXFE;
+        }
+        if self.tx_fifo.len() >= self.fifo_depth() {
+            flags |= UART_FR_TXFF;
+        }
+        if self.transmitting.is_some() || !self.tx_fifo.is_empty() {
+            flags |= UART_FR_BUSY;
+        }
+        flags
+    }
+
+    pub fn read(&self, offset: u32) -> u8 {
+        (self.register(offset) >> (8 * (offset & 3))) as u8
+    }
+
+    pub fn write(&mut self, offset: u32, value: u8) {
+        let index = (offset / 4) as usize;
+        let shift = 8 * (offset & 3);
+        self.registers[index] = (self.registers[index] & !(0xff << shift)) | (value as u32) << shift;
+
+        // Bytes written while the FIFO is full are lost
+        if offset == UART_DR && self.is_tx_enabled() && self.tx_fifo.len() < self.fifo_depth() {
+            self.tx_fifo.push_back(value);
+        }
+        if matches!(offset & !3, UART_IBRD | UART_FBRD) {
+            self.update_baud_rate();
+        }
+        self.start_next();
+    }
+}
+
+impl Peripheral for Uart {
+    fn read(&self, offset: u32, width: Width) -> AccessResult<u32> {
+        Ok((0..width.bytes()).fold(0, |value, i| value | (Uart::read(self, offset + i) as u32) << (8 * i)))
+    }
+
+    fn write(&mut self, offset: u32, width: Width, value: u32) -> AccessResult<()> {
+        for i in 0..width.bytes() {
+            Uart::write(self, offset + i, (value >> (8 * i)) as u8);
+        }
+
+        Ok(())
+    }
+
+    fn tick(&mut self, cycles: u64) {
+        let mut bits = self.bits.count(cycles);
+        while let Some((byte, left)) = self.transmitting {
+            if bits < left {
+                self.transmitting = Some((byte, left - bits));
+                break;
+            }
+
+            bits -= left;
+            self.output.push(byte);
+            self.transmitting = None;
+            self.start_next();
+        }
+    }
+
+    /// Ticked as the byte being sent finishes, to start the next
+    fn next_tick(&self) -> Option<u64> {
+        let (_, left) = self.transmitting?;
+        self.bits.until(left)
+    }
+
+    fn reset(&mut self) {
+        Uart::reset(self);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
@@ -0,0 +1,207 @@
+use std::any::Any;
+
+use crate::peripherals::clocks::ClockDomain;
+use crate::peripherals::{read_modify_write, Peripheral, Width};
+use crate::{AccessResult, BusError};
+
+pub const XOSC_BASE: u32 = 0x40048000;
+pub const XOSC_SIZE: u32 = 0x14;
+
+pub const XOSC_CTRL: u32 = 0x00;
+pub const XOSC_STATUS: u32 = 0x04;
+pub const XOSC_DORMANT: u32 = 0x08;
+pub const XOSC_STARTUP: u32 = 0x0c;
+pub const XOSC_COUNT: u32 = 0x10;
+
+/// The crystal on the Pico 2
+pub const XOSC_HZ: u32 = 12_000_000;
+
+pub const XOSC_CTRL_ENABLE_SHIFT: u32 = 12;
+pub const XOSC_CTRL_ENABLE: u32 = 0xfab;
+pub const XOSC_CTRL_DISABLE: u32 = 0xd1e;
+pub const XOSC_CTRL_FREQ_RANGE_MASK: u32 = 0xfff;
+pub const XOSC_FREQ_RANGE_1_15MHZ: u32 = 0xaa0;
+pub const XOSC_FREQ_RANGE_40_100MHZ: u32 = 0xaa3;
+
+pub const XOSC_STATUS_ENABLED: u32 = 1 << 12;
+/// An invalid value was written to CTRL, write 1 to clear
+pub const XOSC_STATUS_BADWRITE: u32 = 1 << 24;
+pub const XOSC_STATUS_STABLE: u32 = 1 << 31;
+
+/// Multiplies DELAY by 4
+pub const XOSC_STARTUP_X4: u32 = 1 << 20;
+/// The startup delay, in units of 256 crystal cycles
+pub const XOSC_STARTUP_DELAY_MASK: u32 = 0x3fff;
+
+pub const XOSC_DORMANT_VALUE_DORMANT: u32 = 0x636f6d61;
+pub const XOSC_DORMANT_VALUE_WAKE: u32 = 0x77616b65;
+
+/// Disabled, for the 1 to 15 MHz range
+const CTRL_RESET: u32 = XOSC_CTRL_DISABLE << XOSC_CTRL_ENABLE_SHIFT | XOSC_FREQ_RANGE_1_15MHZ;
+const STARTUP_RESET: u32 = 0xc4;
+
+/// The crystal oscillator. It comes up STARTUP.DELAY * 256 crystal cycles after it is enabled, then STATUS shows it
+/// stable, and COUNT counts down at the crystal frequency. Dormant mode isn't modelled, so the oscillator keeps
+/// running whatever DORMANT is set to.
+#[derive(Debug, Clone)]
+pub struct Xosc {
+    ctrl: u32,
+    startup: u32,
+    dormant: u32,
+    count: u32,
+    enabled: bool,
+    badwrite: bool,
+    /// The crystal cycles until the oscillator is stable, 0 once it is
+    starting: u64,
+    /// The crystal cycles in system clock cycles
+    domain: ClockDomain,
+}
+
+impl Default for Xosc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Xosc {
+    pub fn new() -> Self {
+        Self {
+            ctrl: CTRL_RESET,
+            startup: STARTUP_RESET,
+            dormant: 0,
+            count: 0,
+            enabled: false,
+            badwrite: false,
+            starting: 0,
+            domain: ClockDomain::default(),
+        }
+    }
+
+    pub fn is_stable(&self) -> bool {
+        self.enabled && self.starting == 0
+    }
+
+    /// The frequency the oscillator runs at, 0 while it is disabled
+    pub fn frequency(&self) -> u32 {
+        if self.enabled {
+            XOSC_HZ
+        } else {
+            0
+        }
+    }
+
+    /// Sets the clk_sys frequency the startup delay and COUNT are timed in
+    pub fn set_clk_sys(&mut self, hz: u32) {
+        self.domain.set(self.frequency(), hz);
+    }
+
+    fn write_ctrl(&mut self, value: u32) {
+        let range = value & XOSC_CTRL_FREQ_RANGE_MASK;
+        if !(XOSC_FREQ_RANGE_1_15MHZ..=XOSC_FREQ_RANGE_40_100MHZ).contains(&range) {
+            self.badwrite = true;
+        }
+
+        match value >> XOSC_CTRL_ENABLE_SHIFT & 0xfff {
+            XOSC_CTRL_ENABLE if !self.enabled => {
+                self.enabled = true;
+                let delay = (self.startup & XOSC_STARTUP_DELAY_MASK) as u64 * 256;
+                self.starting = if self.startup & XOSC_STARTUP_X4 != 0 { delay * 4 } else { delay };
+            }
+            XOSC_CTRL_ENABLE => {}
+            XOSC_CTRL_DISABLE => {
+                self.enabled = false;
+                self.starting = 0;
+            }
+            // The SDK sets the range with the enable field clear before it sets ENABLE
+            0 => {}
+            _ => self.badwrite = true,
+        }
+
+        self.ctrl = value & 0x00ffffff;
+    }
+
+    fn status(&self) -> u32 {
+        let mut status = self.ctrl & 0x3;
+        if self.enabled {
+            status |= XOSC_STATUS_ENABLED;
+        }
+        if self.badwrite {
+            status |= XOSC_STATUS_BADWRITE;
+        }
+        if self.is_stable() {
+            status |= XOSC_STATUS_STABLE;
+        }
+        status
+    }
+
+    fn register(&self, offset: u32) -> AccessResult<u32> {
+        match offset {
+            XOSC_CTRL => Ok(self.ctrl),
+            XOSC_STATUS => Ok(self.status()),
+            XOSC_DORMANT => Ok(self.dormant),
+            XOSC_STARTUP => Ok(self.startup),
+            XOSC_COUNT => Ok(self.count),
+            _ => Err(BusError::Unmapped),
+        }
+    }
+}
+
+impl Peripheral for Xosc {
+    fn read(&self, offset: u32, width: Width) -> AccessResult<u32> {
+        Ok(width.lane(self.register(offset & !3)?, offset))
+    }
+
+    fn write(&mut self, offset: u32, width: Width, value: u32) -> AccessResult<()> {
+        let value = width.replicate(value);
+        match offset & !3 {
+            XOSC_CTRL => self.write_ctrl(value),
+            XOSC_STATUS if value & XOSC_STATUS_BADWRITE != 0 => self.badwrite = false,
+            XOSC_STATUS => {}
+            XOSC_DORMANT => self.dormant = value,
+            XOSC_STARTUP => self.startup = value & (XOSC_STARTUP_X4 | XOSC_STARTUP_DELAY_MASK),
+            XOSC_COUNT => self.count = value & 0xffff,
+            _ => return Err(BusError::Unmapped),
+        }
+
+        Ok(())
+    }
+
+    /// Writing STATUS back whole would clear BADWRITE whenever it is set
+    fn write_masked(&mut self, offset: u32, mask: u32, value: u32) -> AccessResult<()> {
+        match offset {
+            XOSC_STATUS => self.write(offset, Width::Word, value & mask),
+            _ => read_modify_write(self, offset, mask, value),
+        }
+    }
+
+    fn tick(&mut self, cycles: u64) {
+        let crystal_cycles = self.domain.count(cycles);
+        self.starting = self.starting.saturating_sub(crystal_cycles);
+        self.count = self.count.saturating_sub(crystal_cycles.min(u32::MAX as u64) as u32);
+    }
+
+    /// Ticked at every crystal cycle while COUNT counts, so it reads right, otherwise when it becomes stable
+    fn next_tick(&self) -> Option<u64> {
+        match (self.count, self.starting) {
+            (0, 0) => None,
+            (0, starting) => self.domain.until(starting),
+            _ => self.domain.until(1),
+        }
+    }
+
+    /// The clk_sys frequency is kept, it is set again as the clocks are worked out after a reset
+    fn reset(&mut self) {
+        *self = Self {
+            domain: self.domain,
+            ..Self::new()
+        };
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
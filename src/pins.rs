@@ -0,0 +1,92 @@
+use std::cell::Ref;
+
+use crate::peripherals::pads_bank0::{PadsBank0, PADS_BANK0_BASE};
+use crate::RP2350;
+
+pub use crate::peripherals::pads_bank0::{PinState, Source};
+
+/// A change to what the chip drives onto a pin
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutputChange {
+    pub pin: u32,
+    /// The level the chip now drives the pin to, None once it stops driving it
+    pub output: Option<bool>,
+    /// The core cycle of the instruction that made the change
+    pub cycle: u64,
+}
+
+/// Called with every change to the chip's outputs, and the pins to drive inputs back from
+pub type OutputCallback = Box<dyn FnMut(&mut Pins, OutputChange)>;
+
+/// The bank 0 pins as a test bench outside the chip sees them. The host drives pins to levels or holds them at
+/// voltages, and is called back as the chip's outputs change. Pins are numbered as GPIOs, 0 to 47.
+pub struct Pins<'a> {
+    mcu: &'a mut RP2350,
+}
+
+impl<'a> Pins<'a> {
+    /// Drives a pin to a level, as a button or another chip would
+    pub fn set_input(&mut self, pin: u32, level: bool) {
+        self.mcu.memory_mut().drive_pin(pin, Some(level));
+    }
+
+    /// Holds a pin at a voltage. Its input sees it as high above half the IO supply.
+    pub fn set_analog(&mut self, pin: u32, volts: f32) {
+        self.mcu.memory_mut().drive_pin_analog(pin, volts);
+    }
+
+    /// Stops driving a pin, leaving it to the chip and the pad's pulls
+    pub fn release(&mut self, pin: u32) {
+        self.mcu.memory_mut().drive_pin(pin, None);
+    }
+
+    /// The level of a pin and what puts it there
+    pub fn pin_state(&self, pin: u32) -> PinState {
+        self.pads().state(pin)
+    }
+
+    /// Whether a pin is high
+    pub fn is_high(&self, pin: u32) -> bool {
+        self.pin_state(pin).level
+    }
+
+    /// Registers a callback for every change to what the chip drives onto the pins, from the next instruction on
+    pub fn on_output_change(&mut self, callback: impl FnMut(&mut Pins, OutputChange) + 'static) {
+        self.mcu.pin_callbacks.push(Box::new(callback));
+    }
+
+    fn pads(&self) -> Ref<'_, PadsBank0> {
+        self.mcu.memory().peripherals.get(PADS_BANK0_BASE).expect("PADS_BANK0 is always on the bus")
+    }
+}
+
+impl RP2350 {
+    /// The pins, for driving the chip's inputs and watching its outputs from outside
+    pub fn pins(&mut self) -> Pins<'_> {
+        Pins { mcu: self }
+    }
+
+    /// Passes the changes to the outputs since the last call on to the callbacks, stamped with the cycle. Changes
+    /// are dropped when nothing is listening.
+    pub(crate) fn dispatch_output_changes(&mut self, cycle: u64) {
+        if !self.memory().has_output_changes() {
+            return;
+        }
+
+        let changes = self.memory_mut().take_output_changes();
+        if self.pin_callbacks.is_empty() {
+            return;
+        }
+
+        // The callbacks are taken out while they run so they can drive the pins, any they register are kept
+        let mut callbacks = std::mem::take(&mut self.pin_callbacks);
+        for (pin, output) in changes {
+            let change = OutputChange { pin, output, cycle };
+            for callback in &mut callbacks {
+                callback(&mut self.pins(), change);
+            }
+        }
+        callbacks.append(&mut self.pin_callbacks);
+        self.pin_callbacks = callbacks;
+    }
+}
@@ -1,9 +1,36 @@
 use std::any::Any;
 
+use crate::bootrom::{self, Bootrom, RomFunction, ROM_SIZE, ROM_START_ADDRESS};
+use crate::cortex_m33::decode_cache::CODE_PAGE_SIZE;
+use crate::cortex_m33::fault::Fault;
+use crate::cortex_m33::nvic::IRQ_COUNT;
 use crate::cortex_m33::registers::Register;
 use crate::cortex_m33::{CortexM33, OpCode};
-use crate::MemoryInterface;
-use anyhow::{Context, Result};
+use crate::{AccessResult, BusError, MemoryInterface};
+use crate::loader::elf::{self, ElfError, SymbolTable};
+use crate::loader::hex::{self, HexError, HexErrorKind};
+use crate::loader::uf2::{self, Uf2Error, Uf2ErrorKind, Uf2Report};
+use crate::peripherals::bus::{Bus, ATOMIC_BLOCK_SIZE};
+use crate::peripherals::clocks::{Clocks, CLOCKS_BASE, CLOCKS_SIZE};
+use crate::peripherals::gpio::PinOutputs;
+use crate::peripherals::io_bank0::{IoBank0, IO_BANK0_BASE, IO_BANK0_SIZE};
+use crate::peripherals::pads_bank0::{PadsBank0, PADS_BANK0_BASE, PADS_BANK0_SIZE};
+use crate::peripherals::pll::{Pll, PLL_SIZE, PLL_SYS_BASE, PLL_USB_BASE};
+use crate::peripherals::resets::{
+    Resets, RESETS_BASE, RESETS_SIZE, RESET_IO_BANK0, RESET_PADS_BANK0, RESET_PLL_SYS, RESET_PLL_USB, RESET_UART0,
+    RESET_UART1,
+};
+use crate::peripherals::rosc::{Rosc, ROSC_BASE, ROSC_SIZE};
+use crate::peripherals::sio::{Sio, SIO_SIZE};
+use crate::peripherals::ticks::{Ticks, TICKS_BASE, TICKS_SIZE, TICK_PROC0};
+use crate::peripherals::uart::{Uart, UART0_BASE, UART1_BASE, UART_SIZE};
+use crate::peripherals::xosc::{Xosc, XOSC_BASE, XOSC_SIZE};
+use crate::peripherals::Width;
+use crate::pins::OutputCallback;
+use crate::semihosting::{Semihosting, SEMIHOSTING_BKPT};
+use crate::trace::TraceSink;
+use anyhow::{ensure, Result};
+use byteorder::{ByteOrder, LittleEndian};
 
 const KB_OF_RAM: usize = 520;
 const MB_OF_FLASH: usize = 4;
@@ -29,108 +56,514 @@ Core-local Peripherals (SIO) 	0xd0000000
 Cortex-M33 private registers 	0xe0000000
 */
 pub struct RP2350Memory {
+    pub rom: Box<[u8; ROM_SIZE]>,
+
     // SRAM is partitioned into 10 banks that act like one
     pub sram: [u8; KB_OF_RAM * KB],
 
     // Has to be on the heap, absolutely blows up the stack
     pub flash: Box<[u8; MB_OF_FLASH * MB]>,
+
+    /// Everything from the APB segment up
+    pub peripherals: Bus,
+
+    pub(crate) pin_outputs: PinOutputs,
+
+    /// Which code pages of ROM, flash then SRAM have decoded instructions cached, and so need writes reported
+    code_pages: Vec<bool>,
+    code_writes: Vec<u32>,
+}
+
+const ROM_PAGES: usize = ROM_SIZE.div_ceil(CODE_PAGE_SIZE as usize);
+const FLASH_PAGES: usize = (MB_OF_FLASH * MB).div_ceil(CODE_PAGE_SIZE as usize);
+const SRAM_PAGES: usize = (KB_OF_RAM * KB).div_ceil(CODE_PAGE_SIZE as usize);
+
+impl Default for RP2350Memory {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl RP2350Memory {
+    /// Writes bytes the way a programmer or debugger would, so unlike a bus write it can fill flash.
+    pub fn program(&mut self, address: u32, data: &[u8]) -> AccessResult<()> {
+        self.program_padded(address, data, data.len())
+    }
+
+    /// Programs the data followed by zeros, up to length bytes in all. The whole range is checked before anything
+    /// is written, so a length read from a file doesn't have to be allocated first.
+    pub fn program_padded(&mut self, address: u32, data: &[u8], length: usize) -> AccessResult<()> {
+        let length = length.max(data.len());
+        let (memory, offset) = match address {
+            FLASH_START_ADDRESS..RAM_START_ADDRESS => (&mut self.flash[..], address - FLASH_START_ADDRESS),
+            RAM_START_ADDRESS..APB_START_ADDRESS => (&mut self.sram[..], address - RAM_START_ADDRESS),
+            _ => return Err(BusError::Unmapped),
+        };
+
+        let offset = offset as usize;
+        let (written, padding) = memory
+            .get_mut(offset..offset + length)
+            .ok_or(BusError::Unmapped)?
+            .split_at_mut(data.len());
+        written.copy_from_slice(data);
+        padding.fill(0);
+
+        self.code_written(address, length as u32);
+        Ok(())
+    }
+
+    /// Drops the decoded instructions of everything in flash, as flushing the XIP cache makes the core fetch
+    /// flash again
+    pub fn flush_xip(&mut self) {
+        self.code_written(FLASH_START_ADDRESS, (MB_OF_FLASH * MB) as u32);
+    }
+
+    /// Reports a change to length bytes from the address that didn't come through a bus write, such as flash
+    /// being erased or programmed, so decoded instructions from there are dropped
+    pub fn code_written(&mut self, address: u32, length: u32) {
+        if length == 0 {
+            return;
+        }
+
+        let first = address & !(CODE_PAGE_SIZE - 1);
+        let last = address.saturating_add(length - 1) & !(CODE_PAGE_SIZE - 1);
+        for page in (first..=last).step_by(CODE_PAGE_SIZE as usize) {
+            self.page_written(page);
+        }
+    }
+
+    fn page_written(&mut self, address: u32) {
+        if let Some(index) = Self::code_page(address) {
+            if self.code_pages[index] {
+                self.code_pages[index] = false;
+                self.code_writes.push(address & !(CODE_PAGE_SIZE - 1));
+            }
+        }
+    }
+
+    /// The index of the code page holding the address, None outside ROM, flash and SRAM
+    fn code_page(address: u32) -> Option<usize> {
+        let page = |start: u32, pages: usize| {
+            let index = (address.checked_sub(start)? / CODE_PAGE_SIZE) as usize;
+            (index < pages).then_some(index)
+        };
+
+        page(ROM_START_ADDRESS, ROM_PAGES)
+            .or_else(|| page(FLASH_START_ADDRESS, FLASH_PAGES).map(|index| ROM_PAGES + index))
+            .or_else(|| page(RAM_START_ADDRESS, SRAM_PAGES).map(|index| ROM_PAGES + FLASH_PAGES + index))
+    }
+
+    fn peripherals() -> Bus {
+        let mut bus = Bus::new();
+        bus.register(CLOCKS_BASE, CLOCKS_SIZE, Clocks::new());
+        bus.register(RESETS_BASE, RESETS_SIZE, Resets::new());
+        bus.register(XOSC_BASE, XOSC_SIZE, Xosc::new());
+        bus.register(PLL_SYS_BASE, PLL_SIZE, Pll::new());
+        bus.register(PLL_USB_BASE, PLL_SIZE, Pll::new());
+        bus.register(UART0_BASE, UART_SIZE, Uart::new());
+        bus.register(UART1_BASE, UART_SIZE, Uart::new());
+        bus.register(IO_BANK0_BASE, IO_BANK0_SIZE, IoBank0::new());
+        bus.register(PADS_BANK0_BASE, PADS_BANK0_SIZE, PadsBank0::new());
+        bus.register(ROSC_BASE, ROSC_SIZE, Rosc::new());
+        bus.register(TICKS_BASE, TICKS_SIZE, Ticks::new());
+        bus.register(SIO_START_ADDRESS, SIO_SIZE, Sio::new());
+        bus
+    }
+
+    pub fn uart0(&mut self) -> &mut Uart {
+        self.peripherals.get_mut(UART0_BASE).expect("UART0 is always on the bus")
+    }
+
+    pub fn uart1(&mut self) -> &mut Uart {
+        self.peripherals.get_mut(UART1_BASE).expect("UART1 is always on the bus")
+    }
+
+    pub fn sio(&mut self) -> &mut Sio {
+        self.peripherals.get_mut(SIO_START_ADDRESS).expect("The SIO is always on the bus")
+    }
+
+    /// The bytes of ROM, flash or SRAM at the address
+    fn bytes(&self, address: u32, length: usize) -> AccessResult<&[u8]> {
+        let (memory, offset) = match address {
+            ROM_START_ADDRESS..FLASH_START_ADDRESS => (&self.rom[..], address),
+            FLASH_START_ADDRESS..RAM_START_ADDRESS => (&self.flash[..], address - FLASH_START_ADDRESS),
+            RAM_START_ADDRESS..APB_START_ADDRESS => (&self.sram[..], address - RAM_START_ADDRESS),
+            _ => return Err(BusError::Unmapped),
+        };
+
+        let offset = offset as usize;
+        memory.get(offset..offset + length).ok_or(BusError::Unmapped)
+    }
+
+    /// Writes a peripheral register, then carries any change through the resets and clocks and to the pins
+    fn write_peripheral(&mut self, address: u32, width: Width, value: u32) -> AccessResult<()> {
+        self.peripherals.write(address, width, value)?;
+
+        // Past the registers are their atomic aliases
+        match address & !(ATOMIC_BLOCK_SIZE - 1) {
+            RESETS_BASE => {
+                self.apply_resets();
+                self.update_clocks();
+            }
+            CLOCKS_BASE | XOSC_BASE | ROSC_BASE | PLL_SYS_BASE | PLL_USB_BASE => self.update_clocks(),
+            _ => {}
+        }
+
+        self.update_pins();
+        Ok(())
+    }
+
+    /// Puts the blocks RESETS has just put into reset back to their reset values
+    fn apply_resets(&mut self) {
+        let asserted = self.resets().take_asserted();
+        for (block, base) in RESET_BLOCKS {
+            if asserted >> block & 1 != 0 {
+                self.peripherals.reset_peripheral(base);
+            }
+        }
+    }
+
+    /// Puts every peripheral back to its reset values
+    pub fn reset_peripherals(&mut self) {
+        self.peripherals.reset();
+        self.update_clocks();
+        self.update_pins();
+    }
+
+    /// Writes to SRAM, as the only memory below the peripherals the bus can write
+    fn write_bytes(&mut self, address: u32, data: &[u8]) -> AccessResult<()> {
+        if address < RAM_START_ADDRESS {
+            return Err(BusError::ReadOnly);
+        }
+
+        let offset = address.wrapping_sub(RAM_START_ADDRESS) as usize;
+        self.sram
+            .get_mut(offset..offset + data.len())
+            .ok_or(BusError::Unmapped)?
+            .copy_from_slice(data);
+
+        self.code_written(address, data.len() as u32);
+        Ok(())
+    }
+
     pub fn new() -> Self {
         Self {
+            rom: bootrom::native_rom(),
             sram: [0; KB_OF_RAM * 1024],
             // The box has to be defined like this, cuz for whatever reason this causes stack overflows still in tests. https://github.com/rust-lang/rust/issues/53827
             flash: vec![0xff; MB_OF_FLASH * 1024 * 1024]
                 .into_boxed_slice()
                 .try_into()
                 .unwrap(),
+            peripherals: Self::peripherals(),
+            pin_outputs: PinOutputs::default(),
+            code_pages: vec![false; ROM_PAGES + FLASH_PAGES + SRAM_PAGES],
+            code_writes: Vec::new(),
         }
     }
 }
 
+/// The modelled blocks RESETS controls, by their bit and base address
+const RESET_BLOCKS: [(u32, u32); 6] = [
+    (RESET_IO_BANK0, IO_BANK0_BASE),
+    (RESET_PADS_BANK0, PADS_BANK0_BASE),
+    (RESET_PLL_SYS, PLL_SYS_BASE),
+    (RESET_PLL_USB, PLL_USB_BASE),
+    (RESET_UART0, UART0_BASE),
+    (RESET_UART1, UART1_BASE),
+];
+
+/// Where the bootrom expects the vector table of a flash image, unless its IMAGE_DEF says otherwise
+pub const BOOT_VECTOR_TABLE: u32 = FLASH_START_ADDRESS;
+
 pub struct RP2350 {
     pub cortex_m33: CortexM33,
+    pub bootrom: Bootrom,
+    /// Symbols from the last ELF image loaded, for resolving addresses to names
+    pub symbols: SymbolTable,
+    pub semihosting: Semihosting,
+    /// Gets a record of every instruction executed, tracing is off without one
+    pub tracer: Option<Box<dyn TraceSink>>,
+    /// The IRQ lines the peripherals were asserting when last looked at
+    peripheral_irqs: u64,
+    /// Called with each change to the outputs on the pins
+    pub(crate) pin_callbacks: Vec<OutputCallback>,
 }
 
-impl<'a> MemoryInterface<u32> for RP2350Memory {
-    fn read(&self, address: u32) -> u8 {
+impl MemoryInterface<u32> for RP2350Memory {
+    fn read(&self, address: u32) -> AccessResult<u8> {
         match address {
-            FLASH_START_ADDRESS..RAM_START_ADDRESS => {
-                let flash_address = address - FLASH_START_ADDRESS;
-                self.flash[flash_address as usize]
-            }
-            RAM_START_ADDRESS..APB_START_ADDRESS => {
-                let ram_address = address - RAM_START_ADDRESS;
-                self.sram[ram_address as usize]
-            }
-            _ => unimplemented!("File a github issue and this will get implmented"),
+            APB_START_ADDRESS.. => Ok(self.peripherals.read(address, Width::Byte)? as u8),
+            _ => Ok(self.bytes(address, 1)?[0]),
         }
     }
 
-    fn write(&mut self, address: u32, value: u8) {
+    fn write(&mut self, address: u32, value: u8) -> AccessResult<()> {
         match address {
-            0x00000000..FLASH_START_ADDRESS => {
-                panic!("What the fuck are you doing? ROM is readonly.")
-            }
-            FLASH_START_ADDRESS..RAM_START_ADDRESS => {
-                panic!("What the fuck are you doing? Flash/XIP is readonly.")
-            }
-            RAM_START_ADDRESS..APB_START_ADDRESS => {
-                self.sram[address as usize - 0x20000000 as usize] = value;
-            }
-            _ => {
-                unimplemented!("File a github issue and this will get implmeented")
+            APB_START_ADDRESS.. => self.write_peripheral(address, Width::Byte, value as u32),
+            _ => self.write_bytes(address, &[value]),
+        }
+    }
+
+    // Peripherals see wider accesses whole rather than as bytes
+    fn read_u16(&self, address: u32) -> AccessResult<u16> {
+        match address {
+            APB_START_ADDRESS.. => Ok(self.peripherals.read(address, Width::Halfword)? as u16),
+            _ => Ok(LittleEndian::read_u16(self.bytes(address, 2)?)),
+        }
+    }
+
+    fn read_u32(&self, address: u32) -> AccessResult<u32> {
+        match address {
+            APB_START_ADDRESS.. => self.peripherals.read(address, Width::Word),
+            _ => Ok(LittleEndian::read_u32(self.bytes(address, 4)?)),
+        }
+    }
+
+    fn write_u16(&mut self, address: u32, value: u16) -> AccessResult<()> {
+        match address {
+            APB_START_ADDRESS.. => self.write_peripheral(address, Width::Halfword, value as u32),
+            _ => self.write_bytes(address, &value.to_le_bytes()),
+        }
+    }
+
+    fn write_u32(&mut self, address: u32, value: u32) -> AccessResult<()> {
+        match address {
+            APB_START_ADDRESS.. => self.write_peripheral(address, Width::Word, value),
+            _ => self.write_bytes(address, &value.to_le_bytes()),
+        }
+    }
+
+    fn watch_code(&mut self, address: u32) -> bool {
+        match Self::code_page(address) {
+            Some(index) => {
+                self.code_pages[index] = true;
+                true
             }
-        }   
+            None => false,
+        }
+    }
+
+    fn take_code_writes(&mut self) -> Vec<u32> {
+        std::mem::take(&mut self.code_writes)
     }
 
     fn as_any(&self) -> &(dyn Any + 'static) {
         self
     }
+
+    fn as_any_mut(&mut self) -> &mut (dyn Any + 'static) {
+        self
+    }
 }
 
 
+impl Default for RP2350 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl RP2350 {
     pub fn new() -> Self {
         let memory = RP2350Memory::new();
         let cortex = CortexM33::new(Box::new(memory));
-        RP2350 {
-            cortex_m33: cortex
+        let mut rp2350 = RP2350 {
+            cortex_m33: cortex,
+            bootrom: Bootrom::new(),
+            symbols: SymbolTable::default(),
+            semihosting: Semihosting::new(),
+            tracer: None,
+            peripheral_irqs: 0,
+            pin_callbacks: Vec::new(),
+        };
+        // Passes the clocks out of reset on to the blocks that count them
+        rp2350.memory_mut().update_clocks();
+        rp2350
+    }
+
+    pub fn memory(&self) -> &RP2350Memory {
+        self.cortex_m33
+            .memory
+            .as_any()
+            .downcast_ref()
+            .expect("The core is always attached to RP2350Memory")
+    }
+
+    pub fn memory_mut(&mut self) -> &mut RP2350Memory {
+        self.cortex_m33
+            .memory
+            .as_any_mut()
+            .downcast_mut()
+            .expect("The core is always attached to RP2350Memory")
+    }
+
+    /// Resets the chip as at power on. The core and its peripherals go back to their reset values, then the
+    /// bootrom boots from flash, natively or by running a loaded ROM. Memory contents are kept.
+    pub fn reset(&mut self) {
+        self.cortex_m33.reset();
+        self.memory_mut().reset_peripherals();
+        self.peripheral_irqs = 0;
+        self.semihosting.exit = None;
+
+        let boot = if self.bootrom.native {
+            self.boot()
+        } else {
+            self.boot_loaded_rom()
+        };
+        // Nothing to boot, or a fault while taking reset, leaves the core with nothing it can run
+        self.cortex_m33.locked_up = boot.is_err();
+        self.bootrom.boot = Some(boot);
+    }
+
+    /// Replaces the synthetic bootrom with a ROM image, such as the official 32 kB RP2350 bootrom. The ROM stays
+    /// read only to the core, and from the next reset the core boots by running it. Peripherals the genuine ROM
+    /// touches that aren't emulated will fault.
+    pub fn load_rom(&mut self, image: &[u8]) -> Result<()> {
+        ensure!(
+            image.len() <= ROM_SIZE,
+            "ROM image is {} bytes, more than the {} byte ROM",
+            image.len(),
+            ROM_SIZE
+        );
+
+        let memory = self.memory_mut();
+        memory.rom.fill(0);
+        memory.rom[..image.len()].copy_from_slice(image);
+        memory.code_written(ROM_START_ADDRESS, ROM_SIZE as u32);
+        self.bootrom.native = false;
+
+        Ok(())
+    }
+
+    /// Loads an Intel HEX image the way a programmer would, so data can land in flash as well as SRAM. Returns
+    /// the start address record, if the file has one.
+    pub fn load_hex(&mut self, source: &str) -> Result<Option<u32>> {
+        let file = hex::parse(source)?;
+
+        for data in &file.data {
+            self.memory_mut()
+                .program(data.address, &data.bytes)
+                .map_err(|error| HexError {
+                    line: data.line,
+                    kind: HexErrorKind::Program { address: data.address, error },
+                })?;
         }
+
+        Ok(file.start_address)
     }
 
-    pub fn load_hex(&mut self, source: &str) -> Result<()> {
-        for line in source.split('\n') {
-            if line.get(..=0) == Some(":") && line.get(7..9) == Some("00") {
-                let bytes = line.get(1..3).context("Failed to get bytes from line")?;
-                let bytes = usize::from_str_radix(bytes, 16)?;
+    /// Loads the PT_LOAD segments of an ELF image at their load addresses, zeroing any part of a segment past
+    /// its file data, and keeps the image's symbols. Returns the entry point.
+    pub fn load_elf(&mut self, bytes: &[u8]) -> Result<u32> {
+        let file = elf::parse(bytes)?;
 
-                let addr = line.get(3..7).context("Failed to get address from line")?;
-                let addr = usize::from_str_radix(addr, 16)?;
+        for segment in &file.segments {
+            let address = segment.physical_address;
+            self.memory_mut()
+                .program_padded(address, &segment.data, segment.memory_size as usize)
+                .map_err(|error| ElfError::Program { address, error })?;
+        }
 
-                for i in 0..bytes {
-                    let start = 9 + i * 2;
-                    let value = line
-                        .get(start..(start + 2))
-                        .context("Failed to get value from bytes")?;
-                    let value = u8::from_str_radix(value, 16)?;
+        self.symbols = file.symbols;
+        Ok(file.entry)
+    }
 
-                    self.cortex_m33.memory.write((addr + i) as u32, value);
-                }
-            }
+    /// Loads the blocks of a UF2 file that are for main flash and in one of the families, the way the bootrom
+    /// would on a drag and drop. Missing and duplicated blocks don't stop the load, they are in the report.
+    pub fn load_uf2(&mut self, bytes: &[u8], family_ids: &[u32]) -> Result<Uf2Report> {
+        let blocks = uf2::parse(bytes)?;
+        let (selected, report) = uf2::select(&blocks, family_ids);
+
+        for (index, block) in selected {
+            let address = block.target_address;
+            self.memory_mut()
+                .program(address, &block.payload)
+                .map_err(|error| Uf2Error {
+                    block: index,
+                    kind: Uf2ErrorKind::Program { address, error },
+                })?;
         }
 
-        Ok(())
+        Ok(report)
+    }
+
+    /// Sets the level of an IRQ line into the NVIC, as a peripheral interrupt output would.
+    pub fn set_irq(&mut self, irq: u8, level: bool) {
+        self.cortex_m33.set_irq_line(irq, level);
     }
 
-    pub fn get_opcode(&self) -> OpCode {
+    pub fn get_opcode(&self) -> Result<OpCode, Fault> {
         let address = self.cortex_m33.registers.pc.get();
         OpCode::from_address(&self.cortex_m33, address)
     }
 
+    /// Whether the chip has stopped running instructions, because the core locked up, is halted on a BKPT, or the
+    /// firmware exited through semihosting
+    pub fn is_halted(&self) -> bool {
+        self.cortex_m33.locked_up || self.cortex_m33.breakpoint.is_some() || self.semihosting.exit.is_some()
+    }
+
+    /// Executes the instruction at the PC, or enters the highest priority pending exception in its place, as one
+    /// core cycle. A call to a ROM function is run natively as a single instruction, and a semihosting BKPT runs
+    /// its call and carries on. A reset requested through AIRCR happens first. A fault is recorded and its
+    /// exception pended rather than returned, and a halted chip does nothing but count the cycle. Instructions are
+    /// passed to the tracer once they've run, exception entries aren't. Changes the cycle made to the outputs on the
+    /// pins are then passed to the pin callbacks.
     pub fn execute_instruction(&mut self) {
-        let opcode = self.get_opcode();
-        opcode.execute(&mut self.cortex_m33);
+        if self.cortex_m33.reset_requested {
+            self.reset();
+        }
+
+        if !self.is_halted() && !self.cortex_m33.take_pending_exception() {
+            let trace = self.start_trace();
+            let pc = self.cortex_m33.registers.pc.get();
+            let native_function = RomFunction::at(pc).filter(|_| self.bootrom.native);
+            let result = match native_function {
+                Some(function) => self.call_rom_function(function),
+                None => self.cortex_m33.execute_cached(),
+            };
+
+            if let Err(fault) = result {
+                self.cortex_m33.raise_fault(fault);
+            }
+
+            if self.cortex_m33.breakpoint == Some(SEMIHOSTING_BKPT) {
+                self.semihosting_call();
+                self.cortex_m33.step_over_breakpoint();
+            }
+
+            if let Some(trace) = trace {
+                self.finish_trace(trace);
+            }
+        }
+
+        let cycle = self.cortex_m33.cycles;
+        self.cortex_m33.advance_cycles(1);
+        self.tick_peripherals(1);
+        self.dispatch_output_changes(cycle);
+    }
+
+    /// Moves the peripherals on by a number of cycles, passes the proc0 ticks on to SysTick, and passes changes to
+    /// their IRQ outputs on to the NVIC
+    fn tick_peripherals(&mut self, cycles: u64) {
+        let irqs = self.memory_mut().peripherals.tick(cycles);
+        if self.memory().peripherals.updated() {
+            let peripherals = &self.memory().peripherals;
+            let ticks = peripherals.get::<Ticks>(TICKS_BASE).map_or(0, |ticks| ticks.take_ticks(TICK_PROC0));
+            for _ in 0..ticks {
+                self.cortex_m33.reference_tick();
+            }
+        }
+
+        let changed = irqs ^ self.peripheral_irqs;
+        self.peripheral_irqs = irqs;
+        if changed == 0 {
+            return;
+        }
+
+        for irq in (0..IRQ_COUNT).filter(|irq| changed >> irq & 1 != 0) {
+            self.set_irq(irq, irqs >> irq & 1 != 0);
+        }
     }
 }
@@ -0,0 +1,166 @@
+use std::fmt;
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+use crate::cortex_m33::registers::Register;
+use crate::RP2350;
+
+/// How often the runner checks the timeout and passes on UART output, in instructions
+const POLL_INTERVAL: u64 = 4096;
+
+/// Why a run ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stop {
+    CycleLimit,
+    InstructionLimit,
+    /// The PC reached the breakpoint address
+    Breakpoint(u32),
+    Timeout,
+    LockedUp,
+    /// The core halted on a BKPT other than a semihosting call
+    Bkpt(u8),
+    /// The firmware exited through semihosting with the status
+    Exit(u32),
+}
+
+impl fmt::Display for Stop {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Stop::CycleLimit => write!(f, "cycle limit reached"),
+            Stop::InstructionLimit => write!(f, "instruction limit reached"),
+            Stop::Breakpoint(address) => write!(f, "breakpoint at {:#010x}", address),
+            Stop::Timeout => write!(f, "timed out"),
+            Stop::LockedUp => write!(f, "core locked up"),
+            Stop::Bkpt(imm) => write!(f, "halted on bkpt {:#04x}", imm),
+            Stop::Exit(status) => write!(f, "exited with status {}", status),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RunSummary {
+    pub stop: Stop,
+    pub instructions: u64,
+    pub elapsed: Duration,
+}
+
+/// Runs an RP2350 until one of its stop conditions. With none set it runs until the chip halts.
+#[derive(Default)]
+pub struct Runner {
+    pub max_cycles: Option<u64>,
+    pub max_instructions: Option<u64>,
+    pub breakpoint: Option<u32>,
+    /// Wall clock time the run may take
+    pub timeout: Option<Duration>,
+    /// Where bytes sent by UART0 go, they are dropped without one
+    pub uart0: Option<Box<dyn Write>>,
+    /// Where the semihosting console goes, it is dropped without one
+    pub console: Option<Box<dyn Write>>,
+}
+
+impl Runner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn run(&mut self, mcu: &mut RP2350) -> io::Result<RunSummary> {
+        let start = Instant::now();
+        let mut instructions = 0;
+
+        let stop = loop {
+            if self.max_cycles.is_some_and(|max| mcu.cortex_m33.cycles >= max) {
+                break Stop::CycleLimit;
+            }
+            if self.max_instructions.is_some_and(|max| instructions >= max) {
+                break Stop::InstructionLimit;
+            }
+            let pc = mcu.cortex_m33.registers.pc.get();
+            if self.breakpoint == Some(pc) {
+                break Stop::Breakpoint(pc);
+            }
+            if let Some(status) = mcu.semihosting.exit {
+                break Stop::Exit(status);
+            }
+            if let Some(imm) = mcu.cortex_m33.breakpoint {
+                break Stop::Bkpt(imm);
+            }
+            if mcu.cortex_m33.locked_up {
+                break Stop::LockedUp;
+            }
+            if instructions % POLL_INTERVAL == POLL_INTERVAL - 1 {
+                self.flush_output(mcu)?;
+                if self.timeout.is_some_and(|timeout| start.elapsed() >= timeout) {
+                    break Stop::Timeout;
+                }
+            }
+
+            mcu.execute_instruction();
+            instructions += 1;
+        };
+
+        self.flush_output(mcu)?;
+
+        Ok(RunSummary {
+            stop,
+            instructions,
+            elapsed: start.elapsed(),
+        })
+    }
+
+    fn flush_output(&mut self, mcu: &mut RP2350) -> io::Result<()> {
+        write_to(&mut self.uart0, &mcu.memory_mut().uart0().take_output())?;
+        write_to(&mut self.console, &mcu.semihosting.take_console())
+    }
+}
+
+fn write_to(writer: &mut Option<Box<dyn Write>>, output: &[u8]) -> io::Result<()> {
+    match writer {
+        Some(writer) if !output.is_empty() => {
+            writer.write_all(output)?;
+            writer.flush()
+        }
+        _ => Ok(()),
+    }
+}
+
+/// The core registers as a table, with the symbol the PC is in when the image had symbols
+pub fn register_dump(mcu: &mut RP2350) -> String {
+    let mut dump = String::new();
+
+    for row in 0..4 {
+        let line: Vec<String> = (0..4)
+            .map(|column| {
+                let n = row * 4 + column;
+                let name = match n {
+                    13 => "sp".to_string(),
+                    14 => "lr".to_string(),
+                    15 => "pc".to_string(),
+                    n => format!("r{}", n),
+                };
+                let value = mcu.cortex_m33.get_register_from_number(n).get();
+                format!("{:<5}{:#010x}", name, value)
+            })
+            .collect();
+        dump.push_str(line.join("  ").trim_end());
+        dump.push('\n');
+    }
+
+    let core = &mcu.cortex_m33;
+    dump.push_str(&format!(
+        "{:<5}{:#010x}  {:<5}{:#010x}  {:<5}{:#010x}\n",
+        "xpsr",
+        (core.xpsr.into_u32() & !0x1ff) | core.ipsr as u32,
+        "msp",
+        core.registers.sp.get_msp(),
+        "psp",
+        core.registers.sp.get_psp(),
+    ));
+    dump.push_str(&format!("cycles {}\n", core.cycles));
+
+    let pc = core.registers.pc.get();
+    if let Some(symbol) = mcu.symbols.resolve(pc) {
+        dump.push_str(&format!("pc is in {} + {:#x}\n", symbol.name, pc - symbol.address));
+    }
+
+    dump
+}
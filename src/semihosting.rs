@@ -0,0 +1,279 @@
+use std::collections::VecDeque;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::{Component, Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::cortex_m33::registers::Register;
+use crate::RP2350;
+
+/// The BKPT immediate that makes a semihosting call, the rest are left for a debugger
+pub const SEMIHOSTING_BKPT: u8 = 0xab;
+
+pub const SYS_OPEN: u32 = 0x01;
+pub const SYS_CLOSE: u32 = 0x02;
+pub const SYS_WRITEC: u32 = 0x03;
+pub const SYS_WRITE0: u32 = 0x04;
+pub const SYS_WRITE: u32 = 0x05;
+pub const SYS_READ: u32 = 0x06;
+pub const SYS_ISTTY: u32 = 0x09;
+pub const SYS_SEEK: u32 = 0x0a;
+pub const SYS_FLEN: u32 = 0x0c;
+pub const SYS_CLOCK: u32 = 0x10;
+pub const SYS_TIME: u32 = 0x11;
+pub const SYS_ERRNO: u32 = 0x13;
+pub const SYS_EXIT: u32 = 0x18;
+pub const SYS_EXIT_EXTENDED: u32 = 0x20;
+
+/// The exit reason for a program that finished normally, anything else is a failure
+pub const ADP_STOPPED_APPLICATION_EXIT: u32 = 0x20026;
+
+/// The name that opens the console rather than a file
+const CONSOLE: &[u8] = b":tt";
+const EBADF: i32 = 9;
+const EINVAL: i32 = 22;
+const EIO: i32 = 5;
+const EACCES: i32 = 13;
+
+enum Handle {
+    /// The console, which firmware opens as ":tt" for stdin, stdout and stderr
+    Console,
+    File(File),
+}
+
+/// The host side of ARM semihosting. The console is buffered both ways for the host to feed and drain, and
+/// files can only be opened inside the sandbox directory.
+#[derive(Default)]
+pub struct Semihosting {
+    /// Directory files are opened relative to, SYS_OPEN of anything but the console fails without one
+    pub root: Option<PathBuf>,
+    /// What the firmware has written to the console, until the host takes it
+    pub console: Vec<u8>,
+    /// What the firmware reads from the console
+    pub input: VecDeque<u8>,
+    /// Set by SYS_EXIT or SYS_EXIT_EXTENDED to the exit status, the chip stops running until reset
+    pub exit: Option<u32>,
+    handles: Vec<Option<Handle>>,
+    errno: i32,
+}
+
+impl Semihosting {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Takes what has been written to the console so far
+    pub fn take_console(&mut self) -> Vec<u8> {
+        std::mem::take(&mut self.console)
+    }
+
+    /// Resolves a file name under the sandbox, refusing anything that could lead out of it
+    fn sandboxed(&self, name: &str) -> Option<PathBuf> {
+        let root = self.root.as_ref()?;
+        let path = Path::new(name);
+        let inside = path.components().all(|component| matches!(component, Component::Normal(_)));
+
+        (inside && !name.is_empty()).then(|| root.join(path))
+    }
+
+    fn open(&mut self, name: &[u8], mode: u32) -> Result<u32, i32> {
+        let handle = if name == CONSOLE {
+            Handle::Console
+        } else {
+            let name = std::str::from_utf8(name).map_err(|_| EINVAL)?;
+            let path = self.sandboxed(name).ok_or(EACCES)?;
+
+            // The modes follow fopen's r, r+, w, w+, a and a+, each with a binary variant
+            let mut options = OpenOptions::new();
+            match mode {
+                0 | 1 => options.read(true),
+                2 | 3 => options.read(true).write(true),
+                4 | 5 => options.write(true).create(true).truncate(true),
+                6 | 7 => options.read(true).write(true).create(true).truncate(true),
+                8 | 9 => options.append(true).create(true),
+                10 | 11 => options.read(true).append(true).create(true),
+                _ => return Err(EINVAL),
+            };
+            Handle::File(options.open(path).map_err(host_errno)?)
+        };
+
+        // Handles start at one so zero is never mistaken for a valid one
+        let index = match self.handles.iter().position(Option::is_none) {
+            Some(index) => index,
+            None => {
+                self.handles.push(None);
+                self.handles.len() - 1
+            }
+        };
+        self.handles[index] = Some(handle);
+        Ok(index as u32 + 1)
+    }
+
+    fn handle(&mut self, handle: u32) -> Result<&mut Handle, i32> {
+        let index = (handle as usize).checked_sub(1).ok_or(EBADF)?;
+        self.handles.get_mut(index).and_then(Option::as_mut).ok_or(EBADF)
+    }
+
+    fn close(&mut self, handle: u32) -> Result<u32, i32> {
+        self.handle(handle)?;
+        self.handles[handle as usize - 1] = None;
+        Ok(0)
+    }
+
+    fn write(&mut self, handle: u32, data: &[u8]) -> Result<usize, i32> {
+        match self.handle(handle)? {
+            Handle::Console => {
+                self.console.extend_from_slice(data);
+                Ok(data.len())
+            }
+            Handle::File(file) => file.write_all(data).map(|_| data.len()).map_err(host_errno),
+        }
+    }
+
+    fn read(&mut self, handle: u32, length: usize) -> Result<Vec<u8>, i32> {
+        match self.handle(handle)? {
+            Handle::Console => {
+                let count = length.min(self.input.len());
+                Ok(self.input.drain(..count).collect())
+            }
+            Handle::File(file) => {
+                let mut data = Vec::new();
+                file.take(length as u64).read_to_end(&mut data).map_err(host_errno)?;
+                Ok(data)
+            }
+        }
+    }
+}
+
+fn host_errno(error: io::Error) -> i32 {
+    error.raw_os_error().unwrap_or(EIO)
+}
+
+impl RP2350 {
+    /// Runs the semihosting operation in r0 with the parameter in r1, and puts the result in r0. Parameter blocks
+    /// and buffers the host can't reach make the operation fail with -1.
+    pub(crate) fn semihosting_call(&mut self) {
+        let operation = self.cortex_m33.registers.r0.get();
+        let parameter = self.cortex_m33.registers.r1.get();
+
+        let result = match self.semihosting_operation(operation, parameter) {
+            Ok(result) => result,
+            Err(errno) => {
+                self.semihosting.errno = errno;
+                u32::MAX
+            }
+        };
+
+        self.cortex_m33.registers.r0.set(result);
+    }
+
+    fn semihosting_operation(&mut self, operation: u32, parameter: u32) -> Result<u32, i32> {
+        match operation {
+            SYS_OPEN => {
+                let [name, mode, length] = self.read_parameters(parameter)?;
+                let name = self.read_bytes(name, length)?;
+                self.semihosting.open(&name, mode)
+            }
+            SYS_CLOSE => {
+                let [handle] = self.read_parameters(parameter)?;
+                self.semihosting.close(handle)
+            }
+            SYS_WRITEC => {
+                let character = self.read_bytes(parameter, 1)?;
+                self.semihosting.console.extend(character);
+                Ok(0)
+            }
+            SYS_WRITE0 => {
+                let mut address = parameter;
+                loop {
+                    match self.read_bytes(address, 1)?[0] {
+                        0 => break,
+                        byte => self.semihosting.console.push(byte),
+                    }
+                    address = address.wrapping_add(1);
+                }
+                Ok(0)
+            }
+            SYS_WRITE => {
+                let [handle, buffer, length] = self.read_parameters(parameter)?;
+                let data = self.read_bytes(buffer, length)?;
+                let written = self.semihosting.write(handle, &data)?;
+                // The result is the number of bytes that were not written
+                Ok(length - written as u32)
+            }
+            SYS_READ => {
+                let [handle, buffer, length] = self.read_parameters(parameter)?;
+                let data = self.semihosting.read(handle, length as usize)?;
+                self.write_bytes(buffer, &data)?;
+                Ok(length - data.len() as u32)
+            }
+            SYS_ISTTY => {
+                let [handle] = self.read_parameters(parameter)?;
+                Ok(matches!(self.semihosting.handle(handle)?, Handle::Console) as u32)
+            }
+            SYS_SEEK => {
+                let [handle, position] = self.read_parameters(parameter)?;
+                match self.semihosting.handle(handle)? {
+                    Handle::Console => Err(EINVAL),
+                    Handle::File(file) => {
+                        file.seek(SeekFrom::Start(position as u64)).map_err(host_errno)?;
+                        Ok(0)
+                    }
+                }
+            }
+            SYS_FLEN => {
+                let [handle] = self.read_parameters(parameter)?;
+                match self.semihosting.handle(handle)? {
+                    Handle::Console => Err(EINVAL),
+                    Handle::File(file) => Ok(file.metadata().map_err(host_errno)?.len() as u32),
+                }
+            }
+            SYS_CLOCK => Ok((self.memory().elapsed().as_millis() / 10) as u32),
+            SYS_TIME => Ok(SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |time| time.as_secs() as u32)),
+            SYS_ERRNO => Ok(self.semihosting.errno as u32),
+            SYS_EXIT => {
+                // On 32 bit targets the parameter is the reason itself rather than a pointer to it
+                let status = (parameter != ADP_STOPPED_APPLICATION_EXIT) as u32;
+                self.semihosting.exit = Some(status);
+                Ok(0)
+            }
+            SYS_EXIT_EXTENDED => {
+                let [reason, subcode] = self.read_parameters(parameter)?;
+                let status = if reason == ADP_STOPPED_APPLICATION_EXIT { subcode } else { 1 };
+                self.semihosting.exit = Some(status);
+                Ok(0)
+            }
+            _ => Err(EINVAL),
+        }
+    }
+
+    fn read_parameters<const N: usize>(&self, address: u32) -> Result<[u32; N], i32> {
+        let mut parameters = [0; N];
+        for (i, parameter) in parameters.iter_mut().enumerate() {
+            *parameter = self
+                .cortex_m33
+                .memory
+                .read_u32(address.wrapping_add(4 * i as u32))
+                .map_err(|_| EINVAL)?;
+        }
+        Ok(parameters)
+    }
+
+    fn read_bytes(&self, address: u32, length: u32) -> Result<Vec<u8>, i32> {
+        (0..length)
+            .map(|i| self.cortex_m33.memory.read(address.wrapping_add(i)).map_err(|_| EINVAL))
+            .collect()
+    }
+
+    fn write_bytes(&mut self, address: u32, data: &[u8]) -> Result<(), i32> {
+        for (i, byte) in data.iter().enumerate() {
+            self.cortex_m33
+                .memory
+                .write(address.wrapping_add(i as u32), *byte)
+                .map_err(|_| EINVAL)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,154 @@
+use std::io::{self, ErrorKind, Read, Write};
+
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+
+use crate::trace::{MemoryAccess, TraceRecord, TraceSink};
+
+/// Starts every binary trace, the last byte is the format version
+pub const MAGIC: [u8; 4] = *b"RPT\x01";
+
+const FLAG_32BIT: u8 = 0x80;
+const FLAG_FLAGS_CHANGED: u8 = 0x40;
+const FLAGS_MASK: u8 = 0x1f;
+const ACCESS_WRITE: u8 = 0x80;
+
+/// Writes records in a compact little endian format, without the mnemonics which can be decoded again from the
+/// opcodes. Each record is the PC and opcode words, a byte with the instruction size, whether the flags changed
+/// and their new value, a count of changed registers each as number and value, then a count of accesses each as
+/// size with the write bit, address and value.
+pub struct BinarySink<W: Write> {
+    writer: W,
+    started: bool,
+    error: Option<io::Error>,
+}
+
+impl<W: Write> BinarySink<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            started: false,
+            error: None,
+        }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    fn write_record(&mut self, record: &TraceRecord) -> io::Result<()> {
+        let writer = &mut self.writer;
+        if !self.started {
+            writer.write_all(&MAGIC)?;
+            self.started = true;
+        }
+
+        writer.write_u32::<LittleEndian>(record.pc)?;
+        writer.write_u32::<LittleEndian>(record.opcode)?;
+
+        let mut flags = record.flags.unwrap_or(0) & FLAGS_MASK;
+        if record.flags.is_some() {
+            flags |= FLAG_FLAGS_CHANGED;
+        }
+        if record.is_32bit {
+            flags |= FLAG_32BIT;
+        }
+        writer.write_u8(flags)?;
+
+        writer.write_u8(record.registers.len() as u8)?;
+        for (register, value) in &record.registers {
+            writer.write_u8(*register)?;
+            writer.write_u32::<LittleEndian>(*value)?;
+        }
+
+        writer.write_u8(record.accesses.len() as u8)?;
+        for access in &record.accesses {
+            writer.write_u8(access.size | if access.write { ACCESS_WRITE } else { 0 })?;
+            writer.write_u32::<LittleEndian>(access.address)?;
+            writer.write_u32::<LittleEndian>(access.value)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<W: Write> TraceSink for BinarySink<W> {
+    fn record(&mut self, record: &TraceRecord) {
+        if self.error.is_none() {
+            self.error = self.write_record(record).err();
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self.error.take() {
+            Some(error) => Err(error),
+            None => self.writer.flush(),
+        }
+    }
+}
+
+fn read_record(reader: &mut impl Read, pc: u32) -> io::Result<TraceRecord> {
+    let opcode = reader.read_u32::<LittleEndian>()?;
+    let flags = reader.read_u8()?;
+
+    let registers = (0..reader.read_u8()?)
+        .map(|_| Ok((reader.read_u8()?, reader.read_u32::<LittleEndian>()?)))
+        .collect::<io::Result<_>>()?;
+
+    let accesses = (0..reader.read_u8()?)
+        .map(|_| {
+            let size = reader.read_u8()?;
+            Ok(MemoryAccess {
+                address: reader.read_u32::<LittleEndian>()?,
+                size: size & !ACCESS_WRITE,
+                write: size & ACCESS_WRITE != 0,
+                value: reader.read_u32::<LittleEndian>()?,
+            })
+        })
+        .collect::<io::Result<_>>()?;
+
+    Ok(TraceRecord {
+        pc,
+        opcode,
+        is_32bit: flags & FLAG_32BIT != 0,
+        mnemonic: String::new(),
+        registers,
+        flags: (flags & FLAG_FLAGS_CHANGED != 0).then_some(flags & FLAGS_MASK),
+        accesses,
+    })
+}
+
+/// Reads back a binary trace, so it can be replayed as text or compared with another run
+pub fn read_trace(mut reader: impl Read) -> io::Result<Vec<TraceRecord>> {
+    let mut magic = [0; 4];
+    match reader.read_exact(&mut magic) {
+        Err(error) if error.kind() == ErrorKind::UnexpectedEof => return Ok(Vec::new()),
+        result => result?,
+    }
+    if magic != MAGIC {
+        return Err(io::Error::new(ErrorKind::InvalidData, "not a binary trace"));
+    }
+
+    let mut records = Vec::new();
+    loop {
+        // A trace can only end between records
+        let pc = match reader.read_u32::<LittleEndian>() {
+            Err(error) if error.kind() == ErrorKind::UnexpectedEof => return Ok(records),
+            pc => pc?,
+        };
+        records.push(read_record(&mut reader, pc)?);
+    }
+}
+
+/// The index of the first record where two traces differ, None if they are the same
+pub fn first_difference(expected: &[TraceRecord], actual: &[TraceRecord]) -> Option<usize> {
+    let differs = expected
+        .iter()
+        .zip(actual)
+        .position(|(expected, actual)| !expected.same_execution(actual));
+
+    match differs {
+        Some(index) => Some(index),
+        None if expected.len() != actual.len() => Some(expected.len().min(actual.len())),
+        None => None,
+    }
+}
@@ -0,0 +1,216 @@
+pub mod binary;
+
+use std::cell::RefCell;
+use std::fmt;
+use std::io::{self, Write};
+use std::rc::Rc;
+
+use crate::cortex_m33::registers::Register;
+use crate::cortex_m33::OpCode;
+use crate::RP2350;
+
+/// A load or store made by a traced instruction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryAccess {
+    pub address: u32,
+    /// In bytes, 1, 2 or 4
+    pub size: u8,
+    pub write: bool,
+    pub value: u32,
+}
+
+/// APSR.NZCVQ as the low five bits
+pub type Flags = u8;
+
+/// What one instruction did
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceRecord {
+    pub pc: u32,
+    /// The first halfword in the low half, and for a 32 bit instruction the second in the high half
+    pub opcode: u32,
+    pub is_32bit: bool,
+    /// Empty when the record was read back from the binary format, which leaves it out
+    pub mnemonic: String,
+    /// R0 to R14 that changed, as register number and new value
+    pub registers: Vec<(u8, u32)>,
+    /// The new flags, if any changed
+    pub flags: Option<Flags>,
+    pub accesses: Vec<MemoryAccess>,
+}
+
+impl TraceRecord {
+    /// Whether two records describe the same execution, ignoring the mnemonic
+    pub fn same_execution(&self, other: &TraceRecord) -> bool {
+        self.pc == other.pc
+            && self.opcode == other.opcode
+            && self.is_32bit == other.is_32bit
+            && self.registers == other.registers
+            && self.flags == other.flags
+            && self.accesses == other.accesses
+    }
+}
+
+fn format_flags(flags: Flags) -> String {
+    "nzcvq"
+        .chars()
+        .enumerate()
+        .map(|(i, flag)| if flags & (0x10 >> i) != 0 { flag.to_ascii_uppercase() } else { flag })
+        .collect()
+}
+
+impl fmt::Display for TraceRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let opcode = if self.is_32bit {
+            format!("{:04x} {:04x}", self.opcode & 0xffff, self.opcode >> 16)
+        } else {
+            format!("{:04x}", self.opcode & 0xffff)
+        };
+        write!(f, "{:08x}  {:<9}  {:<24}", self.pc, opcode, self.mnemonic)?;
+
+        for (register, value) in &self.registers {
+            write!(f, "  r{}={:#010x}", register, value)?;
+        }
+        if let Some(flags) = self.flags {
+            write!(f, "  {}", format_flags(flags))?;
+        }
+        for access in &self.accesses {
+            let direction = if access.write { "w" } else { "r" };
+            write!(f, "  [{}{} {:#010x}={:#x}]", direction, access.size, access.address, access.value)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Where trace records go. Sinks that write out hold on to the first error and report it from flush.
+pub trait TraceSink {
+    fn record(&mut self, record: &TraceRecord);
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Writes a line of text per instruction
+pub struct TextSink<W: Write> {
+    writer: W,
+    error: Option<io::Error>,
+}
+
+impl<W: Write> TextSink<W> {
+    pub fn new(writer: W) -> Self {
+        Self { writer, error: None }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: Write> TraceSink for TextSink<W> {
+    fn record(&mut self, record: &TraceRecord) {
+        if self.error.is_none() {
+            self.error = writeln!(self.writer, "{}", record.to_string().trim_end()).err();
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self.error.take() {
+            Some(error) => Err(error),
+            None => self.writer.flush(),
+        }
+    }
+}
+
+/// Keeps the records in memory. Clones share the same records, so one can be given to the chip and another kept
+/// to look at them.
+#[derive(Debug, Clone, Default)]
+pub struct TraceBuffer {
+    records: Rc<RefCell<Vec<TraceRecord>>>,
+}
+
+impl TraceBuffer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn records(&self) -> Vec<TraceRecord> {
+        self.records.borrow().clone()
+    }
+
+    pub fn clear(&self) {
+        self.records.borrow_mut().clear();
+    }
+}
+
+impl TraceSink for TraceBuffer {
+    fn record(&mut self, record: &TraceRecord) {
+        self.records.borrow_mut().push(record.clone());
+    }
+}
+
+/// The state before a traced instruction runs
+pub(crate) struct TraceStart {
+    pc: u32,
+    opcode: u32,
+    is_32bit: bool,
+    mnemonic: String,
+    registers: [u32; 15],
+    flags: Flags,
+}
+
+impl RP2350 {
+    fn trace_registers(&mut self) -> [u32; 15] {
+        std::array::from_fn(|n| self.cortex_m33.get_register_from_number(n as u16).get())
+    }
+
+    fn trace_flags(&self) -> Flags {
+        (self.cortex_m33.xpsr.apsr.into_u32() >> 27) as Flags
+    }
+
+    /// Takes a snapshot before the instruction at the PC runs and starts collecting its data accesses. None when
+    /// nothing is tracing, or the instruction can't be fetched.
+    pub(crate) fn start_trace(&mut self) -> Option<TraceStart> {
+        self.tracer.as_ref()?;
+
+        let pc = self.cortex_m33.registers.pc.get();
+        let first = OpCode::from_address(&self.cortex_m33, pc).ok()?;
+        let second = OpCode::from_address(&self.cortex_m33, pc.wrapping_add(2))
+            .unwrap_or(OpCode { code: 0, address: pc.wrapping_add(2) });
+        let is_32bit = first.is_32bit();
+
+        self.cortex_m33.traced_accesses = Some(Vec::new());
+        Some(TraceStart {
+            pc,
+            opcode: if is_32bit { first.code as u32 | (second.code as u32) << 16 } else { first.code as u32 },
+            is_32bit,
+            mnemonic: first.disassemble_in_it_block(second, self.cortex_m33.xpsr.epsr.it()),
+            registers: self.trace_registers(),
+            flags: self.trace_flags(),
+        })
+    }
+
+    /// Works out what the instruction changed and passes the record to the tracer.
+    pub(crate) fn finish_trace(&mut self, start: TraceStart) {
+        let accesses = self.cortex_m33.traced_accesses.take().unwrap_or_default();
+        let registers = self.trace_registers();
+        let flags = self.trace_flags();
+
+        let record = TraceRecord {
+            pc: start.pc,
+            opcode: start.opcode,
+            is_32bit: start.is_32bit,
+            mnemonic: start.mnemonic,
+            registers: (0..15u8)
+                .filter(|&n| registers[n as usize] != start.registers[n as usize])
+                .map(|n| (n, registers[n as usize]))
+                .collect(),
+            flags: (flags != start.flags).then_some(flags),
+            accesses,
+        };
+
+        if let Some(tracer) = &mut self.tracer {
+            tracer.record(&record);
+        }
+    }
+}
@@ -0,0 +1,146 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::bootrom::picobin::*;
+    use rp2350_sim::bootrom::BootError;
+    use rp2350_sim::cortex_m33::registers::Register;
+    use rp2350_sim::{BOOT_VECTOR_TABLE, FLASH_START_ADDRESS, RAM_START_ADDRESS, RP2350};
+
+    const ARM_SECURE: u16 = IMAGE_TYPE_EXE | IMAGE_TYPE_EXE_SECURITY_S | IMAGE_TYPE_EXE_CPU_ARM | IMAGE_TYPE_EXE_CHIP_RP2350;
+    const RISCV: u16 = IMAGE_TYPE_EXE | IMAGE_TYPE_EXE_SECURITY_S | IMAGE_TYPE_EXE_CPU_RISCV | IMAGE_TYPE_EXE_CHIP_RP2350;
+
+    /// Builds a block the way pico-sdk embeds one, with the link relative to the block's start
+    fn block(image_type: u16, items: &[u32], link: i32) -> Vec<u8> {
+        let mut words = vec![BLOCK_MARKER_START, 0x42 | 1 << 8 | (image_type as u32) << 16];
+        words.extend(items);
+        let size = words.len() as u32 - 1;
+        words.extend([0xff | size << 8, link as u32, BLOCK_MARKER_END]);
+        words.iter().flat_map(|word| word.to_le_bytes()).collect()
+    }
+
+    fn program(rp2350: &mut RP2350, address: u32, bytes: &[u8]) {
+        rp2350.memory_mut().program(address, bytes).unwrap();
+    }
+
+    fn program_vectors(rp2350: &mut RP2350, address: u32, sp: u32, pc: u32) {
+        program(rp2350, address, &sp.to_le_bytes());
+        program(rp2350, address + 4, &pc.to_le_bytes());
+    }
+
+    #[test]
+    fn boot_image_def_vector_table() {
+        // should boot through the vector table named by the IMAGE_DEF
+        let mut rp2350: RP2350 = RP2350::new();
+        let vector_table = FLASH_START_ADDRESS + 0x400;
+        program(&mut rp2350, FLASH_START_ADDRESS + 0x100, &block(ARM_SECURE, &[0x03 | 2 << 8, vector_table], 0));
+        program_vectors(&mut rp2350, vector_table, RAM_START_ADDRESS + 0x2000, FLASH_START_ADDRESS + 0x801);
+        rp2350.reset();
+
+        let boot = rp2350.bootrom.boot.unwrap().unwrap();
+        assert_eq!(boot.image_type, Some(ImageType(ARM_SECURE)));
+        assert_eq!(boot.vector_table, vector_table);
+        assert_eq!(rp2350.cortex_m33.vtor, vector_table);
+        assert_eq!(rp2350.cortex_m33.registers.sp.get(), RAM_START_ADDRESS + 0x2000);
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), FLASH_START_ADDRESS + 0x800);
+        assert!(rp2350.cortex_m33.xpsr.epsr.t());
+        assert!(!rp2350.cortex_m33.locked_up);
+    }
+
+    #[test]
+    fn boot_image_def_default_vector_table() {
+        // should use the vector table at the start of flash when the IMAGE_DEF doesn't name one
+        let mut rp2350: RP2350 = RP2350::new();
+        program_vectors(&mut rp2350, FLASH_START_ADDRESS, RAM_START_ADDRESS + 0x1000, FLASH_START_ADDRESS + 0x201);
+        program(&mut rp2350, FLASH_START_ADDRESS + 0x100, &block(ARM_SECURE, &[], 0));
+        rp2350.reset();
+
+        assert_eq!(rp2350.cortex_m33.vtor, BOOT_VECTOR_TABLE);
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), FLASH_START_ADDRESS + 0x200);
+    }
+
+    #[test]
+    fn boot_entry_point() {
+        // should take PC, SP and the stack limit from the entry point item over the vector table
+        let mut rp2350: RP2350 = RP2350::new();
+        program_vectors(&mut rp2350, FLASH_START_ADDRESS, RAM_START_ADDRESS + 0x1000, FLASH_START_ADDRESS + 0x201);
+        let entry_point = [0x44 | 4 << 8, FLASH_START_ADDRESS + 0x301, RAM_START_ADDRESS + 0x3000, RAM_START_ADDRESS + 0x2000];
+        program(&mut rp2350, FLASH_START_ADDRESS + 0x100, &block(ARM_SECURE, &entry_point, 0));
+        rp2350.reset();
+
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), FLASH_START_ADDRESS + 0x300);
+        assert_eq!(rp2350.cortex_m33.registers.sp.get(), RAM_START_ADDRESS + 0x3000);
+        assert_eq!(rp2350.cortex_m33.msplim, RAM_START_ADDRESS + 0x2000);
+    }
+
+    #[test]
+    fn boot_last_image_def_in_loop() {
+        // should follow the block loop and boot the last bootable IMAGE_DEF
+        let mut rp2350: RP2350 = RP2350::new();
+        let first = block(ARM_SECURE, &[0x44 | 3 << 8, FLASH_START_ADDRESS + 0x301, RAM_START_ADDRESS + 0x1000], 0x400);
+        let second = block(ARM_SECURE, &[0x44 | 3 << 8, FLASH_START_ADDRESS + 0x601, RAM_START_ADDRESS + 0x1000], -0x400);
+        program(&mut rp2350, FLASH_START_ADDRESS + 0x100, &first);
+        program(&mut rp2350, FLASH_START_ADDRESS + 0x500, &second);
+
+        let blocks = find_block_loop(&rp2350.memory().flash[..], FLASH_START_ADDRESS);
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[1].address, FLASH_START_ADDRESS + 0x500);
+
+        rp2350.reset();
+
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), FLASH_START_ADDRESS + 0x600);
+    }
+
+    #[test]
+    fn skip_unbootable_image_def() {
+        // should pass over a RISC-V IMAGE_DEF for an Arm one earlier in the loop
+        let mut rp2350: RP2350 = RP2350::new();
+        let first = block(ARM_SECURE, &[0x44 | 3 << 8, FLASH_START_ADDRESS + 0x301, RAM_START_ADDRESS + 0x1000], 0x400);
+        let second = block(RISCV, &[0x44 | 3 << 8, FLASH_START_ADDRESS + 0x600, RAM_START_ADDRESS + 0x1000], -0x400);
+        program(&mut rp2350, FLASH_START_ADDRESS + 0x100, &first);
+        program(&mut rp2350, FLASH_START_ADDRESS + 0x500, &second);
+        rp2350.reset();
+
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), FLASH_START_ADDRESS + 0x300);
+    }
+
+    #[test]
+    fn no_bootable_image() {
+        // should lock up when the only IMAGE_DEF is for RISC-V
+        let mut rp2350: RP2350 = RP2350::new();
+        program_vectors(&mut rp2350, FLASH_START_ADDRESS, RAM_START_ADDRESS + 0x1000, FLASH_START_ADDRESS + 0x201);
+        program(&mut rp2350, FLASH_START_ADDRESS + 0x100, &block(RISCV, &[], 0));
+        rp2350.reset();
+
+        assert_eq!(rp2350.bootrom.boot, Some(Err(BootError::NoBootableImage(ImageType(RISCV)))));
+        assert!(rp2350.cortex_m33.locked_up);
+    }
+
+    #[test]
+    fn image_type_fields() {
+        // should decode the image type flags
+        let arm = ImageType(ARM_SECURE);
+        let non_secure = ImageType(IMAGE_TYPE_EXE | IMAGE_TYPE_EXE_SECURITY_NS | IMAGE_TYPE_EXE_CHIP_RP2350);
+        let data = ImageType(IMAGE_TYPE_DATA);
+
+        assert!(arm.is_exe() && arm.is_rp2350());
+        assert_eq!(arm.cpu(), Cpu::Arm);
+        assert_eq!(arm.security(), Security::Secure);
+        assert_eq!(ImageType(RISCV).cpu(), Cpu::RiscV);
+        assert_eq!(non_secure.security(), Security::NonSecure);
+        assert!(!data.is_exe());
+    }
+
+    #[test]
+    fn malformed_block_falls_back() {
+        // should ignore a block whose sizes don't add up, booting from the start of flash
+        let mut rp2350: RP2350 = RP2350::new();
+        program_vectors(&mut rp2350, FLASH_START_ADDRESS, RAM_START_ADDRESS + 0x1000, FLASH_START_ADDRESS + 0x201);
+        let mut bad = block(ARM_SECURE, &[], 0);
+        bad[9] = 2;
+        program(&mut rp2350, FLASH_START_ADDRESS + 0x100, &bad);
+        rp2350.reset();
+
+        assert!(find_block_loop(&rp2350.memory().flash[..], FLASH_START_ADDRESS).is_empty());
+        assert_eq!(rp2350.bootrom.boot.unwrap().unwrap().image_type, None);
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), FLASH_START_ADDRESS + 0x200);
+    }
+}
@@ -0,0 +1,3 @@
+mod boot;
+mod rom;
+mod rom_functions;
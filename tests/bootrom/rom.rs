@@ -0,0 +1,83 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::bootrom::{RomFunction, ROM_SIZE};
+    use rp2350_sim::cortex_m33::exception::Exception;
+    use rp2350_sim::cortex_m33::fault::{CFSR_BFARVALID, CFSR_PRECISERR, SHCSR_BUSFAULTENA};
+    use rp2350_sim::cortex_m33::opcodes::*;
+    use rp2350_sim::cortex_m33::registers::Register;
+    use rp2350_sim::{RAM_START_ADDRESS, RP2350};
+
+    /// A ROM whose reset handler at 0x100 runs `movs r0, #7`
+    fn rom_image(rp2350: &RP2350) -> Vec<u8> {
+        let mut rom = vec![0; 0x104];
+        rom[0..4].copy_from_slice(&(RAM_START_ADDRESS + 0x2000).to_le_bytes());
+        rom[4..8].copy_from_slice(&0x101u32.to_le_bytes());
+        let opcode = MovImmediateT1::opcode(&rp2350.cortex_m33.registers.r0, 7);
+        rom[0x100..0x102].copy_from_slice(&opcode.to_le_bytes());
+        rom
+    }
+
+    #[test]
+    fn reset_runs_loaded_rom() {
+        // should take reset from the ROM's own vector table and run it
+        let mut rp2350: RP2350 = RP2350::new();
+        let rom = rom_image(&rp2350);
+        rp2350.load_rom(&rom).unwrap();
+        rp2350.reset();
+
+        assert_eq!(rp2350.cortex_m33.vtor, 0);
+        assert_eq!(rp2350.cortex_m33.registers.sp.get(), RAM_START_ADDRESS + 0x2000);
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), 0x100);
+
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r0.get(), 7);
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), 0x102);
+    }
+
+    #[test]
+    fn rom_write_raises_bus_fault() {
+        // should fault on a `str r1, [r0, #0]` into the ROM, leaving it unchanged
+        let mut rp2350: RP2350 = RP2350::new();
+        let rom = rom_image(&rp2350);
+        rp2350.load_rom(&rom).unwrap();
+        rp2350.cortex_m33.shcsr |= SHCSR_BUSFAULTENA;
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+        rp2350.cortex_m33.registers.r0.set(0x100);
+        rp2350.cortex_m33.registers.r1.set(0xdeadbeef);
+
+        let registers = &rp2350.cortex_m33.registers;
+        let opcode = StrImmediateT1::opcode(&registers.r1, &registers.r0, 0);
+        rp2350.cortex_m33.memory.write_u16(RAM_START_ADDRESS, opcode).unwrap();
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.fault_status.cfsr, CFSR_PRECISERR | CFSR_BFARVALID);
+        assert_eq!(rp2350.cortex_m33.fault_status.bfar, 0x100);
+        assert!(rp2350.cortex_m33.exceptions.is_pending(Exception::BusFault));
+        assert_eq!(rp2350.memory().rom[0x100..0x104], rom[0x100..0x104]);
+    }
+
+    #[test]
+    fn loaded_rom_replaces_native_functions() {
+        // should execute whatever the loaded ROM has where the native functions were
+        let mut rp2350: RP2350 = RP2350::new();
+        let mut rom = vec![0; ROM_SIZE];
+        let address = RomFunction::TableLookup.address() as usize;
+        rom[address..address + 2].copy_from_slice(&MovImmediateT1::opcode(&rp2350.cortex_m33.registers.r0, 9).to_le_bytes());
+        rp2350.load_rom(&rom).unwrap();
+        rp2350.cortex_m33.registers.pc.set(address as u32);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r0.get(), 9);
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), address as u32 + 2);
+    }
+
+    #[test]
+    fn oversized_rom() {
+        // should refuse an image larger than the ROM
+        let mut rp2350: RP2350 = RP2350::new();
+
+        assert!(rp2350.load_rom(&vec![0; ROM_SIZE + 1]).is_err());
+        assert!(rp2350.bootrom.native);
+    }
+}
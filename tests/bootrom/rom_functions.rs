@@ -0,0 +1,143 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::bootrom::*;
+    use rp2350_sim::cortex_m33::opcodes::*;
+    use rp2350_sim::cortex_m33::registers::Register;
+    use rp2350_sim::{FLASH_START_ADDRESS, RAM_START_ADDRESS, RP2350};
+
+    const RETURN_ADDRESS: u32 = RAM_START_ADDRESS + 0x100;
+
+    /// Calls the ROM function at the address with the arguments, returning r0
+    fn call(rp2350: &mut RP2350, function: u32, args: &[u32]) -> u32 {
+        for (i, arg) in args.iter().enumerate() {
+            rp2350.cortex_m33.get_register_from_number(i as u16).set(*arg);
+        }
+        let registers = &mut rp2350.cortex_m33.registers;
+        registers.lr.set(RETURN_ADDRESS | 1);
+        registers.pc.set(function & !1);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), RETURN_ADDRESS);
+        rp2350.cortex_m33.registers.r0.get()
+    }
+
+    fn lookup(rp2350: &mut RP2350, code: u32) -> u32 {
+        let rom_table_lookup = rp2350.cortex_m33.memory.read_u16(ROM_TABLE_LOOKUP_ADDRESS).unwrap() as u32;
+        call(rp2350, rom_table_lookup, &[code, RT_FLAG_FUNC_ARM_SEC])
+    }
+
+    #[test]
+    fn rom_header() {
+        // should have the magic and a Thumb pointer to rom_table_lookup
+        let rp2350: RP2350 = RP2350::new();
+        let memory = &rp2350.cortex_m33.memory;
+
+        assert_eq!(memory.read_u16(ROM_MAGIC_ADDRESS).unwrap(), u16::from_le_bytes([b'M', b'u']));
+        assert_eq!(
+            memory.read_u16(ROM_TABLE_LOOKUP_ADDRESS).unwrap() as u32,
+            RomFunction::TableLookup.address() | 1
+        );
+    }
+
+    #[test]
+    fn rom_func_lookup_from_code() {
+        // should find flash_range_program through the lookup pointer, the way pico-sdk does
+        let mut rp2350: RP2350 = RP2350::new();
+        let registers = &rp2350.cortex_m33.registers;
+        let code = [
+            MovImmediateT1::opcode(&registers.r2, ROM_TABLE_LOOKUP_ADDRESS as u16),
+            LdrhImmediateT1::opcode(&registers.r2, &registers.r2, 0),
+            BlxT1::opcode(&registers.r2),
+        ];
+        for (i, opcode) in code.iter().enumerate() {
+            rp2350.cortex_m33.memory.write_u16(RAM_START_ADDRESS + 2 * i as u32, *opcode).unwrap();
+        }
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+        rp2350.cortex_m33.registers.r0.set(rom_table_code(b'R', b'P'));
+        rp2350.cortex_m33.registers.r1.set(RT_FLAG_FUNC_ARM_SEC);
+
+        for _ in 0..4 {
+            rp2350.execute_instruction();
+        }
+
+        assert_eq!(rp2350.cortex_m33.registers.r0.get(), RomFunction::FlashRangeProgram.address() | 1);
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), RAM_START_ADDRESS + 6);
+    }
+
+    #[test]
+    fn lookup_misses() {
+        // should return null for unknown codes and for RISC-V lookups
+        let mut rp2350: RP2350 = RP2350::new();
+        let rom_table_lookup = RomFunction::TableLookup.address();
+
+        assert_eq!(lookup(&mut rp2350, rom_table_code(b'Z', b'Z')), 0);
+        assert_eq!(call(&mut rp2350, rom_table_lookup, &[rom_table_code(b'R', b'E'), RT_FLAG_FUNC_RISCV]), 0);
+    }
+
+    #[test]
+    fn flash_erase_and_program() {
+        // should erase whole sectors and program by clearing bits
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.memory_mut().flash[0x1000..0x2000].fill(0);
+        rp2350.cortex_m33.memory.write_u32(RAM_START_ADDRESS, 0x0f0f_f00f).unwrap();
+
+        let erase = lookup(&mut rp2350, rom_table_code(b'R', b'E'));
+        call(&mut rp2350, erase, &[0x1000, 0x10, FLASH_SECTOR_SIZE, 0x20]);
+        assert!(rp2350.memory().flash[0x1000..0x2000].iter().all(|&byte| byte == 0xff));
+
+        let program = lookup(&mut rp2350, rom_table_code(b'R', b'P'));
+        call(&mut rp2350, program, &[0x1000, RAM_START_ADDRESS, 4]);
+        call(&mut rp2350, program, &[0x1000, RAM_START_ADDRESS + 1, 4]);
+
+        assert_eq!(rp2350.cortex_m33.memory.read_u32(FLASH_START_ADDRESS + 0x1000).unwrap(), 0x000f_0000);
+    }
+
+    #[test]
+    fn flash_erase_outside_flash() {
+        // should return without erasing anything when the range starts past the end of flash
+        let mut rp2350: RP2350 = RP2350::new();
+        let last = rp2350.memory().flash.len() - 1;
+        rp2350.memory_mut().flash[last] = 0;
+
+        let erase = lookup(&mut rp2350, rom_table_code(b'R', b'E'));
+        call(&mut rp2350, erase, &[0xf000_0000, FLASH_SECTOR_SIZE, FLASH_SECTOR_SIZE, 0x20]);
+        call(&mut rp2350, erase, &[0xffff_f000, 0x2000, FLASH_SECTOR_SIZE, 0x20]);
+
+        assert_eq!(rp2350.memory().flash[last], 0);
+    }
+
+    #[test]
+    fn get_sys_info() {
+        // should fill in the requested words, led by the flags that were filled in
+        let mut rp2350: RP2350 = RP2350::new();
+        let get_sys_info = lookup(&mut rp2350, rom_table_code(b'G', b'S'));
+        let flags = SYS_INFO_CPU_INFO | SYS_INFO_FLASH_DEV_INFO | 0x8000;
+        let words = call(&mut rp2350, get_sys_info, &[RAM_START_ADDRESS, 8, flags]);
+
+        let memory = &rp2350.cortex_m33.memory;
+        assert_eq!(words, 3);
+        assert_eq!(memory.read_u32(RAM_START_ADDRESS).unwrap(), SYS_INFO_CPU_INFO | SYS_INFO_FLASH_DEV_INFO);
+        assert_eq!(memory.read_u32(RAM_START_ADDRESS + 4).unwrap(), 0);
+        assert_eq!(memory.read_u32(RAM_START_ADDRESS + 8).unwrap(), 0xa00);
+
+        let words = call(&mut rp2350, get_sys_info, &[RAM_START_ADDRESS, 2, SYS_INFO_CHIP_INFO]);
+        assert_eq!(words as i32, BOOTROM_ERROR_BUFFER_TOO_SMALL);
+    }
+
+    #[test]
+    fn reboot() {
+        // should reset the chip before the next instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.memory_mut().program(FLASH_START_ADDRESS, &(RAM_START_ADDRESS + 0x1000).to_le_bytes()).unwrap();
+        rp2350.memory_mut().program(FLASH_START_ADDRESS + 4, &(FLASH_START_ADDRESS + 0x201).to_le_bytes()).unwrap();
+        rp2350.memory_mut().program(FLASH_START_ADDRESS + 0x200, &[0x00, 0xbf]).unwrap();
+        let reboot = lookup(&mut rp2350, rom_table_code(b'R', b'B'));
+        call(&mut rp2350, reboot, &[0, 10, 0, 0]);
+
+        assert!(rp2350.cortex_m33.reset_requested);
+
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), FLASH_START_ADDRESS + 0x202);
+    }
+}
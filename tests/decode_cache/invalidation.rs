@@ -0,0 +1,120 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::bootrom::RomFunction;
+    use rp2350_sim::cortex_m33::exception::Exception;
+    use rp2350_sim::cortex_m33::opcodes::*;
+    use rp2350_sim::cortex_m33::registers::Register;
+    use rp2350_sim::{FLASH_START_ADDRESS, RAM_START_ADDRESS, RP2350};
+
+    const CODE: u32 = RAM_START_ADDRESS + 0x100;
+    const FLASH_CODE: u32 = FLASH_START_ADDRESS + 0x100;
+
+    /// Runs the instruction at the address once, returning r0
+    fn run_at(rp2350: &mut RP2350, address: u32) -> u32 {
+        rp2350.cortex_m33.registers.pc.set(address);
+        rp2350.execute_instruction();
+        rp2350.cortex_m33.registers.r0.get()
+    }
+
+    fn mov_r0(value: u16) -> [u8; 2] {
+        MovImmediateT1::opcode(&RP2350::new().cortex_m33.registers.r0, value).to_le_bytes()
+    }
+
+    #[test]
+    fn caches_executed_instructions() {
+        // should keep an instruction once it has run, and run the same from the cache
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.memory_mut().program(CODE, &mov_r0(1)).unwrap();
+
+        assert_eq!(run_at(&mut rp2350, CODE), 1);
+        assert_eq!(rp2350.cortex_m33.decode_cache.len(), 1);
+        rp2350.cortex_m33.registers.r0.set(0);
+        assert_eq!(run_at(&mut rp2350, CODE), 1);
+    }
+
+    #[test]
+    fn sram_write() {
+        // should drop instructions overwritten by a store from the core
+        let mut rp2350: RP2350 = RP2350::new();
+        let registers = rp2350.cortex_m33.registers;
+        rp2350.memory_mut().program(CODE, &mov_r0(1)).unwrap();
+        let store = StrhImmediateT1::opcode(&registers.r1, &registers.r2, 0);
+        rp2350.memory_mut().program(CODE + 0x10, &store.to_le_bytes()).unwrap();
+        assert_eq!(run_at(&mut rp2350, CODE), 1);
+
+        rp2350.cortex_m33.registers.r1.set(u16::from_le_bytes(mov_r0(2)) as u32);
+        rp2350.cortex_m33.registers.r2.set(CODE);
+        run_at(&mut rp2350, CODE + 0x10);
+
+        assert_eq!(run_at(&mut rp2350, CODE), 2);
+    }
+
+    #[test]
+    fn bus_write() {
+        // should drop instructions overwritten by any bus write, such as a debugger's
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.memory_mut().program(CODE, &mov_r0(1)).unwrap();
+        assert_eq!(run_at(&mut rp2350, CODE), 1);
+
+        rp2350.cortex_m33.memory.write_u16(CODE, u16::from_le_bytes(mov_r0(3))).unwrap();
+
+        assert_eq!(run_at(&mut rp2350, CODE), 3);
+    }
+
+    #[test]
+    fn flash_program() {
+        // should drop instructions from flash that is programmed again
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.memory_mut().program(FLASH_CODE, &mov_r0(1)).unwrap();
+        assert_eq!(run_at(&mut rp2350, FLASH_CODE), 1);
+
+        rp2350.memory_mut().program(FLASH_CODE, &mov_r0(4)).unwrap();
+
+        assert_eq!(run_at(&mut rp2350, FLASH_CODE), 4);
+    }
+
+    #[test]
+    fn flash_rom_functions() {
+        // should drop instructions from flash erased through the bootrom
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.memory_mut().program(FLASH_CODE, &mov_r0(1)).unwrap();
+        assert_eq!(run_at(&mut rp2350, FLASH_CODE), 1);
+
+        rp2350.cortex_m33.registers.r0.set(0);
+        rp2350.cortex_m33.registers.r1.set(0x1000);
+        rp2350.cortex_m33.registers.lr.set(CODE | 1);
+        run_at(&mut rp2350, RomFunction::FlashRangeErase.address());
+
+        // An erased halfword is all ones, the first half of a 32 bit instruction that doesn't decode
+        run_at(&mut rp2350, FLASH_CODE);
+        assert!(rp2350.cortex_m33.exceptions.is_pending(Exception::HardFault));
+    }
+
+    #[test]
+    fn xip_flush() {
+        // should fetch flash again after the XIP cache is flushed
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.memory_mut().program(FLASH_CODE, &mov_r0(1)).unwrap();
+        assert_eq!(run_at(&mut rp2350, FLASH_CODE), 1);
+
+        // Changing the flash behind the cache's back keeps the old instruction until the flush
+        rp2350.memory_mut().flash[0x100..0x102].copy_from_slice(&mov_r0(5));
+        assert_eq!(run_at(&mut rp2350, FLASH_CODE), 1);
+
+        rp2350.cortex_m33.registers.lr.set(CODE | 1);
+        run_at(&mut rp2350, RomFunction::FlashFlushCache.address());
+
+        assert_eq!(run_at(&mut rp2350, FLASH_CODE), 5);
+    }
+
+    #[test]
+    fn disabled() {
+        // should decode from memory every time when turned off
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.decode_cache.enabled = false;
+        rp2350.memory_mut().program(CODE, &mov_r0(1)).unwrap();
+
+        assert_eq!(run_at(&mut rp2350, CODE), 1);
+        assert!(rp2350.cortex_m33.decode_cache.is_empty());
+    }
+}
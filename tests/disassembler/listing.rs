@@ -0,0 +1,74 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::cortex_m33::disassembler::disassemble;
+    use rp2350_sim::cortex_m33::opcodes::*;
+    use rp2350_sim::loader::elf::{Symbol, SymbolKind, SymbolTable};
+    use rp2350_sim::{RAM_START_ADDRESS, RP2350};
+
+    const CODE: u32 = RAM_START_ADDRESS + 0x100;
+
+    fn setup(opcodes: &[u16]) -> RP2350 {
+        let mut rp2350: RP2350 = RP2350::new();
+        let code: Vec<u8> = opcodes.iter().flat_map(|opcode| opcode.to_le_bytes()).collect();
+        rp2350.memory_mut().program(CODE, &code).unwrap();
+        rp2350
+    }
+
+    #[test]
+    fn it_block() {
+        // should carry the IT block over the instructions it covers and no further
+        let registers = RP2350::new().cortex_m33.registers;
+        let rp2350 = setup(&[
+            ItT1::opcode(0b0000, 0b1100),
+            MovImmediateT1::opcode(&registers.r0, 1),
+            MovImmediateT1::opcode(&registers.r0, 2),
+            MovImmediateT1::opcode(&registers.r0, 3),
+        ]);
+
+        let lines = disassemble(&rp2350.cortex_m33, &SymbolTable::default(), CODE, 4);
+        let text: Vec<_> = lines.iter().map(|line| line.text.as_str()).collect();
+
+        assert_eq!(text, ["ite eq", "moveq r0, #1", "movne r0, #2", "movs r0, #3"]);
+    }
+
+    #[test]
+    fn wide_instructions() {
+        // should step over 32 bit instructions and keep both halfwords
+        let mut rp2350 = setup(&[]);
+        rp2350.memory_mut().program(CODE, &0x8f5ff3bfu32.to_le_bytes()).unwrap();
+        rp2350.memory_mut().program(CODE + 4, &YieldT1::opcode().to_le_bytes()).unwrap();
+
+        let lines = disassemble(&rp2350.cortex_m33, &SymbolTable::default(), CODE, 2);
+
+        assert_eq!(lines[0].opcode, 0x8f5ff3bf);
+        assert!(lines[0].is_32bit);
+        assert_eq!(lines[1].address, CODE + 4);
+        assert_eq!(lines[1].text, "yield");
+        assert_eq!(lines[0].to_string(), "20000100:  f3bf 8f5f  dmb sy");
+    }
+
+    #[test]
+    fn symbols() {
+        // should name branch targets after the symbol they fall in
+        let rp2350 = setup(&[BT2::opcode(0), BT2::opcode(-6i16 as u16)]);
+        let symbols = SymbolTable::new(vec![Symbol {
+            name: "main".to_string(),
+            address: CODE,
+            size: 8,
+            kind: SymbolKind::Function,
+        }]);
+
+        let lines = disassemble(&rp2350.cortex_m33, &symbols, CODE, 2);
+
+        assert_eq!(lines[0].text, "b.n 20000104 <main+0x4>");
+        assert_eq!(lines[1].text, "b.n 20000100 <main>");
+    }
+
+    #[test]
+    fn unmapped() {
+        // should stop at memory that can't be read
+        let rp2350 = RP2350::new();
+
+        assert!(disassemble(&rp2350.cortex_m33, &SymbolTable::default(), 0xf0000000, 4).is_empty());
+    }
+}
@@ -0,0 +1,2 @@
+mod listing;
+mod opcode;
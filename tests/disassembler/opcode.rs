@@ -0,0 +1,111 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::cortex_m33::opcodes::*;
+    use rp2350_sim::cortex_m33::registers::Register;
+    use rp2350_sim::cortex_m33::OpCode;
+    use rp2350_sim::{registers, RP2350};
+
+    const ADDRESS: u32 = 0x10000200;
+
+    fn narrow(code: u16) -> String {
+        OpCode { code, address: ADDRESS }.disassemble(OpCode { code: 0, address: ADDRESS + 2 })
+    }
+
+    fn wide(code: u32) -> String {
+        let first = OpCode { code: code as u16, address: ADDRESS };
+        first.disassemble(OpCode { code: (code >> 16) as u16, address: ADDRESS + 2 })
+    }
+
+    #[test]
+    fn data_processing() {
+        // should print 16 bit data processing with the flag setting suffix and the GNU register names
+        let registers = RP2350::new().cortex_m33.registers;
+
+        assert_eq!(narrow(MovImmediateT1::opcode(&registers.r0, 0)), "movs r0, #0");
+        assert_eq!(narrow(AddsRegisterT1::opcode(&registers.r6, &registers.r0, &registers.r3)), "adds r6, r0, r3");
+        assert_eq!(narrow(MovRegisterT1::opcode(&registers.r12, &registers.r2)), "mov ip, r2");
+        assert_eq!(narrow(UxtbT1::opcode(&registers.r3, &registers.r3)), "uxtb r3, r3");
+        assert_eq!(narrow(SubSpMinusImmediateT1::opcode(12)), "sub sp, #12");
+        assert_eq!(narrow(0x4353), "muls r3, r2");
+    }
+
+    #[test]
+    fn loads_and_stores() {
+        // should print addressing modes and register lists as objdump does
+        let registers = RP2350::new().cortex_m33.registers;
+
+        assert_eq!(narrow(0x6008), "str r0, [r1, #0]");
+        assert_eq!(narrow(0x5651), "ldrsb r1, [r2, r1]");
+        assert_eq!(narrow(LdmiaT1::opcode(&registers.r0, registers![registers.r1, registers.r2])), "ldmia r0!, {r1, r2}");
+        assert_eq!(narrow(PushT1::opcode(true, registers![registers.r4])), "push {r4, lr}");
+        assert_eq!(narrow(0xbd10), "pop {r4, pc}");
+        assert_eq!(wide(0x0c04f851), "ldr r0, [r1, #-4]");
+        assert_eq!(wide(0x0b04f851), "ldr r0, [r1], #4");
+        assert_eq!(wide(0x0022f851), "ldr.w r0, [r1, r2, lsl #2]");
+        assert_eq!(wide(0x41f0e92d), "push.w {r4, r5, r6, r7, r8, lr}");
+    }
+
+    #[test]
+    fn literal_loads() {
+        // should give the address a PC relative load reads from, from the word aligned PC
+        assert_eq!(narrow(0x491f), "ldr r1, [pc, #124] @ (10000280)");
+        assert_eq!(wide(0x0008f85f), "ldr.w r0, [pc, #-8] @ (100001fc)");
+    }
+
+    #[test]
+    fn branch_targets() {
+        // should print branches with their absolute target
+        assert_eq!(narrow(BT2::opcode(-8i16 as u16)), "b.n 100001fc");
+        assert_eq!(narrow(BT1::opcode(0b0000, 10)), "beq.n 1000020e");
+        assert_eq!(wide(BlT1::opcode(0x1000)), "bl 10001204");
+        assert_eq!(narrow(0xb10a), "cbz r2, 10000206");
+        assert_eq!(narrow(BlxT1::opcode(&RP2350::new().cortex_m33.registers.r1)), "blx r1");
+    }
+
+    #[test]
+    fn modified_immediates() {
+        // should expand the modified immediate constant of 32 bit data processing
+        assert_eq!(wide(0x30fff04f), "mov.w r0, #4294967295");
+        assert_eq!(wide(0x11fff012), "ands.w r1, r2, #16711935");
+        assert_eq!(wide(0x31cdf64a), "movw r1, #43981");
+        assert_eq!(wide(0x00c2ea01), "and.w r0, r1, r2, lsl #3");
+    }
+
+    #[test]
+    fn system() {
+        // should name special registers, barriers and the breakpoint immediate
+        assert_eq!(wide(0x8008f380), "msr MSP, r0");
+        assert_eq!(wide(0x8005f3ef), "mrs r0, IPSR");
+        assert_eq!(wide(0x8f5ff3bf), "dmb sy");
+        assert_eq!(narrow(0xb672), "cpsid i");
+        assert_eq!(narrow(BkptT1::opcode(0xab)), "bkpt 0x00ab");
+        assert_eq!(narrow(0x46c0), "nop @ (mov r8, r8)");
+    }
+
+    #[test]
+    fn it_instruction() {
+        // should spell out the then and else pattern of an IT instruction
+        assert_eq!(narrow(ItT1::opcode(0b0000, 0b1000)), "it eq");
+        assert_eq!(narrow(ItT1::opcode(0b0000, 0b0100)), "itt eq");
+        assert_eq!(narrow(ItT1::opcode(0b0000, 0b1100)), "ite eq");
+        assert_eq!(narrow(ItT1::opcode(0b1011, 0b0101)), "itete lt");
+    }
+
+    #[test]
+    fn inside_it_block() {
+        // should give instructions in an IT block their condition, and drop the flag setting suffix
+        let registers = RP2350::new().cortex_m33.registers;
+        let opcode = OpCode { code: MovImmediateT1::opcode(&registers.r0, 1), address: ADDRESS };
+        let next = OpCode { code: 0, address: ADDRESS + 2 };
+
+        assert_eq!(opcode.disassemble_in_it_block(next, 0b0001_1000), "movne r0, #1");
+        assert_eq!(opcode.disassemble_in_it_block(next, 0), "movs r0, #1");
+    }
+
+    #[test]
+    fn undefined() {
+        // should print encodings that don't decode as raw data
+        assert_eq!(narrow(0xb700), ".inst.n 0xb700");
+        assert_eq!(wide(0x0000ffff), ".inst.w 0xffff0000");
+    }
+}
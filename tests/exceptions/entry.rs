@@ -0,0 +1,161 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::cortex_m33::control::SpSel;
+    use rp2350_sim::cortex_m33::exception::{Exception, InterruptException};
+    use rp2350_sim::cortex_m33::fault::CFSR_INVPC;
+    use rp2350_sim::cortex_m33::opcodes::*;
+    use rp2350_sim::cortex_m33::registers::Register;
+    use rp2350_sim::cortex_m33::Mode;
+    use rp2350_sim::{RAM_START_ADDRESS, RP2350};
+
+    const VECTOR_TABLE: u32 = RAM_START_ADDRESS + 0x400;
+    const HANDLER: u32 = RAM_START_ADDRESS + 0x600;
+    const SYSTICK: Exception = Exception::Interrupt(InterruptException::SysTick);
+
+    /// Puts a `bx lr` handler for SysTick in SRAM and pends the exception
+    fn pend_systick(rp2350: &mut RP2350) {
+        rp2350.cortex_m33.vtor = VECTOR_TABLE;
+        rp2350.cortex_m33.memory.write_u32(VECTOR_TABLE + 4 * 15, HANDLER | 1).unwrap();
+        rp2350.cortex_m33.memory.write_u16(HANDLER, BxT1::opcode(&rp2350.cortex_m33.registers.lr)).unwrap();
+        rp2350.cortex_m33.exceptions.set_pending(SYSTICK);
+    }
+
+    #[test]
+    fn exception_entry_stacks_frame() {
+        // should push the basic frame and branch to the SysTick handler
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+        rp2350.cortex_m33.registers.sp.set(RAM_START_ADDRESS + 0x800);
+        rp2350.cortex_m33.registers.r0.set(0x10);
+        rp2350.cortex_m33.registers.r1.set(0x11);
+        rp2350.cortex_m33.registers.r2.set(0x12);
+        rp2350.cortex_m33.registers.r3.set(0x13);
+        rp2350.cortex_m33.registers.r12.set(0x1c);
+        rp2350.cortex_m33.registers.lr.set(0x10000301);
+        rp2350.cortex_m33.xpsr.apsr.set_n(true);
+        pend_systick(&mut rp2350);
+        rp2350.execute_instruction();
+
+        let frame = RAM_START_ADDRESS + 0x7e0;
+        assert_eq!(rp2350.cortex_m33.registers.sp.get(), frame);
+        assert_eq!(rp2350.cortex_m33.memory.read_u32(frame).unwrap(), 0x10);
+        assert_eq!(rp2350.cortex_m33.memory.read_u32(frame + 0x04).unwrap(), 0x11);
+        assert_eq!(rp2350.cortex_m33.memory.read_u32(frame + 0x08).unwrap(), 0x12);
+        assert_eq!(rp2350.cortex_m33.memory.read_u32(frame + 0x0c).unwrap(), 0x13);
+        assert_eq!(rp2350.cortex_m33.memory.read_u32(frame + 0x10).unwrap(), 0x1c);
+        assert_eq!(rp2350.cortex_m33.memory.read_u32(frame + 0x14).unwrap(), 0x10000301);
+        assert_eq!(rp2350.cortex_m33.memory.read_u32(frame + 0x18).unwrap(), RAM_START_ADDRESS);
+        assert_eq!(rp2350.cortex_m33.memory.read_u32(frame + 0x1c).unwrap(), 0x81000000);
+
+        assert_eq!(rp2350.cortex_m33.registers.lr.get(), 0xFFFFFFF9);
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), HANDLER);
+        assert_eq!(rp2350.cortex_m33.ipsr, 15);
+        assert_eq!(rp2350.cortex_m33.mode, Mode::Handler);
+        assert!(rp2350.cortex_m33.exceptions.active.contains_key(&15));
+        assert!(!rp2350.cortex_m33.exceptions.is_pending(SYSTICK));
+    }
+
+    #[test]
+    fn exception_return_restores_context() {
+        // should unstack the frame when the handler executes `bx lr`
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+        rp2350.cortex_m33.registers.sp.set(RAM_START_ADDRESS + 0x800);
+        rp2350.cortex_m33.registers.r0.set(0x10);
+        rp2350.cortex_m33.registers.r12.set(0x1c);
+        rp2350.cortex_m33.registers.lr.set(0x10000301);
+        rp2350.cortex_m33.xpsr.apsr.set_c(true);
+        pend_systick(&mut rp2350);
+        rp2350.execute_instruction();
+
+        rp2350.cortex_m33.registers.r0.set(0xdead);
+        rp2350.cortex_m33.registers.r12.set(0xbeef);
+        rp2350.cortex_m33.xpsr.apsr.set_c(false);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r0.get(), 0x10);
+        assert_eq!(rp2350.cortex_m33.registers.r12.get(), 0x1c);
+        assert_eq!(rp2350.cortex_m33.registers.lr.get(), 0x10000301);
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), RAM_START_ADDRESS);
+        assert_eq!(rp2350.cortex_m33.registers.sp.get(), RAM_START_ADDRESS + 0x800);
+        assert!(rp2350.cortex_m33.xpsr.apsr.c());
+        assert_eq!(rp2350.cortex_m33.ipsr, 0);
+        assert_eq!(rp2350.cortex_m33.mode, Mode::Thread);
+        assert!(rp2350.cortex_m33.exceptions.active.is_empty());
+    }
+
+    #[test]
+    fn exception_entry_aligns_stack() {
+        // should align the frame to 8 bytes and undo it on return
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+        rp2350.cortex_m33.registers.sp.set(RAM_START_ADDRESS + 0x804);
+        pend_systick(&mut rp2350);
+        rp2350.execute_instruction();
+
+        let frame = RAM_START_ADDRESS + 0x7e0;
+        assert_eq!(rp2350.cortex_m33.registers.sp.get(), frame);
+        assert_eq!(rp2350.cortex_m33.memory.read_u32(frame + 0x1c).unwrap(), 0x01000200);
+
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.sp.get(), RAM_START_ADDRESS + 0x804);
+    }
+
+    #[test]
+    fn exception_entry_from_process_stack() {
+        // should stack onto PSP, run the handler on MSP and return to PSP
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+        rp2350.cortex_m33.registers.sp.set_msp(RAM_START_ADDRESS + 0x800);
+        rp2350.cortex_m33.registers.sp.set_psp(RAM_START_ADDRESS + 0x700);
+        rp2350.cortex_m33.control.spsel = SpSel::SpProcess;
+        rp2350.cortex_m33.update_sp_mode();
+        pend_systick(&mut rp2350);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.lr.get(), 0xFFFFFFFD);
+        assert_eq!(rp2350.cortex_m33.registers.sp.get_psp(), RAM_START_ADDRESS + 0x6e0);
+        assert_eq!(rp2350.cortex_m33.registers.sp.get(), RAM_START_ADDRESS + 0x800);
+
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.sp.get(), RAM_START_ADDRESS + 0x700);
+        assert_eq!(rp2350.cortex_m33.registers.sp.get_msp(), RAM_START_ADDRESS + 0x800);
+    }
+
+    #[test]
+    fn exception_return_restores_itstate() {
+        // should return into the middle of an IT block without advancing ITSTATE
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+        rp2350.cortex_m33.registers.sp.set(RAM_START_ADDRESS + 0x800);
+        rp2350.cortex_m33.xpsr.epsr.set_it(0b0000_0100);
+        pend_systick(&mut rp2350);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.xpsr.epsr.it(), 0);
+
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.xpsr.epsr.it(), 0b0000_0100);
+    }
+
+    #[test]
+    fn invalid_exc_return_raises_usage_fault() {
+        // should fault when the handler returns to handler mode with nothing else active
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+        rp2350.cortex_m33.registers.sp.set(RAM_START_ADDRESS + 0x800);
+        pend_systick(&mut rp2350);
+        rp2350.execute_instruction();
+
+        rp2350.cortex_m33.registers.lr.set(0xFFFFFFF1);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), HANDLER);
+        assert_eq!(rp2350.cortex_m33.fault_status.cfsr, CFSR_INVPC);
+        assert!(rp2350.cortex_m33.exceptions.active.contains_key(&15));
+        assert!(rp2350.cortex_m33.exceptions.is_pending(Exception::HardFault));
+    }
+}
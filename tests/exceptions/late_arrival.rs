@@ -0,0 +1,57 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::cortex_m33::exception::{Exception, InterruptException};
+    use rp2350_sim::cortex_m33::fault::{CFSR_STKERR, HFSR_FORCED};
+    use rp2350_sim::cortex_m33::opcodes::*;
+    use rp2350_sim::cortex_m33::registers::Register;
+    use rp2350_sim::{FLASH_START_ADDRESS, RAM_START_ADDRESS, RP2350};
+
+    const VECTOR_TABLE: u32 = RAM_START_ADDRESS + 0x400;
+    const HARD_FAULT_HANDLER: u32 = RAM_START_ADDRESS + 0x600;
+    const SYSTICK_HANDLER: u32 = RAM_START_ADDRESS + 0x700;
+    const SYSTICK: Exception = Exception::Interrupt(InterruptException::SysTick);
+
+    #[test]
+    fn stacking_fault_arrives_late() {
+        // should take the HardFault from a failed stack push ahead of the SysTick that caused it
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+        rp2350.cortex_m33.registers.sp.set(FLASH_START_ADDRESS + 0x100);
+        rp2350.cortex_m33.vtor = VECTOR_TABLE;
+
+        rp2350.cortex_m33.memory.write_u32(VECTOR_TABLE + 4 * 3, HARD_FAULT_HANDLER | 1).unwrap();
+        rp2350.cortex_m33.memory.write_u32(VECTOR_TABLE + 4 * 15, SYSTICK_HANDLER | 1).unwrap();
+        rp2350.cortex_m33.exceptions.set_pending(SYSTICK);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.ipsr, 3);
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), HARD_FAULT_HANDLER);
+        assert_eq!(rp2350.cortex_m33.fault_status.cfsr, CFSR_STKERR);
+        assert_eq!(rp2350.cortex_m33.fault_status.hfsr, HFSR_FORCED);
+        assert!(rp2350.cortex_m33.exceptions.is_pending(SYSTICK));
+    }
+
+    #[test]
+    fn fault_in_handler_preempts() {
+        // should nest a HardFault raised by `udf` inside the SysTick handler
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+        rp2350.cortex_m33.registers.sp.set(RAM_START_ADDRESS + 0x800);
+        rp2350.cortex_m33.vtor = VECTOR_TABLE;
+
+        rp2350.cortex_m33.memory.write_u32(VECTOR_TABLE + 4 * 3, HARD_FAULT_HANDLER | 1).unwrap();
+        rp2350.cortex_m33.memory.write_u32(VECTOR_TABLE + 4 * 15, SYSTICK_HANDLER | 1).unwrap();
+        rp2350.cortex_m33.memory.write_u16(SYSTICK_HANDLER, UdfT1::opcode(0)).unwrap();
+        rp2350.cortex_m33.exceptions.set_pending(SYSTICK);
+        rp2350.execute_instruction();
+        rp2350.execute_instruction();
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.ipsr, 3);
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), HARD_FAULT_HANDLER);
+        assert_eq!(rp2350.cortex_m33.registers.lr.get(), 0xFFFFFFF1);
+        assert_eq!(rp2350.cortex_m33.registers.sp.get(), RAM_START_ADDRESS + 0x7c0);
+        assert_eq!(rp2350.cortex_m33.memory.read_u32(RAM_START_ADDRESS + 0x7d8).unwrap(), SYSTICK_HANDLER);
+        assert_eq!(rp2350.cortex_m33.exceptions.active.len(), 2);
+    }
+}
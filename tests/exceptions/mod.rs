@@ -0,0 +1,8 @@
+mod entry;
+mod late_arrival;
+mod nvic;
+mod reset;
+mod scb;
+mod svc;
+mod systick;
+mod tail_chain;
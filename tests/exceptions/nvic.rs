@@ -0,0 +1,216 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::cortex_m33::control::NPriv;
+    use rp2350_sim::cortex_m33::exception::Exception;
+    use rp2350_sim::cortex_m33::fault::{CFSR_BFARVALID, CFSR_PRECISERR};
+    use rp2350_sim::cortex_m33::nvic::Nvic;
+    use rp2350_sim::cortex_m33::opcodes::*;
+    use rp2350_sim::cortex_m33::registers::Register;
+    use rp2350_sim::{RAM_START_ADDRESS, RP2350};
+
+    const VECTOR_TABLE: u32 = RAM_START_ADDRESS + 0x400;
+    const HANDLER: u32 = RAM_START_ADDRESS + 0x600;
+
+    const NVIC_ISER0: u32 = 0xE000E100;
+    const NVIC_ISER1: u32 = 0xE000E104;
+    const NVIC_ICER0: u32 = 0xE000E180;
+    const NVIC_ISPR0: u32 = 0xE000E200;
+    const NVIC_ICPR0: u32 = 0xE000E280;
+    const NVIC_IABR0: u32 = 0xE000E300;
+    const NVIC_IPR0: u32 = 0xE000E400;
+
+    /// Points every IRQ at a `bx lr` handler and fills the thread code with `movs r0, #0`
+    fn setup(rp2350: &mut RP2350) {
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+        rp2350.cortex_m33.registers.sp.set(RAM_START_ADDRESS + 0x800);
+        rp2350.cortex_m33.vtor = VECTOR_TABLE;
+
+        for n in 16..68 {
+            rp2350.cortex_m33.memory.write_u32(VECTOR_TABLE + 4 * n, HANDLER | 1).unwrap();
+        }
+        rp2350.cortex_m33.memory.write_u16(HANDLER, BxT1::opcode(&rp2350.cortex_m33.registers.lr)).unwrap();
+        for i in 0..8 {
+            rp2350.cortex_m33.memory.write_u16(
+                RAM_START_ADDRESS + 2 * i,
+                MovImmediateT1::opcode(&rp2350.cortex_m33.registers.r0, 0),
+            ).unwrap();
+        }
+    }
+
+    #[test]
+    fn enable_bits_set_and_clear() {
+        // should set enables through ISER, clear them through ICER, and ignore IRQs past the 52nd
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.write_u32(NVIC_ISER0, 0x8000_0021).unwrap();
+        rp2350.cortex_m33.write_u32(NVIC_ISER1, 0xffff_ffff).unwrap();
+
+        assert_eq!(rp2350.cortex_m33.read_u32(NVIC_ISER0).unwrap(), 0x8000_0021);
+        assert_eq!(rp2350.cortex_m33.read_u32(NVIC_ICER0).unwrap(), 0x8000_0021);
+        assert_eq!(rp2350.cortex_m33.read_u32(NVIC_ISER1).unwrap(), 0x000f_ffff);
+        assert!(rp2350.cortex_m33.nvic.is_enabled(51));
+
+        rp2350.cortex_m33.write_u32(NVIC_ICER0, 0x0000_0001).unwrap();
+
+        assert_eq!(rp2350.cortex_m33.read_u32(NVIC_ISER0).unwrap(), 0x8000_0020);
+    }
+
+    #[test]
+    fn pending_bits_set_and_clear() {
+        // should pend IRQ 3 through ISPR and clear it through ICPR
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.write_u32(NVIC_ISPR0, 1 << 3).unwrap();
+
+        assert!(rp2350.cortex_m33.exceptions.is_pending(Nvic::exception(3)));
+        assert_eq!(rp2350.cortex_m33.read_u32(NVIC_ICPR0).unwrap(), 1 << 3);
+
+        rp2350.cortex_m33.write_u32(NVIC_ICPR0, 1 << 3).unwrap();
+
+        assert!(!rp2350.cortex_m33.exceptions.is_pending(Nvic::exception(3)));
+        assert_eq!(rp2350.cortex_m33.read_u32(NVIC_ISPR0).unwrap(), 0);
+    }
+
+    #[test]
+    fn priority_keeps_four_bits() {
+        // should implement the top four bits of each priority byte
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.write_u8(NVIC_IPR0 + 3, 0xff).unwrap();
+        rp2350.cortex_m33.write_u16(NVIC_IPR0 + 0x30, 0x1234).unwrap();
+
+        assert_eq!(rp2350.cortex_m33.read_u32(NVIC_IPR0).unwrap(), 0xf000_0000);
+        assert_eq!(rp2350.cortex_m33.read_u8(NVIC_IPR0 + 3).unwrap(), 0xf0);
+        assert_eq!(rp2350.cortex_m33.nvic.priority(3), 0xf0);
+        assert_eq!(rp2350.cortex_m33.nvic.priority(48), 0x30);
+        assert_eq!(rp2350.cortex_m33.nvic.priority(49), 0x10);
+    }
+
+    #[test]
+    fn nvic_written_by_str() {
+        // should enable IRQ 40 with a `str r0, [r1, #4]` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u16(
+            RAM_START_ADDRESS,
+            StrImmediateT1::opcode(&rp2350.cortex_m33.registers.r0, &rp2350.cortex_m33.registers.r1, 4),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r0.set(1 << 8);
+        rp2350.cortex_m33.registers.r1.set(NVIC_ISER0);
+        rp2350.execute_instruction();
+
+        assert!(rp2350.cortex_m33.nvic.is_enabled(40));
+    }
+
+    #[test]
+    fn unprivileged_access_faults() {
+        // should raise a precise BusFault for an unprivileged `str r0, [r1]` to the NVIC
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+        rp2350.cortex_m33.control.npriv = NPriv::ThreadModeUnprivileged;
+
+        rp2350.cortex_m33.memory.write_u16(
+            RAM_START_ADDRESS,
+            StrImmediateT1::opcode(&rp2350.cortex_m33.registers.r0, &rp2350.cortex_m33.registers.r1, 0),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r0.set(1);
+        rp2350.cortex_m33.registers.r1.set(NVIC_ISER0);
+        rp2350.execute_instruction();
+
+        assert!(!rp2350.cortex_m33.nvic.is_enabled(0));
+        assert_eq!(rp2350.cortex_m33.fault_status.cfsr, CFSR_PRECISERR | CFSR_BFARVALID);
+        assert_eq!(rp2350.cortex_m33.fault_status.bfar, NVIC_ISER0);
+        assert!(rp2350.cortex_m33.exceptions.is_pending(Exception::HardFault));
+    }
+
+    #[test]
+    fn disabled_irq_stays_pending() {
+        // should only take a raised IRQ once it is enabled
+        let mut rp2350: RP2350 = RP2350::new();
+        setup(&mut rp2350);
+        rp2350.set_irq(5, true);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), RAM_START_ADDRESS + 2);
+        assert!(rp2350.cortex_m33.exceptions.is_pending(Nvic::exception(5)));
+
+        rp2350.cortex_m33.nvic.set_enabled(5, true);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), HANDLER);
+        assert_eq!(rp2350.cortex_m33.ipsr, 21);
+        assert_eq!(rp2350.cortex_m33.read_u32(NVIC_IABR0).unwrap(), 1 << 5);
+    }
+
+    #[test]
+    fn highest_priority_irq_taken_first() {
+        // should take IRQ 2 at priority 0x40 ahead of IRQ 1 at priority 0x80
+        let mut rp2350: RP2350 = RP2350::new();
+        setup(&mut rp2350);
+        rp2350.cortex_m33.nvic.set_priority(1, 0x80);
+        rp2350.cortex_m33.nvic.set_priority(2, 0x40);
+        rp2350.cortex_m33.write_u32(NVIC_ISER0, 0b110).unwrap();
+        rp2350.cortex_m33.write_u32(NVIC_ISPR0, 0b110).unwrap();
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.ipsr, 18);
+
+        // Returning tail-chains into the lower priority IRQ
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.ipsr, 17);
+    }
+
+    #[test]
+    fn higher_priority_irq_preempts() {
+        // should nest IRQ 2 inside the handler for the lower priority IRQ 1
+        let mut rp2350: RP2350 = RP2350::new();
+        setup(&mut rp2350);
+        rp2350.cortex_m33.nvic.set_priority(1, 0x80);
+        rp2350.cortex_m33.nvic.set_priority(2, 0x40);
+        rp2350.cortex_m33.write_u32(NVIC_ISER0, 0b110).unwrap();
+        rp2350.set_irq(1, true);
+        rp2350.execute_instruction();
+        rp2350.set_irq(2, true);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.ipsr, 18);
+        assert_eq!(rp2350.cortex_m33.registers.lr.get(), 0xFFFFFFF1);
+        assert_eq!(rp2350.cortex_m33.read_u32(NVIC_IABR0).unwrap(), 0b110);
+    }
+
+    #[test]
+    fn subpriority_does_not_preempt() {
+        // should not preempt when PRIGROUP puts both priorities in the same group
+        let mut rp2350: RP2350 = RP2350::new();
+        setup(&mut rp2350);
+        rp2350.cortex_m33.nvic.prigroup = 6;
+        rp2350.cortex_m33.nvic.set_priority(1, 0x40);
+        rp2350.cortex_m33.nvic.set_priority(2, 0x20);
+        rp2350.cortex_m33.write_u32(NVIC_ISER0, 0b110).unwrap();
+        rp2350.set_irq(1, true);
+        rp2350.execute_instruction();
+        rp2350.set_irq(2, true);
+
+        assert!(!rp2350.cortex_m33.take_pending_exception());
+        assert_eq!(rp2350.cortex_m33.ipsr, 17);
+    }
+
+    #[test]
+    fn asserted_line_pends_again() {
+        // should re-enter the handler while the IRQ line stays high
+        let mut rp2350: RP2350 = RP2350::new();
+        setup(&mut rp2350);
+        rp2350.cortex_m33.nvic.set_enabled(7, true);
+        rp2350.set_irq(7, true);
+        rp2350.execute_instruction();
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.ipsr, 23);
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), HANDLER);
+
+        rp2350.set_irq(7, false);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.ipsr, 0);
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), RAM_START_ADDRESS);
+    }
+}
@@ -0,0 +1,92 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::cortex_m33::control::SpSel;
+    use rp2350_sim::cortex_m33::exception::{Exception, InterruptException};
+    use rp2350_sim::cortex_m33::fault::CFSR_INVSTATE;
+    use rp2350_sim::cortex_m33::opcodes::*;
+    use rp2350_sim::cortex_m33::registers::Register;
+    use rp2350_sim::cortex_m33::Mode;
+    use rp2350_sim::{BOOT_VECTOR_TABLE, FLASH_START_ADDRESS, RAM_START_ADDRESS, RP2350};
+
+    const INITIAL_SP: u32 = RAM_START_ADDRESS + 0x1000;
+    const RESET_HANDLER: u32 = FLASH_START_ADDRESS + 0x100;
+
+    /// Programs a vector table at the start of flash
+    fn program_vectors(rp2350: &mut RP2350, reset_handler: u32) {
+        rp2350.memory_mut().program(FLASH_START_ADDRESS, &INITIAL_SP.to_le_bytes()).unwrap();
+        rp2350.memory_mut().program(FLASH_START_ADDRESS + 4, &reset_handler.to_le_bytes()).unwrap();
+        let opcode = MovImmediateT1::opcode(&rp2350.cortex_m33.registers.r0, 0x42);
+        rp2350.memory_mut().program(RESET_HANDLER, &opcode.to_le_bytes()).unwrap();
+    }
+
+    #[test]
+    fn reset_loads_msp_and_pc() {
+        // should load MSP and the reset handler from the vector table and run from there
+        let mut rp2350: RP2350 = RP2350::new();
+        program_vectors(&mut rp2350, RESET_HANDLER | 1);
+        rp2350.reset();
+
+        assert_eq!(rp2350.cortex_m33.vtor, BOOT_VECTOR_TABLE);
+        assert_eq!(rp2350.cortex_m33.registers.sp.get(), INITIAL_SP);
+        assert_eq!(rp2350.cortex_m33.registers.lr.get(), 0xFFFFFFFF);
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), RESET_HANDLER);
+        assert!(rp2350.cortex_m33.xpsr.epsr.t());
+
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r0.get(), 0x42);
+    }
+
+    #[test]
+    fn reset_clears_core_state() {
+        // should leave handler mode, drop active and pending exceptions and return to the main stack
+        let mut rp2350: RP2350 = RP2350::new();
+        program_vectors(&mut rp2350, RESET_HANDLER | 1);
+        rp2350.cortex_m33.mode = Mode::Handler;
+        rp2350.cortex_m33.ipsr = 15;
+        rp2350.cortex_m33.exceptions.active.insert(15, Exception::Interrupt(InterruptException::SysTick));
+        rp2350.cortex_m33.exceptions.set_pending(Exception::Interrupt(InterruptException::PendSV));
+        rp2350.cortex_m33.nvic.set_enabled(3, true);
+        rp2350.cortex_m33.control.spsel = SpSel::SpProcess;
+        rp2350.cortex_m33.primask = true;
+        rp2350.cortex_m33.locked_up = true;
+        rp2350.cortex_m33.registers.r7.set(0x1234);
+        rp2350.reset();
+
+        assert_eq!(rp2350.cortex_m33.mode, Mode::Thread);
+        assert_eq!(rp2350.cortex_m33.ipsr, 0);
+        assert!(rp2350.cortex_m33.exceptions.active.is_empty());
+        assert!(rp2350.cortex_m33.exceptions.pending.is_empty());
+        assert!(!rp2350.cortex_m33.nvic.is_enabled(3));
+        assert_eq!(rp2350.cortex_m33.control.spsel, SpSel::SpMain);
+        assert!(!rp2350.cortex_m33.primask);
+        assert!(!rp2350.cortex_m33.locked_up);
+        assert_eq!(rp2350.cortex_m33.registers.r7.get(), 0);
+    }
+
+    #[test]
+    fn reset_handler_without_thumb_bit() {
+        // should clear EPSR.T for an even reset handler, faulting on the first instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        program_vectors(&mut rp2350, RESET_HANDLER);
+        rp2350.reset();
+
+        assert!(!rp2350.cortex_m33.xpsr.epsr.t());
+
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.fault_status.cfsr, CFSR_INVSTATE);
+        assert!(rp2350.cortex_m33.exceptions.is_pending(Exception::HardFault));
+    }
+
+    #[test]
+    fn reset_keeps_memory() {
+        // should leave SRAM alone
+        let mut rp2350: RP2350 = RP2350::new();
+        program_vectors(&mut rp2350, RESET_HANDLER | 1);
+        rp2350.cortex_m33.memory.write_u32(RAM_START_ADDRESS, 0xdeadbeef).unwrap();
+        rp2350.reset();
+
+        assert_eq!(rp2350.cortex_m33.memory.read_u32(RAM_START_ADDRESS).unwrap(), 0xdeadbeef);
+    }
+}
@@ -0,0 +1,223 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::cortex_m33::exception::{Exception, InterruptException};
+    use rp2350_sim::cortex_m33::fault::{CFSR_UNDEFINSTR, SHCSR_USGFAULTENA};
+    use rp2350_sim::cortex_m33::opcodes::*;
+    use rp2350_sim::cortex_m33::registers::Register;
+    use rp2350_sim::cortex_m33::scb::{
+        AIRCR_SYSRESETREQ, AIRCR_VECTKEY, CCR_RESET, CPACR_MASK, CPUID_VALUE, ICSR_PENDSVCLR, ICSR_PENDSVSET,
+        ICSR_RETTOBASE, NSACR_MASK,
+    };
+    use rp2350_sim::{FLASH_START_ADDRESS, RAM_START_ADDRESS, RP2350};
+
+    const VECTOR_TABLE: u32 = RAM_START_ADDRESS + 0x400;
+    const PENDSV_HANDLER: u32 = RAM_START_ADDRESS + 0x600;
+    const SYSTICK_HANDLER: u32 = RAM_START_ADDRESS + 0x700;
+    const PENDSV: Exception = Exception::Interrupt(InterruptException::PendSV);
+    const SYSTICK: Exception = Exception::Interrupt(InterruptException::SysTick);
+
+    const ICTR: u32 = 0xE000E004;
+    const CPUID: u32 = 0xE000ED00;
+    const ICSR: u32 = 0xE000ED04;
+    const VTOR: u32 = 0xE000ED08;
+    const AIRCR: u32 = 0xE000ED0C;
+    const CCR: u32 = 0xE000ED14;
+    const SHPR3: u32 = 0xE000ED20;
+    const SHCSR: u32 = 0xE000ED24;
+    const CFSR: u32 = 0xE000ED28;
+    const CPACR: u32 = 0xE000ED88;
+    const NSACR: u32 = 0xE000ED8C;
+
+    /// Puts a vector table at VECTOR_TABLE, without pointing VTOR at it
+    fn setup(rp2350: &mut RP2350) {
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+        rp2350.cortex_m33.registers.sp.set(RAM_START_ADDRESS + 0x800);
+
+        rp2350.cortex_m33.memory.write_u32(VECTOR_TABLE + 4 * 14, PENDSV_HANDLER | 1).unwrap();
+        rp2350.cortex_m33.memory.write_u32(VECTOR_TABLE + 4 * 15, SYSTICK_HANDLER | 1).unwrap();
+    }
+
+    #[test]
+    fn reset_values() {
+        // should identify as a Cortex-M33 with the architectural reset values
+        let mut rp2350: RP2350 = RP2350::new();
+
+        assert_eq!(rp2350.cortex_m33.read_u32(CPUID).unwrap(), CPUID_VALUE);
+        assert_eq!(rp2350.cortex_m33.read_u32(ICTR).unwrap(), 1);
+        assert_eq!(rp2350.cortex_m33.read_u32(ICSR).unwrap(), 0);
+        assert_eq!(rp2350.cortex_m33.read_u32(VTOR).unwrap(), 0);
+        assert_eq!(rp2350.cortex_m33.read_u32(AIRCR).unwrap(), 0xFA050000);
+        assert_eq!(rp2350.cortex_m33.read_u32(CCR).unwrap(), CCR_RESET);
+    }
+
+    #[test]
+    fn vtor_relocates_vector_table() {
+        // should fetch the SysTick handler from the table VTOR points at
+        let mut rp2350: RP2350 = RP2350::new();
+        setup(&mut rp2350);
+        rp2350.cortex_m33.write_u32(VTOR, VECTOR_TABLE | 0x7f).unwrap();
+
+        assert_eq!(rp2350.cortex_m33.read_u32(VTOR).unwrap(), VECTOR_TABLE);
+
+        rp2350.cortex_m33.exceptions.set_pending(SYSTICK);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), SYSTICK_HANDLER);
+    }
+
+    #[test]
+    fn icsr_pendsvset_pends_pendsv() {
+        // should pend PendSV through a `str r0, [r1]` to ICSR and report it until it is taken
+        let mut rp2350: RP2350 = RP2350::new();
+        setup(&mut rp2350);
+        rp2350.cortex_m33.vtor = VECTOR_TABLE;
+
+        rp2350.cortex_m33.memory.write_u16(
+            RAM_START_ADDRESS,
+            StrImmediateT1::opcode(&rp2350.cortex_m33.registers.r0, &rp2350.cortex_m33.registers.r1, 0),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r0.set(ICSR_PENDSVSET);
+        rp2350.cortex_m33.registers.r1.set(ICSR);
+        rp2350.execute_instruction();
+
+        assert!(rp2350.cortex_m33.exceptions.is_pending(PENDSV));
+        assert_eq!(rp2350.cortex_m33.read_u32(ICSR).unwrap(), ICSR_PENDSVSET | 14 << 12);
+
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), PENDSV_HANDLER);
+        assert_eq!(rp2350.cortex_m33.read_u32(ICSR).unwrap(), ICSR_RETTOBASE | 14);
+    }
+
+    #[test]
+    fn icsr_pendsvclr_clears_pendsv() {
+        // should remove a pending PendSV
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.write_u32(ICSR, ICSR_PENDSVSET).unwrap();
+        rp2350.cortex_m33.write_u32(ICSR, ICSR_PENDSVCLR).unwrap();
+
+        assert!(!rp2350.cortex_m33.exceptions.is_pending(PENDSV));
+    }
+
+    #[test]
+    fn aircr_needs_vectkey() {
+        // should only take PRIGROUP from a word write that carries the key
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.write_u32(AIRCR, 5 << 8).unwrap();
+
+        assert_eq!(rp2350.cortex_m33.nvic.prigroup, 0);
+
+        rp2350.cortex_m33.write_u32(AIRCR, AIRCR_VECTKEY << 16 | 5 << 8).unwrap();
+
+        assert_eq!(rp2350.cortex_m33.nvic.prigroup, 5);
+        assert_eq!(rp2350.cortex_m33.read_u32(AIRCR).unwrap(), 0xFA050500);
+    }
+
+    #[test]
+    fn aircr_sysresetreq_resets() {
+        // should reset the chip and boot from the flash vector table before the next instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        let reset_handler = FLASH_START_ADDRESS + 0x100;
+        rp2350.memory_mut().program(FLASH_START_ADDRESS, &(RAM_START_ADDRESS + 0x1000).to_le_bytes()).unwrap();
+        rp2350.memory_mut().program(FLASH_START_ADDRESS + 4, &(reset_handler | 1).to_le_bytes()).unwrap();
+        let opcode = MovImmediateT1::opcode(&rp2350.cortex_m33.registers.r0, 1);
+        rp2350.memory_mut().program(reset_handler, &opcode.to_le_bytes()).unwrap();
+        rp2350.cortex_m33.vtor = VECTOR_TABLE;
+        rp2350.cortex_m33.registers.r4.set(0x1234);
+        rp2350.cortex_m33.write_u32(AIRCR, AIRCR_VECTKEY << 16 | AIRCR_SYSRESETREQ).unwrap();
+
+        assert!(rp2350.cortex_m33.reset_requested);
+
+        rp2350.execute_instruction();
+
+        assert!(!rp2350.cortex_m33.reset_requested);
+        assert_eq!(rp2350.cortex_m33.vtor, FLASH_START_ADDRESS);
+        assert_eq!(rp2350.cortex_m33.registers.r4.get(), 0);
+        assert_eq!(rp2350.cortex_m33.registers.r0.get(), 1);
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), reset_handler + 2);
+    }
+
+    #[test]
+    fn shpr3_sets_priorities() {
+        // should take SysTick ahead of PendSV once SHPR3 gives it the higher priority
+        let mut rp2350: RP2350 = RP2350::new();
+        setup(&mut rp2350);
+        rp2350.cortex_m33.vtor = VECTOR_TABLE;
+        rp2350.cortex_m33.write_u32(SHPR3, 0x4f8f_ffff).unwrap();
+
+        assert_eq!(rp2350.cortex_m33.read_u32(SHPR3).unwrap(), 0x4080_0000);
+
+        rp2350.cortex_m33.exceptions.set_pending(PENDSV);
+        rp2350.cortex_m33.exceptions.set_pending(SYSTICK);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.ipsr, 15);
+    }
+
+    #[test]
+    fn shcsr_enables_usage_fault() {
+        // should route `udf` to UsageFault once it is enabled, and report it as pended
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+        rp2350.cortex_m33.write_u32(SHCSR, SHCSR_USGFAULTENA).unwrap();
+
+        rp2350.cortex_m33.memory.write_u16(RAM_START_ADDRESS, UdfT1::opcode(0)).unwrap();
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.read_u32(SHCSR).unwrap(), SHCSR_USGFAULTENA | 1 << 12);
+    }
+
+    #[test]
+    fn cfsr_write_one_to_clear() {
+        // should expose the fault status and clear it with the bits written
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u16(RAM_START_ADDRESS, UdfT1::opcode(0)).unwrap();
+        rp2350.execute_instruction();
+
+        let cfsr = rp2350.cortex_m33.read_u32(CFSR).unwrap();
+        assert_eq!(cfsr, CFSR_UNDEFINSTR);
+
+        rp2350.cortex_m33.write_u32(CFSR, cfsr).unwrap();
+
+        assert_eq!(rp2350.cortex_m33.read_u32(CFSR).unwrap(), 0);
+    }
+
+    #[test]
+    fn cpacr_nsacr_storage() {
+        // should keep what is written to CPACR and NSACR, reading the reserved bits as zero
+        let mut rp2350: RP2350 = RP2350::new();
+        assert_eq!(rp2350.cortex_m33.read_u32(CPACR).unwrap(), 0);
+        assert_eq!(rp2350.cortex_m33.read_u32(NSACR).unwrap(), 0);
+
+        rp2350.cortex_m33.write_u32(CPACR, 0xFFFFFFFF).unwrap();
+        rp2350.cortex_m33.write_u32(NSACR, 0xFFFFFFFF).unwrap();
+
+        assert_eq!(rp2350.cortex_m33.read_u32(CPACR).unwrap(), CPACR_MASK);
+        assert_eq!(rp2350.cortex_m33.read_u32(NSACR).unwrap(), NSACR_MASK);
+
+        rp2350.reset();
+        assert_eq!(rp2350.cortex_m33.read_u32(CPACR).unwrap(), 0);
+    }
+
+    #[test]
+    fn cpacr_enables_coprocessors() {
+        // should take the `str r0, [r1]` the pico-sdk runtime init uses to enable the coprocessors without faulting
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u16(
+            RAM_START_ADDRESS,
+            StrImmediateT1::opcode(&rp2350.cortex_m33.registers.r0, &rp2350.cortex_m33.registers.r1, 0),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r0.set(0xF << 20 | 0x3 << 8 | 0x3);
+        rp2350.cortex_m33.registers.r1.set(CPACR);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.cpacr, 0xF << 20 | 0x3 << 8 | 0x3);
+        assert_eq!(rp2350.cortex_m33.fault_status.cfsr, 0);
+        assert!(!rp2350.cortex_m33.exceptions.is_pending(Exception::HardFault));
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), RAM_START_ADDRESS + 2);
+    }
+}
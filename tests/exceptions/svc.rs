@@ -0,0 +1,69 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::cortex_m33::exception::Exception;
+    use rp2350_sim::cortex_m33::fault::HFSR_FORCED;
+    use rp2350_sim::cortex_m33::opcodes::*;
+    use rp2350_sim::cortex_m33::registers::Register;
+    use rp2350_sim::cortex_m33::Mode;
+    use rp2350_sim::{RAM_START_ADDRESS, RP2350};
+
+    const VECTOR_TABLE: u32 = RAM_START_ADDRESS + 0x400;
+    const HANDLER: u32 = RAM_START_ADDRESS + 0x600;
+    const STACK: u32 = RAM_START_ADDRESS + 0x800;
+
+    /// Puts an `svc #1` at the start of SRAM, followed by a `yield`, with a `bx lr` handler for SVCall
+    fn setup() -> RP2350 {
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+        rp2350.cortex_m33.registers.sp.set(STACK);
+        rp2350.cortex_m33.vtor = VECTOR_TABLE;
+        rp2350.cortex_m33.memory.write_u32(VECTOR_TABLE + 4 * 11, HANDLER | 1).unwrap();
+        rp2350.cortex_m33.memory.write_u16(HANDLER, BxT1::opcode(&rp2350.cortex_m33.registers.lr)).unwrap();
+        rp2350.cortex_m33.memory.write_u16(RAM_START_ADDRESS, SvcT1::opcode(1)).unwrap();
+        rp2350.cortex_m33.memory.write_u16(RAM_START_ADDRESS + 2, YieldT1::opcode()).unwrap();
+        rp2350
+    }
+
+    #[test]
+    fn svc_enters_svcall() {
+        // should take SVCall before the next instruction, stacking the address after the `svc` to return to
+        let mut rp2350 = setup();
+        rp2350.execute_instruction();
+
+        assert!(rp2350.cortex_m33.exceptions.is_pending(Exception::SVCall));
+
+        rp2350.execute_instruction();
+
+        let frame = STACK - 0x20;
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), HANDLER);
+        assert_eq!(rp2350.cortex_m33.ipsr, 11);
+        assert_eq!(rp2350.cortex_m33.mode, Mode::Handler);
+        assert_eq!(rp2350.cortex_m33.memory.read_u32(frame + 0x18).unwrap(), RAM_START_ADDRESS + 2);
+        assert!(!rp2350.cortex_m33.exceptions.is_pending(Exception::SVCall));
+    }
+
+    #[test]
+    fn svc_returns_after_call() {
+        // should carry on after the `svc` once the handler returns
+        let mut rp2350 = setup();
+        rp2350.execute_instruction();
+        rp2350.execute_instruction();
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), RAM_START_ADDRESS + 2);
+        assert_eq!(rp2350.cortex_m33.mode, Mode::Thread);
+        assert_eq!(rp2350.cortex_m33.registers.sp.get(), STACK);
+    }
+
+    #[test]
+    fn svc_masked_escalates() {
+        // should escalate to HardFault when PRIMASK stops SVCall from being taken
+        let mut rp2350 = setup();
+        rp2350.cortex_m33.primask = true;
+        rp2350.execute_instruction();
+
+        assert!(!rp2350.cortex_m33.exceptions.is_pending(Exception::SVCall));
+        assert!(rp2350.cortex_m33.exceptions.is_pending(Exception::HardFault));
+        assert_eq!(rp2350.cortex_m33.fault_status.hfsr, HFSR_FORCED);
+    }
+}
@@ -0,0 +1,164 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::cortex_m33::exception::{Exception, InterruptException};
+    use rp2350_sim::cortex_m33::opcodes::*;
+    use rp2350_sim::cortex_m33::registers::Register;
+    use rp2350_sim::cortex_m33::systick::{CSR_CLKSOURCE, CSR_COUNTFLAG, CSR_ENABLE, CSR_TICKINT};
+    use rp2350_sim::peripherals::ticks::{TICKS_BASE, TICK_CTRL, TICK_CTRL_ENABLE, TICK_CYCLES};
+    use rp2350_sim::{RAM_START_ADDRESS, RP2350};
+
+    const VECTOR_TABLE: u32 = RAM_START_ADDRESS + 0x400;
+    const HANDLER: u32 = RAM_START_ADDRESS + 0x600;
+    const SYSTICK: Exception = Exception::Interrupt(InterruptException::SysTick);
+
+    const SYST_CSR: u32 = 0xE000E010;
+    const SYST_RVR: u32 = 0xE000E014;
+    const SYST_CVR: u32 = 0xE000E018;
+    const SYST_CALIB: u32 = 0xE000E01C;
+
+    /// Fills the thread code with `movs r0, #0` and points SysTick at a `bx lr` handler
+    fn setup(rp2350: &mut RP2350) {
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+        rp2350.cortex_m33.registers.sp.set(RAM_START_ADDRESS + 0x800);
+        rp2350.cortex_m33.vtor = VECTOR_TABLE;
+
+        rp2350.cortex_m33.memory.write_u32(VECTOR_TABLE + 4 * 15, HANDLER | 1).unwrap();
+        rp2350.cortex_m33.memory.write_u16(HANDLER, BxT1::opcode(&rp2350.cortex_m33.registers.lr)).unwrap();
+        for i in 0..0x100 {
+            rp2350.cortex_m33.memory.write_u16(
+                RAM_START_ADDRESS + 2 * i,
+                MovImmediateT1::opcode(&rp2350.cortex_m33.registers.r0, 0),
+            ).unwrap();
+        }
+    }
+
+    #[test]
+    fn registers_reset_and_mask() {
+        // should start at zero and keep 24 bits of the reload value
+        let mut rp2350: RP2350 = RP2350::new();
+
+        assert_eq!(rp2350.cortex_m33.read_u32(SYST_CSR).unwrap(), 0);
+        assert_eq!(rp2350.cortex_m33.read_u32(SYST_CVR).unwrap(), 0);
+        assert_eq!(rp2350.cortex_m33.read_u32(SYST_CALIB).unwrap(), 0);
+
+        rp2350.cortex_m33.write_u32(SYST_RVR, 0xffff_ffff).unwrap();
+        rp2350.cortex_m33.write_u32(SYST_CSR, 0xffff_ffff).unwrap();
+
+        assert_eq!(rp2350.cortex_m33.read_u32(SYST_RVR).unwrap(), 0x00ff_ffff);
+        assert_eq!(rp2350.cortex_m33.read_u32(SYST_CSR).unwrap(), CSR_ENABLE | CSR_TICKINT | CSR_CLKSOURCE);
+    }
+
+    #[test]
+    fn counts_processor_cycles() {
+        // should reload on the first cycle and count down once per instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        setup(&mut rp2350);
+        rp2350.cortex_m33.write_u32(SYST_RVR, 9).unwrap();
+        rp2350.cortex_m33.write_u32(SYST_CSR, CSR_ENABLE | CSR_CLKSOURCE).unwrap();
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.read_u32(SYST_CVR).unwrap(), 9);
+
+        for _ in 0..4 {
+            rp2350.execute_instruction();
+        }
+
+        assert_eq!(rp2350.cortex_m33.read_u32(SYST_CVR).unwrap(), 5);
+        assert_eq!(rp2350.cortex_m33.cycles, 5);
+    }
+
+    #[test]
+    fn countflag_set_at_zero_and_cleared_on_read() {
+        // should set COUNTFLAG without pending the exception while TICKINT is clear
+        let mut rp2350: RP2350 = RP2350::new();
+        setup(&mut rp2350);
+        rp2350.cortex_m33.write_u32(SYST_RVR, 3).unwrap();
+        rp2350.cortex_m33.write_u32(SYST_CSR, CSR_ENABLE | CSR_CLKSOURCE).unwrap();
+        for _ in 0..4 {
+            rp2350.execute_instruction();
+        }
+
+        assert_eq!(rp2350.cortex_m33.read_u32(SYST_CVR).unwrap(), 0);
+        assert!(!rp2350.cortex_m33.exceptions.is_pending(SYSTICK));
+        assert_eq!(rp2350.cortex_m33.read_u32(SYST_CSR).unwrap() & CSR_COUNTFLAG, CSR_COUNTFLAG);
+        assert_eq!(rp2350.cortex_m33.read_u32(SYST_CSR).unwrap() & CSR_COUNTFLAG, 0);
+    }
+
+    #[test]
+    fn cvr_write_clears_counter() {
+        // should clear the counter and COUNTFLAG on any write to CVR
+        let mut rp2350: RP2350 = RP2350::new();
+        setup(&mut rp2350);
+        rp2350.cortex_m33.write_u32(SYST_RVR, 1).unwrap();
+        rp2350.cortex_m33.write_u32(SYST_CSR, CSR_ENABLE | CSR_CLKSOURCE).unwrap();
+        rp2350.execute_instruction();
+        rp2350.execute_instruction();
+        rp2350.execute_instruction();
+        rp2350.cortex_m33.write_u32(SYST_CVR, 0x1234).unwrap();
+
+        assert_eq!(rp2350.cortex_m33.read_u32(SYST_CVR).unwrap(), 0);
+        assert_eq!(rp2350.cortex_m33.read_u32(SYST_CSR).unwrap() & CSR_COUNTFLAG, 0);
+    }
+
+    #[test]
+    fn tickint_takes_exception() {
+        // should pend SysTick at zero with TICKINT set, and take it on the next step
+        let mut rp2350: RP2350 = RP2350::new();
+        setup(&mut rp2350);
+        rp2350.cortex_m33.write_u32(SYST_RVR, 2).unwrap();
+        rp2350.cortex_m33.write_u32(SYST_CSR, CSR_ENABLE | CSR_TICKINT | CSR_CLKSOURCE).unwrap();
+        for _ in 0..3 {
+            rp2350.execute_instruction();
+        }
+
+        assert!(rp2350.cortex_m33.exceptions.is_pending(SYSTICK));
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), RAM_START_ADDRESS + 6);
+
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.ipsr, 15);
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), HANDLER);
+    }
+
+    #[test]
+    fn external_reference_tick() {
+        // should ignore processor cycles and count the proc0 reference ticks while CLKSOURCE is clear
+        let mut rp2350: RP2350 = RP2350::new();
+        setup(&mut rp2350);
+        rp2350.cortex_m33.write_u32(SYST_RVR, 2).unwrap();
+        rp2350.cortex_m33.write_u32(SYST_CSR, CSR_ENABLE | CSR_TICKINT).unwrap();
+        for _ in 0..10 {
+            rp2350.execute_instruction();
+        }
+
+        assert_eq!(rp2350.cortex_m33.read_u32(SYST_CVR).unwrap(), 0);
+
+        // clk_ref runs at clk_sys out of reset, so the proc0 generator ticks every fourth cycle
+        rp2350.cortex_m33.write_u32(TICKS_BASE + TICK_CYCLES, 4).unwrap();
+        rp2350.cortex_m33.write_u32(TICKS_BASE + TICK_CTRL, TICK_CTRL_ENABLE).unwrap();
+        for _ in 0..11 {
+            rp2350.execute_instruction();
+        }
+
+        assert_eq!(rp2350.cortex_m33.read_u32(SYST_CVR).unwrap(), 1);
+        assert!(!rp2350.cortex_m33.exceptions.is_pending(SYSTICK));
+
+        rp2350.execute_instruction();
+
+        assert!(rp2350.cortex_m33.exceptions.is_pending(SYSTICK));
+    }
+
+    #[test]
+    fn disabled_does_not_count() {
+        // should hold the counter while ENABLE is clear
+        let mut rp2350: RP2350 = RP2350::new();
+        setup(&mut rp2350);
+        rp2350.cortex_m33.write_u32(SYST_RVR, 5).unwrap();
+        rp2350.cortex_m33.write_u32(SYST_CSR, CSR_CLKSOURCE).unwrap();
+        for _ in 0..3 {
+            rp2350.execute_instruction();
+        }
+
+        assert_eq!(rp2350.cortex_m33.read_u32(SYST_CVR).unwrap(), 0);
+    }
+}
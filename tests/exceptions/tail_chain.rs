@@ -0,0 +1,54 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::cortex_m33::exception::{Exception, InterruptException};
+    use rp2350_sim::cortex_m33::opcodes::*;
+    use rp2350_sim::cortex_m33::registers::Register;
+    use rp2350_sim::cortex_m33::Mode;
+    use rp2350_sim::{RAM_START_ADDRESS, RP2350};
+
+    const VECTOR_TABLE: u32 = RAM_START_ADDRESS + 0x400;
+    const PENDSV_HANDLER: u32 = RAM_START_ADDRESS + 0x600;
+    const SYSTICK_HANDLER: u32 = RAM_START_ADDRESS + 0x700;
+
+    #[test]
+    fn tail_chain_to_pending_exception() {
+        // should go from the PendSV handler straight into the SysTick handler without unstacking
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+        rp2350.cortex_m33.registers.sp.set(RAM_START_ADDRESS + 0x800);
+        rp2350.cortex_m33.registers.r0.set(0x10);
+        rp2350.cortex_m33.vtor = VECTOR_TABLE;
+
+        rp2350.cortex_m33.memory.write_u32(VECTOR_TABLE + 4 * 14, PENDSV_HANDLER | 1).unwrap();
+        rp2350.cortex_m33.memory.write_u32(VECTOR_TABLE + 4 * 15, SYSTICK_HANDLER | 1).unwrap();
+        rp2350.cortex_m33.memory.write_u16(PENDSV_HANDLER, BxT1::opcode(&rp2350.cortex_m33.registers.lr)).unwrap();
+        rp2350.cortex_m33.memory.write_u16(SYSTICK_HANDLER, BxT1::opcode(&rp2350.cortex_m33.registers.lr)).unwrap();
+        rp2350.cortex_m33.exceptions.set_pending(Exception::Interrupt(InterruptException::SysTick));
+        rp2350.cortex_m33.exceptions.set_pending(Exception::Interrupt(InterruptException::PendSV));
+
+        // Both have the same priority, so the lower exception number goes first
+        rp2350.execute_instruction();
+        assert_eq!(rp2350.cortex_m33.ipsr, 14);
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), PENDSV_HANDLER);
+
+        rp2350.cortex_m33.registers.r0.set(0xdead);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.ipsr, 15);
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), SYSTICK_HANDLER);
+        assert_eq!(rp2350.cortex_m33.registers.lr.get(), 0xFFFFFFF9);
+        assert_eq!(rp2350.cortex_m33.registers.sp.get(), RAM_START_ADDRESS + 0x7e0);
+        assert_eq!(rp2350.cortex_m33.registers.r0.get(), 0xdead);
+        assert_eq!(rp2350.cortex_m33.mode, Mode::Handler);
+        assert_eq!(rp2350.cortex_m33.exceptions.active.len(), 1);
+        assert!(rp2350.cortex_m33.exceptions.pending.is_empty());
+
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.ipsr, 0);
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), RAM_START_ADDRESS);
+        assert_eq!(rp2350.cortex_m33.registers.sp.get(), RAM_START_ADDRESS + 0x800);
+        assert_eq!(rp2350.cortex_m33.registers.r0.get(), 0x10);
+        assert_eq!(rp2350.cortex_m33.mode, Mode::Thread);
+    }
+}
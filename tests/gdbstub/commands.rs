@@ -0,0 +1,183 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::cortex_m33::opcodes::*;
+    use rp2350_sim::cortex_m33::registers::Register;
+    use rp2350_sim::cortex_m33::watchpoint::WatchKind;
+    use rp2350_sim::gdbstub::{GdbStub, Response, StopReason, TARGET_XML};
+    use rp2350_sim::{FLASH_START_ADDRESS, RAM_START_ADDRESS, RP2350};
+
+    const LOOP: u32 = RAM_START_ADDRESS + 0x100;
+    const COUNTER: u32 = RAM_START_ADDRESS + 0x800;
+
+    /// Programs a loop that increments r0 and stores it to COUNTER, and stops the core at its start
+    fn setup() -> (RP2350, GdbStub) {
+        let mut rp2350: RP2350 = RP2350::new();
+        let registers = rp2350.cortex_m33.registers;
+        let opcodes = [
+            AddsT2::opcode(&registers.r0, 1),
+            StrImmediateT1::opcode(&registers.r0, &registers.r1, 0),
+            BT2::opcode(-8i16 as u16),
+        ];
+        let code: Vec<u8> = opcodes.iter().flat_map(|opcode| opcode.to_le_bytes()).collect();
+        rp2350.memory_mut().program(LOOP, &code).unwrap();
+
+        rp2350.cortex_m33.registers.pc.set(LOOP);
+        rp2350.cortex_m33.registers.r1.set(COUNTER);
+        rp2350.cortex_m33.registers.sp.set(RAM_START_ADDRESS + 0x1000);
+
+        (rp2350, GdbStub::new())
+    }
+
+    fn reply(stub: &mut GdbStub, rp2350: &mut RP2350, packet: &str) -> String {
+        match stub.handle_packet(rp2350, packet.as_bytes()) {
+            Response::Reply(reply) => String::from_utf8(reply).unwrap(),
+            response => panic!("expected a reply to {}, got {:?}", packet, response),
+        }
+    }
+
+    #[test]
+    fn read_write_registers() {
+        // should read and write single registers as little endian hex
+        let (mut rp2350, mut stub) = setup();
+
+        assert_eq!(reply(&mut stub, &mut rp2350, "p1"), "00080020");
+        assert_eq!(reply(&mut stub, &mut rp2350, "P2=78563412"), "OK");
+        assert_eq!(rp2350.cortex_m33.registers.r2.get(), 0x12345678);
+        assert_eq!(reply(&mut stub, &mut rp2350, "P17=deadbeef"), "E01");
+        assert_eq!(reply(&mut stub, &mut rp2350, "P17=efcdab8967452301"), "OK");
+        assert_eq!(rp2350.cortex_m33.fpu.d(0), 0x0123456789abcdef);
+        assert_eq!(rp2350.cortex_m33.fpu.s[1], 0x01234567);
+        assert_eq!(reply(&mut stub, &mut rp2350, "p30"), "E01");
+    }
+
+    #[test]
+    fn read_all_registers() {
+        // should send every register in the target description in order
+        let (mut rp2350, mut stub) = setup();
+
+        let registers = reply(&mut stub, &mut rp2350, "g");
+
+        assert_eq!(registers.len(), (24 * 4 + 16 * 8) * 2);
+        assert_eq!(&registers[15 * 8..16 * 8], "00010020");
+        assert_eq!(&registers[16 * 8..17 * 8], "00000001");
+    }
+
+    #[test]
+    fn control_register() {
+        // should switch the banked stack pointer when CONTROL.SPSEL is written
+        let (mut rp2350, mut stub) = setup();
+        reply(&mut stub, &mut rp2350, "P12=00200020");
+
+        assert_eq!(reply(&mut stub, &mut rp2350, "P16=02000000"), "OK");
+
+        assert_eq!(rp2350.cortex_m33.registers.sp.get(), RAM_START_ADDRESS + 0x2000);
+        assert_eq!(reply(&mut stub, &mut rp2350, "p11"), "00100020");
+    }
+
+    #[test]
+    fn read_write_memory() {
+        // should read and write memory in hex and binary, programming flash directly
+        let (mut rp2350, mut stub) = setup();
+
+        assert_eq!(reply(&mut stub, &mut rp2350, &format!("M{:x},4:01020304", COUNTER)), "OK");
+        assert_eq!(reply(&mut stub, &mut rp2350, &format!("m{:x},4", COUNTER)), "01020304");
+        assert_eq!(reply(&mut stub, &mut rp2350, &format!("X{:x},2:}}#", COUNTER)), "OK");
+        assert_eq!(reply(&mut stub, &mut rp2350, &format!("m{:x},2", COUNTER)), "7d23");
+        assert_eq!(reply(&mut stub, &mut rp2350, &format!("X{:x},2:a", COUNTER)), "E01");
+        assert_eq!(reply(&mut stub, &mut rp2350, &format!("M{:x},2:abcd", FLASH_START_ADDRESS)), "OK");
+        assert_eq!(reply(&mut stub, &mut rp2350, &format!("m{:x},2", FLASH_START_ADDRESS)), "abcd");
+        assert_eq!(reply(&mut stub, &mut rp2350, "m40000000,4"), "E0e");
+    }
+
+    #[test]
+    fn continue_to_breakpoint() {
+        // should run until the breakpoint, then step off it when continued again
+        let (mut rp2350, mut stub) = setup();
+        assert_eq!(reply(&mut stub, &mut rp2350, &format!("Z1,{:x},2", LOOP + 4)), "OK");
+        assert_eq!(stub.handle_packet(&mut rp2350, b"c"), Response::Continue);
+
+        assert_eq!(stub.resume(&mut rp2350, || false), StopReason::Breakpoint);
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), LOOP + 4);
+        assert_eq!(rp2350.cortex_m33.registers.r0.get(), 1);
+
+        assert_eq!(stub.resume(&mut rp2350, || false), StopReason::Breakpoint);
+        assert_eq!(rp2350.cortex_m33.registers.r0.get(), 2);
+
+        assert_eq!(reply(&mut stub, &mut rp2350, &format!("z1,{:x},2", LOOP + 4)), "OK");
+        assert!(stub.breakpoints.is_empty());
+    }
+
+    #[test]
+    fn step() {
+        // should execute a single instruction
+        let (mut rp2350, mut stub) = setup();
+
+        assert_eq!(stub.handle_packet(&mut rp2350, b"s"), Response::Step);
+        assert_eq!(stub.step(&mut rp2350), StopReason::Step);
+
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), LOOP + 2);
+        assert_eq!(StopReason::Step.reply(), "S05");
+    }
+
+    #[test]
+    fn write_watchpoint() {
+        // should stop after the instruction that writes the watched address and report it
+        let (mut rp2350, mut stub) = setup();
+        assert_eq!(reply(&mut stub, &mut rp2350, &format!("Z2,{:x},4", COUNTER)), "OK");
+
+        let reason = stub.resume(&mut rp2350, || false);
+
+        assert_eq!(reason, StopReason::Watchpoint { kind: WatchKind::Write, address: COUNTER });
+        assert_eq!(reason.reply(), format!("T05watch:{:x};", COUNTER));
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), LOOP + 4);
+
+        assert_eq!(reply(&mut stub, &mut rp2350, &format!("z2,{:x},4", COUNTER)), "OK");
+        assert!(rp2350.cortex_m33.watchpoints.is_empty());
+    }
+
+    #[test]
+    fn interrupt() {
+        // should stop a running target when GDB interrupts it
+        let (mut rp2350, mut stub) = setup();
+
+        let reason = stub.resume(&mut rp2350, || true);
+
+        assert_eq!(reason, StopReason::Interrupted);
+        assert_eq!(reason.reply(), "S02");
+    }
+
+    #[test]
+    fn monitor_disassemble() {
+        // should answer monitor disas with a hex encoded listing from the PC
+        let (mut rp2350, mut stub) = setup();
+        let command: String = b"disas".iter().map(|byte| format!("{:02x}", byte)).collect();
+
+        let reply = reply(&mut stub, &mut rp2350, &format!("qRcmd,{}", command));
+
+        let bytes: Vec<u8> = (0..reply.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&reply[i..i + 2], 16).unwrap())
+            .collect();
+        let listing = String::from_utf8(bytes).unwrap();
+        let lines: Vec<_> = listing.lines().collect();
+        assert_eq!(lines[0], "20000100:  3001       adds r0, #1");
+        assert_eq!(lines[1], "20000102:  6008       str r0, [r1, #0]");
+        assert_eq!(lines[2], "20000104:  e7fc       b.n 20000100");
+        assert_eq!(lines.len(), 8);
+    }
+
+    #[test]
+    fn target_description() {
+        // should send the target description in chunks, marking the last one
+        let (mut rp2350, mut stub) = setup();
+
+        let first = reply(&mut stub, &mut rp2350, "qXfer:features:read:target.xml:0,20");
+        assert_eq!(first, format!("m{}", &TARGET_XML[..0x20]));
+
+        let rest = reply(&mut stub, &mut rp2350, "qXfer:features:read:target.xml:20,10000");
+        assert_eq!(rest, format!("l{}", &TARGET_XML[0x20..]));
+
+        assert!(reply(&mut stub, &mut rp2350, "qSupported:multiprocess+").contains("qXfer:features:read+"));
+        assert_eq!(reply(&mut stub, &mut rp2350, "vMustReplyEmpty"), "");
+    }
+}
@@ -0,0 +1,125 @@
+#[cfg(test)]
+mod tests {
+    use std::io::{self, Cursor, Read, Write};
+
+    use rp2350_sim::cortex_m33::registers::Register;
+    use rp2350_sim::gdbstub::packet::*;
+    use rp2350_sim::gdbstub::GdbStub;
+    use rp2350_sim::{RAM_START_ADDRESS, RP2350};
+
+    /// A connection that reads what GDB would send from a buffer and records what the stub sends back
+    struct Loopback {
+        input: Cursor<Vec<u8>>,
+        output: Vec<u8>,
+    }
+
+    impl Loopback {
+        fn new(input: &[u8]) -> Self {
+            Self {
+                input: Cursor::new(input.to_vec()),
+                output: Vec::new(),
+            }
+        }
+    }
+
+    impl Read for Loopback {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            self.input.read(buf)
+        }
+    }
+
+    impl Write for Loopback {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.output.write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Connection for Loopback {
+        fn poll_interrupt(&mut self) -> io::Result<bool> {
+            Ok(false)
+        }
+    }
+
+    #[test]
+    fn packet_checksum() {
+        // should sum the payload modulo 256
+        assert_eq!(checksum(b"qSupported"), 0x37);
+        assert_eq!(checksum(b""), 0);
+    }
+
+    #[test]
+    fn escape_round_trip() {
+        // should escape the special characters and get the same bytes back after unescaping
+        let data = b"a#b$c}d*e";
+        let escaped = escape(data);
+
+        assert_eq!(escaped, b"a}\x03b}\x04c}]d}\x0ae");
+        assert_eq!(unescape(&escaped), data);
+    }
+
+    #[test]
+    fn hex_round_trip() {
+        // should encode bytes as lower case hex and reject an odd number of digits
+        assert_eq!(to_hex(&[0x00, 0xab, 0x7f]), "00ab7f");
+        assert_eq!(from_hex(b"00ab7f"), Some(vec![0x00, 0xab, 0x7f]));
+        assert_eq!(from_hex(b"abc"), None);
+        assert_eq!(from_hex(b"zz"), None);
+    }
+
+    #[test]
+    fn read_packet_acks() {
+        // should ack a good packet and skip the acks GDB sends between packets
+        let mut connection = Loopback::new(b"+$m0,4#fd");
+
+        let packet = read_packet(&mut connection, true).unwrap();
+
+        assert_eq!(packet, Some(Packet::Command(b"m0,4".to_vec())));
+        assert_eq!(connection.output, b"+");
+    }
+
+    #[test]
+    fn read_packet_bad_checksum() {
+        // should refuse a packet with a bad checksum and read the one sent again
+        let mut connection = Loopback::new(b"$g#00$g#67");
+
+        let packet = read_packet(&mut connection, true).unwrap();
+
+        assert_eq!(packet, Some(Packet::Command(b"g".to_vec())));
+        assert_eq!(connection.output, b"-+");
+    }
+
+    #[test]
+    fn read_packet_interrupt() {
+        // should report an interrupt byte outside of a packet, and None once the connection closes
+        let mut connection = Loopback::new(&[INTERRUPT]);
+
+        assert_eq!(read_packet(&mut connection, true).unwrap(), Some(Packet::Interrupt));
+        assert_eq!(read_packet(&mut connection, true).unwrap(), None);
+    }
+
+    #[test]
+    fn write_packet_resends() {
+        // should frame the payload with its checksum and send it again when GDB naks it
+        let mut connection = Loopback::new(b"-+");
+
+        write_packet(&mut connection, b"OK", true).unwrap();
+
+        assert_eq!(connection.output, b"$OK#9a$OK#9a");
+    }
+
+    #[test]
+    fn serve_session() {
+        // should answer packets until GDB detaches, without acks after QStartNoAckMode
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+        let mut connection = Loopback::new(b"$QStartNoAckMode#b0+$p0f#06$D#44");
+
+        GdbStub::new().serve(&mut rp2350, &mut connection).unwrap();
+
+        assert_eq!(connection.output, b"+$OK#9a$00000020#82$OK#9a");
+    }
+}
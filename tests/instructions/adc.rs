@@ -16,7 +16,7 @@ mod tests {
                 &rp2350.cortex_m33.registers.r5,
                 &rp2350.cortex_m33.registers.r4,
             ),
-        );
+        ).unwrap();
         rp2350.cortex_m33.registers.r4.set(55);
         rp2350.cortex_m33.registers.r5.set(66);
         rp2350.cortex_m33.xpsr.apsr.set_c(true);
@@ -24,10 +24,10 @@ mod tests {
         rp2350.execute_instruction();
 
         assert_eq!(rp2350.cortex_m33.registers.r5.get(), 122);
-        assert_eq!(rp2350.cortex_m33.xpsr.apsr.n(), false);
-        assert_eq!(rp2350.cortex_m33.xpsr.apsr.z(), false);
-        assert_eq!(rp2350.cortex_m33.xpsr.apsr.c(), false);
-        assert_eq!(rp2350.cortex_m33.xpsr.apsr.v(), false);
+        assert!(!rp2350.cortex_m33.xpsr.apsr.n());
+        assert!(!rp2350.cortex_m33.xpsr.apsr.z());
+        assert!(!rp2350.cortex_m33.xpsr.apsr.c());
+        assert!(!rp2350.cortex_m33.xpsr.apsr.v());
     }
 
     #[test]
@@ -42,7 +42,7 @@ mod tests {
                 &rp2350.cortex_m33.registers.r5,
                 &rp2350.cortex_m33.registers.r4,
             ),
-        );
+        ).unwrap();
         rp2350.cortex_m33.registers.r4.set(0x7fffffff); // Max signed INT32
         rp2350.cortex_m33.registers.r5.set(0);
         rp2350.cortex_m33.xpsr.apsr.set_c(true);
@@ -50,10 +50,10 @@ mod tests {
         rp2350.execute_instruction();
 
         assert_eq!(rp2350.cortex_m33.registers.r5.get(), 0x80000000);
-        assert_eq!(rp2350.cortex_m33.xpsr.apsr.n(), true);
-        assert_eq!(rp2350.cortex_m33.xpsr.apsr.z(), false);
-        assert_eq!(rp2350.cortex_m33.xpsr.apsr.c(), false);
-        assert_eq!(rp2350.cortex_m33.xpsr.apsr.v(), true);
+        assert!(rp2350.cortex_m33.xpsr.apsr.n());
+        assert!(!rp2350.cortex_m33.xpsr.apsr.z());
+        assert!(!rp2350.cortex_m33.xpsr.apsr.c());
+        assert!(rp2350.cortex_m33.xpsr.apsr.v());
     }
 
     #[test]
@@ -68,7 +68,7 @@ mod tests {
                 &rp2350.cortex_m33.registers.r3,
                 &rp2350.cortex_m33.registers.r2,
             ),
-        );
+        ).unwrap();
         rp2350.cortex_m33.registers.r2.set(0); // Max signed INT32
         rp2350.cortex_m33.registers.r3.set(0);
         rp2350.cortex_m33.xpsr.apsr.set_c(true);
@@ -77,10 +77,10 @@ mod tests {
         rp2350.execute_instruction();
 
         assert_eq!(rp2350.cortex_m33.registers.r3.get(), 1);
-        assert_eq!(rp2350.cortex_m33.xpsr.apsr.n(), false);
-        assert_eq!(rp2350.cortex_m33.xpsr.apsr.z(), false);
-        assert_eq!(rp2350.cortex_m33.xpsr.apsr.c(), false);
-        assert_eq!(rp2350.cortex_m33.xpsr.apsr.v(), false);
+        assert!(!rp2350.cortex_m33.xpsr.apsr.n());
+        assert!(!rp2350.cortex_m33.xpsr.apsr.z());
+        assert!(!rp2350.cortex_m33.xpsr.apsr.c());
+        assert!(!rp2350.cortex_m33.xpsr.apsr.v());
     }
 
     #[test]
@@ -95,16 +95,16 @@ mod tests {
                 &rp2350.cortex_m33.registers.r0,
                 &rp2350.cortex_m33.registers.r0,
             ),
-        );
+        ).unwrap();
         rp2350.cortex_m33.registers.r0.set(0x80000000); // Max signed INT32
         rp2350.cortex_m33.xpsr.apsr.set_c(false);
 
         rp2350.execute_instruction();
 
         assert_eq!(rp2350.cortex_m33.registers.r0.get(), 0);
-        assert_eq!(rp2350.cortex_m33.xpsr.apsr.n(), false);
-        assert_eq!(rp2350.cortex_m33.xpsr.apsr.z(), true);
-        assert_eq!(rp2350.cortex_m33.xpsr.apsr.c(), true);
-        assert_eq!(rp2350.cortex_m33.xpsr.apsr.v(), true);
+        assert!(!rp2350.cortex_m33.xpsr.apsr.n());
+        assert!(rp2350.cortex_m33.xpsr.apsr.z());
+        assert!(rp2350.cortex_m33.xpsr.apsr.c());
+        assert!(rp2350.cortex_m33.xpsr.apsr.v());
     }
 }
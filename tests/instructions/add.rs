@@ -11,7 +11,7 @@ mod tests {
         rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
         rp2350.cortex_m33.registers.sp.set(0x10000040);
 
-        rp2350.cortex_m33.memory.write_u16(RAM_START_ADDRESS, AddSpPlusImmediateT2::opcode(0x10));
+        rp2350.cortex_m33.memory.write_u16(RAM_START_ADDRESS, AddSpPlusImmediateT2::opcode(0x10)).unwrap();
         rp2350.execute_instruction();
 
         assert_eq!(rp2350.cortex_m33.registers.sp.get(), 0x10000050)
@@ -27,7 +27,7 @@ mod tests {
         rp2350.cortex_m33.memory.write_u16(
             RAM_START_ADDRESS,
             AddSpPlusImmediateT1::opcode(&rp2350.cortex_m33.registers.r1, 0x10),
-        );
+        ).unwrap();
         rp2350.cortex_m33.registers.r1.set(0);
         rp2350.execute_instruction();
 
@@ -48,15 +48,15 @@ mod tests {
                 &rp2350.cortex_m33.registers.r2,
                 3,
             ),
-        );
+        ).unwrap();
         rp2350.cortex_m33.registers.r2.set(2);
         rp2350.execute_instruction();
 
         assert_eq!(rp2350.cortex_m33.registers.r1.get(), 5);
-        assert_eq!(rp2350.cortex_m33.xpsr.apsr.n(), false);
-        assert_eq!(rp2350.cortex_m33.xpsr.apsr.z(), false);
-        assert_eq!(rp2350.cortex_m33.xpsr.apsr.c(), false);
-        assert_eq!(rp2350.cortex_m33.xpsr.apsr.v(), false);
+        assert!(!rp2350.cortex_m33.xpsr.apsr.n());
+        assert!(!rp2350.cortex_m33.xpsr.apsr.z());
+        assert!(!rp2350.cortex_m33.xpsr.apsr.c());
+        assert!(!rp2350.cortex_m33.xpsr.apsr.v());
     }
 
     #[test]
@@ -68,7 +68,7 @@ mod tests {
         rp2350.cortex_m33.memory.write_u16(
             RAM_START_ADDRESS,
             AddsT2::opcode(&rp2350.cortex_m33.registers.r1, 1),
-        );
+        ).unwrap();
         rp2350.cortex_m33.registers.r1.set(0xffffffff);
         rp2350.execute_instruction();
 
@@ -79,10 +79,10 @@ mod tests {
         println!("apsr: {:?}", rp2350.cortex_m33.xpsr.apsr);
 
         assert_eq!(rp2350.cortex_m33.registers.r1.get(), 0);
-        assert_eq!(rp2350.cortex_m33.xpsr.apsr.n(), false);
-        assert_eq!(rp2350.cortex_m33.xpsr.apsr.z(), true);
-        assert_eq!(rp2350.cortex_m33.xpsr.apsr.c(), true);
-        assert_eq!(rp2350.cortex_m33.xpsr.apsr.v(), false);
+        assert!(!rp2350.cortex_m33.xpsr.apsr.n());
+        assert!(rp2350.cortex_m33.xpsr.apsr.z());
+        assert!(rp2350.cortex_m33.xpsr.apsr.c());
+        assert!(!rp2350.cortex_m33.xpsr.apsr.v());
     }
 
     #[test]
@@ -98,17 +98,17 @@ mod tests {
                 &rp2350.cortex_m33.registers.r2,
                 &rp2350.cortex_m33.registers.r7,
             ),
-        );
+        ).unwrap();
         rp2350.cortex_m33.registers.r2.set(2);
         rp2350.cortex_m33.registers.r7.set(27);
 
         rp2350.execute_instruction();
 
         assert_eq!(rp2350.cortex_m33.registers.r1.get(), 29);
-        assert_eq!(rp2350.cortex_m33.xpsr.apsr.n(), false);
-        assert_eq!(rp2350.cortex_m33.xpsr.apsr.z(), false);
-        assert_eq!(rp2350.cortex_m33.xpsr.apsr.c(), false);
-        assert_eq!(rp2350.cortex_m33.xpsr.apsr.v(), false);
+        assert!(!rp2350.cortex_m33.xpsr.apsr.n());
+        assert!(!rp2350.cortex_m33.xpsr.apsr.z());
+        assert!(!rp2350.cortex_m33.xpsr.apsr.c());
+        assert!(!rp2350.cortex_m33.xpsr.apsr.v());
     }
 
     #[test]
@@ -124,17 +124,17 @@ mod tests {
                 &rp2350.cortex_m33.registers.r4,
                 &rp2350.cortex_m33.registers.r2,
             ),
-        );
+        ).unwrap();
         rp2350.cortex_m33.registers.r2.set(0x74bc8000);
         rp2350.cortex_m33.registers.r4.set(0x43740000);
 
         rp2350.execute_instruction();
 
         assert_eq!(rp2350.cortex_m33.registers.r4.get(), 0xb8308000);
-        assert_eq!(rp2350.cortex_m33.xpsr.apsr.n(), true);
-        assert_eq!(rp2350.cortex_m33.xpsr.apsr.z(), false);
-        assert_eq!(rp2350.cortex_m33.xpsr.apsr.c(), false);
-        assert_eq!(rp2350.cortex_m33.xpsr.apsr.v(), true);
+        assert!(rp2350.cortex_m33.xpsr.apsr.n());
+        assert!(!rp2350.cortex_m33.xpsr.apsr.z());
+        assert!(!rp2350.cortex_m33.xpsr.apsr.c());
+        assert!(rp2350.cortex_m33.xpsr.apsr.v());
     }
 
     #[test]
@@ -150,17 +150,17 @@ mod tests {
                 &rp2350.cortex_m33.registers.r1,
                 &rp2350.cortex_m33.registers.r1,
             ),
-        );
+        ).unwrap();
         rp2350.cortex_m33.registers.r1.set(0xbf8d1424);
         rp2350.cortex_m33.xpsr.apsr.set_c(true);
 
         rp2350.execute_instruction();
 
         assert_eq!(rp2350.cortex_m33.registers.r1.get(), 0x7f1a2848);
-        assert_eq!(rp2350.cortex_m33.xpsr.apsr.n(), false);
-        assert_eq!(rp2350.cortex_m33.xpsr.apsr.z(), false);
-        assert_eq!(rp2350.cortex_m33.xpsr.apsr.c(), true);
-        assert_eq!(rp2350.cortex_m33.xpsr.apsr.v(), true);
+        assert!(!rp2350.cortex_m33.xpsr.apsr.n());
+        assert!(!rp2350.cortex_m33.xpsr.apsr.z());
+        assert!(rp2350.cortex_m33.xpsr.apsr.c());
+        assert!(rp2350.cortex_m33.xpsr.apsr.v());
     }
 
     #[test]
@@ -175,7 +175,7 @@ mod tests {
                 &rp2350.cortex_m33.registers.r1,
                 &rp2350.cortex_m33.registers.r12,
             ),
-        );
+        ).unwrap();
         rp2350.cortex_m33.registers.r1.set(66);
         rp2350.cortex_m33.registers.r12.set(44);
 
@@ -183,4 +183,57 @@ mod tests {
 
         assert_eq!(rp2350.cortex_m33.registers.r1.get(), 110);
     }
+
+    #[test]
+    fn adds_immediate_t3() {
+        // should execute a `adds.w r0, r1, #0x80000000` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u32(
+            RAM_START_ADDRESS,
+            AddImmediateT3::opcode(true, &rp2350.cortex_m33.registers.r0, &rp2350.cortex_m33.registers.r1, 0x400),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r1.set(0x80000000);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r0.get(), 0);
+        assert!(rp2350.cortex_m33.xpsr.apsr.z());
+        assert!(rp2350.cortex_m33.xpsr.apsr.c());
+        assert!(rp2350.cortex_m33.xpsr.apsr.v());
+        assert!(!rp2350.cortex_m33.xpsr.apsr.n());
+    }
+
+    #[test]
+    fn add_immediate_t4() {
+        // should execute a `addw r3, r4, #0xfff` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u32(
+            RAM_START_ADDRESS,
+            AddImmediateT4::opcode(&rp2350.cortex_m33.registers.r3, &rp2350.cortex_m33.registers.r4, 0xfff),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r4.set(1);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r3.get(), 0x1000);
+    }
+
+    #[test]
+    fn add_register_t3() {
+        // should execute a `add.w r5, r6, r7, lsl #4` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u32(
+            RAM_START_ADDRESS,
+            AddRegisterT3::opcode(false, &rp2350.cortex_m33.registers.r5, &rp2350.cortex_m33.registers.r6, &rp2350.cortex_m33.registers.r7, 0b00, 4),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r6.set(0x10);
+        rp2350.cortex_m33.registers.r7.set(0x3);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r5.get(), 0x40);
+    }
 }
@@ -13,7 +13,7 @@ mod tests {
         rp2350.cortex_m33.memory.write_u16(
             RAM_START_ADDRESS,
             AdrT1::opcode(&rp2350.cortex_m33.registers.r4, 0x50),
-        );
+        ).unwrap();
         rp2350.execute_instruction();
 
         assert_eq!(rp2350.cortex_m33.registers.r4.get(), 0x20000054);
@@ -16,13 +16,29 @@ mod tests {
                 &rp2350.cortex_m33.registers.r5,
                 &rp2350.cortex_m33.registers.r0,
             ),
-        );
+        ).unwrap();
         rp2350.cortex_m33.registers.r5.set(0xffff0000);
         rp2350.cortex_m33.registers.r0.set(0xf00fffff);
         rp2350.execute_instruction();
 
         assert_eq!(rp2350.cortex_m33.registers.r5.get(), 0xf00f0000);
-        assert_eq!(rp2350.cortex_m33.xpsr.apsr.n(), true);
-        assert_eq!(rp2350.cortex_m33.xpsr.apsr.z(), false);
+        assert!(rp2350.cortex_m33.xpsr.apsr.n());
+        assert!(!rp2350.cortex_m33.xpsr.apsr.z());
+    }
+
+    #[test]
+    fn and_immediate() {
+        // should execute a `and.w r1, r2, #0xff00ff00` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u32(
+            RAM_START_ADDRESS,
+            AndImmediateT1::opcode(false, &rp2350.cortex_m33.registers.r1, &rp2350.cortex_m33.registers.r2, 0x2ff),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r2.set(0x12345678);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r1.get(), 0x12005600);
     }
 }
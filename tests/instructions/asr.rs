@@ -17,16 +17,16 @@ mod tests {
                 &rp2350.cortex_m33.registers.r2,
                 31,
             ),
-        );
+        ).unwrap();
         rp2350.cortex_m33.registers.r2.set(0x80000000);
         rp2350.cortex_m33.xpsr.apsr.set_c(true);
         rp2350.execute_instruction();
 
         assert_eq!(rp2350.cortex_m33.registers.r3.get(), 0xffffffff);
         assert_eq!(rp2350.cortex_m33.registers.pc.get(), 0x20000002);
-        assert_eq!(rp2350.cortex_m33.xpsr.apsr.n(), true);
-        assert_eq!(rp2350.cortex_m33.xpsr.apsr.z(), false);
-        assert_eq!(rp2350.cortex_m33.xpsr.apsr.c(), false);
+        assert!(rp2350.cortex_m33.xpsr.apsr.n());
+        assert!(!rp2350.cortex_m33.xpsr.apsr.z());
+        assert!(!rp2350.cortex_m33.xpsr.apsr.c());
     }
 
     #[test]
@@ -42,16 +42,16 @@ mod tests {
                 &rp2350.cortex_m33.registers.r2,
                 0,
             ),
-        );
+        ).unwrap();
         rp2350.cortex_m33.registers.r2.set(0x80000000);
         rp2350.cortex_m33.xpsr.apsr.set_c(false);
         rp2350.execute_instruction();
 
         assert_eq!(rp2350.cortex_m33.registers.r3.get(), 0xffffffff);
         assert_eq!(rp2350.cortex_m33.registers.pc.get(), 0x20000002);
-        assert_eq!(rp2350.cortex_m33.xpsr.apsr.n(), true);
-        assert_eq!(rp2350.cortex_m33.xpsr.apsr.z(), false);
-        assert_eq!(rp2350.cortex_m33.xpsr.apsr.c(), true);
+        assert!(rp2350.cortex_m33.xpsr.apsr.n());
+        assert!(!rp2350.cortex_m33.xpsr.apsr.z());
+        assert!(rp2350.cortex_m33.xpsr.apsr.c());
     }
 
     #[test]
@@ -66,16 +66,16 @@ mod tests {
                 &rp2350.cortex_m33.registers.r3,
                 &rp2350.cortex_m33.registers.r4,
             ),
-        );
+        ).unwrap();
         rp2350.cortex_m33.registers.r3.set(0x80000040);
         rp2350.cortex_m33.registers.r4.set(0xff500007);
         rp2350.execute_instruction();
 
         assert_eq!(rp2350.cortex_m33.registers.r3.get(), 0xff000000);
         assert_eq!(rp2350.cortex_m33.registers.pc.get(), 0x20000002);
-        assert_eq!(rp2350.cortex_m33.xpsr.apsr.n(), true);
-        assert_eq!(rp2350.cortex_m33.xpsr.apsr.z(), false);
-        assert_eq!(rp2350.cortex_m33.xpsr.apsr.c(), true);
+        assert!(rp2350.cortex_m33.xpsr.apsr.n());
+        assert!(!rp2350.cortex_m33.xpsr.apsr.z());
+        assert!(rp2350.cortex_m33.xpsr.apsr.c());
     }
 
     #[test]
@@ -90,7 +90,7 @@ mod tests {
                 &rp2350.cortex_m33.registers.r3,
                 &rp2350.cortex_m33.registers.r4,
             ),
-        );
+        ).unwrap();
         rp2350.cortex_m33.registers.r3.set(0x40000040);
         rp2350.cortex_m33.registers.r4.set(50);
         rp2350.cortex_m33.xpsr.apsr.set_c(true);
@@ -98,9 +98,9 @@ mod tests {
 
         assert_eq!(rp2350.cortex_m33.registers.r3.get(), 0);
         assert_eq!(rp2350.cortex_m33.registers.pc.get(), 0x20000002);
-        assert_eq!(rp2350.cortex_m33.xpsr.apsr.n(), false);
-        assert_eq!(rp2350.cortex_m33.xpsr.apsr.z(), true);
-        assert_eq!(rp2350.cortex_m33.xpsr.apsr.c(), false);
+        assert!(!rp2350.cortex_m33.xpsr.apsr.n());
+        assert!(rp2350.cortex_m33.xpsr.apsr.z());
+        assert!(!rp2350.cortex_m33.xpsr.apsr.c());
     }
 
     #[test]
@@ -115,7 +115,7 @@ mod tests {
                 &rp2350.cortex_m33.registers.r3,
                 &rp2350.cortex_m33.registers.r4,
             ),
-        );
+        ).unwrap();
         rp2350.cortex_m33.registers.r3.set(0x40000040);
         rp2350.cortex_m33.registers.r4.set(31);
         rp2350.cortex_m33.xpsr.apsr.set_c(true);
@@ -123,9 +123,9 @@ mod tests {
 
         assert_eq!(rp2350.cortex_m33.registers.r3.get(), 0);
         assert_eq!(rp2350.cortex_m33.registers.pc.get(), 0x20000002);
-        assert_eq!(rp2350.cortex_m33.xpsr.apsr.n(), false);
-        assert_eq!(rp2350.cortex_m33.xpsr.apsr.z(), true);
-        assert_eq!(rp2350.cortex_m33.xpsr.apsr.c(), true);
+        assert!(!rp2350.cortex_m33.xpsr.apsr.n());
+        assert!(rp2350.cortex_m33.xpsr.apsr.z());
+        assert!(rp2350.cortex_m33.xpsr.apsr.c());
     }
 
     #[test]
@@ -140,7 +140,7 @@ mod tests {
                 &rp2350.cortex_m33.registers.r3,
                 &rp2350.cortex_m33.registers.r4,
             ),
-        );
+        ).unwrap();
         rp2350.cortex_m33.registers.r3.set(0x80000040);
         rp2350.cortex_m33.registers.r4.set(50);
         rp2350.cortex_m33.xpsr.apsr.set_c(true);
@@ -148,9 +148,9 @@ mod tests {
 
         assert_eq!(rp2350.cortex_m33.registers.r3.get(), 0xffffffff);
         assert_eq!(rp2350.cortex_m33.registers.pc.get(), 0x20000002);
-        assert_eq!(rp2350.cortex_m33.xpsr.apsr.n(), true);
-        assert_eq!(rp2350.cortex_m33.xpsr.apsr.z(), false);
-        assert_eq!(rp2350.cortex_m33.xpsr.apsr.c(), true);
+        assert!(rp2350.cortex_m33.xpsr.apsr.n());
+        assert!(!rp2350.cortex_m33.xpsr.apsr.z());
+        assert!(rp2350.cortex_m33.xpsr.apsr.c());
     }
 
     #[test]
@@ -165,7 +165,7 @@ mod tests {
                 &rp2350.cortex_m33.registers.r3,
                 &rp2350.cortex_m33.registers.r4,
             ),
-        );
+        ).unwrap();
         rp2350.cortex_m33.registers.r3.set(0x80000040);
         rp2350.cortex_m33.registers.r4.set(0);
         rp2350.cortex_m33.xpsr.apsr.set_c(true);
@@ -173,8 +173,8 @@ mod tests {
 
         assert_eq!(rp2350.cortex_m33.registers.r3.get(), 0x80000040);
         assert_eq!(rp2350.cortex_m33.registers.pc.get(), 0x20000002);
-        assert_eq!(rp2350.cortex_m33.xpsr.apsr.n(), true);
-        assert_eq!(rp2350.cortex_m33.xpsr.apsr.z(), false);
-        assert_eq!(rp2350.cortex_m33.xpsr.apsr.c(), true);
+        assert!(rp2350.cortex_m33.xpsr.apsr.n());
+        assert!(!rp2350.cortex_m33.xpsr.apsr.z());
+        assert!(rp2350.cortex_m33.xpsr.apsr.c());
     }
 }
@@ -17,7 +17,7 @@ mod tests {
         rp2350.cortex_m33.xpsr.apsr.set_z(false);
 
         let opcode = BT1::opcode(1, 0x1f8);
-        rp2350.cortex_m33.memory.write_u16(RAM_START_ADDRESS + 9 * 2, opcode);
+        rp2350.cortex_m33.memory.write_u16(RAM_START_ADDRESS + 9 * 2, opcode).unwrap();
         rp2350.execute_instruction();
 
         assert_eq_hex!(rp2350.cortex_m33.registers.pc.get(), 0x2000000e);
@@ -33,9 +33,71 @@ mod tests {
             .pc
             .set(RAM_START_ADDRESS + 9 * 2);
 
-        rp2350.cortex_m33.memory.write_u16(RAM_START_ADDRESS + 9 * 2, BT2::opcode(0xfec));
+        rp2350.cortex_m33.memory.write_u16(RAM_START_ADDRESS + 9 * 2, BT2::opcode(0xfec)).unwrap();
         rp2350.execute_instruction();
 
         assert_eq!(rp2350.cortex_m33.registers.pc.get(), 0x20000002);
     }
+
+    #[test]
+    fn bt1_not_taken() {
+        // should execute a `bne.n .-6` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u16(
+            RAM_START_ADDRESS,
+            BT1::opcode(1, 0x1f8),
+        ).unwrap();
+        rp2350.cortex_m33.xpsr.apsr.set_z(true);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), RAM_START_ADDRESS + 2);
+    }
+
+    #[test]
+    fn bt3() {
+        // should execute a `bne.w .+0x1004` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u32(
+            RAM_START_ADDRESS,
+            BT3::opcode(1, 0x1000),
+        ).unwrap();
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), RAM_START_ADDRESS + 4 + 0x1000);
+    }
+
+    #[test]
+    fn bt3_backwards() {
+        // should execute a `beq.w .-0xfc` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS + 0x200);
+
+        rp2350.cortex_m33.memory.write_u32(
+            RAM_START_ADDRESS + 0x200,
+            BT3::opcode(0, -0x100),
+        ).unwrap();
+        rp2350.cortex_m33.xpsr.apsr.set_z(true);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), RAM_START_ADDRESS + 0x200 + 4 - 0x100);
+    }
+
+    #[test]
+    fn bt4() {
+        // should execute a `b.w .+0x12348` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u32(
+            RAM_START_ADDRESS,
+            BT4::opcode(0x12344),
+        ).unwrap();
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), RAM_START_ADDRESS + 4 + 0x12344);
+    }
 }
@@ -0,0 +1,65 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::cortex_m33::opcodes::*;
+    use rp2350_sim::cortex_m33::registers::Register;
+    use rp2350_sim::semihosting::SYS_WRITEC;
+    use rp2350_sim::{RAM_START_ADDRESS, RP2350};
+
+    #[test]
+    fn bkpt_halts() {
+        // should halt on a `bkpt #0x12` with the PC left on it, until a debugger steps over it
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+        rp2350.cortex_m33.memory.write_u16(RAM_START_ADDRESS, BkptT1::opcode(0x12)).unwrap();
+        let opcode = MovImmediateT1::opcode(&rp2350.cortex_m33.registers.r0, 0x42);
+        rp2350.cortex_m33.memory.write_u16(RAM_START_ADDRESS + 2, opcode).unwrap();
+
+        rp2350.execute_instruction();
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.breakpoint, Some(0x12));
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), RAM_START_ADDRESS);
+        assert!(rp2350.is_halted());
+
+        rp2350.cortex_m33.step_over_breakpoint();
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.breakpoint, None);
+        assert_eq!(rp2350.cortex_m33.registers.r0.get(), 0x42);
+    }
+
+    #[test]
+    fn bkpt_semihosting() {
+        // should run a `bkpt #0xab` as a semihosting call and carry on after it
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+        rp2350.cortex_m33.registers.r0.set(SYS_WRITEC);
+        rp2350.cortex_m33.registers.r1.set(RAM_START_ADDRESS + 0x100);
+        rp2350.cortex_m33.memory.write(RAM_START_ADDRESS + 0x100, b'k').unwrap();
+        rp2350.cortex_m33.memory.write_u16(RAM_START_ADDRESS, BkptT1::opcode(0xab)).unwrap();
+
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.breakpoint, None);
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), RAM_START_ADDRESS + 2);
+        assert_eq!(rp2350.semihosting.take_console(), b"k");
+    }
+
+    #[test]
+    fn bkpt_in_it_block() {
+        // should halt on a `bkpt` in an IT block whatever the condition, leaving ITSTATE for the next instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+        // it ne, so with Z set the BKPT fails its condition
+        rp2350.cortex_m33.memory.write_u16(RAM_START_ADDRESS, ItT1::opcode(0b0001, 0b1000)).unwrap();
+        rp2350.cortex_m33.xpsr.apsr.set_z(true);
+        rp2350.cortex_m33.memory.write_u16(RAM_START_ADDRESS + 2, BkptT1::opcode(0x01)).unwrap();
+
+        rp2350.execute_instruction();
+        let itstate = rp2350.cortex_m33.xpsr.epsr.it();
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.breakpoint, Some(0x01));
+        assert_eq!(rp2350.cortex_m33.xpsr.epsr.it(), itstate);
+    }
+}
@@ -14,7 +14,7 @@ mod tests {
         rp2350.cortex_m33.memory.write_u16(
             RAM_START_ADDRESS,
             BlxT1::opcode(&rp2350.cortex_m33.registers.r3),
-        );
+        ).unwrap();
 
         rp2350.execute_instruction();
 
@@ -0,0 +1,39 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::cortex_m33::opcodes::*;
+    use rp2350_sim::cortex_m33::registers::Register;
+    use rp2350_sim::{RAM_START_ADDRESS, RP2350};
+
+    #[test]
+    fn bx() {
+        // should execute a `bx r2` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350
+            .cortex_m33
+            .memory
+            .write_u16(RAM_START_ADDRESS, BxT1::opcode(&rp2350.cortex_m33.registers.r2)).unwrap();
+        rp2350.cortex_m33.registers.r2.set(0x20000101);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), 0x20000100);
+        assert!(rp2350.cortex_m33.xpsr.epsr.t());
+    }
+
+    #[test]
+    fn bx_lr() {
+        // should execute a `bx lr` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350
+            .cortex_m33
+            .memory
+            .write_u16(RAM_START_ADDRESS, BxT1::opcode(&rp2350.cortex_m33.registers.lr)).unwrap();
+        rp2350.cortex_m33.registers.lr.set(0x10000355);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), 0x10000354);
+    }
+}
@@ -0,0 +1,22 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::cortex_m33::opcodes::*;
+    use rp2350_sim::cortex_m33::registers::Register;
+    use rp2350_sim::{RAM_START_ADDRESS, RP2350};
+
+    #[test]
+    fn cbnz_taken() {
+        // should execute a `cbnz r3, #0x7e` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u16(
+            RAM_START_ADDRESS,
+            CbnzT1::opcode(&rp2350.cortex_m33.registers.r3, 0x7e),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r3.set(1);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), RAM_START_ADDRESS + 4 + 0x7e);
+    }
+}
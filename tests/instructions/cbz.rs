@@ -0,0 +1,38 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::cortex_m33::opcodes::*;
+    use rp2350_sim::cortex_m33::registers::Register;
+    use rp2350_sim::{RAM_START_ADDRESS, RP2350};
+
+    #[test]
+    fn cbz_taken() {
+        // should execute a `cbz r0, #0x40` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u16(
+            RAM_START_ADDRESS,
+            CbzT1::opcode(&rp2350.cortex_m33.registers.r0, 0x40),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r0.set(0);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), RAM_START_ADDRESS + 4 + 0x40);
+    }
+
+    #[test]
+    fn cbz_not_taken() {
+        // should execute a `cbz r0, #0x40` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u16(
+            RAM_START_ADDRESS,
+            CbzT1::opcode(&rp2350.cortex_m33.registers.r0, 0x40),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r0.set(1);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), RAM_START_ADDRESS + 2);
+    }
+}
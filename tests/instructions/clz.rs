@@ -0,0 +1,22 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::cortex_m33::opcodes::*;
+    use rp2350_sim::cortex_m33::registers::Register;
+    use rp2350_sim::{RAM_START_ADDRESS, RP2350};
+
+    #[test]
+    fn clz() {
+        // should execute a `clz r0, r1` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u32(
+            RAM_START_ADDRESS,
+            ClzT1::opcode(&rp2350.cortex_m33.registers.r0, &rp2350.cortex_m33.registers.r1),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r1.set(0x00010000);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r0.get(), 15);
+    }
+}
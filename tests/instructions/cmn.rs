@@ -0,0 +1,30 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::cortex_m33::opcodes::*;
+    use rp2350_sim::cortex_m33::registers::Register;
+    use rp2350_sim::{RAM_START_ADDRESS, RP2350};
+
+    #[test]
+    fn cmn() {
+        // should execute a `cmn r7, r2` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u16(
+            RAM_START_ADDRESS,
+            CmnRegisterT1::opcode(
+                &rp2350.cortex_m33.registers.r7,
+                &rp2350.cortex_m33.registers.r2,
+            ),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r2.set(1);
+        rp2350.cortex_m33.registers.r7.set(0xffffffff);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r7.get(), 0xffffffff);
+        assert!(!rp2350.cortex_m33.xpsr.apsr.n());
+        assert!(rp2350.cortex_m33.xpsr.apsr.z());
+        assert!(rp2350.cortex_m33.xpsr.apsr.c());
+        assert!(!rp2350.cortex_m33.xpsr.apsr.v());
+    }
+}
@@ -0,0 +1,109 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::cortex_m33::opcodes::*;
+    use rp2350_sim::cortex_m33::registers::Register;
+    use rp2350_sim::{RAM_START_ADDRESS, RP2350};
+
+    #[test]
+    fn cmp_immediate() {
+        // should execute a `cmp r5, #66` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u16(
+            RAM_START_ADDRESS,
+            CmpImmediateT1::opcode(&rp2350.cortex_m33.registers.r5, 66),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r5.set(60);
+        rp2350.execute_instruction();
+
+        assert!(rp2350.cortex_m33.xpsr.apsr.n());
+        assert!(!rp2350.cortex_m33.xpsr.apsr.z());
+        assert!(!rp2350.cortex_m33.xpsr.apsr.c());
+        assert!(!rp2350.cortex_m33.xpsr.apsr.v());
+    }
+
+    #[test]
+    fn cmp_immediate_equal() {
+        // should execute a `cmp r5, #66` instruction and set the zero and carry flags
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u16(
+            RAM_START_ADDRESS,
+            CmpImmediateT1::opcode(&rp2350.cortex_m33.registers.r5, 66),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r5.set(66);
+        rp2350.execute_instruction();
+
+        assert!(!rp2350.cortex_m33.xpsr.apsr.n());
+        assert!(rp2350.cortex_m33.xpsr.apsr.z());
+        assert!(rp2350.cortex_m33.xpsr.apsr.c());
+        assert!(!rp2350.cortex_m33.xpsr.apsr.v());
+    }
+
+    #[test]
+    fn cmp_register() {
+        // should execute a `cmp r5, r0` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u16(
+            RAM_START_ADDRESS,
+            CmpRegisterT1::opcode(
+                &rp2350.cortex_m33.registers.r5,
+                &rp2350.cortex_m33.registers.r0,
+            ),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r5.set(0x80000000);
+        rp2350.cortex_m33.registers.r0.set(1);
+        rp2350.execute_instruction();
+
+        assert!(!rp2350.cortex_m33.xpsr.apsr.n());
+        assert!(!rp2350.cortex_m33.xpsr.apsr.z());
+        assert!(rp2350.cortex_m33.xpsr.apsr.c());
+        assert!(rp2350.cortex_m33.xpsr.apsr.v());
+    }
+
+    #[test]
+    fn cmp_register_high() {
+        // should execute a `cmp r11, r2` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u16(
+            RAM_START_ADDRESS,
+            CmpRegisterT2::opcode(
+                &rp2350.cortex_m33.registers.r11,
+                &rp2350.cortex_m33.registers.r2,
+            ),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r11.set(3);
+        rp2350.cortex_m33.registers.r2.set(5);
+        rp2350.execute_instruction();
+
+        assert!(rp2350.cortex_m33.xpsr.apsr.n());
+        assert!(!rp2350.cortex_m33.xpsr.apsr.z());
+        assert!(!rp2350.cortex_m33.xpsr.apsr.c());
+        assert!(!rp2350.cortex_m33.xpsr.apsr.v());
+    }
+
+    #[test]
+    fn cmp_immediate_t2() {
+        // should execute a `cmp.w r8, #0x100` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u32(
+            RAM_START_ADDRESS,
+            CmpImmediateT2::opcode(&rp2350.cortex_m33.registers.r8, 0xf80),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r8.set(0x100);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r8.get(), 0x100);
+        assert!(rp2350.cortex_m33.xpsr.apsr.z());
+        assert!(rp2350.cortex_m33.xpsr.apsr.c());
+        assert!(!rp2350.cortex_m33.xpsr.apsr.n());
+    }
+}
@@ -0,0 +1,82 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::cortex_m33::exception::{Exception, InterruptException};
+    use rp2350_sim::cortex_m33::opcodes::*;
+    use rp2350_sim::cortex_m33::registers::Register;
+    use rp2350_sim::{RAM_START_ADDRESS, RP2350};
+
+    const VECTOR_TABLE: u32 = RAM_START_ADDRESS + 0x400;
+    const HANDLER: u32 = RAM_START_ADDRESS + 0x600;
+    const SYSTICK: Exception = Exception::Interrupt(InterruptException::SysTick);
+
+    #[test]
+    fn cpsid() {
+        // should execute a `cpsid i` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u16(RAM_START_ADDRESS, CpsT1Id::opcode()).unwrap();
+        rp2350.execute_instruction();
+
+        assert!(rp2350.cortex_m33.primask);
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), 0x20000002);
+    }
+
+    #[test]
+    fn cpsie() {
+        // should execute a `cpsie i` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+        rp2350.cortex_m33.primask = true;
+
+        rp2350.cortex_m33.memory.write_u16(RAM_START_ADDRESS, CpsT1Ie::opcode()).unwrap();
+        rp2350.execute_instruction();
+
+        assert!(!rp2350.cortex_m33.primask);
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), 0x20000002);
+    }
+
+    #[test]
+    fn cpsid_unprivileged() {
+        // should leave PRIMASK alone when `cpsid i` runs in unprivileged Thread mode
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u32(
+            RAM_START_ADDRESS,
+            MsrT1::opcode(0b10, 20, &rp2350.cortex_m33.registers.r0),
+        ).unwrap();
+        rp2350.cortex_m33.memory.write_u16(RAM_START_ADDRESS + 4, CpsT1Id::opcode()).unwrap();
+        rp2350.cortex_m33.registers.r0.set(1);
+        rp2350.execute_instruction();
+        rp2350.execute_instruction();
+
+        assert!(!rp2350.cortex_m33.primask);
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), 0x20000006);
+    }
+
+    #[test]
+    fn cpsid_masks_interrupts() {
+        // should hold a pending SysTick off between `cpsid i` and `cpsie i`
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+        rp2350.cortex_m33.registers.sp.set(RAM_START_ADDRESS + 0x800);
+        rp2350.cortex_m33.vtor = VECTOR_TABLE;
+        rp2350.cortex_m33.memory.write_u32(VECTOR_TABLE + 4 * 15, HANDLER | 1).unwrap();
+
+        rp2350.cortex_m33.memory.write_u16(RAM_START_ADDRESS, CpsT1Id::opcode()).unwrap();
+        rp2350.cortex_m33.memory.write_u16(RAM_START_ADDRESS + 2, YieldT1::opcode()).unwrap();
+        rp2350.cortex_m33.memory.write_u16(RAM_START_ADDRESS + 4, CpsT1Ie::opcode()).unwrap();
+        rp2350.execute_instruction();
+        rp2350.cortex_m33.exceptions.set_pending(SYSTICK);
+        rp2350.execute_instruction();
+        rp2350.execute_instruction();
+
+        assert!(rp2350.cortex_m33.exceptions.is_pending(SYSTICK));
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), 0x20000006);
+
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), HANDLER);
+    }
+}
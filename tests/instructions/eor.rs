@@ -0,0 +1,28 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::cortex_m33::opcodes::*;
+    use rp2350_sim::cortex_m33::registers::Register;
+    use rp2350_sim::{RAM_START_ADDRESS, RP2350};
+
+    #[test]
+    fn eor() {
+        // should execute an `eors r1, r3` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u16(
+            RAM_START_ADDRESS,
+            EorRegisterT1::opcode(
+                &rp2350.cortex_m33.registers.r1,
+                &rp2350.cortex_m33.registers.r3,
+            ),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r1.set(0xf0f0f0f0);
+        rp2350.cortex_m33.registers.r3.set(0x08ff3007);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r1.get(), 0xf80fc0f7);
+        assert!(rp2350.cortex_m33.xpsr.apsr.n());
+        assert!(!rp2350.cortex_m33.xpsr.apsr.z());
+    }
+}
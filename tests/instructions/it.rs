@@ -0,0 +1,137 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::cortex_m33::opcodes::*;
+    use rp2350_sim::cortex_m33::registers::Register;
+    use rp2350_sim::{RAM_START_ADDRESS, RP2350};
+
+    #[test]
+    fn it_condition_passed() {
+        // should execute `it eq` followed by `moveq r0, #1`
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        let mov = MovImmediateT1::opcode(&rp2350.cortex_m33.registers.r0, 1);
+        rp2350.cortex_m33.memory.write_u16(RAM_START_ADDRESS, ItT1::opcode(0b0000, 0b1000)).unwrap();
+        rp2350.cortex_m33.memory.write_u16(RAM_START_ADDRESS + 2, mov).unwrap();
+        rp2350.cortex_m33.xpsr.apsr.set_z(true);
+
+        rp2350.execute_instruction();
+        assert_eq!(rp2350.cortex_m33.xpsr.epsr.it(), 0b00001000);
+
+        rp2350.execute_instruction();
+        assert_eq!(rp2350.cortex_m33.registers.r0.get(), 1);
+        assert_eq!(rp2350.cortex_m33.xpsr.epsr.it(), 0);
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), RAM_START_ADDRESS + 4);
+
+        // A MOVS outside of an IT block sets the flags again
+        assert!(rp2350.cortex_m33.xpsr.apsr.z());
+    }
+
+    #[test]
+    fn ite_skips_failed_condition() {
+        // should execute `ite ne`, `movne r0, #1`, `moveq r0, #2`
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        let movne = MovImmediateT1::opcode(&rp2350.cortex_m33.registers.r0, 1);
+        let moveq = MovImmediateT1::opcode(&rp2350.cortex_m33.registers.r0, 2);
+        rp2350.cortex_m33.memory.write_u16(RAM_START_ADDRESS, ItT1::opcode(0b0001, 0b0100)).unwrap();
+        rp2350.cortex_m33.memory.write_u16(RAM_START_ADDRESS + 2, movne).unwrap();
+        rp2350.cortex_m33.memory.write_u16(RAM_START_ADDRESS + 4, moveq).unwrap();
+        rp2350.cortex_m33.registers.r0.set(0xff);
+        rp2350.cortex_m33.xpsr.apsr.set_z(true);
+
+        rp2350.execute_instruction();
+        rp2350.execute_instruction();
+        assert_eq!(rp2350.cortex_m33.registers.r0.get(), 0xff);
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), RAM_START_ADDRESS + 4);
+
+        rp2350.execute_instruction();
+        assert_eq!(rp2350.cortex_m33.registers.r0.get(), 2);
+        assert_eq!(rp2350.cortex_m33.xpsr.epsr.it(), 0);
+    }
+
+    #[test]
+    fn it_suppresses_flag_setting() {
+        // should execute `it eq` followed by `addeq r0, #1`, which does not set flags inside the block
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        let add = AddsT2::opcode(&rp2350.cortex_m33.registers.r0, 1);
+        rp2350.cortex_m33.memory.write_u16(RAM_START_ADDRESS, ItT1::opcode(0b0000, 0b1000)).unwrap();
+        rp2350.cortex_m33.memory.write_u16(RAM_START_ADDRESS + 2, add).unwrap();
+        rp2350.cortex_m33.registers.r0.set(0xffffffff);
+        rp2350.cortex_m33.xpsr.apsr.set_z(true);
+
+        rp2350.execute_instruction();
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r0.get(), 0);
+        assert!(rp2350.cortex_m33.xpsr.apsr.z());
+        assert!(!rp2350.cortex_m33.xpsr.apsr.c());
+    }
+
+    #[test]
+    fn it_suppresses_logical_flag_setting() {
+        // should execute `it eq` followed by `andeq r0, r1`, which does not set N inside the block
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        let registers = rp2350.cortex_m33.registers;
+        let and = AndRegisterT1::opcode(&registers.r0, &registers.r1);
+        rp2350.cortex_m33.memory.write_u16(RAM_START_ADDRESS, ItT1::opcode(0b0000, 0b1000)).unwrap();
+        rp2350.cortex_m33.memory.write_u16(RAM_START_ADDRESS + 2, and).unwrap();
+        rp2350.cortex_m33.registers.r0.set(0x80000001);
+        rp2350.cortex_m33.registers.r1.set(0x80000000);
+        rp2350.cortex_m33.xpsr.apsr.set_z(true);
+
+        rp2350.execute_instruction();
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r0.get(), 0x80000000);
+        assert!(!rp2350.cortex_m33.xpsr.apsr.n());
+        assert!(rp2350.cortex_m33.xpsr.apsr.z());
+    }
+
+    #[test]
+    fn it_four_instructions() {
+        // should execute `itttt eq` and step ITSTATE through all four instructions
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        let add = AddsT2::opcode(&rp2350.cortex_m33.registers.r0, 1);
+        rp2350.cortex_m33.memory.write_u16(RAM_START_ADDRESS, ItT1::opcode(0b0000, 0b0001)).unwrap();
+        for i in 1..=4 {
+            rp2350.cortex_m33.memory.write_u16(RAM_START_ADDRESS + i * 2, add).unwrap();
+        }
+        rp2350.cortex_m33.xpsr.apsr.set_z(true);
+
+        rp2350.execute_instruction();
+        assert_eq!(rp2350.cortex_m33.xpsr.epsr.it(), 0b00000001);
+        rp2350.execute_instruction();
+        assert_eq!(rp2350.cortex_m33.xpsr.epsr.it(), 0b00000010);
+        rp2350.execute_instruction();
+        assert_eq!(rp2350.cortex_m33.xpsr.epsr.it(), 0b00000100);
+        rp2350.execute_instruction();
+        assert_eq!(rp2350.cortex_m33.xpsr.epsr.it(), 0b00001000);
+        rp2350.execute_instruction();
+        assert_eq!(rp2350.cortex_m33.xpsr.epsr.it(), 0);
+
+        assert_eq!(rp2350.cortex_m33.registers.r0.get(), 4);
+    }
+
+    #[test]
+    fn itstate_in_stacked_xpsr() {
+        // the ITSTATE bits should survive being saved to and restored from the stacked xPSR
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.xpsr.epsr.set_it(0b10110110);
+        rp2350.cortex_m33.xpsr.epsr.set_t(true);
+
+        let xpsr = rp2350.cortex_m33.xpsr.into_u32();
+        assert_eq!(xpsr, 0b0000_0101_0000_0000_1011_0100_0000_0000);
+
+        rp2350.cortex_m33.xpsr.epsr.set_it(0);
+        rp2350.cortex_m33.xpsr.set_from_u32(xpsr);
+        assert_eq!(rp2350.cortex_m33.xpsr.epsr.it(), 0b10110110);
+    }
+}
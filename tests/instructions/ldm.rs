@@ -0,0 +1,28 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::cortex_m33::opcodes::*;
+    use rp2350_sim::cortex_m33::registers::Register;
+    use rp2350_sim::{registers, RAM_START_ADDRESS, RP2350};
+
+    #[test]
+    fn ldm_t2() {
+        // should execute a `ldm.w r0!, {r1, r2, r8}` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u32(
+            RAM_START_ADDRESS,
+            LdmT2::opcode(true, &rp2350.cortex_m33.registers.r0, registers![rp2350.cortex_m33.registers.r1, rp2350.cortex_m33.registers.r2, rp2350.cortex_m33.registers.r8]),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r0.set(RAM_START_ADDRESS + 0x100);
+        rp2350.cortex_m33.memory.write_u32(RAM_START_ADDRESS + 0x100, 1).unwrap();
+        rp2350.cortex_m33.memory.write_u32(RAM_START_ADDRESS + 0x104, 2).unwrap();
+        rp2350.cortex_m33.memory.write_u32(RAM_START_ADDRESS + 0x108, 8).unwrap();
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r1.get(), 1);
+        assert_eq!(rp2350.cortex_m33.registers.r2.get(), 2);
+        assert_eq!(rp2350.cortex_m33.registers.r8.get(), 8);
+        assert_eq!(rp2350.cortex_m33.registers.r0.get(), RAM_START_ADDRESS + 0x10c);
+    }
+}
@@ -14,12 +14,12 @@ mod tests {
             rp2350.cortex_m33.registers.r2
         ];
 
-        let opcode = LdmiaT1::opcode(&rp2350.cortex_m33.registers.r0, registers.into());
-        rp2350.cortex_m33.memory.write_u16(RAM_START_ADDRESS, opcode);
+        let opcode = LdmiaT1::opcode(&rp2350.cortex_m33.registers.r0, registers);
+        rp2350.cortex_m33.memory.write_u16(RAM_START_ADDRESS, opcode).unwrap();
         rp2350.cortex_m33.registers.r0.set(0x20000010);
 
-        rp2350.cortex_m33.memory.write_u32(0x20000010, 0xf00df00d as u32);
-        rp2350.cortex_m33.memory.write_u16(0x20000014, 0x4242 as u16);
+        rp2350.cortex_m33.memory.write_u32(0x20000010, 0xf00df00d_u32).unwrap();
+        rp2350.cortex_m33.memory.write_u16(0x20000014, 0x4242_u16).unwrap();
 
         rp2350.execute_instruction();
 
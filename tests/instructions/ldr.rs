@@ -0,0 +1,210 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::cortex_m33::exception::Exception;
+    use rp2350_sim::cortex_m33::fault::{CFSR_BFARVALID, CFSR_PRECISERR};
+    use rp2350_sim::cortex_m33::opcodes::*;
+    use rp2350_sim::cortex_m33::registers::Register;
+    use rp2350_sim::{RAM_START_ADDRESS, RP2350};
+
+    #[test]
+    fn ldr_immediate() {
+        // should execute a `ldr r3, [r2, #24]` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u16(
+            RAM_START_ADDRESS,
+            LdrImmediateT1::opcode(
+                &rp2350.cortex_m33.registers.r3,
+                &rp2350.cortex_m33.registers.r2,
+                24,
+            ),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r2.set(0x20001000);
+        rp2350.cortex_m33.memory.write_u32(0x20001018, 0x001f0000).unwrap();
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r3.get(), 0x001f0000);
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), 0x20000002);
+    }
+
+    #[test]
+    fn ldr_sp_plus_immediate() {
+        // should execute a `ldr r3, [sp, #12]` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u16(
+            RAM_START_ADDRESS,
+            LdrImmediateT2::opcode(&rp2350.cortex_m33.registers.r3, 12),
+        ).unwrap();
+        rp2350.cortex_m33.registers.sp.set(0x20000100);
+        rp2350.cortex_m33.memory.write_u32(0x2000010c, 0x55).unwrap();
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r3.get(), 0x55);
+    }
+
+    #[test]
+    fn ldr_literal() {
+        // should execute a `ldr r0, [pc, #148]` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS + 2);
+
+        rp2350.cortex_m33.memory.write_u16(
+            RAM_START_ADDRESS + 2,
+            LdrLiteralT1::opcode(&rp2350.cortex_m33.registers.r0, 148),
+        ).unwrap();
+        rp2350.cortex_m33.memory.write_u32(RAM_START_ADDRESS + 4 + 148, 0x42).unwrap();
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r0.get(), 0x42);
+    }
+
+    #[test]
+    fn ldr_register() {
+        // should execute a `ldr r3, [r5, r6]` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u16(
+            RAM_START_ADDRESS,
+            LdrRegisterT1::opcode(
+                &rp2350.cortex_m33.registers.r3,
+                &rp2350.cortex_m33.registers.r5,
+                &rp2350.cortex_m33.registers.r6,
+            ),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r5.set(0x20000100);
+        rp2350.cortex_m33.registers.r6.set(8);
+        rp2350.cortex_m33.memory.write_u32(0x20000108, 0xdeadbeef).unwrap();
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r3.get(), 0xdeadbeef);
+    }
+
+    #[test]
+    fn ldr_immediate_t3() {
+        // should execute a `ldr.w r0, [r1, #0x100]` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u32(
+            RAM_START_ADDRESS,
+            LdrImmediateT3::opcode(&rp2350.cortex_m33.registers.r0, &rp2350.cortex_m33.registers.r1, 0x100),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r1.set(RAM_START_ADDRESS + 0x10);
+        rp2350.cortex_m33.memory.write_u32(RAM_START_ADDRESS + 0x110, 0xdeadbeef).unwrap();
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r0.get(), 0xdeadbeef);
+    }
+
+    #[test]
+    fn ldr_immediate_t4_post_index() {
+        // should execute a `ldr r0, [r1], #-4` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u32(
+            RAM_START_ADDRESS,
+            LdrImmediateT4::opcode(&rp2350.cortex_m33.registers.r0, &rp2350.cortex_m33.registers.r1, 4, false, false, true),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r1.set(RAM_START_ADDRESS + 0x20);
+        rp2350.cortex_m33.memory.write_u32(RAM_START_ADDRESS + 0x20, 0x12345678).unwrap();
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r0.get(), 0x12345678);
+        assert_eq!(rp2350.cortex_m33.registers.r1.get(), RAM_START_ADDRESS + 0x1c);
+    }
+
+    #[test]
+    fn ldr_immediate_t4_pre_index() {
+        // should execute a `ldr r0, [r1, #8]!` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u32(
+            RAM_START_ADDRESS,
+            LdrImmediateT4::opcode(&rp2350.cortex_m33.registers.r0, &rp2350.cortex_m33.registers.r1, 8, true, true, true),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r1.set(RAM_START_ADDRESS + 0x20);
+        rp2350.cortex_m33.memory.write_u32(RAM_START_ADDRESS + 0x28, 0x12345678).unwrap();
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r0.get(), 0x12345678);
+        assert_eq!(rp2350.cortex_m33.registers.r1.get(), RAM_START_ADDRESS + 0x28);
+    }
+
+    #[test]
+    fn ldr_literal_t2() {
+        // should execute a `ldr.w r0, [pc, #8]` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u32(
+            RAM_START_ADDRESS,
+            LdrLiteralT2::opcode(&rp2350.cortex_m33.registers.r0, 8),
+        ).unwrap();
+        rp2350.cortex_m33.memory.write_u32(RAM_START_ADDRESS + 12, 0xcafef00d).unwrap();
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r0.get(), 0xcafef00d);
+    }
+
+    #[test]
+    fn ldr_register_t2() {
+        // should execute a `ldr.w r0, [r1, r2, lsl #2]` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u32(
+            RAM_START_ADDRESS,
+            LdrRegisterT2::opcode(&rp2350.cortex_m33.registers.r0, &rp2350.cortex_m33.registers.r1, &rp2350.cortex_m33.registers.r2, 2),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r1.set(RAM_START_ADDRESS + 0x100);
+        rp2350.cortex_m33.registers.r2.set(3);
+        rp2350.cortex_m33.memory.write_u32(RAM_START_ADDRESS + 0x10c, 0x55aa55aa).unwrap();
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r0.get(), 0x55aa55aa);
+    }
+
+    #[test]
+    fn ldr_pc() {
+        // should execute a `ldr.w pc, [r1]` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u32(
+            RAM_START_ADDRESS,
+            LdrImmediateT3::opcode(&rp2350.cortex_m33.registers.pc, &rp2350.cortex_m33.registers.r1, 0),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r1.set(RAM_START_ADDRESS + 0x100);
+        rp2350.cortex_m33.memory.write_u32(RAM_START_ADDRESS + 0x100, 0x20000201).unwrap();
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), 0x20000200);
+    }
+
+    #[test]
+    fn ldr_unmapped_raises_bus_fault() {
+        // should fault on a `ldr r0, [r1, #4]` instruction that reads past the end of SRAM
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u16(
+            RAM_START_ADDRESS,
+            LdrImmediateT1::opcode(&rp2350.cortex_m33.registers.r0, &rp2350.cortex_m33.registers.r1, 4),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r0.set(0xdeadbeef);
+        rp2350.cortex_m33.registers.r1.set(0x20100000);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), RAM_START_ADDRESS);
+        assert_eq!(rp2350.cortex_m33.registers.r0.get(), 0xdeadbeef);
+        assert_eq!(rp2350.cortex_m33.fault_status.cfsr, CFSR_PRECISERR | CFSR_BFARVALID);
+        assert_eq!(rp2350.cortex_m33.fault_status.bfar, 0x20100004);
+        assert!(rp2350.cortex_m33.exceptions.is_pending(Exception::HardFault));
+    }
+}
@@ -0,0 +1,49 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::cortex_m33::opcodes::*;
+    use rp2350_sim::cortex_m33::registers::Register;
+    use rp2350_sim::{RAM_START_ADDRESS, RP2350};
+
+    #[test]
+    fn ldrb_immediate() {
+        // should execute a `ldrb r4, [r2, #5]` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u16(
+            RAM_START_ADDRESS,
+            LdrbImmediateT1::opcode(
+                &rp2350.cortex_m33.registers.r4,
+                &rp2350.cortex_m33.registers.r2,
+                5,
+            ),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r2.set(0x20000100);
+        rp2350.cortex_m33.memory.write_u32(0x20000104, 0xf1e2d3c4).unwrap();
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r4.get(), 0xd3);
+    }
+
+    #[test]
+    fn ldrb_register() {
+        // should execute a `ldrb r0, [r1, r2]` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u16(
+            RAM_START_ADDRESS,
+            LdrbRegisterT1::opcode(
+                &rp2350.cortex_m33.registers.r0,
+                &rp2350.cortex_m33.registers.r1,
+                &rp2350.cortex_m33.registers.r2,
+            ),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r1.set(0x20000100);
+        rp2350.cortex_m33.registers.r2.set(3);
+        rp2350.cortex_m33.memory.write_u32(0x20000100, 0xf1e2d3c4).unwrap();
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r0.get(), 0xf1);
+    }
+}
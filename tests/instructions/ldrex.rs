@@ -0,0 +1,68 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::cortex_m33::opcodes::*;
+    use rp2350_sim::cortex_m33::registers::Register;
+    use rp2350_sim::{RAM_START_ADDRESS, RP2350};
+
+    #[test]
+    fn ldrex_strex() {
+        // should execute `ldrex r0, [r1]` followed by `strex r2, r3, [r1]`
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        let registers = &rp2350.cortex_m33.registers;
+        let ldrex = LdrexT1::opcode(&registers.r0, &registers.r1, 0);
+        let strex = StrexT1::opcode(&registers.r2, &registers.r3, &registers.r1, 0);
+        rp2350.cortex_m33.memory.write_u32(RAM_START_ADDRESS, ldrex).unwrap();
+        rp2350.cortex_m33.memory.write_u32(RAM_START_ADDRESS + 4, strex).unwrap();
+        rp2350.cortex_m33.memory.write_u32(RAM_START_ADDRESS + 0x100, 0x1234).unwrap();
+        rp2350.cortex_m33.registers.r1.set(RAM_START_ADDRESS + 0x100);
+        rp2350.cortex_m33.registers.r3.set(0x5678);
+
+        rp2350.execute_instruction();
+        assert_eq!(rp2350.cortex_m33.registers.r0.get(), 0x1234);
+
+        rp2350.execute_instruction();
+        assert_eq!(rp2350.cortex_m33.registers.r2.get(), 0);
+        assert_eq!(rp2350.cortex_m33.memory.read_u32(RAM_START_ADDRESS + 0x100).unwrap(), 0x5678);
+    }
+
+    #[test]
+    fn strex_without_ldrex() {
+        // should execute a `strex r2, r3, [r1]` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        let registers = &rp2350.cortex_m33.registers;
+        let strex = StrexT1::opcode(&registers.r2, &registers.r3, &registers.r1, 0);
+        rp2350.cortex_m33.memory.write_u32(RAM_START_ADDRESS, strex).unwrap();
+        rp2350.cortex_m33.memory.write_u32(RAM_START_ADDRESS + 0x100, 0x1234).unwrap();
+        rp2350.cortex_m33.registers.r1.set(RAM_START_ADDRESS + 0x100);
+        rp2350.cortex_m33.registers.r3.set(0x5678);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r2.get(), 1);
+        assert_eq!(rp2350.cortex_m33.memory.read_u32(RAM_START_ADDRESS + 0x100).unwrap(), 0x1234);
+    }
+
+    #[test]
+    fn clrex_clears_monitor() {
+        // should execute `ldrex r0, [r1]`, `clrex` and then fail `strex r2, r3, [r1]`
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        let registers = &rp2350.cortex_m33.registers;
+        let ldrex = LdrexT1::opcode(&registers.r0, &registers.r1, 0);
+        let strex = StrexT1::opcode(&registers.r2, &registers.r3, &registers.r1, 0);
+        rp2350.cortex_m33.memory.write_u32(RAM_START_ADDRESS, ldrex).unwrap();
+        rp2350.cortex_m33.memory.write_u32(RAM_START_ADDRESS + 4, ClrexT1::opcode()).unwrap();
+        rp2350.cortex_m33.memory.write_u32(RAM_START_ADDRESS + 8, strex).unwrap();
+        rp2350.cortex_m33.registers.r1.set(RAM_START_ADDRESS + 0x100);
+
+        rp2350.execute_instruction();
+        rp2350.execute_instruction();
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r2.get(), 1);
+    }
+}
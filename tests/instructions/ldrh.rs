@@ -0,0 +1,49 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::cortex_m33::opcodes::*;
+    use rp2350_sim::cortex_m33::registers::Register;
+    use rp2350_sim::{RAM_START_ADDRESS, RP2350};
+
+    #[test]
+    fn ldrh_immediate() {
+        // should execute a `ldrh r3, [r7, #2]` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u16(
+            RAM_START_ADDRESS,
+            LdrhImmediateT1::opcode(
+                &rp2350.cortex_m33.registers.r3,
+                &rp2350.cortex_m33.registers.r7,
+                2,
+            ),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r7.set(0x20000100);
+        rp2350.cortex_m33.memory.write_u32(0x20000100, 0xf1e2d3c4).unwrap();
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r3.get(), 0xf1e2);
+    }
+
+    #[test]
+    fn ldrh_register() {
+        // should execute a `ldrh r3, [r0, r1]` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u16(
+            RAM_START_ADDRESS,
+            LdrhRegisterT1::opcode(
+                &rp2350.cortex_m33.registers.r3,
+                &rp2350.cortex_m33.registers.r0,
+                &rp2350.cortex_m33.registers.r1,
+            ),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r0.set(0x20000100);
+        rp2350.cortex_m33.registers.r1.set(4);
+        rp2350.cortex_m33.memory.write_u32(0x20000104, 0xf1e2d3c4).unwrap();
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r3.get(), 0xd3c4);
+    }
+}
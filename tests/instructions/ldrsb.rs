@@ -0,0 +1,28 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::cortex_m33::opcodes::*;
+    use rp2350_sim::cortex_m33::registers::Register;
+    use rp2350_sim::{RAM_START_ADDRESS, RP2350};
+
+    #[test]
+    fn ldrsb() {
+        // should execute a `ldrsb r2, [r3, r5]` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u16(
+            RAM_START_ADDRESS,
+            LdrsbRegisterT1::opcode(
+                &rp2350.cortex_m33.registers.r2,
+                &rp2350.cortex_m33.registers.r3,
+                &rp2350.cortex_m33.registers.r5,
+            ),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r3.set(0x20000100);
+        rp2350.cortex_m33.registers.r5.set(1);
+        rp2350.cortex_m33.memory.write_u32(0x20000100, 0x0000f100).unwrap();
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r2.get(), 0xfffffff1);
+    }
+}
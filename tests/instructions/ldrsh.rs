@@ -0,0 +1,45 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::cortex_m33::opcodes::*;
+    use rp2350_sim::cortex_m33::registers::Register;
+    use rp2350_sim::{RAM_START_ADDRESS, RP2350};
+
+    #[test]
+    fn ldrsh() {
+        // should execute a `ldrsh r5, [r3, r5]` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u16(
+            RAM_START_ADDRESS,
+            LdrshRegisterT1::opcode(
+                &rp2350.cortex_m33.registers.r5,
+                &rp2350.cortex_m33.registers.r3,
+                &rp2350.cortex_m33.registers.r5,
+            ),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r3.set(0x20000100);
+        rp2350.cortex_m33.registers.r5.set(2);
+        rp2350.cortex_m33.memory.write_u32(0x20000100, 0x8073f100).unwrap();
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r5.get(), 0xffff8073);
+    }
+
+    #[test]
+    fn ldrsh_immediate_t1() {
+        // should execute a `ldrsh.w r0, [r1, #2]` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u32(
+            RAM_START_ADDRESS,
+            LdrshImmediateT1::opcode(&rp2350.cortex_m33.registers.r0, &rp2350.cortex_m33.registers.r1, 2),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r1.set(RAM_START_ADDRESS + 0x100);
+        rp2350.cortex_m33.memory.write_u32(RAM_START_ADDRESS + 0x100, 0x80010000).unwrap();
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r0.get(), 0xffff8001);
+    }
+}
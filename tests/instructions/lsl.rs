@@ -0,0 +1,113 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::cortex_m33::opcodes::*;
+    use rp2350_sim::cortex_m33::registers::Register;
+    use rp2350_sim::{RAM_START_ADDRESS, RP2350};
+
+    #[test]
+    fn lsl_immediate() {
+        // should execute a `lsls r5, r5, #18` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u16(
+            RAM_START_ADDRESS,
+            LslImmediateT1::opcode(
+                &rp2350.cortex_m33.registers.r5,
+                &rp2350.cortex_m33.registers.r5,
+                18,
+            ),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r5.set(0b00000000000000000011);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r5.get(), 0b11000000000000000000);
+        assert!(!rp2350.cortex_m33.xpsr.apsr.n());
+        assert!(!rp2350.cortex_m33.xpsr.apsr.z());
+        assert!(!rp2350.cortex_m33.xpsr.apsr.c());
+    }
+
+    #[test]
+    fn lsl_immediate_carry() {
+        // should execute a `lsls r5, r5, #18` instruction and set the carry flag
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u16(
+            RAM_START_ADDRESS,
+            LslImmediateT1::opcode(
+                &rp2350.cortex_m33.registers.r5,
+                &rp2350.cortex_m33.registers.r5,
+                18,
+            ),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r5.set(0x00004001);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r5.get(), 0x40000);
+        assert!(rp2350.cortex_m33.xpsr.apsr.c());
+    }
+
+    #[test]
+    fn lsl_register() {
+        // should execute a `lsls r5, r0` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u16(
+            RAM_START_ADDRESS,
+            LslRegisterT1::opcode(
+                &rp2350.cortex_m33.registers.r5,
+                &rp2350.cortex_m33.registers.r0,
+            ),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r5.set(0b00000000000000000011);
+        rp2350.cortex_m33.registers.r0.set(0xff003302);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r5.get(), 0b1100);
+        assert!(!rp2350.cortex_m33.xpsr.apsr.c());
+    }
+
+    #[test]
+    fn lsl_register_large_shift() {
+        // should execute a `lsls r3, r4` instruction with a shift amount larger than 32
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u16(
+            RAM_START_ADDRESS,
+            LslRegisterT1::opcode(
+                &rp2350.cortex_m33.registers.r3,
+                &rp2350.cortex_m33.registers.r4,
+            ),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r3.set(0xffffffff);
+        rp2350.cortex_m33.registers.r4.set(0x40);
+        rp2350.cortex_m33.xpsr.apsr.set_c(true);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r3.get(), 0);
+        assert!(rp2350.cortex_m33.xpsr.apsr.z());
+        assert!(!rp2350.cortex_m33.xpsr.apsr.c());
+    }
+
+    #[test]
+    fn lsl_register_t2() {
+        // should execute a `lsls.w r11, r12, r0` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u32(
+            RAM_START_ADDRESS,
+            LslRegisterT2::opcode(true, &rp2350.cortex_m33.registers.r11, &rp2350.cortex_m33.registers.r12, &rp2350.cortex_m33.registers.r0),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r12.set(0x80000001);
+        rp2350.cortex_m33.registers.r0.set(1);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r11.get(), 0x2);
+        assert!(rp2350.cortex_m33.xpsr.apsr.c());
+        assert!(!rp2350.cortex_m33.xpsr.apsr.n());
+    }
+}
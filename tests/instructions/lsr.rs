@@ -0,0 +1,70 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::cortex_m33::opcodes::*;
+    use rp2350_sim::cortex_m33::registers::Register;
+    use rp2350_sim::{RAM_START_ADDRESS, RP2350};
+
+    #[test]
+    fn lsr_immediate() {
+        // should execute a `lsrs r1, r1, #1` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u16(
+            RAM_START_ADDRESS,
+            LsrImmediateT1::opcode(
+                &rp2350.cortex_m33.registers.r1,
+                &rp2350.cortex_m33.registers.r1,
+                1,
+            ),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r1.set(0b10);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r1.get(), 0b1);
+        assert!(!rp2350.cortex_m33.xpsr.apsr.c());
+    }
+
+    #[test]
+    fn lsr_immediate_32() {
+        // should execute a `lsrs r1, r1, #32` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u16(
+            RAM_START_ADDRESS,
+            LsrImmediateT1::opcode(
+                &rp2350.cortex_m33.registers.r1,
+                &rp2350.cortex_m33.registers.r1,
+                0,
+            ),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r1.set(0x80000000);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r1.get(), 0);
+        assert!(rp2350.cortex_m33.xpsr.apsr.z());
+        assert!(rp2350.cortex_m33.xpsr.apsr.c());
+    }
+
+    #[test]
+    fn lsr_register() {
+        // should execute a `lsrs r5, r0` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u16(
+            RAM_START_ADDRESS,
+            LsrRegisterT1::opcode(
+                &rp2350.cortex_m33.registers.r5,
+                &rp2350.cortex_m33.registers.r0,
+            ),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r5.set(0xff00000f);
+        rp2350.cortex_m33.registers.r0.set(0xff003302);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r5.get(), 0x3fc00003);
+        assert!(rp2350.cortex_m33.xpsr.apsr.c());
+    }
+}
@@ -4,18 +4,74 @@ mod adr;
 mod and;
 mod asr;
 mod b;
+mod bfc;
+mod bfi;
+mod bkpt;
 mod bl;
 mod blx;
+mod bx;
+mod cbnz;
+mod cbz;
+mod clz;
+mod cmn;
+mod cmp;
+mod cps;
 mod dmb;
 mod dsb;
+mod eor;
 mod isb;
+mod it;
+mod ldm;
 mod ldmia;
+mod ldr;
+mod ldrb;
+mod ldrd;
+mod ldrex;
+mod ldrh;
+mod ldrsb;
+mod ldrsh;
+mod lsl;
+mod lsr;
+mod mla;
+mod mls;
 mod mov;
+mod movt;
+mod mrs;
+mod msr;
+mod mul;
+mod mvn;
+mod orr;
+mod pop;
 mod push;
-mod rev;
+mod rbit;
 mod rev16;
+mod rev;
+mod revsh;
+mod ror;
+mod rsb;
+mod sbc;
+mod sbfx;
+mod sdiv;
+mod smull;
+mod ssat;
+mod stmdb;
 mod stmia;
+mod str;
+mod strb;
+mod strd;
+mod strh;
 mod sub;
+mod sxtb;
+mod sxth;
+mod tbb;
+mod tbh;
+mod tst;
+mod ubfx;
+mod udf;
+mod udiv;
+mod umlal;
+mod umull;
+mod usat;
 mod uxtb;
 mod uxth;
 mod r#yield;
@@ -16,7 +16,7 @@ mod tests {
                 &rp2350.cortex_m33.registers.r3,
                 &rp2350.cortex_m33.registers.r8,
             ),
-        );
+        ).unwrap();
         rp2350.cortex_m33.registers.r8.set(55);
         rp2350.execute_instruction();
 
@@ -35,9 +35,58 @@ mod tests {
                 &rp2350.cortex_m33.registers.r3,
                 &rp2350.cortex_m33.registers.pc,
             ),
-        );
+        ).unwrap();
         rp2350.execute_instruction();
 
         assert_eq!(rp2350.cortex_m33.registers.r3.get(), 0x20000004);
     }
+
+    #[test]
+    fn mov_immediate() {
+        // should execute a `movs r3, #0x7a` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u16(
+            RAM_START_ADDRESS,
+            MovImmediateT1::opcode(&rp2350.cortex_m33.registers.r3, 0x7a),
+        ).unwrap();
+        rp2350.cortex_m33.xpsr.apsr.set_z(true);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r3.get(), 0x7a);
+        assert!(!rp2350.cortex_m33.xpsr.apsr.n());
+        assert!(!rp2350.cortex_m33.xpsr.apsr.z());
+    }
+
+    #[test]
+    fn mov_immediate_t2() {
+        // should execute a `mov.w r9, #0x00ab00ab` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u32(
+            RAM_START_ADDRESS,
+            MovImmediateT2::opcode(false, &rp2350.cortex_m33.registers.r9, 0x1ab),
+        ).unwrap();
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r9.get(), 0x00ab00ab);
+    }
+
+    #[test]
+    fn mov_immediate_t3() {
+        // should execute a `movw r10, #0xbeef` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u32(
+            RAM_START_ADDRESS,
+            MovImmediateT3::opcode(&rp2350.cortex_m33.registers.r10, 0xbeef),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r10.set(0xffffffff);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r10.get(), 0xbeef);
+    }
 }
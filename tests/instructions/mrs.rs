@@ -0,0 +1,39 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::cortex_m33::opcodes::*;
+    use rp2350_sim::cortex_m33::registers::Register;
+    use rp2350_sim::{RAM_START_ADDRESS, RP2350};
+
+    #[test]
+    fn mrs_apsr() {
+        // should execute a `mrs r0, apsr` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u32(
+            RAM_START_ADDRESS,
+            MrsT1::opcode(&rp2350.cortex_m33.registers.r0, 0),
+        ).unwrap();
+        rp2350.cortex_m33.xpsr.apsr.set_n(true);
+        rp2350.cortex_m33.xpsr.apsr.set_c(true);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r0.get(), 0xa0000000);
+    }
+
+    #[test]
+    fn mrs_primask() {
+        // should execute a `mrs r0, primask` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u32(
+            RAM_START_ADDRESS,
+            MrsT1::opcode(&rp2350.cortex_m33.registers.r0, 16),
+        ).unwrap();
+        rp2350.cortex_m33.primask = true;
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r0.get(), 1);
+    }
+}
@@ -0,0 +1,58 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::cortex_m33::opcodes::*;
+    use rp2350_sim::cortex_m33::registers::Register;
+    use rp2350_sim::{RAM_START_ADDRESS, RP2350};
+
+    #[test]
+    fn msr_apsr() {
+        // should execute a `msr apsr_nzcvq, r0` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u32(
+            RAM_START_ADDRESS,
+            MsrT1::opcode(0b10, 0, &rp2350.cortex_m33.registers.r0),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r0.set(0x60000000);
+        rp2350.execute_instruction();
+
+        assert!(rp2350.cortex_m33.xpsr.apsr.z());
+        assert!(rp2350.cortex_m33.xpsr.apsr.c());
+        assert!(!rp2350.cortex_m33.xpsr.apsr.n());
+        assert!(!rp2350.cortex_m33.xpsr.apsr.v());
+    }
+
+    #[test]
+    fn msr_basepri() {
+        // should execute a `msr basepri, r0` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u32(
+            RAM_START_ADDRESS,
+            MsrT1::opcode(0b10, 17, &rp2350.cortex_m33.registers.r0),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r0.set(0x1c0);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.basepri, 0xc0);
+    }
+
+    #[test]
+    fn msr_control() {
+        // should execute a `msr control, r0` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u32(
+            RAM_START_ADDRESS,
+            MsrT1::opcode(0b10, 20, &rp2350.cortex_m33.registers.r0),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r0.set(0b10);
+        rp2350.cortex_m33.registers.sp.set_psp(RAM_START_ADDRESS + 0x200);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.sp.get(), RAM_START_ADDRESS + 0x200);
+    }
+}
@@ -0,0 +1,28 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::cortex_m33::opcodes::*;
+    use rp2350_sim::cortex_m33::registers::Register;
+    use rp2350_sim::{RAM_START_ADDRESS, RP2350};
+
+    #[test]
+    fn mul() {
+        // should execute a `muls r1, r2, r1` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u16(
+            RAM_START_ADDRESS,
+            MulT1::opcode(
+                &rp2350.cortex_m33.registers.r1,
+                &rp2350.cortex_m33.registers.r2,
+            ),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r1.set(0xfffffffe);
+        rp2350.cortex_m33.registers.r2.set(5);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r1.get(), 0xfffffff6);
+        assert!(rp2350.cortex_m33.xpsr.apsr.n());
+        assert!(!rp2350.cortex_m33.xpsr.apsr.z());
+    }
+}
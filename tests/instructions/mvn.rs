@@ -0,0 +1,27 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::cortex_m33::opcodes::*;
+    use rp2350_sim::cortex_m33::registers::Register;
+    use rp2350_sim::{RAM_START_ADDRESS, RP2350};
+
+    #[test]
+    fn mvn() {
+        // should execute a `mvns r4, r3` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u16(
+            RAM_START_ADDRESS,
+            MvnT1::opcode(
+                &rp2350.cortex_m33.registers.r4,
+                &rp2350.cortex_m33.registers.r3,
+            ),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r3.set(0x11115555);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r4.get(), 0xeeeeaaaa);
+        assert!(rp2350.cortex_m33.xpsr.apsr.n());
+        assert!(!rp2350.cortex_m33.xpsr.apsr.z());
+    }
+}
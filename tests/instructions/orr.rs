@@ -0,0 +1,45 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::cortex_m33::opcodes::*;
+    use rp2350_sim::cortex_m33::registers::Register;
+    use rp2350_sim::{RAM_START_ADDRESS, RP2350};
+
+    #[test]
+    fn orr() {
+        // should execute an `orrs r5, r0` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u16(
+            RAM_START_ADDRESS,
+            OrrRegisterT1::opcode(
+                &rp2350.cortex_m33.registers.r5,
+                &rp2350.cortex_m33.registers.r0,
+            ),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r5.set(0xf00f0000);
+        rp2350.cortex_m33.registers.r0.set(0xf00fa);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r5.get(), 0xf00f00fa);
+        assert!(rp2350.cortex_m33.xpsr.apsr.n());
+        assert!(!rp2350.cortex_m33.xpsr.apsr.z());
+    }
+
+    #[test]
+    fn orr_register_t2() {
+        // should execute a `orr.w r0, r1, r2, lsr #8` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u32(
+            RAM_START_ADDRESS,
+            OrrRegisterT2::opcode(false, &rp2350.cortex_m33.registers.r0, &rp2350.cortex_m33.registers.r1, &rp2350.cortex_m33.registers.r2, 0b01, 8),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r1.set(0xf0000000);
+        rp2350.cortex_m33.registers.r2.set(0x00ff0000);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r0.get(), 0xf000ff00);
+    }
+}
@@ -0,0 +1,73 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::cortex_m33::opcodes::*;
+    use rp2350_sim::cortex_m33::registers::Register;
+    use rp2350_sim::{registers, RAM_START_ADDRESS, RP2350};
+
+    #[test]
+    fn pop() {
+        // should execute a `pop {r4, r5, r6}` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+        rp2350
+            .cortex_m33
+            .registers
+            .sp
+            .set(RAM_START_ADDRESS + 0xf4);
+
+        let registers = registers![
+            rp2350.cortex_m33.registers.r4,
+            rp2350.cortex_m33.registers.r5,
+            rp2350.cortex_m33.registers.r6
+        ];
+        rp2350
+            .cortex_m33
+            .memory
+            .write_u16(RAM_START_ADDRESS, PopT1::opcode(false, registers)).unwrap();
+
+        rp2350.cortex_m33.memory.write_u32(RAM_START_ADDRESS + 0xf4, 0x40).unwrap();
+        rp2350.cortex_m33.memory.write_u32(RAM_START_ADDRESS + 0xf8, 0x50).unwrap();
+        rp2350.cortex_m33.memory.write_u32(RAM_START_ADDRESS + 0xfc, 0x60).unwrap();
+
+        rp2350.execute_instruction();
+
+        assert_eq!(
+            rp2350.cortex_m33.registers.sp.get(),
+            RAM_START_ADDRESS + 0x100
+        );
+        assert_eq!(rp2350.cortex_m33.registers.r4.get(), 0x40);
+        assert_eq!(rp2350.cortex_m33.registers.r5.get(), 0x50);
+        assert_eq!(rp2350.cortex_m33.registers.r6.get(), 0x60);
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), 0x20000002);
+    }
+
+    #[test]
+    fn pop_pc() {
+        // should execute a `pop {r4, pc}` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+        rp2350
+            .cortex_m33
+            .registers
+            .sp
+            .set(RAM_START_ADDRESS + 0xf8);
+
+        let registers = registers![rp2350.cortex_m33.registers.r4];
+        rp2350
+            .cortex_m33
+            .memory
+            .write_u16(RAM_START_ADDRESS, PopT1::opcode(true, registers)).unwrap();
+
+        rp2350.cortex_m33.memory.write_u32(RAM_START_ADDRESS + 0xf8, 0x40).unwrap();
+        rp2350.cortex_m33.memory.write_u32(RAM_START_ADDRESS + 0xfc, 0x10000419).unwrap();
+
+        rp2350.execute_instruction();
+
+        assert_eq!(
+            rp2350.cortex_m33.registers.sp.get(),
+            RAM_START_ADDRESS + 0x100
+        );
+        assert_eq!(rp2350.cortex_m33.registers.r4.get(), 0x40);
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), 0x10000418);
+    }
+}
@@ -21,9 +21,9 @@ mod tests {
             rp2350.cortex_m33.registers.r5,
             rp2350.cortex_m33.registers.r6
         ];
-        let binary = PushT1::opcode(true, registers.into());
+        let binary = PushT1::opcode(true, registers);
 
-        rp2350.cortex_m33.memory.write_u16(RAM_START_ADDRESS, binary);
+        rp2350.cortex_m33.memory.write_u16(RAM_START_ADDRESS, binary).unwrap();
 
         rp2350.cortex_m33.registers.r4.set(0x40);
         rp2350.cortex_m33.registers.r5.set(0x50);
@@ -16,7 +16,7 @@ mod tests {
                 &rp2350.cortex_m33.registers.r2,
                 &rp2350.cortex_m33.registers.r3,
             ),
-        );
+        ).unwrap();
 
         rp2350.cortex_m33.registers.r3.set(0x11223344);
 
@@ -16,7 +16,7 @@ mod tests {
                 &rp2350.cortex_m33.registers.r0,
                 &rp2350.cortex_m33.registers.r5,
             ),
-        );
+        ).unwrap();
 
         rp2350.cortex_m33.registers.r5.set(0x11223344);
 
@@ -0,0 +1,51 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::cortex_m33::opcodes::*;
+    use rp2350_sim::cortex_m33::registers::Register;
+    use rp2350_sim::{RAM_START_ADDRESS, RP2350};
+
+    #[test]
+    fn ror() {
+        // should execute a `rors r5, r3` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u16(
+            RAM_START_ADDRESS,
+            RorRegisterT1::opcode(
+                &rp2350.cortex_m33.registers.r5,
+                &rp2350.cortex_m33.registers.r3,
+            ),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r5.set(0x12345678);
+        rp2350.cortex_m33.registers.r3.set(0x2004);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r5.get(), 0x81234567);
+        assert!(rp2350.cortex_m33.xpsr.apsr.n());
+        assert!(!rp2350.cortex_m33.xpsr.apsr.z());
+        assert!(rp2350.cortex_m33.xpsr.apsr.c());
+    }
+
+    #[test]
+    fn ror_multiple_of_32() {
+        // should execute a `rors r5, r3` instruction with a rotation of 32
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u16(
+            RAM_START_ADDRESS,
+            RorRegisterT1::opcode(
+                &rp2350.cortex_m33.registers.r5,
+                &rp2350.cortex_m33.registers.r3,
+            ),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r5.set(0x12345678);
+        rp2350.cortex_m33.registers.r3.set(32);
+        rp2350.cortex_m33.xpsr.apsr.set_c(true);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r5.get(), 0x12345678);
+        assert!(!rp2350.cortex_m33.xpsr.apsr.c());
+    }
+}
@@ -0,0 +1,56 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::cortex_m33::opcodes::*;
+    use rp2350_sim::cortex_m33::registers::Register;
+    use rp2350_sim::{RAM_START_ADDRESS, RP2350};
+
+    #[test]
+    fn sbc() {
+        // should execute a `sbcs r0, r3` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u16(
+            RAM_START_ADDRESS,
+            SbcRegisterT1::opcode(
+                &rp2350.cortex_m33.registers.r0,
+                &rp2350.cortex_m33.registers.r3,
+            ),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r0.set(100);
+        rp2350.cortex_m33.registers.r3.set(55);
+        rp2350.cortex_m33.xpsr.apsr.set_c(false);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r0.get(), 44);
+        assert!(!rp2350.cortex_m33.xpsr.apsr.n());
+        assert!(!rp2350.cortex_m33.xpsr.apsr.z());
+        assert!(rp2350.cortex_m33.xpsr.apsr.c());
+        assert!(!rp2350.cortex_m33.xpsr.apsr.v());
+    }
+
+    #[test]
+    fn sbc_overflow() {
+        // should execute a `sbcs r0, r3` instruction and set the overflow flag
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u16(
+            RAM_START_ADDRESS,
+            SbcRegisterT1::opcode(
+                &rp2350.cortex_m33.registers.r0,
+                &rp2350.cortex_m33.registers.r3,
+            ),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r0.set(0);
+        rp2350.cortex_m33.registers.r3.set(0x80000000);
+        rp2350.cortex_m33.xpsr.apsr.set_c(true);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r0.get(), 0x80000000);
+        assert!(rp2350.cortex_m33.xpsr.apsr.n());
+        assert!(!rp2350.cortex_m33.xpsr.apsr.z());
+        assert!(!rp2350.cortex_m33.xpsr.apsr.c());
+        assert!(rp2350.cortex_m33.xpsr.apsr.v());
+    }
+}
@@ -0,0 +1,41 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::cortex_m33::opcodes::*;
+    use rp2350_sim::cortex_m33::registers::Register;
+    use rp2350_sim::{RAM_START_ADDRESS, RP2350};
+
+    #[test]
+    fn sdiv() {
+        // should execute a `sdiv r0, r1, r2` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u32(
+            RAM_START_ADDRESS,
+            SdivT1::opcode(&rp2350.cortex_m33.registers.r0, &rp2350.cortex_m33.registers.r1, &rp2350.cortex_m33.registers.r2),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r1.set((-7i32) as u32);
+        rp2350.cortex_m33.registers.r2.set(2);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r0.get(), (-3i32) as u32);
+    }
+
+    #[test]
+    fn sdiv_by_zero() {
+        // should execute a `sdiv r0, r1, r2` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u32(
+            RAM_START_ADDRESS,
+            SdivT1::opcode(&rp2350.cortex_m33.registers.r0, &rp2350.cortex_m33.registers.r1, &rp2350.cortex_m33.registers.r2),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r0.set(0x1234);
+        rp2350.cortex_m33.registers.r1.set(7);
+        rp2350.cortex_m33.registers.r2.set(0);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r0.get(), 0);
+    }
+}
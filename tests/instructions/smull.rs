@@ -0,0 +1,24 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::cortex_m33::opcodes::*;
+    use rp2350_sim::cortex_m33::registers::Register;
+    use rp2350_sim::{RAM_START_ADDRESS, RP2350};
+
+    #[test]
+    fn smull() {
+        // should execute a `smull r0, r1, r2, r3` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u32(
+            RAM_START_ADDRESS,
+            SmullT1::opcode(&rp2350.cortex_m33.registers.r0, &rp2350.cortex_m33.registers.r1, &rp2350.cortex_m33.registers.r2, &rp2350.cortex_m33.registers.r3),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r2.set((-2i32) as u32);
+        rp2350.cortex_m33.registers.r3.set(3);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r0.get(), 0xfffffffa);
+        assert_eq!(rp2350.cortex_m33.registers.r1.get(), 0xffffffff);
+    }
+}
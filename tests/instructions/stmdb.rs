@@ -0,0 +1,28 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::cortex_m33::opcodes::*;
+    use rp2350_sim::cortex_m33::registers::Register;
+    use rp2350_sim::{registers, RAM_START_ADDRESS, RP2350};
+
+    #[test]
+    fn stmdb_t1() {
+        // should execute a `stmdb sp!, {r4, r5, lr}` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u32(
+            RAM_START_ADDRESS,
+            StmdbT1::opcode(true, &rp2350.cortex_m33.registers.sp, registers![rp2350.cortex_m33.registers.r4, rp2350.cortex_m33.registers.r5, rp2350.cortex_m33.registers.lr]),
+        ).unwrap();
+        rp2350.cortex_m33.registers.sp.set(RAM_START_ADDRESS + 0x100);
+        rp2350.cortex_m33.registers.r4.set(4);
+        rp2350.cortex_m33.registers.r5.set(5);
+        rp2350.cortex_m33.registers.lr.set(0x10000001);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.memory.read_u32(RAM_START_ADDRESS + 0xf4).unwrap(), 4);
+        assert_eq!(rp2350.cortex_m33.memory.read_u32(RAM_START_ADDRESS + 0xf8).unwrap(), 5);
+        assert_eq!(rp2350.cortex_m33.memory.read_u32(RAM_START_ADDRESS + 0xfc).unwrap(), 0x10000001);
+        assert_eq!(rp2350.cortex_m33.registers.sp.get(), RAM_START_ADDRESS + 0xf4);
+    }
+}
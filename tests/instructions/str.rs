@@ -0,0 +1,128 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::cortex_m33::exception::Exception;
+    use rp2350_sim::cortex_m33::fault::{CFSR_BFARVALID, CFSR_PRECISERR, HFSR_FORCED, SHCSR_BUSFAULTENA};
+    use rp2350_sim::cortex_m33::opcodes::*;
+    use rp2350_sim::cortex_m33::registers::Register;
+    use rp2350_sim::{FLASH_START_ADDRESS, RAM_START_ADDRESS, RP2350};
+
+    #[test]
+    fn str_immediate() {
+        // should execute a `str r6, [r4, #20]` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u16(
+            RAM_START_ADDRESS,
+            StrImmediateT1::opcode(
+                &rp2350.cortex_m33.registers.r6,
+                &rp2350.cortex_m33.registers.r4,
+                20,
+            ),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r4.set(0x20000100);
+        rp2350.cortex_m33.registers.r6.set(0xf00d);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.memory.read_u32(0x20000114).unwrap(), 0xf00d);
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), 0x20000002);
+    }
+
+    #[test]
+    fn str_sp_plus_immediate() {
+        // should execute a `str r0, [sp, #4]` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u16(
+            RAM_START_ADDRESS,
+            StrImmediateT2::opcode(&rp2350.cortex_m33.registers.r0, 4),
+        ).unwrap();
+        rp2350.cortex_m33.registers.sp.set(0x20000100);
+        rp2350.cortex_m33.registers.r0.set(0x12345678);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.memory.read_u32(0x20000104).unwrap(), 0x12345678);
+    }
+
+    #[test]
+    fn str_register() {
+        // should execute a `str r1, [r2, r3]` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u16(
+            RAM_START_ADDRESS,
+            StrRegisterT1::opcode(
+                &rp2350.cortex_m33.registers.r1,
+                &rp2350.cortex_m33.registers.r2,
+                &rp2350.cortex_m33.registers.r3,
+            ),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r1.set(0xf00dd00d);
+        rp2350.cortex_m33.registers.r2.set(0x20000100);
+        rp2350.cortex_m33.registers.r3.set(0x8);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.memory.read_u32(0x20000108).unwrap(), 0xf00dd00d);
+    }
+
+    #[test]
+    fn str_immediate_t4_pre_index() {
+        // should execute a `str r0, [sp, #-4]!` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u32(
+            RAM_START_ADDRESS,
+            StrImmediateT4::opcode(&rp2350.cortex_m33.registers.r0, &rp2350.cortex_m33.registers.sp, 4, true, false, true),
+        ).unwrap();
+        rp2350.cortex_m33.registers.sp.set(RAM_START_ADDRESS + 0x100);
+        rp2350.cortex_m33.registers.r0.set(0x12345678);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.memory.read_u32(RAM_START_ADDRESS + 0xfc).unwrap(), 0x12345678);
+        assert_eq!(rp2350.cortex_m33.registers.sp.get(), RAM_START_ADDRESS + 0xfc);
+    }
+
+    #[test]
+    fn str_to_flash_raises_bus_fault() {
+        // should fault on a `str r0, [r1, #-4]!` instruction that targets read only flash
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u32(
+            RAM_START_ADDRESS,
+            StrImmediateT4::opcode(&rp2350.cortex_m33.registers.r0, &rp2350.cortex_m33.registers.r1, 4, true, false, true),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r1.set(FLASH_START_ADDRESS + 0x104);
+        rp2350.cortex_m33.registers.r0.set(0x12345678);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), RAM_START_ADDRESS);
+        assert_eq!(rp2350.cortex_m33.registers.r1.get(), FLASH_START_ADDRESS + 0x104);
+        assert_eq!(rp2350.cortex_m33.fault_status.cfsr, CFSR_PRECISERR | CFSR_BFARVALID);
+        assert_eq!(rp2350.cortex_m33.fault_status.bfar, FLASH_START_ADDRESS + 0x100);
+        assert_eq!(rp2350.cortex_m33.fault_status.hfsr, HFSR_FORCED);
+        assert!(rp2350.cortex_m33.exceptions.is_pending(Exception::HardFault));
+    }
+
+    #[test]
+    fn str_unmapped_raises_bus_fault() {
+        // should fault on a `str r0, [r1, #0]` instruction with BusFault enabled
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+        rp2350.cortex_m33.shcsr |= SHCSR_BUSFAULTENA;
+
+        rp2350.cortex_m33.memory.write_u16(
+            RAM_START_ADDRESS,
+            StrImmediateT1::opcode(&rp2350.cortex_m33.registers.r0, &rp2350.cortex_m33.registers.r1, 0),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r1.set(0x30000000);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.fault_status.bfar, 0x30000000);
+        assert_eq!(rp2350.cortex_m33.fault_status.hfsr, 0);
+        assert!(rp2350.cortex_m33.exceptions.is_pending(Exception::BusFault));
+    }
+}
@@ -0,0 +1,68 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::cortex_m33::opcodes::*;
+    use rp2350_sim::cortex_m33::registers::Register;
+    use rp2350_sim::{RAM_START_ADDRESS, RP2350};
+
+    #[test]
+    fn strb_immediate() {
+        // should execute a `strb r6, [r4, #1]` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u16(
+            RAM_START_ADDRESS,
+            StrbImmediateT1::opcode(
+                &rp2350.cortex_m33.registers.r6,
+                &rp2350.cortex_m33.registers.r4,
+                1,
+            ),
+        ).unwrap();
+        rp2350.cortex_m33.memory.write_u32(0x20000100, 0xaabbccdd).unwrap();
+        rp2350.cortex_m33.registers.r4.set(0x20000100);
+        rp2350.cortex_m33.registers.r6.set(0x55);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.memory.read_u32(0x20000100).unwrap(), 0xaabb55dd);
+    }
+
+    #[test]
+    fn strb_register() {
+        // should execute a `strb r6, [r4, r5]` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u16(
+            RAM_START_ADDRESS,
+            StrbRegisterT1::opcode(
+                &rp2350.cortex_m33.registers.r6,
+                &rp2350.cortex_m33.registers.r4,
+                &rp2350.cortex_m33.registers.r5,
+            ),
+        ).unwrap();
+        rp2350.cortex_m33.memory.write_u32(0x20000100, 0xaabbccdd).unwrap();
+        rp2350.cortex_m33.registers.r4.set(0x20000100);
+        rp2350.cortex_m33.registers.r5.set(3);
+        rp2350.cortex_m33.registers.r6.set(0x1255);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.memory.read_u32(0x20000100).unwrap(), 0x55bbccdd);
+    }
+
+    #[test]
+    fn strb_immediate_t2() {
+        // should execute a `strb.w r0, [r1, #0x123]` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u32(
+            RAM_START_ADDRESS,
+            StrbImmediateT2::opcode(&rp2350.cortex_m33.registers.r0, &rp2350.cortex_m33.registers.r1, 0x123),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r1.set(RAM_START_ADDRESS);
+        rp2350.cortex_m33.registers.r0.set(0x123456ab);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.memory.read(RAM_START_ADDRESS + 0x123).unwrap(), 0xab);
+    }
+}
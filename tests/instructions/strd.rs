@@ -0,0 +1,26 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::cortex_m33::opcodes::*;
+    use rp2350_sim::cortex_m33::registers::Register;
+    use rp2350_sim::{RAM_START_ADDRESS, RP2350};
+
+    #[test]
+    fn strd_immediate_pre_index() {
+        // should execute a `strd r0, r1, [r2, #-8]!` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u32(
+            RAM_START_ADDRESS,
+            StrdImmediateT1::opcode(&rp2350.cortex_m33.registers.r0, &rp2350.cortex_m33.registers.r1, &rp2350.cortex_m33.registers.r2, 8, true, false, true),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r2.set(RAM_START_ADDRESS + 0x100);
+        rp2350.cortex_m33.registers.r0.set(0x11111111);
+        rp2350.cortex_m33.registers.r1.set(0x22222222);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.memory.read_u32(RAM_START_ADDRESS + 0xf8).unwrap(), 0x11111111);
+        assert_eq!(rp2350.cortex_m33.memory.read_u32(RAM_START_ADDRESS + 0xfc).unwrap(), 0x22222222);
+        assert_eq!(rp2350.cortex_m33.registers.r2.get(), RAM_START_ADDRESS + 0xf8);
+    }
+}
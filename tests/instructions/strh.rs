@@ -0,0 +1,51 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::cortex_m33::opcodes::*;
+    use rp2350_sim::cortex_m33::registers::Register;
+    use rp2350_sim::{RAM_START_ADDRESS, RP2350};
+
+    #[test]
+    fn strh_immediate() {
+        // should execute a `strh r1, [r3, #2]` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u16(
+            RAM_START_ADDRESS,
+            StrhImmediateT1::opcode(
+                &rp2350.cortex_m33.registers.r1,
+                &rp2350.cortex_m33.registers.r3,
+                2,
+            ),
+        ).unwrap();
+        rp2350.cortex_m33.memory.write_u32(0x20000100, 0xaabbccdd).unwrap();
+        rp2350.cortex_m33.registers.r1.set(0x1234);
+        rp2350.cortex_m33.registers.r3.set(0x20000100);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.memory.read_u32(0x20000100).unwrap(), 0x1234ccdd);
+    }
+
+    #[test]
+    fn strh_register() {
+        // should execute a `strh r1, [r3, r2]` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u16(
+            RAM_START_ADDRESS,
+            StrhRegisterT1::opcode(
+                &rp2350.cortex_m33.registers.r1,
+                &rp2350.cortex_m33.registers.r3,
+                &rp2350.cortex_m33.registers.r2,
+            ),
+        ).unwrap();
+        rp2350.cortex_m33.memory.write_u32(0x20000100, 0xaabbccdd).unwrap();
+        rp2350.cortex_m33.registers.r1.set(0x44771234);
+        rp2350.cortex_m33.registers.r2.set(0);
+        rp2350.cortex_m33.registers.r3.set(0x20000100);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.memory.read_u32(0x20000100).unwrap(), 0xaabb1234);
+    }
+}
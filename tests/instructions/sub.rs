@@ -10,7 +10,7 @@ mod tests {
         let mut rp2350: RP2350 = RP2350::new();
         rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
 
-        rp2350.cortex_m33.memory.write_u16(RAM_START_ADDRESS, SubSpMinusImmediateT1::opcode(0x10));
+        rp2350.cortex_m33.memory.write_u16(RAM_START_ADDRESS, SubSpMinusImmediateT1::opcode(0x10)).unwrap();
 
         rp2350.cortex_m33.registers.sp.set(0x10000040);
 
@@ -18,4 +18,91 @@ mod tests {
 
         assert_eq!(rp2350.cortex_m33.registers.sp.get(), 0x10000030);
     }
+
+    #[test]
+    fn subs_t1() {
+        // should execute a `subs r1, r2, #3` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u16(
+            RAM_START_ADDRESS,
+            SubT1::opcode(
+                &rp2350.cortex_m33.registers.r1,
+                &rp2350.cortex_m33.registers.r2,
+                3,
+            ),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r2.set(5);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r1.get(), 2);
+        assert!(!rp2350.cortex_m33.xpsr.apsr.n());
+        assert!(!rp2350.cortex_m33.xpsr.apsr.z());
+        assert!(rp2350.cortex_m33.xpsr.apsr.c());
+        assert!(!rp2350.cortex_m33.xpsr.apsr.v());
+    }
+
+    #[test]
+    fn subs_t2() {
+        // should execute a `subs r1, #1` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u16(
+            RAM_START_ADDRESS,
+            SubT2::opcode(&rp2350.cortex_m33.registers.r1, 1),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r1.set(0);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r1.get(), 0xffffffff);
+        assert!(rp2350.cortex_m33.xpsr.apsr.n());
+        assert!(!rp2350.cortex_m33.xpsr.apsr.z());
+        assert!(!rp2350.cortex_m33.xpsr.apsr.c());
+        assert!(!rp2350.cortex_m33.xpsr.apsr.v());
+    }
+
+    #[test]
+    fn subs_register() {
+        // should execute a `subs r3, r3, r2` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u16(
+            RAM_START_ADDRESS,
+            SubRegisterT1::opcode(
+                &rp2350.cortex_m33.registers.r3,
+                &rp2350.cortex_m33.registers.r3,
+                &rp2350.cortex_m33.registers.r2,
+            ),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r2.set(0x80000000);
+        rp2350.cortex_m33.registers.r3.set(0x80000000);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r3.get(), 0);
+        assert!(!rp2350.cortex_m33.xpsr.apsr.n());
+        assert!(rp2350.cortex_m33.xpsr.apsr.z());
+        assert!(rp2350.cortex_m33.xpsr.apsr.c());
+        assert!(!rp2350.cortex_m33.xpsr.apsr.v());
+    }
+
+    #[test]
+    fn subs_immediate_t3() {
+        // should execute a `subs.w r2, r2, #1` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u32(
+            RAM_START_ADDRESS,
+            SubImmediateT3::opcode(true, &rp2350.cortex_m33.registers.r2, &rp2350.cortex_m33.registers.r2, 0x001),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r2.set(1);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r2.get(), 0);
+        assert!(rp2350.cortex_m33.xpsr.apsr.z());
+        assert!(rp2350.cortex_m33.xpsr.apsr.c());
+    }
 }
@@ -0,0 +1,25 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::cortex_m33::opcodes::*;
+    use rp2350_sim::cortex_m33::registers::Register;
+    use rp2350_sim::{RAM_START_ADDRESS, RP2350};
+
+    #[test]
+    fn sxtb() {
+        // should execute a `sxtb r2, r2` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u16(
+            RAM_START_ADDRESS,
+            SxtbT1::opcode(
+                &rp2350.cortex_m33.registers.r2,
+                &rp2350.cortex_m33.registers.r2,
+            ),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r2.set(0x22446688);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r2.get(), 0xffffff88);
+    }
+}
@@ -0,0 +1,24 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::cortex_m33::opcodes::*;
+    use rp2350_sim::cortex_m33::registers::Register;
+    use rp2350_sim::{RAM_START_ADDRESS, RP2350};
+
+    #[test]
+    fn tbh() {
+        // should execute a `tbh [r1, r0, lsl #1]` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u32(
+            RAM_START_ADDRESS,
+            TbhT1::opcode(&rp2350.cortex_m33.registers.r1, &rp2350.cortex_m33.registers.r0),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r0.set(1);
+        rp2350.cortex_m33.registers.r1.set(RAM_START_ADDRESS + 0x100);
+        rp2350.cortex_m33.memory.write_u32(RAM_START_ADDRESS + 0x100, 0x00200000).unwrap();
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), RAM_START_ADDRESS + 4 + 0x40);
+    }
+}
@@ -0,0 +1,22 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::cortex_m33::opcodes::*;
+    use rp2350_sim::cortex_m33::registers::Register;
+    use rp2350_sim::{RAM_START_ADDRESS, RP2350};
+
+    #[test]
+    fn ubfx() {
+        // should execute a `ubfx r2, r3, #4, #8` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u32(
+            RAM_START_ADDRESS,
+            UbfxT1::opcode(&rp2350.cortex_m33.registers.r2, &rp2350.cortex_m33.registers.r3, 4, 8),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r3.set(0x12345678);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r2.get(), 0x67);
+    }
+}
@@ -0,0 +1,69 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::cortex_m33::exception::Exception;
+    use rp2350_sim::cortex_m33::fault::{CFSR_UNDEFINSTR, HFSR_FORCED, SHCSR_USGFAULTENA};
+    use rp2350_sim::cortex_m33::opcodes::*;
+    use rp2350_sim::cortex_m33::registers::Register;
+    use rp2350_sim::{RAM_START_ADDRESS, RP2350};
+
+    #[test]
+    fn udf_escalates_to_hard_fault() {
+        // should execute a `udf #0x12` instruction with UsageFault disabled
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u16(RAM_START_ADDRESS, UdfT1::opcode(0x12)).unwrap();
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), RAM_START_ADDRESS);
+        assert_eq!(rp2350.cortex_m33.fault_status.cfsr, CFSR_UNDEFINSTR);
+        assert_eq!(rp2350.cortex_m33.fault_status.hfsr, HFSR_FORCED);
+        assert!(rp2350.cortex_m33.exceptions.is_pending(Exception::HardFault));
+        assert!(!rp2350.cortex_m33.exceptions.is_pending(Exception::UsageFault));
+    }
+
+    #[test]
+    fn udf_w_raises_usage_fault() {
+        // should execute a `udf.w #0x1234` instruction with UsageFault enabled
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+        rp2350.cortex_m33.shcsr |= SHCSR_USGFAULTENA;
+
+        rp2350.cortex_m33.memory.write_u32(RAM_START_ADDRESS, UdfT2::opcode(0x1234)).unwrap();
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), RAM_START_ADDRESS);
+        assert_eq!(rp2350.cortex_m33.fault_status.cfsr, CFSR_UNDEFINSTR);
+        assert_eq!(rp2350.cortex_m33.fault_status.hfsr, 0);
+        assert!(rp2350.cortex_m33.exceptions.is_pending(Exception::UsageFault));
+    }
+
+    #[test]
+    fn unknown_encoding_raises_usage_fault() {
+        // should treat an unallocated 32-bit encoding as undefined
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+        rp2350.cortex_m33.shcsr |= SHCSR_USGFAULTENA;
+
+        rp2350.cortex_m33.memory.write_u32(RAM_START_ADDRESS, 0xffff_ffff).unwrap();
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), RAM_START_ADDRESS);
+        assert_eq!(rp2350.cortex_m33.fault_status.cfsr, CFSR_UNDEFINSTR);
+        assert!(rp2350.cortex_m33.exceptions.is_pending(Exception::UsageFault));
+    }
+
+    #[test]
+    fn fault_in_hard_fault_locks_up() {
+        // should lock up when a fault is raised while HardFault is active
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+        rp2350.cortex_m33.exceptions.active.insert(Exception::HardFault.number(), Exception::HardFault);
+
+        rp2350.cortex_m33.memory.write_u16(RAM_START_ADDRESS, UdfT1::opcode(0)).unwrap();
+        rp2350.execute_instruction();
+
+        assert!(rp2350.cortex_m33.locked_up);
+        assert!(!rp2350.cortex_m33.exceptions.is_pending(Exception::HardFault));
+    }
+}
@@ -0,0 +1,67 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::cortex_m33::exception::Exception;
+    use rp2350_sim::cortex_m33::fault::{CFSR_DIVBYZERO, SHCSR_USGFAULTENA};
+    use rp2350_sim::cortex_m33::opcodes::*;
+    use rp2350_sim::cortex_m33::scb::CCR_DIV_0_TRP;
+    use rp2350_sim::cortex_m33::registers::Register;
+    use rp2350_sim::{RAM_START_ADDRESS, RP2350};
+
+    #[test]
+    fn udiv() {
+        // should execute a `udiv r0, r1, r2` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u32(
+            RAM_START_ADDRESS,
+            UdivT1::opcode(&rp2350.cortex_m33.registers.r0, &rp2350.cortex_m33.registers.r1, &rp2350.cortex_m33.registers.r2),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r1.set(0xfffffffe);
+        rp2350.cortex_m33.registers.r2.set(2);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r0.get(), 0x7fffffff);
+    }
+
+    #[test]
+    fn udiv_by_zero() {
+        // should execute a `udiv r0, r1, r2` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u32(
+            RAM_START_ADDRESS,
+            UdivT1::opcode(&rp2350.cortex_m33.registers.r0, &rp2350.cortex_m33.registers.r1, &rp2350.cortex_m33.registers.r2),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r0.set(0x1234);
+        rp2350.cortex_m33.registers.r1.set(7);
+        rp2350.cortex_m33.registers.r2.set(0);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r0.get(), 0);
+    }
+
+    #[test]
+    fn udiv_by_zero_traps() {
+        // should fault on a `udiv r0, r1, r2` instruction by zero with CCR.DIV_0_TRP set
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+        rp2350.cortex_m33.ccr |= CCR_DIV_0_TRP;
+        rp2350.cortex_m33.shcsr |= SHCSR_USGFAULTENA;
+
+        rp2350.cortex_m33.memory.write_u32(
+            RAM_START_ADDRESS,
+            UdivT1::opcode(&rp2350.cortex_m33.registers.r0, &rp2350.cortex_m33.registers.r1, &rp2350.cortex_m33.registers.r2),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r0.set(0x1234);
+        rp2350.cortex_m33.registers.r1.set(7);
+        rp2350.cortex_m33.registers.r2.set(0);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r0.get(), 0x1234);
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), RAM_START_ADDRESS);
+        assert_eq!(rp2350.cortex_m33.fault_status.cfsr, CFSR_DIVBYZERO);
+        assert!(rp2350.cortex_m33.exceptions.is_pending(Exception::UsageFault));
+    }
+}
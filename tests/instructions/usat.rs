@@ -0,0 +1,23 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::cortex_m33::opcodes::*;
+    use rp2350_sim::cortex_m33::registers::Register;
+    use rp2350_sim::{RAM_START_ADDRESS, RP2350};
+
+    #[test]
+    fn usat() {
+        // should execute a `usat r0, #8, r1` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u32(
+            RAM_START_ADDRESS,
+            UsatT1::opcode(&rp2350.cortex_m33.registers.r0, 8, &rp2350.cortex_m33.registers.r1),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r1.set((-5i32) as u32);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r0.get(), 0);
+        assert!(rp2350.cortex_m33.xpsr.apsr.q());
+    }
+}
@@ -16,7 +16,7 @@ mod tests {
                 &rp2350.cortex_m33.registers.r5,
                 &rp2350.cortex_m33.registers.r3,
             ),
-        );
+        ).unwrap();
 
         rp2350.cortex_m33.registers.r3.set(0x12345678);
 
@@ -24,4 +24,20 @@ mod tests {
 
         assert_eq!(rp2350.cortex_m33.registers.r5.get(), 0x78);
     }
+
+    #[test]
+    fn uxtb_t2() {
+        // should execute a `uxtb.w r0, r1, ror #8` instruction
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+
+        rp2350.cortex_m33.memory.write_u32(
+            RAM_START_ADDRESS,
+            UxtbT2::opcode(&rp2350.cortex_m33.registers.r0, &rp2350.cortex_m33.registers.r1, 8),
+        ).unwrap();
+        rp2350.cortex_m33.registers.r1.set(0x11223344);
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.r0.get(), 0x33);
+    }
 }
@@ -16,7 +16,7 @@ mod tests {
                 &rp2350.cortex_m33.registers.r3,
                 &rp2350.cortex_m33.registers.r1,
             ),
-        );
+        ).unwrap();
 
         rp2350.cortex_m33.registers.r1.set(0x12345678);
 
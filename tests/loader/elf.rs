@@ -0,0 +1,254 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::cortex_m33::registers::Register;
+    use rp2350_sim::loader::elf::{self, ElfError, SymbolKind};
+    use rp2350_sim::{BusError, FLASH_START_ADDRESS, RAM_START_ADDRESS, RP2350};
+
+    const PT_LOAD: u32 = 1;
+    const PT_NOTE: u32 = 4;
+    const STT_OBJECT: u8 = 1;
+    const STT_FUNC: u8 = 2;
+    const STT_SECTION: u8 = 3;
+    const STB_GLOBAL: u8 = 1 << 4;
+
+    struct TestSegment {
+        kind: u32,
+        virtual_address: u32,
+        physical_address: u32,
+        data: Vec<u8>,
+        memory_size: u32,
+    }
+
+    struct TestSymbol {
+        name: &'static str,
+        value: u32,
+        size: u32,
+        info: u8,
+        section: u16,
+    }
+
+    fn put_u16(bytes: &mut Vec<u8>, value: u16) {
+        bytes.extend(value.to_le_bytes());
+    }
+
+    fn put_u32(bytes: &mut Vec<u8>, value: u32) {
+        bytes.extend(value.to_le_bytes());
+    }
+
+    /// Builds a 32-bit Arm ELF file with a program header per segment and a symbol table section
+    fn build_elf(entry: u32, segments: &[TestSegment], symbols: &[TestSymbol]) -> Vec<u8> {
+        let program_headers = 52;
+        let mut data_offset = program_headers + 32 * segments.len();
+
+        let mut strings = vec![0u8];
+        let mut symbol_table = vec![0u8; 16];
+        for symbol in symbols {
+            put_u32(&mut symbol_table, strings.len() as u32);
+            strings.extend(symbol.name.as_bytes());
+            strings.push(0);
+            put_u32(&mut symbol_table, symbol.value);
+            put_u32(&mut symbol_table, symbol.size);
+            symbol_table.push(symbol.info);
+            symbol_table.push(0);
+            put_u16(&mut symbol_table, symbol.section);
+        }
+
+        let segment_data: usize = segments.iter().map(|segment| segment.data.len()).sum();
+        let symbol_table_offset = data_offset + segment_data;
+        let strings_offset = symbol_table_offset + symbol_table.len();
+        let section_headers = strings_offset + strings.len();
+
+        let mut bytes = vec![0x7f, b'E', b'L', b'F', 1, 1, 1];
+        bytes.resize(16, 0);
+        put_u16(&mut bytes, 2); // ET_EXEC
+        put_u16(&mut bytes, 40); // EM_ARM
+        put_u32(&mut bytes, 1);
+        put_u32(&mut bytes, entry);
+        put_u32(&mut bytes, program_headers as u32);
+        put_u32(&mut bytes, section_headers as u32);
+        put_u32(&mut bytes, 0x05000400);
+        put_u16(&mut bytes, 52);
+        put_u16(&mut bytes, 32);
+        put_u16(&mut bytes, segments.len() as u16);
+        put_u16(&mut bytes, 40);
+        put_u16(&mut bytes, 3);
+        put_u16(&mut bytes, 2);
+
+        for segment in segments {
+            put_u32(&mut bytes, segment.kind);
+            put_u32(&mut bytes, data_offset as u32);
+            put_u32(&mut bytes, segment.virtual_address);
+            put_u32(&mut bytes, segment.physical_address);
+            put_u32(&mut bytes, segment.data.len() as u32);
+            put_u32(&mut bytes, segment.memory_size);
+            put_u32(&mut bytes, 0);
+            put_u32(&mut bytes, 4);
+            data_offset += segment.data.len();
+        }
+        for segment in segments {
+            bytes.extend(&segment.data);
+        }
+        bytes.extend(&symbol_table);
+        bytes.extend(&strings);
+
+        // Null, .symtab and .strtab section headers
+        bytes.extend([0; 40]);
+        for (kind, offset, size, link) in [
+            (2, symbol_table_offset, symbol_table.len(), 2),
+            (3, strings_offset, strings.len(), 0),
+        ] {
+            put_u32(&mut bytes, 0);
+            put_u32(&mut bytes, kind);
+            put_u32(&mut bytes, 0);
+            put_u32(&mut bytes, 0);
+            put_u32(&mut bytes, offset as u32);
+            put_u32(&mut bytes, size as u32);
+            put_u32(&mut bytes, link);
+            put_u32(&mut bytes, 0);
+            put_u32(&mut bytes, 4);
+            put_u32(&mut bytes, if kind == 2 { 16 } else { 0 });
+        }
+
+        bytes
+    }
+
+    /// A vector table and reset handler in flash, and .data with its load address in flash and .bss after it
+    fn test_image() -> Vec<u8> {
+        let reset_handler = FLASH_START_ADDRESS + 0x8;
+        let mut text = Vec::new();
+        text.extend((RAM_START_ADDRESS + 0x1000).to_le_bytes());
+        text.extend((reset_handler | 1).to_le_bytes());
+        text.extend([0x2a, 0x20, 0x00, 0xbf]); // movs r0, #42; nop
+
+        build_elf(
+            reset_handler | 1,
+            &[
+                TestSegment {
+                    kind: PT_LOAD,
+                    virtual_address: FLASH_START_ADDRESS,
+                    physical_address: FLASH_START_ADDRESS,
+                    data: text,
+                    memory_size: 12,
+                },
+                TestSegment {
+                    kind: PT_NOTE,
+                    virtual_address: 0,
+                    physical_address: 0,
+                    data: vec![0xaa; 4],
+                    memory_size: 4,
+                },
+                TestSegment {
+                    kind: PT_LOAD,
+                    virtual_address: RAM_START_ADDRESS,
+                    physical_address: FLASH_START_ADDRESS + 0x100,
+                    data: vec![0x11, 0x22, 0x33, 0x44],
+                    memory_size: 8,
+                },
+            ],
+            &[
+                TestSymbol { name: "reset_handler", value: reset_handler | 1, size: 4, info: STB_GLOBAL | STT_FUNC, section: 1 },
+                TestSymbol { name: "counter", value: RAM_START_ADDRESS, size: 4, info: STB_GLOBAL | STT_OBJECT, section: 2 },
+                TestSymbol { name: "$t", value: reset_handler, size: 0, info: 0, section: 1 },
+                TestSymbol { name: ".text", value: FLASH_START_ADDRESS, size: 0, info: STT_SECTION, section: 1 },
+                TestSymbol { name: "gpio_put", value: 0, size: 0, info: STB_GLOBAL | STT_FUNC, section: 0 },
+            ],
+        )
+    }
+
+    #[test]
+    fn load_segments_at_load_address() {
+        // should copy each PT_LOAD segment to its physical address, including .data destined for SRAM
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.memory_mut().program(FLASH_START_ADDRESS + 0x104, &[0x55; 4]).unwrap();
+        let entry = rp2350.load_elf(&test_image()).unwrap();
+
+        assert_eq!(entry, FLASH_START_ADDRESS + 0x9);
+        assert_eq!(rp2350.memory().flash[8..12], [0x2a, 0x20, 0x00, 0xbf]);
+        assert_eq!(rp2350.memory().flash[0x100..0x108], [0x11, 0x22, 0x33, 0x44, 0, 0, 0, 0]);
+        assert_eq!(rp2350.memory().sram[..4], [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn load_then_run() {
+        // should boot from the vector table the image placed in flash
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.load_elf(&test_image()).unwrap();
+        rp2350.reset();
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.cortex_m33.registers.sp.get(), RAM_START_ADDRESS + 0x1000);
+        assert_eq!(rp2350.cortex_m33.registers.r0.get(), 42);
+    }
+
+    #[test]
+    fn symbol_table() {
+        // should keep named functions and objects, dropping mapping, section and undefined symbols
+        let file = elf::parse(&test_image()).unwrap();
+        let names: Vec<&str> = file.symbols.symbols().iter().map(|symbol| symbol.name.as_str()).collect();
+
+        assert_eq!(names, ["reset_handler", "counter"]);
+
+        let reset_handler = file.symbols.get("reset_handler").unwrap();
+        assert_eq!(reset_handler.address, FLASH_START_ADDRESS + 0x8);
+        assert_eq!(reset_handler.kind, SymbolKind::Function);
+    }
+
+    #[test]
+    fn resolve_address() {
+        // should resolve an address inside a function to that function
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.load_elf(&test_image()).unwrap();
+
+        assert_eq!(rp2350.symbols.resolve(FLASH_START_ADDRESS + 0xa).unwrap().name, "reset_handler");
+        assert_eq!(rp2350.symbols.resolve(RAM_START_ADDRESS + 3).unwrap().name, "counter");
+        assert!(rp2350.symbols.resolve(FLASH_START_ADDRESS + 0xc).is_none());
+    }
+
+    #[test]
+    fn reject_other_files() {
+        // should reject files that are not 32-bit little endian Arm ELF images
+        let mut image = test_image();
+        assert_eq!(elf::parse(b"\x7fELE"), Err(ElfError::NotElf));
+        assert_eq!(elf::parse(&image[..40]), Err(ElfError::Truncated));
+
+        image[18] = 62; // EM_X86_64
+        assert!(matches!(elf::parse(&image), Err(ElfError::Unsupported(_))));
+    }
+
+    #[test]
+    fn load_outside_programmable_memory() {
+        // should fail for a segment with a load address nothing can be programmed at
+        let mut rp2350: RP2350 = RP2350::new();
+        let image = build_elf(
+            0,
+            &[TestSegment { kind: PT_LOAD, virtual_address: 0, physical_address: 0, data: vec![0; 4], memory_size: 4 }],
+            &[],
+        );
+        let error = rp2350.load_elf(&image).unwrap_err();
+
+        assert_eq!(
+            error.downcast_ref::<ElfError>(),
+            Some(&ElfError::Program { address: 0, error: BusError::Unmapped })
+        );
+    }
+
+    #[test]
+    fn memory_size_past_memory() {
+        // should fail without writing anything for a segment whose memory size runs past the end of SRAM
+        let mut rp2350: RP2350 = RP2350::new();
+        let segment = TestSegment {
+            kind: PT_LOAD,
+            virtual_address: RAM_START_ADDRESS,
+            physical_address: RAM_START_ADDRESS,
+            data: vec![0x11; 4],
+            memory_size: u32::MAX,
+        };
+        let error = rp2350.load_elf(&build_elf(0, &[segment], &[])).unwrap_err();
+
+        assert_eq!(
+            error.downcast_ref::<ElfError>(),
+            Some(&ElfError::Program { address: RAM_START_ADDRESS, error: BusError::Unmapped })
+        );
+        assert_eq!(rp2350.memory().sram[0], 0);
+    }
+}
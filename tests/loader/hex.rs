@@ -0,0 +1,130 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::cortex_m33::registers::Register;
+    use rp2350_sim::loader::hex::{self, HexError, HexErrorKind};
+    use rp2350_sim::{BusError, FLASH_START_ADDRESS, RAM_START_ADDRESS, RP2350};
+
+    const BLINKY: &str = include_str!("../../programs/blinky.hex");
+
+    /// Builds a record with a correct checksum
+    fn record(record_type: u8, offset: u16, data: &[u8]) -> String {
+        let mut bytes = vec![data.len() as u8];
+        bytes.extend(offset.to_be_bytes());
+        bytes.push(record_type);
+        bytes.extend(data);
+        let checksum = bytes.iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte)).wrapping_neg();
+        bytes.push(checksum);
+
+        let digits: String = bytes.iter().map(|byte| format!("{:02X}", byte)).collect();
+        format!(":{}\n", digits)
+    }
+
+    fn error_of(source: &str) -> HexError {
+        hex::parse(source).unwrap_err()
+    }
+
+    #[test]
+    fn load_blinky_into_flash() {
+        // should place blinky in flash using its extended linear address record
+        let mut rp2350: RP2350 = RP2350::new();
+        let start_address = rp2350.load_hex(BLINKY).unwrap();
+
+        assert_eq!(start_address, Some(0x100001E9));
+        assert_eq!(rp2350.memory().flash[..4], [0x00, 0xB5, 0x32, 0x4B]);
+        assert_eq!(rp2350.memory().flash[0x21AC..0x21B0], [0x4D, 0x1A, 0x00, 0x10]);
+    }
+
+    #[test]
+    fn extended_linear_address() {
+        // should add the upper address to the record offsets that follow
+        let source = record(0x04, 0, &[0x20, 0x00]) + &record(0x00, 0x0010, &[1, 2, 3]) + &record(0x01, 0, &[]);
+        let file = hex::parse(&source).unwrap();
+
+        assert_eq!(file.data.len(), 1);
+        assert_eq!(file.data[0].address, RAM_START_ADDRESS + 0x10);
+        assert_eq!(file.data[0].bytes, [1, 2, 3]);
+        assert_eq!(file.data[0].line, 2);
+    }
+
+    #[test]
+    fn extended_segment_address() {
+        // should add the segment shifted by four to the record offsets that follow
+        let source = record(0x02, 0, &[0x12, 0x34]) + &record(0x00, 0x0010, &[1]);
+        let file = hex::parse(&source).unwrap();
+
+        assert_eq!(file.data[0].address, 0x12350);
+    }
+
+    #[test]
+    fn start_addresses() {
+        // should report CS:IP as a linear address, and start linear addresses as they are
+        let segment = hex::parse(&record(0x03, 0, &[0x10, 0x00, 0x01, 0x23])).unwrap();
+        let linear = hex::parse(&record(0x05, 0, &[0x10, 0x00, 0x01, 0x23])).unwrap();
+
+        assert_eq!(segment.start_address, Some(0x10123));
+        assert_eq!(linear.start_address, Some(0x10000123));
+    }
+
+    #[test]
+    fn stop_at_end_of_file() {
+        // should ignore anything after the end of file record
+        let source = record(0x00, 0, &[1]) + &record(0x01, 0, &[]) + "garbage\n";
+        let file = hex::parse(&source).unwrap();
+
+        assert_eq!(file.data.len(), 1);
+    }
+
+    #[test]
+    fn checksum_mismatch() {
+        // should reject a record whose checksum does not match, naming the line
+        let source = record(0x04, 0, &[0x10, 0x00]) + "\n:0400000001020304F1\n";
+
+        assert_eq!(
+            error_of(&source),
+            HexError {
+                line: 3,
+                kind: HexErrorKind::Checksum { expected: 0xf2, actual: 0xf1 },
+            }
+        );
+    }
+
+    #[test]
+    fn malformed_records() {
+        // should reject records that are not well formed
+        assert_eq!(error_of("0400000001020304F2").kind, HexErrorKind::MissingStartCode);
+        assert_eq!(error_of(":04000000010203G4F2").kind, HexErrorKind::InvalidDigits);
+        assert_eq!(error_of(":0400000001020304F").kind, HexErrorKind::InvalidDigits);
+        assert_eq!(error_of(":05000000010203F2").kind, HexErrorKind::LengthMismatch);
+        assert_eq!(error_of(&record(0x06, 0, &[])).kind, HexErrorKind::UnknownRecordType(0x06));
+        assert_eq!(error_of(&record(0x04, 0, &[0x10])).kind, HexErrorKind::InvalidRecord(0x04));
+    }
+
+    #[test]
+    fn load_outside_programmable_memory() {
+        // should fail with the line of the data record that misses memory
+        let mut rp2350: RP2350 = RP2350::new();
+        let source = record(0x00, 0, &[1]);
+        let error = rp2350.load_hex(&source).unwrap_err();
+
+        assert_eq!(
+            error.downcast_ref::<HexError>(),
+            Some(&HexError {
+                line: 1,
+                kind: HexErrorKind::Program { address: 0, error: BusError::Unmapped },
+            })
+        );
+    }
+
+    #[test]
+    fn load_then_reset() {
+        // should boot from the vectors of the loaded image
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.load_hex(BLINKY).unwrap();
+        rp2350.reset();
+
+        let vectors = &rp2350.memory().flash;
+        let reset_handler = u32::from_le_bytes(vectors[4..8].try_into().unwrap());
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), reset_handler & !1);
+        assert!(rp2350.cortex_m33.registers.pc.get() > FLASH_START_ADDRESS);
+    }
+}
@@ -0,0 +1,3 @@
+mod elf;
+mod hex;
+mod uf2;
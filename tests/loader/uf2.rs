@@ -0,0 +1,140 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::loader::uf2::{self, *};
+    use rp2350_sim::{BusError, FLASH_START_ADDRESS, RAM_START_ADDRESS, RP2350};
+
+    /// Builds a 512-byte block with a 256-byte payload, the way picotool and elf2uf2 lay them out
+    fn block(address: u32, number: u32, count: u32, family_id: u32, fill: u8) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        for word in [0x0A324655, 0x9E5D5157, FLAG_FAMILY_ID_PRESENT, address, 256, number, count, family_id] {
+            bytes.extend(u32::to_le_bytes(word));
+        }
+        bytes.extend([fill; 256]);
+        bytes.resize(508, 0);
+        bytes.extend(u32::to_le_bytes(0x0AB16F30));
+        bytes
+    }
+
+    fn image(blocks: &[Vec<u8>]) -> Vec<u8> {
+        blocks.concat()
+    }
+
+    #[test]
+    fn load_into_flash() {
+        // should write each payload at its target address
+        let mut rp2350: RP2350 = RP2350::new();
+        let file = image(&[
+            block(FLASH_START_ADDRESS, 0, 2, RP2350_ARM_S_FAMILY_ID, 0x11),
+            block(FLASH_START_ADDRESS + 0x100, 1, 2, RP2350_ARM_S_FAMILY_ID, 0x22),
+        ]);
+        let report = rp2350.load_uf2(&file, &ARM_SECURE_FAMILY_IDS).unwrap();
+
+        assert_eq!(report.blocks_written, 2);
+        assert!(report.is_complete());
+        assert_eq!(rp2350.memory().flash[..0x100], [0x11; 0x100]);
+        assert_eq!(rp2350.memory().flash[0x100..0x200], [0x22; 0x100]);
+        assert_eq!(rp2350.memory().flash[0x200], 0xff);
+    }
+
+    #[test]
+    fn filter_families() {
+        // should skip blocks for families that were not asked for
+        let mut rp2350: RP2350 = RP2350::new();
+        let file = image(&[
+            block(FLASH_START_ADDRESS, 0, 1, RP2350_RISCV_FAMILY_ID, 0x11),
+            block(FLASH_START_ADDRESS + 0x100, 0, 1, ABSOLUTE_FAMILY_ID, 0x22),
+            block(FLASH_START_ADDRESS + 0x200, 0, 1, RP2350_ARM_NS_FAMILY_ID, 0x33),
+        ]);
+        let report = rp2350.load_uf2(&file, &ARM_SECURE_FAMILY_IDS).unwrap();
+
+        assert_eq!(report.blocks_written, 1);
+        assert_eq!(report.blocks_skipped, 2);
+        assert_eq!(rp2350.memory().flash[0], 0xff);
+        assert_eq!(rp2350.memory().flash[0x100], 0x22);
+        assert_eq!(rp2350.memory().flash[0x200], 0xff);
+    }
+
+    #[test]
+    fn skip_not_main_flash() {
+        // should skip blocks flagged as not for main flash
+        let mut rp2350: RP2350 = RP2350::new();
+        let mut file = block(FLASH_START_ADDRESS, 0, 1, DATA_FAMILY_ID, 0x11);
+        file[8..12].copy_from_slice(&(FLAG_FAMILY_ID_PRESENT | FLAG_NOT_MAIN_FLASH).to_le_bytes());
+        let report = rp2350.load_uf2(&file, &ARM_SECURE_FAMILY_IDS).unwrap();
+
+        assert_eq!(report.blocks_written, 0);
+        assert_eq!(report.blocks_skipped, 1);
+        assert_eq!(rp2350.memory().flash[0], 0xff);
+    }
+
+    #[test]
+    fn report_missing_and_duplicated() {
+        // should list blocks that never appeared and blocks that appeared twice, per family
+        let mut rp2350: RP2350 = RP2350::new();
+        let file = image(&[
+            block(FLASH_START_ADDRESS, 0, 4, RP2350_ARM_S_FAMILY_ID, 0x11),
+            block(FLASH_START_ADDRESS + 0x200, 2, 4, RP2350_ARM_S_FAMILY_ID, 0x22),
+            block(FLASH_START_ADDRESS + 0x200, 2, 4, RP2350_ARM_S_FAMILY_ID, 0x33),
+            block(RAM_START_ADDRESS, 0, 1, ABSOLUTE_FAMILY_ID, 0x44),
+        ]);
+        let report = rp2350.load_uf2(&file, &ARM_SECURE_FAMILY_IDS).unwrap();
+        let arm_s = |number| BlockNumber { family_id: Some(RP2350_ARM_S_FAMILY_ID), number };
+
+        assert!(!report.is_complete());
+        assert_eq!(report.missing, [arm_s(1), arm_s(3)]);
+        assert_eq!(report.duplicated, [arm_s(2)]);
+        assert_eq!(rp2350.memory().flash[0x200], 0x33);
+        assert_eq!(rp2350.memory().sram[0], 0x44);
+    }
+
+    #[test]
+    fn reject_bad_blocks() {
+        // should reject blocks with bad magic, a short final block or an oversized payload
+        let good = block(FLASH_START_ADDRESS, 0, 1, DATA_FAMILY_ID, 0);
+
+        let mut bad_magic = image(&[good.clone(), good.clone()]);
+        bad_magic[512 + 508] ^= 1;
+        assert_eq!(uf2::parse(&bad_magic), Err(Uf2Error { block: 1, kind: Uf2ErrorKind::BadMagic }));
+
+        assert_eq!(uf2::parse(&good[..500]), Err(Uf2Error { block: 0, kind: Uf2ErrorKind::Truncated }));
+
+        let mut oversized = good.clone();
+        oversized[16..20].copy_from_slice(&477u32.to_le_bytes());
+        assert_eq!(uf2::parse(&oversized).unwrap_err().kind, Uf2ErrorKind::PayloadTooLarge(477));
+    }
+
+    #[test]
+    fn reject_bad_block_numbers() {
+        // should reject block numbers past the block count, and block counts larger than fit in flash
+        let past_count = block(FLASH_START_ADDRESS, 2, 2, DATA_FAMILY_ID, 0);
+        assert_eq!(
+            uf2::parse(&past_count).unwrap_err().kind,
+            Uf2ErrorKind::BlockNumberOutOfRange { number: 2, count: 2 }
+        );
+
+        let too_many = block(FLASH_START_ADDRESS, 0, u32::MAX, DATA_FAMILY_ID, 0);
+        assert_eq!(uf2::parse(&too_many).unwrap_err().kind, Uf2ErrorKind::TooManyBlocks(u32::MAX));
+
+        let largest = block(FLASH_START_ADDRESS, MAX_BLOCK_COUNT - 1, MAX_BLOCK_COUNT, DATA_FAMILY_ID, 0);
+        assert!(uf2::parse(&largest).is_ok());
+    }
+
+    #[test]
+    fn load_outside_programmable_memory() {
+        // should fail with the index of the block that misses memory
+        let mut rp2350: RP2350 = RP2350::new();
+        let file = image(&[
+            block(FLASH_START_ADDRESS, 0, 2, DATA_FAMILY_ID, 0),
+            block(0x0, 1, 2, DATA_FAMILY_ID, 0),
+        ]);
+        let error = rp2350.load_uf2(&file, &ARM_SECURE_FAMILY_IDS).unwrap_err();
+
+        assert_eq!(
+            error.downcast_ref::<Uf2Error>(),
+            Some(&Uf2Error {
+                block: 1,
+                kind: Uf2ErrorKind::Program { address: 0, error: BusError::Unmapped },
+            })
+        );
+    }
+}
@@ -1 +1,12 @@
+mod bootrom;
+mod decode_cache;
+mod disassembler;
+mod exceptions;
+mod gdbstub;
 mod instructions;
+mod loader;
+mod peripherals;
+mod pins;
+mod runner;
+mod semihosting;
+mod trace;
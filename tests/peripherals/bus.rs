@@ -0,0 +1,220 @@
+#[cfg(test)]
+mod tests {
+    use std::any::Any;
+
+    use rp2350_sim::peripherals::bus::*;
+    use rp2350_sim::peripherals::clocks::{Clock, CLK_CTRL, CLK_CTRL_ENABLE, CLOCKS_BASE};
+    use rp2350_sim::peripherals::uart::*;
+    use rp2350_sim::peripherals::{Peripheral, Width};
+    use rp2350_sim::{AccessResult, BusError, RP2350};
+
+    const BASE: u32 = 0x40100000;
+
+    /// Registers that keep what is written to them, recording the accesses and ticks. The first register holds the
+    /// IRQ lines it asserts.
+    #[derive(Default)]
+    struct Scratch {
+        registers: [u32; 4],
+        writes: Vec<(u32, Width, u32)>,
+        ticks: Vec<u64>,
+        period: Option<u64>,
+    }
+
+    impl Peripheral for Scratch {
+        fn read(&self, offset: u32, width: Width) -> AccessResult<u32> {
+            Ok(self.registers[(offset / 4) as usize] >> (8 * (offset & 3)) & width.mask())
+        }
+
+        fn write(&mut self, offset: u32, width: Width, value: u32) -> AccessResult<()> {
+            self.writes.push((offset, width, value));
+            let shift = 8 * (offset & 3);
+            let register = &mut self.registers[(offset / 4) as usize];
+            *register = *register & !(width.mask() << shift) | value << shift;
+            Ok(())
+        }
+
+        fn tick(&mut self, cycles: u64) {
+            self.ticks.push(cycles);
+        }
+
+        fn next_tick(&self) -> Option<u64> {
+            self.period
+        }
+
+        fn irqs(&self) -> u64 {
+            self.registers[0] as u64
+        }
+
+        fn reset(&mut self) {
+            self.registers = [0; 4];
+        }
+
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+
+        fn as_any_mut(&mut self) -> &mut dyn Any {
+            self
+        }
+    }
+
+    fn bus_with_scratch(base: u32) -> Bus {
+        let mut bus = Bus::new();
+        bus.register(base, 16, Scratch::default());
+        bus
+    }
+
+    #[test]
+    fn routes_by_base() {
+        // should hand accesses to the peripheral with offsets from its base, and answer nothing around it
+        let mut bus = bus_with_scratch(BASE);
+        bus.write(BASE + 4, Width::Word, 0x12345678).unwrap();
+
+        assert_eq!(bus.read(BASE + 4, Width::Word), Ok(0x12345678));
+        assert_eq!(bus.read(BASE + 6, Width::Halfword), Ok(0x1234));
+        assert_eq!(bus.read(BASE + 5, Width::Byte), Ok(0x56));
+        assert_eq!(bus.get::<Scratch>(BASE).unwrap().writes, vec![(4, Width::Word, 0x12345678)]);
+        assert_eq!(bus.read(BASE - 4, Width::Word), Err(BusError::Unmapped));
+        assert_eq!(bus.read(BASE + 16, Width::Word), Err(BusError::Unmapped));
+        assert_eq!(bus.read(BASE + ATOMIC_BLOCK_SIZE, Width::Word), Err(BusError::Unmapped));
+    }
+
+    #[test]
+    fn unaligned() {
+        // should not answer accesses that aren't aligned to their width
+        let mut bus = bus_with_scratch(BASE);
+
+        assert_eq!(bus.read(BASE + 2, Width::Word), Err(BusError::Unmapped));
+        assert_eq!(bus.write(BASE + 1, Width::Halfword, 0), Err(BusError::Unmapped));
+    }
+
+    #[test]
+    fn atomic_aliases() {
+        // should XOR, set and clear bits of the register through the aliases above it
+        let mut bus = bus_with_scratch(BASE);
+        bus.write(BASE + 8, Width::Word, 0x0000ff00).unwrap();
+
+        bus.write(BASE + ATOMIC_XOR + 8, Width::Word, 0x00000ff0).unwrap();
+        assert_eq!(bus.read(BASE + 8, Width::Word), Ok(0x0000f0f0));
+        bus.write(BASE + ATOMIC_SET + 8, Width::Word, 0x80000001).unwrap();
+        assert_eq!(bus.read(BASE + 8, Width::Word), Ok(0x8000f0f1));
+        bus.write(BASE + ATOMIC_CLEAR + 8, Width::Word, 0x0000f000).unwrap();
+        assert_eq!(bus.read(BASE + 8, Width::Word), Ok(0x800000f1));
+        assert_eq!(bus.read(BASE + ATOMIC_SET + 8, Width::Word), Ok(0x800000f1));
+    }
+
+    #[test]
+    fn narrow_atomic_write() {
+        // should apply a narrow alias write to every byte lane of the register, as the bus replicates it
+        let mut bus = bus_with_scratch(BASE);
+        bus.write(BASE + ATOMIC_SET + 1, Width::Byte, 0x81).unwrap();
+        bus.write(BASE + ATOMIC_CLEAR + 4, Width::Halfword, 0x0001).unwrap();
+
+        assert_eq!(bus.read(BASE, Width::Word), Ok(0x81818181));
+        assert_eq!(bus.get::<Scratch>(BASE).unwrap().writes[0], (0, Width::Word, 0x81818181));
+    }
+
+    #[test]
+    fn no_aliases_outside_apb_and_ahb() {
+        // should leave the alias addresses unmapped for a peripheral outside the APB and AHB segments
+        let mut bus = bus_with_scratch(0xd0000000);
+        bus.write(0xd0000000, Width::Word, 1).unwrap();
+
+        assert_eq!(bus.write(0xd0000000 + ATOMIC_SET, Width::Word, 2), Err(BusError::Unmapped));
+        assert_eq!(bus.read(0xd0000000, Width::Word), Ok(1));
+    }
+
+    #[test]
+    #[should_panic(expected = "overlaps")]
+    fn overlap() {
+        // should refuse a peripheral in the atomic aliases of another
+        let mut bus = bus_with_scratch(BASE);
+        bus.register(BASE + ATOMIC_CLEAR, 16, Scratch::default());
+    }
+
+    #[test]
+    fn ticks_when_asked() {
+        // should only tick a peripheral once the cycles it asked for have gone by, with all of them
+        let mut bus = Bus::new();
+        bus.register(BASE, 16, Scratch { period: Some(4), ..Scratch::default() });
+        bus.register(BASE + ATOMIC_BLOCK_SIZE, 16, Scratch::default());
+
+        for _ in 0..3 {
+            bus.tick(1);
+        }
+        assert!(bus.get::<Scratch>(BASE).unwrap().ticks.is_empty());
+        bus.tick(2);
+
+        assert_eq!(bus.now(), 5);
+        assert_eq!(bus.get::<Scratch>(BASE).unwrap().ticks, vec![5]);
+        assert!(bus.get::<Scratch>(BASE + ATOMIC_BLOCK_SIZE).unwrap().ticks.is_empty());
+    }
+
+    #[test]
+    fn catches_up_before_access() {
+        // should tick a peripheral up to now before it is written or handed out to change
+        let mut bus = bus_with_scratch(BASE);
+        bus.tick(7);
+        bus.write(BASE, Width::Word, 0).unwrap();
+        bus.tick(2);
+
+        assert_eq!(bus.get_mut::<Scratch>(BASE).unwrap().ticks, vec![7, 2]);
+    }
+
+    #[test]
+    fn catches_up_before_read() {
+        // should tick a peripheral up to now before it is read, so it answers as of now
+        let mut bus = bus_with_scratch(BASE);
+        bus.tick(7);
+        bus.read(BASE, Width::Word).unwrap();
+
+        assert_eq!(bus.get::<Scratch>(BASE).unwrap().ticks, vec![7]);
+    }
+
+    #[test]
+    fn irqs() {
+        // should report the IRQ lines peripherals assert from the tick after they change
+        let mut bus = bus_with_scratch(BASE);
+        bus.register(BASE + ATOMIC_BLOCK_SIZE, 16, Scratch::default());
+        assert_eq!(bus.tick(1), 0);
+
+        bus.write(BASE, Width::Word, 1 << 3).unwrap();
+        bus.get_mut::<Scratch>(BASE + ATOMIC_BLOCK_SIZE).unwrap().registers[0] = 1 << 8;
+
+        assert_eq!(bus.tick(1), 1 << 3 | 1 << 8);
+        assert_eq!(bus.irqs(), 1 << 3 | 1 << 8);
+    }
+
+    #[test]
+    fn reset() {
+        // should put every peripheral back to its reset values
+        let mut bus = bus_with_scratch(BASE);
+        bus.register(BASE + ATOMIC_BLOCK_SIZE, 16, Scratch::default());
+        bus.write(BASE, Width::Word, 5).unwrap();
+        bus.write(BASE + ATOMIC_BLOCK_SIZE, Width::Word, 6).unwrap();
+
+        bus.reset();
+
+        assert_eq!(bus.read(BASE, Width::Word), Ok(0));
+        assert_eq!(bus.read(BASE + ATOMIC_BLOCK_SIZE, Width::Word), Ok(0));
+        assert_eq!(bus.tick(1), 0);
+    }
+
+    #[test]
+    fn core_uses_aliases() {
+        // should let the core enable UART0 through the SET alias of its control register
+        let mut rp2350 = RP2350::new();
+        let memory = &mut rp2350.cortex_m33.memory;
+        // clk_peri from clk_sys, and eight data bits at a sixteenth of it
+        memory.write_u32(CLOCKS_BASE + 0xc * Clock::Peri as u32 + CLK_CTRL, CLK_CTRL_ENABLE).unwrap();
+        memory.write_u32(UART0_BASE + UART_IBRD, 1).unwrap();
+        memory.write_u32(UART0_BASE + ATOMIC_SET + UART_LCR_H, UART_LCR_H_WLEN_MASK).unwrap();
+        memory.write_u32(UART0_BASE + ATOMIC_SET + UART_CR, UART_CR_UARTEN).unwrap();
+        memory.write_u32(UART0_BASE + UART_DR, b'!' as u32).unwrap();
+
+        assert_eq!(memory.read_u32(UART0_BASE + UART_CR), Ok(UART_CR_UARTEN | UART_CR_TXE | UART_CR_RXE));
+        assert_eq!(memory.read_u32(UART0_BASE + UART_LCR_H), Ok(UART_LCR_H_WLEN_MASK));
+        rp2350.memory_mut().peripherals.tick(160);
+        assert_eq!(rp2350.memory_mut().uart0().take_output(), b"!");
+    }
+}
@@ -0,0 +1,128 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::peripherals::bus::{ATOMIC_CLEAR, ATOMIC_SET};
+    use rp2350_sim::peripherals::clocks::*;
+    use rp2350_sim::peripherals::pll::*;
+    use rp2350_sim::peripherals::resets::*;
+    use rp2350_sim::peripherals::rosc::ROSC_HZ;
+    use rp2350_sim::peripherals::ticks::*;
+    use rp2350_sim::peripherals::xosc::*;
+    use rp2350_sim::{MemoryInterface, RP2350Memory, RP2350};
+    use std::time::Duration;
+
+    fn register(clock: Clock, offset: u32) -> u32 {
+        CLOCKS_BASE + clock as u32 * 0xc + offset
+    }
+
+    /// Runs the bus a cycle at a time until a register reads back as expected, as firmware spinning on it would
+    fn wait_for(memory: &mut RP2350Memory, address: u32, mask: u32, value: u32) {
+        for _ in 0..1_000_000 {
+            if memory.read_u32(address).unwrap() & mask == value {
+                return;
+            }
+            memory.peripherals.tick(1);
+        }
+        panic!("{address:#x} never read {value:#x}");
+    }
+
+    /// pll_init from the pico-sdk
+    fn pll_init(memory: &mut RP2350Memory, base: u32, reset: u32, fbdiv: u32, postdiv1: u32, postdiv2: u32) {
+        memory.write_u32(RESETS_BASE + ATOMIC_SET, 1 << reset).unwrap();
+        memory.write_u32(RESETS_BASE + ATOMIC_CLEAR, 1 << reset).unwrap();
+        wait_for(memory, RESETS_BASE + RESETS_RESET_DONE, 1 << reset, 1 << reset);
+
+        memory.write_u32(base + PLL_CS, 1).unwrap();
+        memory.write_u32(base + PLL_FBDIV_INT, fbdiv).unwrap();
+        memory.write_u32(base + PLL_PWR + ATOMIC_CLEAR, PLL_PWR_PD | PLL_PWR_VCOPD).unwrap();
+        wait_for(memory, base + PLL_CS, PLL_CS_LOCK, PLL_CS_LOCK);
+
+        let prim = postdiv1 << PLL_PRIM_POSTDIV1_SHIFT | postdiv2 << PLL_PRIM_POSTDIV2_SHIFT;
+        memory.write_u32(base + PLL_PRIM, prim).unwrap();
+        memory.write_u32(base + PLL_PWR + ATOMIC_CLEAR, PLL_PWR_POSTDIVPD).unwrap();
+    }
+
+    /// runtime_init_clocks from the pico-sdk, for 150 MHz from a 12 MHz crystal
+    fn runtime_init(memory: &mut RP2350Memory) {
+        let blocks = RESET_ALL & !(1 << RESET_IO_QSPI | 1 << RESET_PADS_QSPI | 1 << RESET_PLL_SYS | 1 << RESET_PLL_USB);
+        memory.write_u32(RESETS_BASE + ATOMIC_CLEAR, blocks).unwrap();
+        wait_for(memory, RESETS_BASE + RESETS_RESET_DONE, blocks, blocks);
+
+        memory.write_u32(XOSC_BASE + XOSC_CTRL, XOSC_FREQ_RANGE_1_15MHZ).unwrap();
+        memory.write_u32(XOSC_BASE + XOSC_STARTUP, 47).unwrap();
+        memory.write_u32(XOSC_BASE + XOSC_CTRL + ATOMIC_SET, XOSC_CTRL_ENABLE << XOSC_CTRL_ENABLE_SHIFT).unwrap();
+        wait_for(memory, XOSC_BASE + XOSC_STATUS, XOSC_STATUS_STABLE, XOSC_STATUS_STABLE);
+
+        memory.write_u32(register(Clock::Sys, CLK_CTRL) + ATOMIC_CLEAR, CLK_CTRL_SRC_MASK).unwrap();
+        wait_for(memory, register(Clock::Sys, CLK_SELECTED), u32::MAX, 1);
+        memory.write_u32(register(Clock::Ref, CLK_CTRL) + ATOMIC_CLEAR, CLK_CTRL_SRC_MASK).unwrap();
+        wait_for(memory, register(Clock::Ref, CLK_SELECTED), u32::MAX, 1);
+
+        pll_init(memory, PLL_SYS_BASE, RESET_PLL_SYS, 125, 5, 2);
+        pll_init(memory, PLL_USB_BASE, RESET_PLL_USB, 100, 5, 5);
+
+        memory.write_u32(register(Clock::Ref, CLK_CTRL), CLK_REF_SRC_XOSC).unwrap();
+        wait_for(memory, register(Clock::Ref, CLK_SELECTED), u32::MAX, 1 << CLK_REF_SRC_XOSC);
+
+        memory.write_u32(register(Clock::Sys, CLK_CTRL), CLK_SYS_AUXSRC_PLL_SYS << CLK_CTRL_AUXSRC_SHIFT).unwrap();
+        memory.write_u32(register(Clock::Sys, CLK_CTRL) + ATOMIC_SET, CLK_SYS_SRC_AUX).unwrap();
+        wait_for(memory, register(Clock::Sys, CLK_SELECTED), u32::MAX, 1 << CLK_SYS_SRC_AUX);
+
+        let peri = CLK_CTRL_ENABLE | CLK_PERI_AUXSRC_CLK_SYS << CLK_CTRL_AUXSRC_SHIFT;
+        memory.write_u32(register(Clock::Peri, CLK_CTRL), peri).unwrap();
+
+        for generator in [TICK_PROC0, TICK_TIMER0, TICK_WATCHDOG] {
+            memory.write_u32(TICKS_BASE + generator * 0xc + TICK_CYCLES, 12).unwrap();
+            memory.write_u32(TICKS_BASE + generator * 0xc + TICK_CTRL, TICK_CTRL_ENABLE).unwrap();
+        }
+    }
+
+    #[test]
+    fn reset_clocks() {
+        // should run clk_sys and clk_ref from the ROSC out of reset, with clk_peri stopped
+        let rp2350 = RP2350::new();
+        let memory = rp2350.memory();
+
+        assert_eq!(memory.clk_sys(), ROSC_HZ);
+        assert_eq!(memory.frequency(Clock::Ref), ROSC_HZ);
+        assert_eq!(memory.clk_peri(), 0);
+        assert_eq!(memory.elapsed(), Duration::ZERO);
+    }
+
+    #[test]
+    fn runtime_init_clocks() {
+        // should bring the clocks up the way the pico-sdk does, to 150 MHz from the crystal
+        let mut rp2350 = RP2350::new();
+        let memory = rp2350.memory_mut();
+        runtime_init(memory);
+
+        assert_eq!(memory.frequency(Clock::Ref), XOSC_HZ);
+        assert_eq!(memory.clk_sys(), 150_000_000);
+        assert_eq!(memory.clk_peri(), 150_000_000);
+        assert_eq!(memory.frequency(Clock::Usb), 0);
+        assert_eq!(memory.pll_usb().frequency(), 48_000_000);
+    }
+
+    #[test]
+    fn time_at_clk_sys() {
+        // should count simulated time at the clk_sys frequency once it has changed
+        let mut rp2350 = RP2350::new();
+        let memory = rp2350.memory_mut();
+        runtime_init(memory);
+
+        let start = memory.elapsed();
+        memory.peripherals.tick(150_000);
+        assert_eq!(memory.elapsed() - start, Duration::from_millis(1));
+    }
+
+    #[test]
+    fn pll_reset() {
+        // should drop clk_sys back when PLL_SYS is put into reset under it
+        let mut rp2350 = RP2350::new();
+        let memory = rp2350.memory_mut();
+        runtime_init(memory);
+        memory.write_u32(RESETS_BASE + ATOMIC_SET, 1 << RESET_PLL_SYS).unwrap();
+
+        assert!(!memory.pll_sys().is_locked());
+        assert_eq!(memory.clk_sys(), 0);
+    }
+}
@@ -0,0 +1,148 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::peripherals::clocks::*;
+    use rp2350_sim::peripherals::rosc::ROSC_HZ;
+    use rp2350_sim::peripherals::{Peripheral, Width};
+    use std::time::Duration;
+
+    const SOURCES: Sources = Sources {
+        xosc: 12_000_000,
+        rosc: ROSC_HZ,
+        lposc: LPOSC_HZ,
+        pll_sys: 150_000_000,
+        pll_usb: 48_000_000,
+        gpin0: 0,
+        gpin1: 0,
+    };
+
+    fn register(clock: Clock, offset: u32) -> u32 {
+        clock as u32 * 0xc + offset
+    }
+
+    fn write(clocks: &mut Clocks, clock: Clock, offset: u32, value: u32) {
+        clocks.write(register(clock, offset), Width::Word, value).unwrap();
+        clocks.update(SOURCES, 0);
+    }
+
+    #[test]
+    fn reset_values() {
+        // should come out of reset with clk_ref and clk_sys on the ROSC, undivided, and the other clocks stopped
+        let clocks = Clocks::new();
+
+        for clock in [Clock::GpOut0, Clock::Ref, Clock::Sys, Clock::Peri, Clock::Usb, Clock::Adc] {
+            assert_eq!(clocks.read(register(clock, CLK_CTRL), Width::Word), Ok(0));
+            assert_eq!(clocks.read(register(clock, CLK_DIV), Width::Word), Ok(0x00010000));
+            assert_eq!(clocks.read(register(clock, CLK_SELECTED), Width::Word), Ok(1));
+        }
+        assert_eq!(clocks.frequency(Clock::Ref), ROSC_HZ);
+        assert_eq!(clocks.frequency(Clock::Sys), ROSC_HZ);
+        assert_eq!(clocks.frequency(Clock::Peri), 0);
+        assert_eq!(clocks.read(CLOCKS_CLK_SYS_RESUS_CTRL, Width::Word), Ok(0xff));
+    }
+
+    #[test]
+    fn glitchless_select() {
+        // should show the glitchless source picked in SELECTED, one hot
+        let mut clocks = Clocks::new();
+        write(&mut clocks, Clock::Ref, CLK_CTRL, CLK_REF_SRC_XOSC);
+        write(&mut clocks, Clock::Sys, CLK_CTRL, CLK_SYS_AUXSRC_PLL_SYS << CLK_CTRL_AUXSRC_SHIFT | CLK_SYS_SRC_AUX);
+
+        assert_eq!(clocks.read(register(Clock::Ref, CLK_SELECTED), Width::Word), Ok(1 << CLK_REF_SRC_XOSC));
+        assert_eq!(clocks.read(register(Clock::Sys, CLK_SELECTED), Width::Word), Ok(1 << CLK_SYS_SRC_AUX));
+        assert_eq!(clocks.frequency(Clock::Ref), 12_000_000);
+        assert_eq!(clocks.frequency(Clock::Sys), 150_000_000);
+    }
+
+    #[test]
+    fn aux_sources() {
+        // should run clk_sys from its aux source only while SRC picks it
+        let mut clocks = Clocks::new();
+        write(&mut clocks, Clock::Sys, CLK_CTRL, CLK_SYS_AUXSRC_XOSC << CLK_CTRL_AUXSRC_SHIFT);
+        assert_eq!(clocks.frequency(Clock::Sys), ROSC_HZ);
+
+        write(&mut clocks, Clock::Sys, CLK_CTRL, CLK_SYS_AUXSRC_XOSC << CLK_CTRL_AUXSRC_SHIFT | CLK_SYS_SRC_AUX);
+        assert_eq!(clocks.frequency(Clock::Sys), 12_000_000);
+    }
+
+    #[test]
+    fn dividers() {
+        // should divide by DIV, with its fraction, and take an integer part of 0 as the largest divisor
+        let mut clocks = Clocks::new();
+        write(&mut clocks, Clock::Sys, CLK_CTRL, CLK_SYS_SRC_AUX);
+        write(&mut clocks, Clock::Sys, CLK_DIV, 2 << CLK_DIV_INT_SHIFT | 0x8000);
+        assert_eq!(clocks.frequency(Clock::Sys), 60_000_000);
+
+        write(&mut clocks, Clock::Ref, CLK_CTRL, CLK_REF_SRC_XOSC);
+        write(&mut clocks, Clock::Ref, CLK_DIV, 0);
+        assert_eq!(clocks.frequency(Clock::Ref), 12_000_000 / 256);
+    }
+
+    #[test]
+    fn gated_clocks() {
+        // should only run clk_peri while it is enabled and not killed, showing ENABLED in CTRL
+        let mut clocks = Clocks::new();
+        write(&mut clocks, Clock::Peri, CLK_CTRL, CLK_PERI_AUXSRC_PLL_USB << CLK_CTRL_AUXSRC_SHIFT);
+        assert_eq!(clocks.frequency(Clock::Peri), 0);
+
+        let ctrl = CLK_CTRL_ENABLE | CLK_PERI_AUXSRC_PLL_USB << CLK_CTRL_AUXSRC_SHIFT;
+        write(&mut clocks, Clock::Peri, CLK_CTRL, ctrl);
+        assert_eq!(clocks.frequency(Clock::Peri), 48_000_000);
+        assert_eq!(clocks.read(register(Clock::Peri, CLK_CTRL), Width::Word), Ok(ctrl | CLK_CTRL_ENABLED));
+
+        write(&mut clocks, Clock::Peri, CLK_CTRL, ctrl | CLK_CTRL_KILL);
+        assert_eq!(clocks.frequency(Clock::Peri), 0);
+    }
+
+    #[test]
+    fn clocks_from_clocks() {
+        // should run a clock from clk_sys as it is after its own change
+        let mut clocks = Clocks::new();
+        write(&mut clocks, Clock::Peri, CLK_CTRL, CLK_CTRL_ENABLE | CLK_PERI_AUXSRC_CLK_SYS << CLK_CTRL_AUXSRC_SHIFT);
+        assert_eq!(clocks.frequency(Clock::Peri), ROSC_HZ);
+
+        write(&mut clocks, Clock::Sys, CLK_CTRL, CLK_SYS_SRC_AUX);
+        assert_eq!(clocks.frequency(Clock::Peri), 150_000_000);
+    }
+
+    #[test]
+    fn frequency_counter() {
+        // should measure the source FC0_SRC picks in kHz, and check it against the limits
+        let mut clocks = Clocks::new();
+        clocks.update(SOURCES, 0);
+        clocks.write(CLOCKS_FC0_MIN_KHZ, Width::Word, 10_000).unwrap();
+        clocks.write(CLOCKS_FC0_MAX_KHZ, Width::Word, 20_000).unwrap();
+
+        clocks.write(CLOCKS_FC0_SRC, Width::Word, 5).unwrap();
+        assert_eq!(clocks.read(CLOCKS_FC0_RESULT, Width::Word), Ok(12_000 << FC0_RESULT_KHZ_SHIFT));
+        assert_eq!(clocks.read(CLOCKS_FC0_STATUS, Width::Word), Ok(FC0_STATUS_DONE | FC0_STATUS_PASS));
+
+        clocks.write(CLOCKS_FC0_SRC, Width::Word, 1).unwrap();
+        assert_eq!(clocks.read(CLOCKS_FC0_RESULT, Width::Word), Ok(150_000 << FC0_RESULT_KHZ_SHIFT));
+        let status = FC0_STATUS_DONE | FC0_STATUS_FAIL | FC0_STATUS_FAST;
+        assert_eq!(clocks.read(CLOCKS_FC0_STATUS, Width::Word), Ok(status));
+
+        clocks.write(CLOCKS_FC0_SRC, Width::Word, 6).unwrap();
+        let status = FC0_STATUS_DONE | FC0_STATUS_FAIL | FC0_STATUS_DIED;
+        assert_eq!(clocks.read(CLOCKS_FC0_STATUS, Width::Word), Ok(status));
+    }
+
+    #[test]
+    fn elapsed() {
+        // should count time at the clk_sys frequency each stretch of cycles ran at
+        let mut clocks = Clocks::new();
+        assert_eq!(clocks.elapsed(ROSC_HZ as u64), Duration::from_secs(1));
+
+        clocks.write(register(Clock::Sys, CLK_CTRL), Width::Word, CLK_SYS_SRC_AUX).unwrap();
+        clocks.update(SOURCES, ROSC_HZ as u64);
+        assert_eq!(clocks.elapsed(ROSC_HZ as u64 + 150_000), Duration::from_millis(1001));
+    }
+
+    #[test]
+    fn reset_keeps_time() {
+        // should carry on the time kept across a reset
+        let mut clocks = Clocks::new();
+        clocks.reset();
+
+        assert_eq!(clocks.elapsed(ROSC_HZ as u64 / 2), Duration::from_millis(500));
+    }
+}
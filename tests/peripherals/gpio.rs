@@ -0,0 +1,106 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::cortex_m33::opcodes::*;
+    use rp2350_sim::cortex_m33::registers::Register;
+    use rp2350_sim::peripherals::bus::ATOMIC_CLEAR;
+    use rp2350_sim::peripherals::io_bank0::*;
+    use rp2350_sim::peripherals::pads_bank0::*;
+    use rp2350_sim::peripherals::sio::*;
+    use rp2350_sim::{RAM_START_ADDRESS, RP2350, SIO_START_ADDRESS};
+
+    const LED: u32 = 25;
+    const BUTTON: u32 = 12;
+
+    /// Sets a pin up the way the pico-sdk's gpio_init does: SIO function, input enabled and isolation removed
+    fn gpio_init(rp2350: &mut RP2350, pin: u32) {
+        let memory = &mut rp2350.cortex_m33.memory;
+        let pad = PADS_BANK0_BASE + PADS_GPIO0 + 4 * pin;
+        let control = memory.read_u32(pad).unwrap();
+        memory.write_u32(pad, (control | PADS_IE) & !PADS_OD).unwrap();
+        memory.write_u32(IO_BANK0_BASE + 8 * pin + GPIO_CTRL, Function::Sio as u32).unwrap();
+        memory.write_u32(pad + ATOMIC_CLEAR, PADS_ISO).unwrap();
+    }
+
+    /// Programs stores of r0 to each offset from r1
+    fn program(rp2350: &mut RP2350, offsets: &[u32]) {
+        let registers = rp2350.cortex_m33.registers;
+        let code: Vec<u8> = offsets
+            .iter()
+            .map(|&offset| StrImmediateT1::opcode(&registers.r0, &registers.r1, offset as u16))
+            .flat_map(|opcode| opcode.to_le_bytes())
+            .collect();
+        rp2350.memory_mut().program(RAM_START_ADDRESS, &code).unwrap();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+    }
+
+    #[test]
+    fn led_goes_high() {
+        // should take the LED pin high and low as firmware sets and clears it through the SIO
+        let mut rp2350 = RP2350::new();
+        gpio_init(&mut rp2350, LED);
+        program(&mut rp2350, &[SIO_GPIO_OE_SET, SIO_GPIO_OUT_SET, SIO_GPIO_OUT_CLR]);
+        rp2350.cortex_m33.registers.r0.set(1 << LED);
+        rp2350.cortex_m33.registers.r1.set(SIO_START_ADDRESS);
+
+        rp2350.execute_instruction();
+        assert!(!rp2350.memory_mut().pads_bank0().level(LED));
+        rp2350.execute_instruction();
+        assert!(rp2350.memory_mut().pads_bank0().level(LED));
+        assert!(rp2350.memory_mut().sio().level(LED));
+        rp2350.execute_instruction();
+        assert!(!rp2350.memory_mut().pads_bank0().level(LED));
+    }
+
+    #[test]
+    fn led_needs_sio_function() {
+        // should leave the pin undriven while its FUNCSEL doesn't pick the SIO
+        let mut rp2350 = RP2350::new();
+        let memory = &mut rp2350.cortex_m33.memory;
+        memory.write_u32(PADS_BANK0_BASE + PADS_GPIO0 + 4 * LED, PADS_IE).unwrap();
+        memory.write_u32(SIO_START_ADDRESS + SIO_GPIO_OE_SET, 1 << LED).unwrap();
+        memory.write_u32(SIO_START_ADDRESS + SIO_GPIO_OUT_SET, 1 << LED).unwrap();
+
+        assert!(!rp2350.memory_mut().pads_bank0().level(LED));
+        let status = rp2350.cortex_m33.memory.read_u32(IO_BANK0_BASE + 8 * LED + GPIO_STATUS).unwrap();
+        assert_eq!(status, 0);
+    }
+
+    #[test]
+    fn button_reaches_gpio_in() {
+        // should show a pin the host drives in GPIO_IN once its pad passes the input in
+        let mut rp2350 = RP2350::new();
+        rp2350.memory_mut().drive_pin(BUTTON, Some(true));
+        let gpio_in = SIO_START_ADDRESS + SIO_GPIO_IN;
+        assert_eq!(rp2350.cortex_m33.memory.read_u32(gpio_in), Ok(0));
+
+        gpio_init(&mut rp2350, BUTTON);
+        assert_eq!(rp2350.cortex_m33.memory.read_u32(gpio_in), Ok(1 << BUTTON));
+
+        rp2350.memory_mut().drive_pin(BUTTON, None);
+        assert_eq!(rp2350.cortex_m33.memory.read_u32(gpio_in), Ok(0));
+    }
+
+    #[test]
+    fn button_interrupt() {
+        // should raise IO_IRQ_BANK0 in the NVIC on an enabled edge, and drop it once the edge is cleared
+        let mut rp2350 = RP2350::new();
+        gpio_init(&mut rp2350, BUTTON);
+        let enable = INT_EDGE_LOW << (4 * (BUTTON % 8));
+        let memory = &mut rp2350.cortex_m33.memory;
+        memory.write_u32(IO_BANK0_BASE + PROC0_INTE0 + 4, enable).unwrap();
+        program(&mut rp2350, &[0, 0]);
+
+        rp2350.memory_mut().drive_pin(BUTTON, Some(true));
+        rp2350.execute_instruction();
+        assert!(!rp2350.cortex_m33.nvic.line(IO_IRQ_BANK0));
+
+        rp2350.memory_mut().drive_pin(BUTTON, Some(false));
+        rp2350.execute_instruction();
+        assert!(rp2350.cortex_m33.nvic.line(IO_IRQ_BANK0));
+        assert_eq!(rp2350.cortex_m33.memory.read_u32(IO_BANK0_BASE + PROC0_INTS0 + 4), Ok(enable));
+
+        rp2350.cortex_m33.memory.write_u32(IO_BANK0_BASE + INTR0 + 4, enable).unwrap();
+        rp2350.execute_instruction();
+        assert!(!rp2350.cortex_m33.nvic.line(IO_IRQ_BANK0));
+    }
+}
@@ -0,0 +1,194 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::peripherals::bus::{Bus, ATOMIC_CLEAR, ATOMIC_SET};
+    use rp2350_sim::peripherals::io_bank0::*;
+    use rp2350_sim::peripherals::{Peripheral, Width};
+    use rp2350_sim::BusError;
+
+    fn ctrl(pin: u32) -> u32 {
+        8 * pin + GPIO_CTRL
+    }
+
+    fn status(pin: u32) -> u32 {
+        8 * pin + GPIO_STATUS
+    }
+
+    /// Bank 0 with the pin on the SIO, and the SIO driving it high
+    fn sio_pin(pin: u32) -> IoBank0 {
+        let mut io = IoBank0::new();
+        io.write(ctrl(pin), Width::Word, Function::Sio as u32).unwrap();
+        io.to_pads(1 << pin, 1 << pin);
+        io
+    }
+
+    #[test]
+    fn reset_values() {
+        // should come out of reset with every pin on the null function and no interrupts
+        let io = IoBank0::new();
+
+        assert_eq!(io.read(ctrl(0), Width::Word), Ok(0x1f));
+        assert_eq!(io.read(ctrl(47), Width::Word), Ok(0x1f));
+        assert_eq!(io.function(7), Function::Null);
+        assert_eq!(io.read(PROC0_INTE0, Width::Word), Ok(0));
+        assert_eq!(io.irqs(), 0);
+    }
+
+    #[test]
+    fn funcsel() {
+        // should only pass the SIO outputs to pads whose FUNCSEL picks the SIO
+        let mut io = IoBank0::new();
+        io.write(ctrl(1), Width::Word, Function::Sio as u32).unwrap();
+        io.write(ctrl(2), Width::Word, Function::Uart as u32).unwrap();
+
+        assert_eq!(io.to_pads(0b111, 0b111), (0b010, 0b010));
+        assert_eq!(io.function(2), Function::Uart);
+        assert_eq!(Function::from_funcsel(8), Function::Pio2);
+        assert_eq!(Function::from_funcsel(0), Function::Hstx);
+        assert_eq!(Function::from_funcsel(20), Function::Null);
+    }
+
+    #[test]
+    fn output_overrides() {
+        // should invert, force low and force high the output and output enable going to the pad
+        let mut io = IoBank0::new();
+        let sio = Function::Sio as u32;
+        io.write(ctrl(0), Width::Word, sio | 1 << GPIO_CTRL_OUTOVER_SHIFT).unwrap();
+        io.write(ctrl(1), Width::Word, sio | 2 << GPIO_CTRL_OUTOVER_SHIFT | 3 << GPIO_CTRL_OEOVER_SHIFT).unwrap();
+        io.write(ctrl(2), Width::Word, 3 << GPIO_CTRL_OUTOVER_SHIFT | 3 << GPIO_CTRL_OEOVER_SHIFT).unwrap();
+        io.write(ctrl(3), Width::Word, sio | 2 << GPIO_CTRL_OEOVER_SHIFT).unwrap();
+
+        assert_eq!(io.to_pads(0b1011, 0b1001), (0b1100, 0b0111));
+    }
+
+    #[test]
+    fn input_override() {
+        // should invert and force the input the peripherals see, keeping the pad's input in the status
+        let mut io = IoBank0::new();
+        io.write(ctrl(0), Width::Word, 1 << GPIO_CTRL_INOVER_SHIFT).unwrap();
+        io.write(ctrl(1), Width::Word, 2 << GPIO_CTRL_INOVER_SHIFT).unwrap();
+        io.write(ctrl(2), Width::Word, 3 << GPIO_CTRL_INOVER_SHIFT).unwrap();
+
+        assert_eq!(io.from_pads(0b0011), 0b0100);
+        assert_eq!(io.read(status(1), Width::Word), Ok(GPIO_STATUS_INFROMPAD));
+    }
+
+    #[test]
+    fn pad_signals() {
+        // should show the signals going to and coming from the pad
+        let mut io = sio_pin(5);
+        io.from_pads(1 << 5);
+
+        let expected = GPIO_STATUS_OUTTOPAD | GPIO_STATUS_OETOPAD | GPIO_STATUS_INFROMPAD;
+        assert_eq!(io.read(status(5), Width::Word), Ok(expected));
+        assert_eq!(io.read(status(4), Width::Word), Ok(0));
+        // STATUS is read only
+        io.write(status(4), Width::Word, u32::MAX).unwrap();
+        assert_eq!(io.read(status(4), Width::Word), Ok(0));
+    }
+
+    #[test]
+    fn edge_interrupts() {
+        // should latch rising and falling edges until a 1 is written to them
+        let mut io = IoBank0::new();
+        io.from_pads(1 << 9);
+        io.from_pads(0);
+
+        let intr1 = INTR0 + 4;
+        assert_eq!(io.read(intr1, Width::Word), Ok((INT_EDGE_HIGH | INT_EDGE_LOW | INT_LEVEL_LOW) << 4 | 0x11111101));
+        io.write(intr1, Width::Word, INT_EDGE_HIGH << 4).unwrap();
+        assert_eq!(io.read(intr1, Width::Word), Ok((INT_EDGE_LOW | INT_LEVEL_LOW) << 4 | 0x11111101));
+    }
+
+    #[test]
+    fn edge_interrupts_through_aliases() {
+        // should only clear the latched edges a write through the atomic aliases targets
+        let mut bus = Bus::new();
+        bus.register(IO_BANK0_BASE, IO_BANK0_SIZE, IoBank0::new());
+        let io: &mut IoBank0 = bus.get_mut(IO_BANK0_BASE).unwrap();
+        io.from_pads(1 << 8 | 1 << 9);
+        io.from_pads(0);
+
+        let intr1 = IO_BANK0_BASE + INTR0 + 4;
+        let edges = (INT_EDGE_HIGH | INT_EDGE_LOW) * 0x11;
+        bus.write(intr1 + ATOMIC_CLEAR, Width::Word, INT_EDGE_HIGH).unwrap();
+        assert_eq!(bus.read(intr1, Width::Word).unwrap() & edges, edges);
+
+        bus.write(intr1 + ATOMIC_SET, Width::Word, INT_EDGE_HIGH << 4).unwrap();
+        assert_eq!(bus.read(intr1, Width::Word).unwrap() & edges, edges & !(INT_EDGE_HIGH << 4));
+    }
+
+    #[test]
+    fn level_interrupts() {
+        // should follow the level of the input without latching, ignoring writes to clear it
+        let mut io = IoBank0::new();
+        io.from_pads(1);
+        io.write(INTR0, Width::Word, INT_LEVEL_HIGH).unwrap();
+        assert_eq!(io.read(INTR0, Width::Word).unwrap() & 0xf, INT_LEVEL_HIGH | INT_EDGE_HIGH);
+
+        io.write(INTR0, Width::Word, INT_EDGE_HIGH).unwrap();
+        io.from_pads(0);
+        assert_eq!(io.read(INTR0, Width::Word).unwrap() & 0xf, INT_LEVEL_LOW | INT_EDGE_LOW);
+    }
+
+    #[test]
+    fn irq_to_core0() {
+        // should raise IO_IRQ_BANK0 for enabled interrupts, and show the pin in the status and summary
+        let mut io = IoBank0::new();
+        let inte5 = PROC0_INTE0 + 4 * 5;
+        io.write(inte5, Width::Word, INT_EDGE_HIGH << 4).unwrap();
+        assert_eq!(io.irqs(), 0);
+
+        io.from_pads(1 << 41);
+
+        assert_eq!(io.irqs(), 1 << IO_IRQ_BANK0);
+        assert_eq!(io.read(PROC0_INTS0 + 4 * 5, Width::Word), Ok(INT_EDGE_HIGH << 4));
+        assert_eq!(io.read(IRQSUMMARY_PROC0_SECURE1, Width::Word), Ok(1 << 9));
+        assert_eq!(io.read(status(41), Width::Word).unwrap() & GPIO_STATUS_IRQTOPROC, GPIO_STATUS_IRQTOPROC);
+
+        io.write(INTR0 + 4 * 5, Width::Word, INT_EDGE_HIGH << 4).unwrap();
+        assert_eq!(io.irqs(), 0);
+    }
+
+    #[test]
+    fn forced_and_overridden_irqs() {
+        // should raise the interrupt from INTF, and let IRQOVER invert or force it
+        let mut io = IoBank0::new();
+        io.write(PROC0_INTF0, Width::Word, INT_LEVEL_LOW << 8).unwrap();
+        assert_eq!(io.read(IRQSUMMARY_PROC0_SECURE0, Width::Word), Ok(1 << 2));
+
+        io.write(ctrl(2), Width::Word, 2 << GPIO_CTRL_IRQOVER_SHIFT).unwrap();
+        io.write(ctrl(3), Width::Word, 1 << GPIO_CTRL_IRQOVER_SHIFT).unwrap();
+        assert_eq!(io.read(IRQSUMMARY_PROC0_SECURE0, Width::Word), Ok(1 << 3));
+        assert_eq!(io.irqs(), 1 << IO_IRQ_BANK0);
+    }
+
+    #[test]
+    fn other_destinations() {
+        // should keep core 1 and dormant wake enables apart from core 0, without raising its IRQ
+        let mut io = IoBank0::new();
+        io.write(PROC1_INTE0, Width::Word, 0x11).unwrap();
+        io.write(DORMANT_WAKE_INTE0 + 4, Width::Word, 0x22).unwrap();
+
+        assert_eq!(io.read(PROC1_INTE0, Width::Word), Ok(0x11));
+        assert_eq!(io.read(DORMANT_WAKE_INTE0 + 4, Width::Word), Ok(0x22));
+        assert_eq!(io.read(PROC0_INTE0, Width::Word), Ok(0));
+        assert_eq!(io.irqs(), 0);
+        assert_eq!(io.read(IO_BANK0_SIZE, Width::Word), Err(BusError::Unmapped));
+    }
+
+    #[test]
+    fn reset() {
+        // should put the function selects and enables back, without seeing an edge on pins already high
+        let mut io = sio_pin(3);
+        io.from_pads(1 << 3);
+        io.write(INTR0, Width::Word, u32::MAX).unwrap();
+        io.write(PROC0_INTE0, Width::Word, INT_EDGE_HIGH << 12).unwrap();
+
+        io.reset();
+        io.from_pads(1 << 3);
+
+        assert_eq!(io.function(3), Function::Null);
+        assert_eq!(io.read(INTR0, Width::Word).unwrap() >> 12 & 0xf, INT_LEVEL_HIGH);
+        assert_eq!(io.read(PROC0_INTE0, Width::Word), Ok(0));
+    }
+}
@@ -0,0 +1,13 @@
+mod bus;
+mod clock_tree;
+mod clocks;
+mod gpio;
+mod io_bank0;
+mod pads_bank0;
+mod pll;
+mod resets;
+mod rosc;
+mod sio;
+mod ticks;
+mod uart;
+mod xosc;
@@ -0,0 +1,111 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::peripherals::pads_bank0::*;
+    use rp2350_sim::peripherals::{Peripheral, Width};
+
+    fn pad(pin: u32) -> u32 {
+        PADS_GPIO0 + 4 * pin
+    }
+
+    /// Pads with the isolation removed and the inputs enabled on the pins, otherwise left at reset
+    fn pads(pins: &[u32]) -> PadsBank0 {
+        let mut pads = PadsBank0::new();
+        for &pin in pins {
+            pads.write(pad(pin), Width::Word, PADS_IE | 1 << PADS_DRIVE_SHIFT | PADS_PDE | PADS_SCHMITT)
+                .unwrap();
+        }
+        pads
+    }
+
+    #[test]
+    fn reset_values() {
+        // should come out of reset with the GPIO pads isolated and pulled down, and the SWD pads pulled up
+        let pads = PadsBank0::new();
+
+        assert_eq!(pads.read(pad(0), Width::Word), Ok(0x116));
+        assert_eq!(pads.read(pad(47), Width::Word), Ok(0x116));
+        assert_eq!(pads.read(PADS_SWCLK, Width::Word), Ok(0x5a));
+        assert_eq!(pads.read(PADS_SWD, Width::Word), Ok(0x5a));
+        assert_eq!(pads.read(PADS_VOLTAGE_SELECT, Width::Word), Ok(0));
+    }
+
+    #[test]
+    fn pulls() {
+        // should pull undriven pins up or down, and keep their level with both pulls or neither
+        let mut pads = pads(&[0, 1, 2, 3]);
+        pads.write(pad(1), Width::Word, PADS_IE | PADS_PUE).unwrap();
+        pads.write(pad(2), Width::Word, PADS_IE | PADS_PUE | PADS_PDE).unwrap();
+        pads.write(pad(3), Width::Word, PADS_IE).unwrap();
+
+        assert_eq!(pads.update(0, 0), 0b0010);
+
+        pads.drive(2, Some(true));
+        pads.drive(3, Some(true));
+        pads.update(0, 0);
+        pads.drive(2, None);
+        pads.drive(3, None);
+        assert_eq!(pads.update(0, 0), 0b1110);
+    }
+
+    #[test]
+    fn outputs() {
+        // should drive pins with an output enabled, over both the pulls and the host
+        let mut pads = pads(&[4, 5]);
+        pads.drive(4, Some(false));
+
+        assert_eq!(pads.update(1 << 4 | 1 << 5, 1 << 4 | 1 << 5), 1 << 4 | 1 << 5);
+        assert!(pads.level(4));
+    }
+
+    #[test]
+    fn output_disable() {
+        // should leave a pin with OD set undriven whatever its output enable
+        let mut pads = pads(&[6]);
+        pads.write(pad(6), Width::Word, PADS_IE | PADS_OD | PADS_PDE).unwrap();
+
+        assert_eq!(pads.update(1 << 6, 1 << 6), 0);
+    }
+
+    #[test]
+    fn input_enable() {
+        // should only pass a pin's level into the chip with its input enabled
+        let mut pads = pads(&[]);
+        pads.write(pad(7), Width::Word, PADS_ISO ^ 0x116).unwrap();
+        pads.drive(7, Some(true));
+        pads.drive(8, Some(true));
+
+        assert_eq!(pads.update(0, 0), 0);
+        assert!(pads.level(7));
+
+        pads.write(pad(7), Width::Word, PADS_IE).unwrap();
+        assert_eq!(pads.update(0, 0), 1 << 7);
+    }
+
+    #[test]
+    fn isolation() {
+        // should hold an isolated pad's output as it was, and follow the output again once isolation is removed
+        let mut pads = pads(&[9]);
+        pads.update(1 << 9, 1 << 9);
+        pads.write(pad(9), Width::Word, PADS_ISO | PADS_IE).unwrap();
+
+        assert_eq!(pads.update(0, 0), 1 << 9);
+
+        pads.write(pad(9), Width::Word, PADS_IE).unwrap();
+        assert_eq!(pads.update(0, 1 << 9), 0);
+        assert_eq!(pads.read(pad(9), Width::Word), Ok(PADS_IE));
+    }
+
+    #[test]
+    fn reset_keeps_outside() {
+        // should keep what the host drives through a reset
+        let mut pads = pads(&[10]);
+        pads.drive(10, Some(true));
+        pads.update(0, 0);
+
+        pads.reset();
+
+        assert!(pads.level(10));
+        assert_eq!(pads.update(0, 0), 0);
+        assert_eq!(pads.control(10), 0x116);
+    }
+}
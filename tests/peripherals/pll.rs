@@ -0,0 +1,108 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::peripherals::pll::*;
+    use rp2350_sim::peripherals::xosc::XOSC_HZ;
+    use rp2350_sim::peripherals::{Peripheral, Width};
+
+    /// A PLL from the crystal, with clk_sys at the crystal frequency, set up the way the pico-sdk's pll_init does
+    fn pll(refdiv: u32, fbdiv: u32, postdiv1: u32, postdiv2: u32) -> Pll {
+        let mut pll = Pll::new();
+        pll.set_clocks(XOSC_HZ, XOSC_HZ);
+        pll.write(PLL_CS, Width::Word, refdiv).unwrap();
+        pll.write(PLL_FBDIV_INT, Width::Word, fbdiv).unwrap();
+        pll.write(PLL_PWR, Width::Word, PLL_PWR_POSTDIVPD | PLL_PWR_DSMPD).unwrap();
+        pll.tick(PLL_LOCK_CYCLES);
+        pll.write(PLL_PRIM, Width::Word, postdiv1 << PLL_PRIM_POSTDIV1_SHIFT | postdiv2 << PLL_PRIM_POSTDIV2_SHIFT)
+            .unwrap();
+        pll.write(PLL_PWR, Width::Word, PLL_PWR_DSMPD).unwrap();
+        pll
+    }
+
+    #[test]
+    fn reset_values() {
+        // should come out of reset powered down and unlocked
+        let pll = Pll::new();
+
+        assert_eq!(pll.read(PLL_CS, Width::Word), Ok(0x00000001));
+        assert_eq!(pll.read(PLL_PWR, Width::Word), Ok(0x0000002d));
+        assert_eq!(pll.read(PLL_FBDIV_INT, Width::Word), Ok(0));
+        assert_eq!(pll.read(PLL_PRIM, Width::Word), Ok(0x00077000));
+        assert_eq!(pll.frequency(), 0);
+        assert_eq!(pll.next_tick(), None);
+    }
+
+    #[test]
+    fn frequency() {
+        // should run at the reference / REFDIV * FBDIV_INT / (POSTDIV1 * POSTDIV2)
+        assert_eq!(pll(1, 125, 5, 2).frequency(), 150_000_000);
+        assert_eq!(pll(1, 125, 5, 2).vco_frequency(), 1_500_000_000);
+        assert_eq!(pll(1, 120, 6, 5).frequency(), 48_000_000);
+        assert_eq!(pll(2, 250, 5, 2).frequency(), 150_000_000);
+    }
+
+    #[test]
+    fn lock_time() {
+        // should lock PLL_LOCK_CYCLES reference cycles after the PLL and VCO are powered up
+        let mut pll = Pll::new();
+        pll.set_clocks(XOSC_HZ, 2 * XOSC_HZ);
+        pll.write(PLL_FBDIV_INT, Width::Word, 125).unwrap();
+        pll.write(PLL_PWR, Width::Word, PLL_PWR_POSTDIVPD | PLL_PWR_DSMPD).unwrap();
+        assert_eq!(pll.next_tick(), Some(2 * PLL_LOCK_CYCLES));
+
+        pll.tick(2 * PLL_LOCK_CYCLES - 1);
+        assert!(!pll.is_locked());
+        pll.tick(1);
+        assert_eq!(pll.read(PLL_CS, Width::Word), Ok(PLL_CS_LOCK | 1));
+        assert_eq!(pll.next_tick(), None);
+    }
+
+    #[test]
+    fn no_lock_out_of_range() {
+        // should never lock with FBDIV_INT out of range
+        let mut pll = Pll::new();
+        pll.set_clocks(XOSC_HZ, XOSC_HZ);
+        pll.write(PLL_FBDIV_INT, Width::Word, 8).unwrap();
+        pll.write(PLL_PWR, Width::Word, 0).unwrap();
+
+        assert_eq!(pll.next_tick(), None);
+        assert_eq!(pll.frequency(), 0);
+    }
+
+    #[test]
+    fn relock() {
+        // should lose lock when the feedback divider or the reference changes, but not the post dividers
+        let mut pll = pll(1, 125, 5, 2);
+        assert!(pll.is_locked());
+
+        pll.write(PLL_PRIM, Width::Word, 7 << PLL_PRIM_POSTDIV1_SHIFT | 1 << PLL_PRIM_POSTDIV2_SHIFT).unwrap();
+        assert!(pll.is_locked());
+
+        pll.write(PLL_FBDIV_INT, Width::Word, 100).unwrap();
+        assert!(!pll.is_locked());
+        pll.tick(PLL_LOCK_CYCLES);
+        assert!(pll.is_locked());
+
+        pll.set_clocks(XOSC_HZ / 2, XOSC_HZ);
+        assert!(!pll.is_locked());
+    }
+
+    #[test]
+    fn bypass() {
+        // should pass the reference straight through with BYPASS set
+        let mut pll = Pll::new();
+        pll.set_clocks(XOSC_HZ, XOSC_HZ);
+        pll.write(PLL_CS, Width::Word, PLL_CS_BYPASS | 1).unwrap();
+
+        assert_eq!(pll.frequency(), XOSC_HZ);
+    }
+
+    #[test]
+    fn reset_drops_lock() {
+        // should go back to unlocked and powered down on reset
+        let mut pll = pll(1, 125, 5, 2);
+        pll.reset();
+
+        assert!(!pll.is_locked());
+        assert_eq!(pll.frequency(), 0);
+    }
+}
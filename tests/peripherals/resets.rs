@@ -0,0 +1,58 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::peripherals::bus::{ATOMIC_CLEAR, ATOMIC_SET};
+    use rp2350_sim::peripherals::io_bank0::*;
+    use rp2350_sim::peripherals::resets::*;
+    use rp2350_sim::peripherals::{Peripheral, Width};
+    use rp2350_sim::RP2350;
+
+    #[test]
+    fn reset_values() {
+        // should come out of reset with every block held in reset
+        let resets = Resets::new();
+
+        assert_eq!(resets.read(RESETS_RESET, Width::Word), Ok(0x1fffffff));
+        assert_eq!(resets.read(RESETS_WDSEL, Width::Word), Ok(0));
+        assert_eq!(resets.read(RESETS_RESET_DONE, Width::Word), Ok(0));
+    }
+
+    #[test]
+    fn reset_done() {
+        // should show a block done as soon as it is taken out of reset
+        let mut resets = Resets::new();
+        resets.write(RESETS_RESET, Width::Word, RESET_ALL & !(1 << RESET_UART0 | 1 << RESET_PLL_SYS)).unwrap();
+
+        assert_eq!(resets.read(RESETS_RESET_DONE, Width::Word), Ok(1 << RESET_UART0 | 1 << RESET_PLL_SYS));
+        assert!(!resets.in_reset(RESET_UART0));
+        assert!(resets.in_reset(RESET_UART1));
+    }
+
+    #[test]
+    fn asserted() {
+        // should collect the blocks put into reset, but not those already held there
+        let mut resets = Resets::new();
+        resets.write(RESETS_RESET, Width::Word, 0).unwrap();
+        assert_eq!(resets.take_asserted(), 0);
+
+        resets.write(RESETS_RESET, Width::Word, 1 << RESET_IO_BANK0).unwrap();
+        resets.write(RESETS_RESET, Width::Word, 1 << RESET_IO_BANK0 | 1 << RESET_TIMER0).unwrap();
+        assert_eq!(resets.take_asserted(), 1 << RESET_IO_BANK0 | 1 << RESET_TIMER0);
+        assert_eq!(resets.take_asserted(), 0);
+    }
+
+    #[test]
+    fn resets_block() {
+        // should put a block back to its reset values when firmware puts it into reset
+        let mut rp2350 = RP2350::new();
+        let memory = &mut rp2350.cortex_m33.memory;
+        let ctrl = IO_BANK0_BASE + 8 * 3 + GPIO_CTRL;
+        memory.write_u32(RESETS_BASE + ATOMIC_CLEAR, 1 << RESET_IO_BANK0).unwrap();
+        memory.write_u32(ctrl, Function::Sio as u32).unwrap();
+
+        memory.write_u32(RESETS_BASE + ATOMIC_SET, 1 << RESET_UART0).unwrap();
+        assert_eq!(memory.read_u32(ctrl), Ok(Function::Sio as u32));
+
+        memory.write_u32(RESETS_BASE + ATOMIC_SET, 1 << RESET_IO_BANK0).unwrap();
+        assert_eq!(memory.read_u32(ctrl), Ok(Function::Null as u32));
+    }
+}
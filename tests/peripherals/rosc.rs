@@ -0,0 +1,71 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::peripherals::rosc::*;
+    use rp2350_sim::peripherals::{Peripheral, Width};
+
+    fn status(rosc: &Rosc) -> u32 {
+        rosc.read(ROSC_STATUS, Width::Word).unwrap()
+    }
+
+    #[test]
+    fn reset_values() {
+        // should come out of reset enabled and stable at its nominal frequency
+        let rosc = Rosc::new();
+
+        assert_eq!(rosc.read(ROSC_CTRL, Width::Word), Ok(0x00fabaa0));
+        assert_eq!(rosc.read(ROSC_RANDOM, Width::Word), Ok(0x3f04b16d));
+        assert_eq!(rosc.read(ROSC_PHASE, Width::Word), Ok(0x08));
+        assert_eq!(status(&rosc), ROSC_STATUS_STABLE | ROSC_STATUS_DIV_RUNNING | ROSC_STATUS_ENABLED);
+        assert_eq!(rosc.frequency(), ROSC_HZ);
+    }
+
+    #[test]
+    fn disable() {
+        // should stop when disabled and start again when enabled
+        let mut rosc = Rosc::new();
+        rosc.write(ROSC_CTRL, Width::Word, ROSC_CTRL_DISABLE << ROSC_CTRL_ENABLE_SHIFT | 0xaa0).unwrap();
+        assert_eq!(status(&rosc), 0);
+        assert_eq!(rosc.frequency(), 0);
+
+        rosc.write(ROSC_CTRL, Width::Word, ROSC_CTRL_ENABLE << ROSC_CTRL_ENABLE_SHIFT | 0xaa0).unwrap();
+        assert_eq!(rosc.frequency(), ROSC_HZ);
+    }
+
+    #[test]
+    fn frequency_password() {
+        // should only take FREQA and FREQB writes carrying the password, flagging the others as BADWRITE
+        let mut rosc = Rosc::new();
+        rosc.write(ROSC_FREQA, Width::Word, 0x1234).unwrap();
+        assert_eq!(rosc.read(ROSC_FREQA, Width::Word), Ok(0));
+        assert_eq!(status(&rosc) & ROSC_STATUS_BADWRITE, ROSC_STATUS_BADWRITE);
+
+        rosc.write(ROSC_FREQB, Width::Word, ROSC_FREQ_PASSWD << 16 | 0x1234).unwrap();
+        assert_eq!(rosc.read(ROSC_FREQB, Width::Word), Ok(0x1234));
+    }
+
+    #[test]
+    fn random_bits() {
+        // should read a mix of ones and zeros from RANDOMBIT
+        let rosc = Rosc::new();
+        let bits: Vec<u32> = (0..32).map(|_| rosc.read(ROSC_RANDOMBIT, Width::Word).unwrap()).collect();
+
+        assert!(bits.iter().all(|&bit| bit <= 1));
+        assert!(bits.contains(&0));
+        assert!(bits.contains(&1));
+    }
+
+    #[test]
+    fn count() {
+        // should count COUNT down at the oscillator frequency
+        let mut rosc = Rosc::new();
+        rosc.set_clk_sys(2 * ROSC_HZ);
+        rosc.write(ROSC_COUNT, Width::Word, 4).unwrap();
+        assert_eq!(rosc.next_tick(), Some(2));
+
+        rosc.tick(6);
+        assert_eq!(rosc.read(ROSC_COUNT, Width::Word), Ok(1));
+        rosc.tick(10);
+        assert_eq!(rosc.read(ROSC_COUNT, Width::Word), Ok(0));
+        assert_eq!(rosc.next_tick(), None);
+    }
+}
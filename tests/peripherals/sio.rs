@@ -0,0 +1,131 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::cortex_m33::opcodes::*;
+    use rp2350_sim::cortex_m33::registers::Register;
+    use rp2350_sim::peripherals::sio::*;
+    use rp2350_sim::peripherals::{Peripheral, Width};
+    use rp2350_sim::{BusError, RAM_START_ADDRESS, RP2350, SIO_START_ADDRESS};
+
+    const LED: u32 = 25;
+
+    #[test]
+    fn cpuid() {
+        // should tell the core reading it which core it is
+        let mut sio = Sio::new();
+        assert_eq!(sio.read(SIO_CPUID, Width::Word), Ok(0));
+
+        sio.core = 1;
+        assert_eq!(sio.read(SIO_CPUID, Width::Word), Ok(1));
+    }
+
+    #[test]
+    fn output_set_clear_xor() {
+        // should set, clear and flip output bits through the GPIO_OUT registers
+        let mut sio = Sio::new();
+        sio.write(SIO_GPIO_OUT, Width::Word, 0x0000_00f0).unwrap();
+        sio.write(SIO_GPIO_OUT_SET, Width::Word, 0x8000_0001).unwrap();
+        sio.write(SIO_GPIO_OUT_CLR, Width::Word, 0x0000_0030).unwrap();
+        sio.write(SIO_GPIO_OUT_XOR, Width::Word, 0x0000_0101).unwrap();
+
+        assert_eq!(sio.read(SIO_GPIO_OUT, Width::Word), Ok(0x8000_01c0));
+        assert_eq!(sio.read(SIO_GPIO_OUT_SET, Width::Word), Ok(0));
+        assert_eq!(sio.read(SIO_GPIO_HI_OUT, Width::Word), Ok(0));
+    }
+
+    #[test]
+    fn output_enable() {
+        // should set, clear and flip output enables through the GPIO_OE registers
+        let mut sio = Sio::new();
+        sio.write(SIO_GPIO_OE_SET, Width::Word, 0b1111).unwrap();
+        sio.write(SIO_GPIO_OE_CLR, Width::Word, 0b0011).unwrap();
+        sio.write(SIO_GPIO_OE_XOR, Width::Word, 0b0110).unwrap();
+
+        assert_eq!(sio.read(SIO_GPIO_OE, Width::Word), Ok(0b1010));
+        assert!(sio.is_output(3));
+        assert!(!sio.is_output(2));
+    }
+
+    #[test]
+    fn high_bank() {
+        // should keep the high bank apart, with only the bits that have a GPIO, USB or QSPI pin
+        let mut sio = Sio::new();
+        sio.write(SIO_GPIO_HI_OE, Width::Word, u32::MAX).unwrap();
+        sio.write(SIO_GPIO_HI_OUT_SET, Width::Word, 1 << (QSPI_CSN - 32) | 1 << 16 | 1).unwrap();
+        sio.write(SIO_GPIO_HI_OUT_XOR, Width::Word, 1 << (QSPI_SD0 - 32)).unwrap();
+        sio.write(SIO_GPIO_HI_OE_CLR, Width::Word, 1).unwrap();
+        sio.set_input(QSPI_CSN, true);
+
+        assert_eq!(sio.read(SIO_GPIO_HI_OE, Width::Word), Ok(0xff00_fffe));
+        assert_eq!(sio.read(SIO_GPIO_HI_OUT, Width::Word), Ok(0x1800_0001));
+        assert_eq!(sio.read(SIO_GPIO_OUT, Width::Word), Ok(0));
+        assert_eq!(sio.read(SIO_GPIO_HI_IN, Width::Word), Ok(1 << (QSPI_CSN - 32)));
+        assert!(sio.level(QSPI_CSN));
+        assert!(!sio.level(32));
+    }
+
+    #[test]
+    fn inputs() {
+        // should read GPIO_IN from the pin levels fed back to it rather than from its own outputs
+        let mut sio = Sio::new();
+        sio.set_input(3, true);
+        sio.set_input(4, true);
+        sio.set_input(QSPI_SCK, true);
+        sio.write(SIO_GPIO_OE_SET, Width::Word, 1 << 4 | 1 << 5).unwrap();
+        sio.write(SIO_GPIO_OUT_SET, Width::Word, 1 << 5).unwrap();
+
+        assert_eq!(sio.read(SIO_GPIO_IN, Width::Word), Ok(1 << 3 | 1 << 4));
+        assert_eq!(sio.read(SIO_GPIO_HI_IN, Width::Word), Ok(1 << (QSPI_SCK - 32)));
+    }
+
+    #[test]
+    fn reset_keeps_inputs() {
+        // should stop driving pins on a reset without losing the levels the host holds them at
+        let mut sio = Sio::new();
+        sio.set_input(7, true);
+        sio.write(SIO_GPIO_OE_SET, Width::Word, 1 << 8).unwrap();
+        sio.write(SIO_GPIO_OUT_SET, Width::Word, 1 << 8).unwrap();
+
+        sio.reset();
+
+        assert_eq!(sio.read(SIO_GPIO_IN, Width::Word), Ok(1 << 7));
+        assert_eq!(sio.read(SIO_GPIO_OUT, Width::Word), Ok(0));
+    }
+
+    #[test]
+    fn unmapped() {
+        // should not answer registers it doesn't model, or the atomic aliases the SIO doesn't have
+        let mut sio = Sio::new();
+
+        assert_eq!(sio.read(0x0c, Width::Word), Err(BusError::Unmapped));
+        assert_eq!(sio.write(0x180, Width::Word, 0), Err(BusError::Unmapped));
+
+        let mut rp2350 = RP2350::new();
+        let memory = &mut rp2350.cortex_m33.memory;
+        assert_eq!(memory.write_u32(SIO_START_ADDRESS + 0x2000 + SIO_GPIO_OUT, 1), Err(BusError::Unmapped));
+    }
+
+    #[test]
+    fn led_output() {
+        // should let firmware turn the LED output on and off through GPIO_OUT_SET and GPIO_OUT_CLR
+        let mut rp2350 = RP2350::new();
+        let registers = rp2350.cortex_m33.registers;
+        let opcodes = [
+            StrImmediateT1::opcode(&registers.r0, &registers.r1, SIO_GPIO_OE_SET as u16),
+            StrImmediateT1::opcode(&registers.r0, &registers.r1, SIO_GPIO_OUT_SET as u16),
+            StrImmediateT1::opcode(&registers.r0, &registers.r1, SIO_GPIO_OUT_CLR as u16),
+        ];
+        let code: Vec<u8> = opcodes.iter().flat_map(|opcode| opcode.to_le_bytes()).collect();
+        rp2350.memory_mut().program(RAM_START_ADDRESS, &code).unwrap();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+        rp2350.cortex_m33.registers.r0.set(1 << LED);
+        rp2350.cortex_m33.registers.r1.set(SIO_START_ADDRESS);
+
+        rp2350.execute_instruction();
+        assert_eq!(rp2350.memory_mut().sio().out(), 0);
+        rp2350.execute_instruction();
+        assert_eq!(rp2350.memory_mut().sio().out(), 1 << LED);
+        rp2350.execute_instruction();
+        assert_eq!(rp2350.memory_mut().sio().out(), 0);
+        assert!(rp2350.memory_mut().sio().is_output(LED));
+    }
+}
@@ -0,0 +1,98 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::peripherals::ticks::*;
+    use rp2350_sim::peripherals::{Peripheral, Width};
+
+    fn register(generator: u32, offset: u32) -> u32 {
+        generator * 0xc + offset
+    }
+
+    #[test]
+    fn reset_values() {
+        // should come out of reset with every generator stopped
+        let ticks = Ticks::new();
+
+        for generator in [TICK_PROC0, TICK_TIMER0, TICK_WATCHDOG, TICK_RISCV] {
+            assert_eq!(ticks.read(register(generator, TICK_CTRL), Width::Word), Ok(0));
+            assert_eq!(ticks.read(register(generator, TICK_CYCLES), Width::Word), Ok(0));
+            assert_eq!(ticks.read(register(generator, TICK_COUNT), Width::Word), Ok(0));
+            assert!(!ticks.is_running(generator));
+        }
+    }
+
+    #[test]
+    fn start() {
+        // should show RUNNING once a generator is enabled, with the cycles between its ticks
+        let mut ticks = Ticks::new();
+        ticks.write(register(TICK_TIMER1, TICK_CYCLES), Width::Word, 0xfff).unwrap();
+        ticks.write(register(TICK_TIMER1, TICK_CTRL), Width::Word, TICK_CTRL_ENABLE).unwrap();
+
+        assert!(ticks.is_running(TICK_TIMER1));
+        assert!(!ticks.is_running(TICK_TIMER0));
+        assert_eq!(ticks.cycles(TICK_TIMER1), TICK_CYCLES_MASK);
+        let ctrl = TICK_CTRL_ENABLE | TICK_CTRL_RUNNING;
+        assert_eq!(ticks.read(register(TICK_TIMER1, TICK_CTRL), Width::Word), Ok(ctrl));
+        assert_eq!(ticks.read(register(TICK_TIMER1, TICK_COUNT), Width::Word), Ok(TICK_CYCLES_MASK));
+    }
+
+    #[test]
+    fn count_down() {
+        // should count COUNT down a clk_ref cycle at a time, ticking and going back to CYCLES as it reaches zero
+        let mut ticks = Ticks::new();
+        ticks.set_clocks(12_000_000, 12_000_000);
+        ticks.write(register(TICK_PROC0, TICK_CYCLES), Width::Word, 4).unwrap();
+        ticks.write(register(TICK_PROC0, TICK_CTRL), Width::Word, TICK_CTRL_ENABLE).unwrap();
+        assert_eq!(ticks.next_tick(), Some(4));
+
+        ticks.tick(3);
+        assert_eq!(ticks.read(register(TICK_PROC0, TICK_COUNT), Width::Word), Ok(1));
+        assert_eq!(ticks.take_ticks(TICK_PROC0), 0);
+
+        ticks.tick(1);
+        assert_eq!(ticks.read(register(TICK_PROC0, TICK_COUNT), Width::Word), Ok(4));
+        assert_eq!(ticks.take_ticks(TICK_PROC0), 1);
+        assert_eq!(ticks.take_ticks(TICK_PROC0), 0);
+
+        ticks.tick(10);
+        assert_eq!(ticks.read(register(TICK_PROC0, TICK_COUNT), Width::Word), Ok(2));
+        assert_eq!(ticks.take_ticks(TICK_PROC0), 2);
+
+        // Stopping the generator clears COUNT, and it starts from CYCLES again
+        ticks.write(register(TICK_PROC0, TICK_CTRL), Width::Word, 0).unwrap();
+        assert_eq!(ticks.read(register(TICK_PROC0, TICK_COUNT), Width::Word), Ok(0));
+        assert_eq!(ticks.next_tick(), None);
+        ticks.write(register(TICK_PROC0, TICK_CTRL), Width::Word, TICK_CTRL_ENABLE).unwrap();
+        assert_eq!(ticks.read(register(TICK_PROC0, TICK_COUNT), Width::Word), Ok(4));
+    }
+
+    #[test]
+    fn counts_clk_ref() {
+        // should count clk_ref cycles, not system clock cycles, with clk_ref at a quarter of clk_sys
+        let mut ticks = Ticks::new();
+        ticks.set_clocks(12_000_000, 48_000_000);
+        ticks.write(register(TICK_TIMER0, TICK_CYCLES), Width::Word, 12).unwrap();
+        ticks.write(register(TICK_TIMER0, TICK_CTRL), Width::Word, TICK_CTRL_ENABLE).unwrap();
+        assert_eq!(ticks.next_tick(), Some(48));
+
+        ticks.tick(47);
+        assert_eq!(ticks.take_ticks(TICK_TIMER0), 0);
+        ticks.tick(1);
+        assert_eq!(ticks.take_ticks(TICK_TIMER0), 1);
+
+        ticks.tick(48 * 1000);
+        assert_eq!(ticks.take_ticks(TICK_TIMER0), 1000);
+    }
+
+    #[test]
+    fn stopped_clk_ref() {
+        // should not tick while clk_ref isn't running
+        let mut ticks = Ticks::new();
+        ticks.write(register(TICK_PROC0, TICK_CYCLES), Width::Word, 1).unwrap();
+        ticks.write(register(TICK_PROC0, TICK_CTRL), Width::Word, TICK_CTRL_ENABLE).unwrap();
+
+        assert_eq!(ticks.next_tick(), None);
+        ticks.tick(1000);
+        assert_eq!(ticks.take_ticks(TICK_PROC0), 0);
+        assert_eq!(ticks.read(register(TICK_PROC0, TICK_COUNT), Width::Word), Ok(1));
+    }
+}
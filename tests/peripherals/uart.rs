@@ -0,0 +1,145 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::peripherals::uart::*;
+    use rp2350_sim::peripherals::Peripheral;
+
+    /// Sets eight data bit frames at a sixteenth of clk_peri, which runs at clk_sys, so each byte takes 160 cycles
+    fn configured() -> Uart {
+        let mut uart = Uart::new();
+        uart.set_clocks(48_000_000, 48_000_000);
+        uart.write(UART_IBRD, 1);
+        uart.write(UART_LCR_H, (UART_LCR_H_FEN | UART_LCR_H_WLEN_MASK) as u8);
+        uart
+    }
+
+    #[test]
+    fn reset_values() {
+        // should come out of reset with the transmitter and receiver enabled but the UART off, and both FIFOs empty
+        let uart = Uart::new();
+
+        assert_eq!(uart.read(UART_CR), 0);
+        assert_eq!(uart.read(UART_CR + 1), ((UART_CR_TXE | UART_CR_RXE) >> 8) as u8);
+        assert_eq!(uart.read(UART_FR), (UART_FR_TXFE | UART_FR_RXFE) as u8);
+    }
+
+    #[test]
+    fn transmit_when_enabled() {
+        // should only send bytes written to DR while UARTEN and TXE are set
+        let mut uart = configured();
+        uart.write(UART_DR, b'x');
+        uart.write(UART_CR, UART_CR_UARTEN as u8);
+        uart.write(UART_DR, b'o');
+        uart.write(UART_DR + 1, 0xff);
+        uart.write(UART_DR, b'k');
+        uart.tick(320);
+
+        assert_eq!(uart.take_output(), b"ok");
+        assert!(uart.output.is_empty());
+    }
+
+    #[test]
+    fn registers_read_back() {
+        // should keep what is written to the baud rate and control registers
+        let mut uart = Uart::new();
+        uart.write(0x24, 0x34);
+        uart.write(0x25, 0x12);
+
+        assert_eq!(uart.read(0x24), 0x34);
+        assert_eq!(uart.read(0x25), 0x12);
+    }
+
+    #[test]
+    fn reset_keeps_output() {
+        // should go back to the reset values without losing output the host hasn't taken
+        let mut uart = configured();
+        uart.write(UART_CR, UART_CR_UARTEN as u8);
+        uart.write(UART_DR, b'!');
+        uart.tick(160);
+
+        uart.reset();
+
+        assert_eq!(uart.read(UART_CR) & UART_CR_UARTEN as u8, 0);
+        assert_eq!(uart.output, b"!");
+    }
+
+    #[test]
+    fn baud_rate() {
+        // should work the baud rate out from clk_peri and the integer and fractional divisors
+        let mut uart = Uart::new();
+        assert_eq!(uart.baud_rate(150_000_000), 0);
+
+        uart.write(UART_IBRD, 81);
+        uart.write(UART_FBRD, 24);
+        assert_eq!(uart.baud_rate(150_000_000), 115_207);
+        assert_eq!(uart.baud_rate(48_000_000), 36_866);
+    }
+
+    #[test]
+    fn transmit_at_baud_rate() {
+        // should send each byte over the bits of its frame, showing BUSY until the FIFO has drained
+        let mut uart = configured();
+        uart.write(UART_CR, UART_CR_UARTEN as u8);
+        uart.write(UART_DR, b'a');
+        uart.write(UART_DR, b'b');
+
+        assert_eq!(uart.read(UART_FR), (UART_FR_BUSY | UART_FR_RXFE) as u8);
+        assert_eq!(uart.next_tick(), Some(160));
+        uart.tick(159);
+        assert!(uart.output.is_empty());
+
+        uart.tick(1);
+        assert_eq!(uart.output, b"a");
+        assert_eq!(uart.read(UART_FR), (UART_FR_TXFE | UART_FR_BUSY | UART_FR_RXFE) as u8);
+
+        uart.tick(160);
+        assert_eq!(uart.output, b"ab");
+        assert_eq!(uart.read(UART_FR), (UART_FR_TXFE | UART_FR_RXFE) as u8);
+        assert_eq!(uart.next_tick(), None);
+    }
+
+    #[test]
+    fn frame_bits() {
+        // should take a start bit, the data bits, parity and the stop bits to send a byte
+        let mut uart = configured();
+        uart.write(UART_CR, UART_CR_UARTEN as u8);
+        uart.write(UART_LCR_H, (UART_LCR_H_PEN | UART_LCR_H_STP2 | UART_LCR_H_FEN) as u8);
+        uart.write(UART_DR, b'a');
+
+        // Five data bits, so 1 + 5 + 1 + 2
+        assert_eq!(uart.next_tick(), Some(16 * 9));
+    }
+
+    #[test]
+    fn full_without_fifo() {
+        // should hold a single byte behind the one being sent while the FIFO is off, losing any more
+        let mut uart = configured();
+        uart.write(UART_CR, UART_CR_UARTEN as u8);
+        uart.write(UART_LCR_H, UART_LCR_H_WLEN_MASK as u8);
+        uart.write(UART_DR, b'a');
+        uart.write(UART_DR, b'b');
+        uart.write(UART_DR, b'c');
+
+        assert_eq!(uart.read(UART_FR), (UART_FR_TXFF | UART_FR_BUSY | UART_FR_RXFE) as u8);
+        uart.tick(1000);
+        assert_eq!(uart.take_output(), b"ab");
+    }
+
+    #[test]
+    fn no_baud_rate() {
+        // should not send anything until the divisors and clk_peri give it a baud rate
+        let mut uart = Uart::new();
+        uart.write(UART_CR, UART_CR_UARTEN as u8);
+        uart.write(UART_DR, b'a');
+        uart.tick(1000);
+
+        assert!(uart.output.is_empty());
+        assert_eq!(uart.next_tick(), None);
+        assert_eq!(uart.read(UART_FR) as u32 & UART_FR_BUSY, UART_FR_BUSY);
+
+        uart.set_clocks(48_000_000, 48_000_000);
+        uart.write(UART_IBRD, 1);
+        // Five data bits out of reset, so seven bits of 16 cycles
+        uart.tick(112);
+        assert_eq!(uart.take_output(), b"a");
+    }
+}
@@ -0,0 +1,129 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::peripherals::bus::{Bus, ATOMIC_CLEAR, ATOMIC_SET};
+    use rp2350_sim::peripherals::xosc::*;
+    use rp2350_sim::peripherals::{Peripheral, Width};
+
+    /// The crystal cycles the startup delay of 1 ms the pico-sdk sets takes, in 256s
+    const STARTUP_DELAY: u32 = 47;
+
+    /// An XOSC at the clk_sys frequency, enabled the way the pico-sdk's xosc_init does
+    fn enabled(clk_sys_hz: u32) -> Xosc {
+        let mut xosc = Xosc::new();
+        xosc.write(XOSC_CTRL, Width::Word, XOSC_FREQ_RANGE_1_15MHZ).unwrap();
+        xosc.write(XOSC_STARTUP, Width::Word, STARTUP_DELAY).unwrap();
+        xosc.write(XOSC_CTRL, Width::Word, XOSC_FREQ_RANGE_1_15MHZ | XOSC_CTRL_ENABLE << XOSC_CTRL_ENABLE_SHIFT)
+            .unwrap();
+        xosc.set_clk_sys(clk_sys_hz);
+        xosc
+    }
+
+    fn status(xosc: &Xosc) -> u32 {
+        xosc.read(XOSC_STATUS, Width::Word).unwrap()
+    }
+
+    #[test]
+    fn reset_values() {
+        // should come out of reset disabled and not stable, with the startup delay at 0xc4
+        let xosc = Xosc::new();
+
+        assert_eq!(xosc.read(XOSC_CTRL, Width::Word), Ok(0x00d1eaa0));
+        assert_eq!(status(&xosc), 0);
+        assert_eq!(xosc.read(XOSC_STARTUP, Width::Word), Ok(0xc4));
+        assert_eq!(xosc.read(XOSC_COUNT, Width::Word), Ok(0));
+        assert_eq!(xosc.frequency(), 0);
+        assert_eq!(xosc.next_tick(), None);
+    }
+
+    #[test]
+    fn stable_after_startup_delay() {
+        // should become stable DELAY * 256 crystal cycles after it is enabled, timed in clk_sys
+        let mut xosc = enabled(XOSC_HZ);
+        assert_eq!(status(&xosc), XOSC_STATUS_ENABLED);
+        assert_eq!(xosc.frequency(), XOSC_HZ);
+        assert_eq!(xosc.next_tick(), Some(STARTUP_DELAY as u64 * 256));
+
+        xosc.tick(STARTUP_DELAY as u64 * 256 - 1);
+        assert_eq!(status(&xosc) & XOSC_STATUS_STABLE, 0);
+        xosc.tick(1);
+        assert_eq!(status(&xosc), XOSC_STATUS_STABLE | XOSC_STATUS_ENABLED);
+        assert_eq!(xosc.next_tick(), None);
+    }
+
+    #[test]
+    fn startup_at_clk_sys() {
+        // should count the startup delay in system clock cycles at the clk_sys frequency
+        let xosc = enabled(150_000_000);
+
+        assert_eq!(xosc.next_tick(), Some(STARTUP_DELAY as u64 * 256 * 150 / 12));
+    }
+
+    #[test]
+    fn startup_x4() {
+        // should take four times as long with STARTUP.X4 set
+        let mut xosc = Xosc::new();
+        xosc.write(XOSC_STARTUP, Width::Word, XOSC_STARTUP_X4 | 1).unwrap();
+        xosc.write(XOSC_CTRL, Width::Word, XOSC_FREQ_RANGE_1_15MHZ | XOSC_CTRL_ENABLE << XOSC_CTRL_ENABLE_SHIFT)
+            .unwrap();
+        xosc.set_clk_sys(XOSC_HZ);
+
+        assert_eq!(xosc.next_tick(), Some(4 * 256));
+    }
+
+    #[test]
+    fn disable() {
+        // should stop and lose stability when disabled
+        let mut xosc = enabled(XOSC_HZ);
+        xosc.tick(STARTUP_DELAY as u64 * 256);
+        xosc.write(XOSC_CTRL, Width::Word, XOSC_FREQ_RANGE_1_15MHZ | XOSC_CTRL_DISABLE << XOSC_CTRL_ENABLE_SHIFT)
+            .unwrap();
+
+        assert_eq!(status(&xosc), 0);
+        assert_eq!(xosc.frequency(), 0);
+    }
+
+    #[test]
+    fn bad_write() {
+        // should flag an invalid enable value or frequency range until BADWRITE is cleared
+        let mut xosc = Xosc::new();
+        xosc.write(XOSC_CTRL, Width::Word, 0x123 << XOSC_CTRL_ENABLE_SHIFT | XOSC_FREQ_RANGE_1_15MHZ).unwrap();
+        assert_eq!(status(&xosc), XOSC_STATUS_BADWRITE);
+
+        xosc.write(XOSC_STATUS, Width::Word, XOSC_STATUS_BADWRITE).unwrap();
+        assert_eq!(status(&xosc), 0);
+
+        xosc.write(XOSC_CTRL, Width::Word, 0x555).unwrap();
+        assert_eq!(status(&xosc) & XOSC_STATUS_BADWRITE, XOSC_STATUS_BADWRITE);
+    }
+
+    #[test]
+    fn bad_write_through_aliases() {
+        // should only clear BADWRITE when a write through the atomic aliases targets it
+        let mut bus = Bus::new();
+        bus.register(XOSC_BASE, XOSC_SIZE, Xosc::new());
+        bus.write(XOSC_BASE + XOSC_CTRL, Width::Word, 0x555).unwrap();
+
+        let badwrite = |bus: &Bus| bus.read(XOSC_BASE + XOSC_STATUS, Width::Word).unwrap() & XOSC_STATUS_BADWRITE;
+
+        bus.write(XOSC_BASE + ATOMIC_CLEAR + XOSC_STATUS, Width::Word, 1).unwrap();
+        assert_eq!(badwrite(&bus), XOSC_STATUS_BADWRITE);
+
+        bus.write(XOSC_BASE + ATOMIC_SET + XOSC_STATUS, Width::Word, XOSC_STATUS_BADWRITE).unwrap();
+        assert_eq!(badwrite(&bus), 0);
+    }
+
+    #[test]
+    fn count() {
+        // should count COUNT down at the crystal frequency
+        let mut xosc = enabled(3 * XOSC_HZ);
+        xosc.write(XOSC_COUNT, Width::Word, 10).unwrap();
+        assert_eq!(xosc.next_tick(), Some(3));
+
+        xosc.tick(3);
+        assert_eq!(xosc.read(XOSC_COUNT, Width::Word), Ok(9));
+        xosc.tick(2);
+        assert_eq!(xosc.read(XOSC_COUNT, Width::Word), Ok(9));
+        xosc.tick(100);
+        assert_eq!(xosc.read(XOSC_COUNT, Width::Word), Ok(0));
+    }
+}
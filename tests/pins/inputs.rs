@@ -0,0 +1,88 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::peripherals::bus::ATOMIC_CLEAR;
+    use rp2350_sim::peripherals::io_bank0::*;
+    use rp2350_sim::peripherals::pads_bank0::*;
+    use rp2350_sim::peripherals::sio::*;
+    use rp2350_sim::pins::{PinState, Source};
+    use rp2350_sim::{RP2350, SIO_START_ADDRESS};
+
+    const BUTTON: u32 = 12;
+
+    /// Sets a pin up the way the pico-sdk's gpio_init does: SIO function, input enabled and isolation removed
+    fn gpio_init(rp2350: &mut RP2350, pin: u32) {
+        let memory = &mut rp2350.cortex_m33.memory;
+        let pad = PADS_BANK0_BASE + PADS_GPIO0 + 4 * pin;
+        let control = memory.read_u32(pad).unwrap();
+        memory.write_u32(pad, (control | PADS_IE) & !PADS_OD).unwrap();
+        memory.write_u32(IO_BANK0_BASE + 8 * pin + GPIO_CTRL, Function::Sio as u32).unwrap();
+        memory.write_u32(pad + ATOMIC_CLEAR, PADS_ISO).unwrap();
+    }
+
+    fn gpio_in(rp2350: &RP2350) -> u32 {
+        rp2350.cortex_m33.memory.read_u32(SIO_START_ADDRESS + SIO_GPIO_IN).unwrap()
+    }
+
+    #[test]
+    fn button_press() {
+        // should show a pressed button in GPIO_IN, and the pad's pull once it is let go
+        let mut rp2350 = RP2350::new();
+        gpio_init(&mut rp2350, BUTTON);
+
+        rp2350.pins().set_input(BUTTON, true);
+        assert_eq!(gpio_in(&rp2350), 1 << BUTTON);
+        assert_eq!(rp2350.pins().pin_state(BUTTON), PinState { level: true, source: Source::Input });
+
+        rp2350.pins().release(BUTTON);
+        assert_eq!(gpio_in(&rp2350), 0);
+        assert_eq!(rp2350.pins().pin_state(BUTTON), PinState { level: false, source: Source::PullDown });
+    }
+
+    #[test]
+    fn pull_up() {
+        // should leave a released pin pulled up when its pad pulls it up
+        let mut rp2350 = RP2350::new();
+        gpio_init(&mut rp2350, BUTTON);
+        let pad = PADS_BANK0_BASE + PADS_GPIO0 + 4 * BUTTON;
+        rp2350.cortex_m33.memory.write_u32(pad, PADS_IE | PADS_PUE).unwrap();
+
+        assert_eq!(rp2350.pins().pin_state(BUTTON), PinState { level: true, source: Source::PullUp });
+        rp2350.pins().set_input(BUTTON, false);
+        assert_eq!(gpio_in(&rp2350), 0);
+    }
+
+    #[test]
+    fn analog_threshold() {
+        // should see a voltage as high above half the IO supply
+        let mut rp2350 = RP2350::new();
+        gpio_init(&mut rp2350, BUTTON);
+
+        rp2350.pins().set_analog(BUTTON, 1.7);
+        assert_eq!(gpio_in(&rp2350), 1 << BUTTON);
+        assert_eq!(rp2350.pins().pin_state(BUTTON).source, Source::Analog(1.7));
+
+        rp2350.pins().set_analog(BUTTON, 1.6);
+        assert_eq!(gpio_in(&rp2350), 0);
+    }
+
+    #[test]
+    fn analog_threshold_at_1v8() {
+        // should take the threshold from the IO supply VOLTAGE_SELECT picks
+        let mut rp2350 = RP2350::new();
+        gpio_init(&mut rp2350, BUTTON);
+        rp2350.cortex_m33.memory.write_u32(PADS_BANK0_BASE + PADS_VOLTAGE_SELECT, 1).unwrap();
+
+        rp2350.pins().set_analog(BUTTON, 1.0);
+        assert_eq!(gpio_in(&rp2350), 1 << BUTTON);
+        rp2350.pins().set_analog(BUTTON, 0.8);
+        assert_eq!(gpio_in(&rp2350), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn pin_out_of_range() {
+        // should refuse a pin that isn't in bank 0
+        let mut rp2350 = RP2350::new();
+        rp2350.pins().set_input(GPIO_COUNT, true);
+    }
+}
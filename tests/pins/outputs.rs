@@ -0,0 +1,128 @@
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use rp2350_sim::cortex_m33::opcodes::*;
+    use rp2350_sim::cortex_m33::registers::Register;
+    use rp2350_sim::peripherals::bus::ATOMIC_CLEAR;
+    use rp2350_sim::peripherals::io_bank0::*;
+    use rp2350_sim::peripherals::pads_bank0::*;
+    use rp2350_sim::peripherals::sio::*;
+    use rp2350_sim::pins::{OutputChange, PinState, Source};
+    use rp2350_sim::{RAM_START_ADDRESS, RP2350, SIO_START_ADDRESS};
+
+    const LED: u32 = 25;
+    const BUTTON: u32 = 12;
+
+    /// Sets a pin up the way the pico-sdk's gpio_init does: SIO function, input enabled and isolation removed
+    fn gpio_init(rp2350: &mut RP2350, pin: u32) {
+        let memory = &mut rp2350.cortex_m33.memory;
+        let pad = PADS_BANK0_BASE + PADS_GPIO0 + 4 * pin;
+        let control = memory.read_u32(pad).unwrap();
+        memory.write_u32(pad, (control | PADS_IE) & !PADS_OD).unwrap();
+        memory.write_u32(IO_BANK0_BASE + 8 * pin + GPIO_CTRL, Function::Sio as u32).unwrap();
+        memory.write_u32(pad + ATOMIC_CLEAR, PADS_ISO).unwrap();
+    }
+
+    /// Sets the LED up and runs a loop that enables its output, then toggles it every other cycle
+    fn blinky() -> RP2350 {
+        let mut rp2350 = RP2350::new();
+        gpio_init(&mut rp2350, LED);
+
+        let registers = rp2350.cortex_m33.registers;
+        let (r0, r1) = (&registers.r0, &registers.r1);
+        let code: Vec<u8> = [
+            StrImmediateT1::opcode(r0, r1, SIO_GPIO_OE_SET as u16),
+            StrImmediateT1::opcode(r0, r1, SIO_GPIO_OUT_XOR as u16),
+            BT2::opcode(-6i16 as u16),
+        ]
+        .iter()
+        .flat_map(|opcode| opcode.to_le_bytes())
+        .collect();
+        rp2350.memory_mut().program(RAM_START_ADDRESS, &code).unwrap();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+        rp2350.cortex_m33.registers.r0.set(1 << LED);
+        rp2350.cortex_m33.registers.r1.set(SIO_START_ADDRESS);
+        rp2350
+    }
+
+    fn record(rp2350: &mut RP2350) -> Rc<RefCell<Vec<OutputChange>>> {
+        let changes = Rc::new(RefCell::new(Vec::new()));
+        let recorded = changes.clone();
+        rp2350.pins().on_output_change(move |_, change| recorded.borrow_mut().push(change));
+        changes
+    }
+
+    #[test]
+    fn blink_timing() {
+        // should call back with each LED change, stamped with the cycle of the store that made it
+        let mut rp2350 = blinky();
+        let changes = record(&mut rp2350);
+        for _ in 0..6 {
+            rp2350.execute_instruction();
+        }
+
+        let change = |output, cycle| OutputChange { pin: LED, output: Some(output), cycle };
+        let expected = [change(false, 0), change(true, 1), change(false, 3), change(true, 5)];
+        assert_eq!(*changes.borrow(), expected);
+    }
+
+    #[test]
+    fn output_state() {
+        // should report a pin the chip drives as an output at the level it drives
+        let mut rp2350 = blinky();
+        rp2350.execute_instruction();
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.pins().pin_state(LED), PinState { level: true, source: Source::Output });
+        assert!(rp2350.pins().is_high(LED));
+    }
+
+    #[test]
+    fn output_released() {
+        // should call back with None once the chip stops driving a pin
+        let mut rp2350 = blinky();
+        rp2350.execute_instruction();
+        let changes = record(&mut rp2350);
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS + 2);
+        rp2350.cortex_m33.registers.r1.set(SIO_START_ADDRESS + SIO_GPIO_OE_CLR - SIO_GPIO_OUT_XOR);
+        rp2350.execute_instruction();
+
+        assert_eq!(*changes.borrow(), [OutputChange { pin: LED, output: None, cycle: 1 }]);
+        assert_eq!(rp2350.pins().pin_state(LED).source, Source::PullDown);
+    }
+
+    #[test]
+    fn no_changes_before_callback() {
+        // should only pass on changes made once the callback is registered
+        let mut rp2350 = blinky();
+        rp2350.execute_instruction();
+        rp2350.execute_instruction();
+        let changes = record(&mut rp2350);
+        rp2350.execute_instruction();
+        rp2350.execute_instruction();
+
+        assert_eq!(*changes.borrow(), [OutputChange { pin: LED, output: Some(false), cycle: 3 }]);
+    }
+
+    #[test]
+    fn external_model() {
+        // should let a callback drive an input, here wiring the LED back to the button
+        let mut rp2350 = blinky();
+        gpio_init(&mut rp2350, BUTTON);
+        rp2350.pins().on_output_change(|pins, change| {
+            if let Some(level) = change.output {
+                pins.set_input(BUTTON, level);
+            }
+        });
+
+        let gpio_in = SIO_START_ADDRESS + SIO_GPIO_IN;
+        rp2350.execute_instruction();
+        rp2350.execute_instruction();
+        assert_eq!(rp2350.cortex_m33.memory.read_u32(gpio_in).unwrap() & 1 << BUTTON, 1 << BUTTON);
+        rp2350.execute_instruction();
+        rp2350.execute_instruction();
+        assert_eq!(rp2350.cortex_m33.memory.read_u32(gpio_in).unwrap() & 1 << BUTTON, 0);
+    }
+}
@@ -0,0 +1,133 @@
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::io::{self, Write};
+    use std::rc::Rc;
+
+    use rp2350_sim::cortex_m33::opcodes::*;
+    use rp2350_sim::cortex_m33::registers::Register;
+    use rp2350_sim::peripherals::clocks::{Clock, CLK_CTRL, CLK_CTRL_ENABLE, CLOCKS_BASE};
+    use rp2350_sim::peripherals::uart::*;
+    use rp2350_sim::runner::{register_dump, Runner, Stop};
+    use rp2350_sim::{RAM_START_ADDRESS, RP2350};
+
+    const LOOP: u32 = RAM_START_ADDRESS + 0x100;
+
+    /// Collects what the runner writes so the test can look at it afterwards
+    #[derive(Clone, Default)]
+    struct Output(Rc<RefCell<Vec<u8>>>);
+
+    impl Write for Output {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.borrow_mut().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// Programs a loop that increments r0 and stores its low byte to the address in r1
+    fn setup(store_to: u32) -> RP2350 {
+        let mut rp2350: RP2350 = RP2350::new();
+        let registers = rp2350.cortex_m33.registers;
+        let opcodes = [
+            AddsT2::opcode(&registers.r0, 1),
+            StrbImmediateT1::opcode(&registers.r0, &registers.r1, 0),
+            BT2::opcode(-8i16 as u16),
+        ];
+        let code: Vec<u8> = opcodes.iter().flat_map(|opcode| opcode.to_le_bytes()).collect();
+        rp2350.memory_mut().program(LOOP, &code).unwrap();
+
+        rp2350.cortex_m33.registers.pc.set(LOOP);
+        rp2350.cortex_m33.registers.r0.set(0x40);
+        rp2350.cortex_m33.registers.r1.set(store_to);
+        rp2350
+    }
+
+    #[test]
+    fn instruction_limit() {
+        // should stop after the given number of instructions
+        let mut rp2350 = setup(RAM_START_ADDRESS + 0x800);
+        let mut runner = Runner::new();
+        runner.max_instructions = Some(7);
+
+        let summary = runner.run(&mut rp2350).unwrap();
+
+        assert_eq!(summary.stop, Stop::InstructionLimit);
+        assert_eq!(summary.instructions, 7);
+        assert_eq!(rp2350.cortex_m33.registers.r0.get(), 0x43);
+    }
+
+    #[test]
+    fn cycle_limit() {
+        // should stop once the core has run the given number of cycles
+        let mut rp2350 = setup(RAM_START_ADDRESS + 0x800);
+        let mut runner = Runner::new();
+        runner.max_cycles = Some(rp2350.cortex_m33.cycles + 5);
+
+        let summary = runner.run(&mut rp2350).unwrap();
+
+        assert_eq!(summary.stop, Stop::CycleLimit);
+        assert_eq!(summary.instructions, 5);
+    }
+
+    #[test]
+    fn breakpoint() {
+        // should stop with the PC at the breakpoint, before running the instruction there
+        let mut rp2350 = setup(RAM_START_ADDRESS + 0x800);
+        let mut runner = Runner::new();
+        runner.breakpoint = Some(LOOP + 4);
+
+        let summary = runner.run(&mut rp2350).unwrap();
+
+        assert_eq!(summary.stop, Stop::Breakpoint(LOOP + 4));
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), LOOP + 4);
+        assert_eq!(rp2350.cortex_m33.registers.r0.get(), 0x41);
+    }
+
+    #[test]
+    fn locked_up() {
+        // should stop when the core locks up, here from a bus fault with no vector table to handle it
+        let mut rp2350 = setup(0xa000_0000);
+        let mut runner = Runner::new();
+
+        let summary = runner.run(&mut rp2350).unwrap();
+
+        assert_eq!(summary.stop, Stop::LockedUp);
+        assert!(rp2350.cortex_m33.locked_up);
+    }
+
+    #[test]
+    fn uart0_output() {
+        // should pass on what the firmware writes to UART0 once it is enabled
+        let mut rp2350 = setup(UART0_BASE);
+        let memory = &mut rp2350.cortex_m33.memory;
+        memory.write_u32(CLOCKS_BASE + 0xc * Clock::Peri as u32 + CLK_CTRL, CLK_CTRL_ENABLE).unwrap();
+        memory.write_u32(UART0_BASE + UART_IBRD, 1).unwrap();
+        memory.write_u32(UART0_BASE + UART_LCR_H, UART_LCR_H_FEN | UART_LCR_H_WLEN_MASK).unwrap();
+        memory.write_u32(UART0_BASE + UART_CR, UART_CR_UARTEN | UART_CR_TXE).unwrap();
+        let output = Output::default();
+        let mut runner = Runner::new();
+        // At a sixteenth of clk_sys a byte takes 160 cycles, so three are sent while the rest wait in the FIFO
+        runner.max_instructions = Some(500);
+        runner.uart0 = Some(Box::new(output.clone()));
+
+        runner.run(&mut rp2350).unwrap();
+
+        assert_eq!(*output.0.borrow(), b"ABC");
+    }
+
+    #[test]
+    fn dump() {
+        // should list every core register by name
+        let mut rp2350 = setup(RAM_START_ADDRESS + 0x800);
+
+        let dump = register_dump(&mut rp2350);
+
+        assert!(dump.contains("r0   0x00000040"));
+        assert!(dump.contains(&format!("pc   {:#010x}", LOOP)));
+        assert!(dump.contains("xpsr 0x01000000"));
+    }
+}
@@ -0,0 +1,122 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::cortex_m33::opcodes::*;
+    use rp2350_sim::cortex_m33::registers::Register;
+    use rp2350_sim::peripherals::rosc::ROSC_HZ;
+    use rp2350_sim::semihosting::*;
+    use rp2350_sim::{FLASH_START_ADDRESS, RAM_START_ADDRESS, RP2350};
+
+    const DATA: u32 = RAM_START_ADDRESS + 0x100;
+    const BLOCK: u32 = RAM_START_ADDRESS + 0x200;
+
+    /// Makes a semihosting call from firmware and returns r0 afterwards
+    fn call(rp2350: &mut RP2350, operation: u32, parameter: u32) -> u32 {
+        rp2350.cortex_m33.memory.write_u16(RAM_START_ADDRESS, BkptT1::opcode(SEMIHOSTING_BKPT as u16)).unwrap();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+        rp2350.cortex_m33.registers.r0.set(operation);
+        rp2350.cortex_m33.registers.r1.set(parameter);
+        rp2350.execute_instruction();
+        rp2350.cortex_m33.registers.r0.get()
+    }
+
+    fn write_words(rp2350: &mut RP2350, address: u32, words: &[u32]) {
+        for (i, word) in words.iter().enumerate() {
+            rp2350.cortex_m33.memory.write_u32(address + 4 * i as u32, *word).unwrap();
+        }
+    }
+
+    fn write_bytes(rp2350: &mut RP2350, address: u32, bytes: &[u8]) {
+        for (i, byte) in bytes.iter().enumerate() {
+            rp2350.cortex_m33.memory.write(address + i as u32, *byte).unwrap();
+        }
+    }
+
+    fn open_console(rp2350: &mut RP2350, mode: u32) -> u32 {
+        write_bytes(rp2350, DATA + 0x80, b":tt\0");
+        write_words(rp2350, BLOCK + 0x40, &[DATA + 0x80, mode, 3]);
+        call(rp2350, SYS_OPEN, BLOCK + 0x40)
+    }
+
+    #[test]
+    fn writec_and_write0() {
+        // should write a character and a null terminated string to the console
+        let mut rp2350: RP2350 = RP2350::new();
+        write_bytes(&mut rp2350, DATA, b"hello\0");
+
+        assert_eq!(call(&mut rp2350, SYS_WRITEC, DATA), 0);
+        assert_eq!(call(&mut rp2350, SYS_WRITE0, DATA), 0);
+
+        assert_eq!(rp2350.semihosting.take_console(), b"hhello");
+        assert!(rp2350.semihosting.console.is_empty());
+    }
+
+    #[test]
+    fn write_to_console() {
+        // should write a buffer to a handle opened on ":tt" and return how much was left unwritten
+        let mut rp2350: RP2350 = RP2350::new();
+        let handle = open_console(&mut rp2350, 4);
+        write_bytes(&mut rp2350, DATA, b"printf");
+        write_words(&mut rp2350, BLOCK, &[handle, DATA, 6]);
+
+        assert_ne!(handle, u32::MAX);
+        assert_eq!(call(&mut rp2350, SYS_WRITE, BLOCK), 0);
+        assert_eq!(rp2350.semihosting.console, b"printf");
+        write_words(&mut rp2350, BLOCK, &[handle]);
+        assert_eq!(call(&mut rp2350, SYS_ISTTY, BLOCK), 1);
+    }
+
+    #[test]
+    fn read_from_console() {
+        // should read the host's input into the buffer and return how much of the request was left unread
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.semihosting.input.extend(b"abc");
+        let handle = open_console(&mut rp2350, 0);
+        write_words(&mut rp2350, BLOCK, &[handle, DATA, 8]);
+
+        assert_eq!(call(&mut rp2350, SYS_READ, BLOCK), 5);
+
+        assert_eq!(rp2350.cortex_m33.memory.read_u16(DATA).unwrap(), u16::from_le_bytes(*b"ab"));
+        assert_eq!(rp2350.cortex_m33.memory.read(DATA + 2).unwrap(), b'c');
+        assert!(rp2350.semihosting.input.is_empty());
+    }
+
+    #[test]
+    fn bad_handle() {
+        // should fail with -1 and set the errno for a handle that was never opened
+        let mut rp2350: RP2350 = RP2350::new();
+        write_words(&mut rp2350, BLOCK, &[7, DATA, 1]);
+
+        assert_eq!(call(&mut rp2350, SYS_WRITE, BLOCK), u32::MAX);
+        assert_eq!(call(&mut rp2350, SYS_ERRNO, 0), 9);
+    }
+
+    #[test]
+    fn unreadable_buffer() {
+        // should fail with -1 when a buffer is somewhere the host can't read or write
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.semihosting.input.extend(b"abc");
+        let handle = open_console(&mut rp2350, 0);
+        write_words(&mut rp2350, BLOCK, &[handle, FLASH_START_ADDRESS, 3]);
+
+        assert_eq!(call(&mut rp2350, SYS_READ, BLOCK), u32::MAX);
+        assert_eq!(call(&mut rp2350, SYS_WRITE0, 0x4000_0000), u32::MAX);
+    }
+
+    #[test]
+    fn clock_and_time() {
+        // should count simulated centiseconds at clk_sys for SYS_CLOCK and give the host's time for SYS_TIME
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.memory_mut().peripherals.tick(3 * ROSC_HZ as u64 / 100);
+
+        assert_eq!(call(&mut rp2350, SYS_CLOCK, 0), 3);
+        assert!(call(&mut rp2350, SYS_TIME, 0) > 1_700_000_000);
+    }
+
+    #[test]
+    fn unknown_operation() {
+        // should fail an operation it doesn't support
+        let mut rp2350: RP2350 = RP2350::new();
+
+        assert_eq!(call(&mut rp2350, 0x30, 0), u32::MAX);
+    }
+}
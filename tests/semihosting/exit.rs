@@ -0,0 +1,71 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::cortex_m33::opcodes::*;
+    use rp2350_sim::cortex_m33::registers::Register;
+    use rp2350_sim::runner::{Runner, Stop};
+    use rp2350_sim::semihosting::*;
+    use rp2350_sim::{RAM_START_ADDRESS, RP2350};
+
+    const BLOCK: u32 = RAM_START_ADDRESS + 0x200;
+
+    fn exit_program(rp2350: &mut RP2350, operation: u32, parameter: u32) {
+        let code = [BkptT1::opcode(SEMIHOSTING_BKPT as u16), BT2::opcode(-4i16 as u16)];
+        for (i, opcode) in code.iter().enumerate() {
+            rp2350.cortex_m33.memory.write_u16(RAM_START_ADDRESS + 2 * i as u32, *opcode).unwrap();
+        }
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+        rp2350.cortex_m33.registers.r0.set(operation);
+        rp2350.cortex_m33.registers.r1.set(parameter);
+    }
+
+    #[test]
+    fn exit_application() {
+        // should exit with status 0 for ADP_Stopped_ApplicationExit and stop running
+        let mut rp2350: RP2350 = RP2350::new();
+        exit_program(&mut rp2350, SYS_EXIT, ADP_STOPPED_APPLICATION_EXIT);
+
+        rp2350.execute_instruction();
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.semihosting.exit, Some(0));
+        assert!(rp2350.is_halted());
+        assert_eq!(rp2350.cortex_m33.registers.pc.get(), RAM_START_ADDRESS + 2);
+    }
+
+    #[test]
+    fn exit_failure() {
+        // should exit with status 1 for any other reason
+        let mut rp2350: RP2350 = RP2350::new();
+        exit_program(&mut rp2350, SYS_EXIT, 0x20023);
+
+        rp2350.execute_instruction();
+
+        assert_eq!(rp2350.semihosting.exit, Some(1));
+    }
+
+    #[test]
+    fn exit_extended() {
+        // should exit with the subcode as the status, which the runner stops on
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.memory.write_u32(BLOCK, ADP_STOPPED_APPLICATION_EXIT).unwrap();
+        rp2350.cortex_m33.memory.write_u32(BLOCK + 4, 42).unwrap();
+        exit_program(&mut rp2350, SYS_EXIT_EXTENDED, BLOCK);
+
+        let summary = Runner::new().run(&mut rp2350).unwrap();
+
+        assert_eq!(summary.stop, Stop::Exit(42));
+        assert_eq!(summary.instructions, 1);
+    }
+
+    #[test]
+    fn reset_clears_exit() {
+        // should run again after a reset
+        let mut rp2350: RP2350 = RP2350::new();
+        exit_program(&mut rp2350, SYS_EXIT, ADP_STOPPED_APPLICATION_EXIT);
+        rp2350.execute_instruction();
+
+        rp2350.reset();
+
+        assert_eq!(rp2350.semihosting.exit, None);
+    }
+}
@@ -0,0 +1,92 @@
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use rp2350_sim::cortex_m33::opcodes::*;
+    use rp2350_sim::cortex_m33::registers::Register;
+    use rp2350_sim::semihosting::*;
+    use rp2350_sim::{RAM_START_ADDRESS, RP2350};
+
+    const NAME: u32 = RAM_START_ADDRESS + 0x100;
+    const DATA: u32 = RAM_START_ADDRESS + 0x180;
+    const BLOCK: u32 = RAM_START_ADDRESS + 0x200;
+
+    fn call(rp2350: &mut RP2350, operation: u32, parameters: &[u32]) -> u32 {
+        for (i, parameter) in parameters.iter().enumerate() {
+            rp2350.cortex_m33.memory.write_u32(BLOCK + 4 * i as u32, *parameter).unwrap();
+        }
+        rp2350.cortex_m33.memory.write_u16(RAM_START_ADDRESS, BkptT1::opcode(SEMIHOSTING_BKPT as u16)).unwrap();
+        rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+        rp2350.cortex_m33.registers.r0.set(operation);
+        rp2350.cortex_m33.registers.r1.set(BLOCK);
+        rp2350.execute_instruction();
+        rp2350.cortex_m33.registers.r0.get()
+    }
+
+    fn write_bytes(rp2350: &mut RP2350, address: u32, bytes: &[u8]) {
+        for (i, byte) in bytes.iter().enumerate() {
+            rp2350.cortex_m33.memory.write(address + i as u32, *byte).unwrap();
+        }
+    }
+
+    fn open(rp2350: &mut RP2350, name: &str, mode: u32) -> u32 {
+        write_bytes(rp2350, NAME, name.as_bytes());
+        call(rp2350, SYS_OPEN, &[NAME, mode, name.len() as u32])
+    }
+
+    /// A fresh sandbox directory for one test
+    fn sandbox(test: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("rp2350-sim-semihosting-{}-{}", std::process::id(), test));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(&root).unwrap();
+        root
+    }
+
+    #[test]
+    fn write_then_read_file() {
+        // should create a file in the sandbox, then open it again and read it back
+        let root = sandbox("write_then_read_file");
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.semihosting.root = Some(root.clone());
+
+        let handle = open(&mut rp2350, "out.txt", 4);
+        write_bytes(&mut rp2350, DATA, b"result: pass");
+        assert_eq!(call(&mut rp2350, SYS_WRITE, &[handle, DATA, 12]), 0);
+        assert_eq!(call(&mut rp2350, SYS_CLOSE, &[handle]), 0);
+        assert_eq!(std::fs::read(root.join("out.txt")).unwrap(), b"result: pass");
+
+        let handle = open(&mut rp2350, "out.txt", 0);
+        assert_eq!(call(&mut rp2350, SYS_FLEN, &[handle]), 12);
+        assert_eq!(call(&mut rp2350, SYS_SEEK, &[handle, 8]), 0);
+        assert_eq!(call(&mut rp2350, SYS_READ, &[handle, DATA + 0x40, 16]), 12);
+        assert_eq!(rp2350.cortex_m33.memory.read_u32(DATA + 0x40).unwrap(), u32::from_le_bytes(*b"pass"));
+        assert_eq!(call(&mut rp2350, SYS_ISTTY, &[handle]), 0);
+        assert_eq!(call(&mut rp2350, SYS_CLOSE, &[handle]), 0);
+        assert_eq!(call(&mut rp2350, SYS_CLOSE, &[handle]), u32::MAX);
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn stays_in_sandbox() {
+        // should refuse paths that are absolute or lead out of the sandbox
+        let root = sandbox("stays_in_sandbox");
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.semihosting.root = Some(root.join("inner"));
+
+        assert_eq!(open(&mut rp2350, "../escape.txt", 4), u32::MAX);
+        assert_eq!(open(&mut rp2350, "/tmp/escape.txt", 4), u32::MAX);
+        assert!(!root.join("escape.txt").exists());
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn no_sandbox() {
+        // should fail to open files without a sandbox directory, though the console still opens
+        let mut rp2350: RP2350 = RP2350::new();
+
+        assert_eq!(open(&mut rp2350, "file.txt", 0), u32::MAX);
+        assert_eq!(open(&mut rp2350, ":tt", 0), 1);
+    }
+}
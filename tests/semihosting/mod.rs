@@ -0,0 +1,3 @@
+mod console;
+mod exit;
+mod files;
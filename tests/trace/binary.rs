@@ -0,0 +1,100 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::cortex_m33::opcodes::*;
+    use rp2350_sim::cortex_m33::registers::Register;
+    use rp2350_sim::trace::binary::*;
+    use rp2350_sim::trace::{MemoryAccess, TraceBuffer, TraceRecord, TraceSink};
+    use rp2350_sim::{RAM_START_ADDRESS, RP2350};
+
+    fn record(pc: u32) -> TraceRecord {
+        TraceRecord {
+            pc,
+            opcode: 0x6008,
+            is_32bit: false,
+            mnemonic: "StrImmediateT1".to_string(),
+            registers: vec![(0, 1), (14, 0xffff_fff9)],
+            flags: Some(0b00011),
+            accesses: vec![MemoryAccess { address: 0x20000800, size: 2, write: true, value: 0xbeef }],
+        }
+    }
+
+    fn write(records: &[TraceRecord]) -> Vec<u8> {
+        let mut sink = BinarySink::new(Vec::new());
+        for record in records {
+            sink.record(record);
+        }
+        sink.flush().unwrap();
+        sink.into_inner()
+    }
+
+    #[test]
+    fn round_trip() {
+        // should read back the records it wrote, less the mnemonics
+        let records = [record(0x100), TraceRecord { flags: None, accesses: vec![], ..record(0x102) }];
+
+        let bytes = write(&records);
+        let read = read_trace(&bytes[..]).unwrap();
+
+        assert_eq!(&bytes[..4], &MAGIC);
+        assert_eq!(read.len(), 2);
+        assert!(read[0].mnemonic.is_empty());
+        assert!(read[0].same_execution(&records[0]));
+        assert!(read[1].same_execution(&records[1]));
+        assert_eq!(first_difference(&records, &read), None);
+    }
+
+    #[test]
+    fn compact() {
+        // should take 11 bytes for an instruction that changed nothing
+        let records = [TraceRecord { registers: vec![], flags: None, accesses: vec![], ..record(0x100) }];
+
+        assert_eq!(write(&records).len(), MAGIC.len() + 11);
+        assert_eq!(read_trace(&[][..]).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn rejects_other_data() {
+        // should refuse data without the magic, and a trace that ends part way through a record
+        assert!(read_trace(&b"nope"[..]).is_err());
+
+        let bytes = write(&[record(0x100)]);
+        assert!(read_trace(&bytes[..bytes.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn difference() {
+        // should find the first record where two runs went different ways, or where one ended early
+        let expected = [record(0x100), record(0x102), record(0x104)];
+        let mut actual = expected.to_vec();
+        actual[1].registers[0].1 = 2;
+
+        assert_eq!(first_difference(&expected, &actual), Some(1));
+        assert_eq!(first_difference(&expected, &expected[..2]), Some(2));
+    }
+
+    #[test]
+    fn replay_run() {
+        // should give the same trace for two runs of the same program
+        let run = || {
+            let mut rp2350: RP2350 = RP2350::new();
+            let r0 = rp2350.cortex_m33.registers.r0;
+            let opcodes = [AddsT2::opcode(&r0, 1), BT2::opcode(-6i16 as u16)];
+            for (i, opcode) in opcodes.iter().enumerate() {
+                rp2350.cortex_m33.memory.write_u16(RAM_START_ADDRESS + 2 * i as u32, *opcode).unwrap();
+            }
+            rp2350.cortex_m33.registers.pc.set(RAM_START_ADDRESS);
+            let buffer = TraceBuffer::new();
+            rp2350.tracer = Some(Box::new(buffer.clone()));
+            for _ in 0..10 {
+                rp2350.execute_instruction();
+            }
+            buffer.records()
+        };
+
+        let expected = run();
+        let actual = read_trace(&write(&run())[..]).unwrap();
+
+        assert_eq!(expected.len(), 10);
+        assert_eq!(first_difference(&expected, &actual), None);
+    }
+}
@@ -0,0 +1,132 @@
+#[cfg(test)]
+mod tests {
+    use rp2350_sim::cortex_m33::opcodes::*;
+    use rp2350_sim::cortex_m33::registers::Register;
+    use rp2350_sim::trace::{MemoryAccess, TextSink, TraceBuffer, TraceRecord, TraceSink};
+    use rp2350_sim::{RAM_START_ADDRESS, RP2350};
+
+    const CODE: u32 = RAM_START_ADDRESS + 0x100;
+    const DATA: u32 = RAM_START_ADDRESS + 0x800;
+
+    fn setup(opcodes: &[u16]) -> (RP2350, TraceBuffer) {
+        let mut rp2350: RP2350 = RP2350::new();
+        for (i, opcode) in opcodes.iter().enumerate() {
+            rp2350.cortex_m33.memory.write_u16(CODE + 2 * i as u32, *opcode).unwrap();
+        }
+        rp2350.cortex_m33.registers.pc.set(CODE);
+        rp2350.cortex_m33.registers.r1.set(DATA);
+
+        let buffer = TraceBuffer::new();
+        rp2350.tracer = Some(Box::new(buffer.clone()));
+        (rp2350, buffer)
+    }
+
+    #[test]
+    fn off_by_default() {
+        // should not trace anything without a tracer
+        let mut rp2350: RP2350 = RP2350::new();
+        rp2350.cortex_m33.registers.pc.set(CODE);
+
+        rp2350.execute_instruction();
+
+        assert!(rp2350.tracer.is_none());
+        assert!(rp2350.cortex_m33.traced_accesses.is_none());
+    }
+
+    #[test]
+    fn registers_and_flags() {
+        // should record the registers and flags an instruction changed
+        let registers = RP2350::new().cortex_m33.registers;
+        let (mut rp2350, buffer) = setup(&[
+            MovImmediateT1::opcode(&registers.r0, 0x42),
+            MovImmediateT1::opcode(&registers.r2, 0),
+        ]);
+
+        rp2350.execute_instruction();
+        rp2350.execute_instruction();
+
+        let records = buffer.records();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].pc, CODE);
+        assert_eq!(records[0].opcode, MovImmediateT1::opcode(&registers.r0, 0x42) as u32);
+        assert!(!records[0].is_32bit);
+        assert_eq!(records[0].mnemonic, "movs r0, #66");
+        assert_eq!(records[0].registers, vec![(0, 0x42)]);
+        assert_eq!(records[0].flags, None);
+        // movs r2, #0 sets Z, and r2 was already 0
+        assert_eq!(records[1].registers, vec![]);
+        assert_eq!(records[1].flags, Some(0b01000));
+    }
+
+    #[test]
+    fn memory_accesses() {
+        // should record loads and stores with their values
+        let registers = RP2350::new().cortex_m33.registers;
+        let (mut rp2350, buffer) = setup(&[
+            StrImmediateT1::opcode(&registers.r1, &registers.r1, 4),
+            LdrImmediateT1::opcode(&registers.r3, &registers.r1, 4),
+        ]);
+
+        rp2350.execute_instruction();
+        rp2350.execute_instruction();
+
+        let records = buffer.records();
+        let store = MemoryAccess { address: DATA + 4, size: 4, write: true, value: DATA };
+        let load = MemoryAccess { address: DATA + 4, size: 4, write: false, value: DATA };
+        assert_eq!(records[0].accesses, vec![store]);
+        assert_eq!(records[1].accesses, vec![load]);
+        assert_eq!(records[1].registers, vec![(3, DATA)]);
+    }
+
+    #[test]
+    fn disassembled_in_it_block() {
+        // should disassemble with the condition of the IT block the instruction runs in
+        let registers = RP2350::new().cortex_m33.registers;
+        let (mut rp2350, buffer) = setup(&[ItT1::opcode(0b0001, 0b1000), MovImmediateT1::opcode(&registers.r0, 1)]);
+
+        rp2350.execute_instruction();
+        rp2350.execute_instruction();
+
+        let records = buffer.records();
+        assert_eq!(records[0].mnemonic, "it ne");
+        assert_eq!(records[1].mnemonic, "movne r0, #1");
+    }
+
+    #[test]
+    fn exception_entry_not_traced() {
+        // should leave exception entry out of the trace, and trace the faulting instruction without its access
+        let (mut rp2350, buffer) = setup(&[UdfT1::opcode(0)]);
+
+        rp2350.execute_instruction();
+        rp2350.execute_instruction();
+
+        let records = buffer.records();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].mnemonic, "udf #0");
+        assert!(rp2350.cortex_m33.traced_accesses.is_none());
+    }
+
+    #[test]
+    fn text_format() {
+        // should write one line per record with the changes after the mnemonic
+        let record = TraceRecord {
+            pc: 0x10000100,
+            opcode: 0x2800_f8d1,
+            is_32bit: true,
+            mnemonic: "LdrImmediateT3".to_string(),
+            registers: vec![(0, 0x12)],
+            flags: Some(0b10100),
+            accesses: vec![MemoryAccess { address: 0x20000000, size: 4, write: false, value: 0x12 }],
+        };
+        let mut sink = TextSink::new(Vec::new());
+
+        sink.record(&record);
+        sink.flush().unwrap();
+
+        let text = String::from_utf8(sink.into_inner()).unwrap();
+        assert_eq!(
+            text,
+            "10000100  f8d1 2800  LdrImmediateT3            r0=0x00000012  NzCvq  [r4 0x20000000=0x12]\n"
+        );
+    }
+}